@@ -0,0 +1,32 @@
+// With the c-api-tests feature (e.g. `cargo test --features c-api-tests`), generate the header of
+// the C API (src/c_api.rs) into include/zendoo_sc.h, and compile the C tests against it. The tests
+// are linked only into the test executable of the crate. Regular builds don't touch the sources.
+#[cfg(feature = "c-api-tests")]
+fn main() {
+    use std::{env, path::PathBuf};
+
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    cbindgen::generate(&crate_dir)
+        .expect("Unable to generate the C API header")
+        .write_to_file(crate_dir.join("include").join("zendoo_sc.h"));
+
+    cc::Build::new()
+        .file(crate_dir.join("tests").join("c").join("c_api_tests.c"))
+        .include(crate_dir.join("include"))
+        .cargo_metadata(false)
+        .compile("zendoo_sc_c_tests");
+
+    println!(
+        "cargo:rustc-link-search=native={}",
+        env::var("OUT_DIR").unwrap()
+    );
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/c_api.rs");
+    println!("cargo:rerun-if-changed=tests/c/c_api_tests.c");
+}
+
+#[cfg(not(feature = "c-api-tests"))]
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+}
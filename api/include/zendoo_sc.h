@@ -0,0 +1,421 @@
+/* C API of zendoo-sc-cryptolib. See api/src/c_api.rs for the conventions. */
+
+#ifndef ZENDOO_SC_H
+#define ZENDOO_SC_H
+
+/* Warning: this file is autogenerated by cbindgen. Don't modify it manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Size in bytes of a mainchain public key hash.
+#define ZENDOO_MC_PK_SIZE 20
+
+// Error codes of the C API.
+typedef enum ZendooError {
+  // No error.
+  ZENDOO_ERROR_OK = 0,
+  // A required pointer was NULL.
+  ZENDOO_ERROR_NULL_POINTER = 1,
+  // An input was malformed or not valid for the operation (e.g. not deserializable,
+  // semantically invalid, or rejected by the object it was passed to).
+  ZENDOO_ERROR_INVALID_INPUT = 2,
+  // A cryptographic operation failed.
+  ZENDOO_ERROR_CRYPTO_ERROR = 3,
+  // An unexpected internal error happened.
+  ZENDOO_ERROR_PANIC = 4,
+} ZendooError;
+
+// Proving systems, with the same ordinals of the ProvingSystem Java enum. Functions take
+// the ordinal as an uint32_t, and fail with ZENDOO_ERROR_INVALID_INPUT on unknown values.
+typedef enum ZendooProvingSystem {
+  ZENDOO_PROVING_SYSTEM_UNDEFINED = 0,
+  ZENDOO_PROVING_SYSTEM_DARLIN = 1,
+  ZENDOO_PROVING_SYSTEM_COBOUNDARY_MARLIN = 2,
+} ZendooProvingSystem;
+
+typedef struct ZendooFieldElement ZendooFieldElement;
+
+typedef struct ZendooPoseidonHash ZendooPoseidonHash;
+
+typedef struct ZendooMerkleTree ZendooMerkleTree;
+
+typedef struct ZendooMerklePath ZendooMerklePath;
+
+typedef struct ZendooSchnorrSecretKey ZendooSchnorrSecretKey;
+
+typedef struct ZendooSchnorrPublicKey ZendooSchnorrPublicKey;
+
+typedef struct ZendooSchnorrSignature ZendooSchnorrSignature;
+
+typedef struct ZendooVrfSecretKey ZendooVrfSecretKey;
+
+typedef struct ZendooVrfPublicKey ZendooVrfPublicKey;
+
+typedef struct ZendooVrfProof ZendooVrfProof;
+
+typedef struct ZendooCommitmentTree ZendooCommitmentTree;
+
+// Byte string allocated by the library.
+typedef struct ZendooBuffer {
+  uint8_t *data;
+  uintptr_t len;
+} ZendooBuffer;
+
+// Backward transfer of a certificate.
+typedef struct ZendooBackwardTransfer {
+  uint8_t pk_dest[ZENDOO_MC_PK_SIZE];
+  uint64_t amount;
+} ZendooBackwardTransfer;
+
+// Configuration of a custom bit vector field of a sidechain.
+typedef struct ZendooBitVectorElementsConfig {
+  uint32_t bit_vector_size_bits;
+  uint32_t max_compressed_byte_size;
+} ZendooBitVectorElementsConfig;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Release a buffer returned by the library.
+void zendoo_buffer_free(ZendooBuffer buffer);
+
+// Size in bytes of a serialized field element.
+uintptr_t zendoo_field_element_size(void);
+
+ZendooError zendoo_field_element_from_u64(uint64_t value, ZendooFieldElement **out);
+
+// Deserialize a field element from `len` bytes, which must be zendoo_field_element_size().
+ZendooError zendoo_field_element_from_bytes(const uint8_t *bytes,
+                                            uintptr_t len,
+                                            ZendooFieldElement **out);
+
+ZendooError zendoo_field_element_to_bytes(const ZendooFieldElement *fe, ZendooBuffer *out);
+
+ZendooError zendoo_field_element_equals(const ZendooFieldElement *fe_1,
+                                        const ZendooFieldElement *fe_2,
+                                        bool *out);
+
+void zendoo_field_element_free(ZendooFieldElement *fe);
+
+// New Poseidon hash expecting exactly `input_size` inputs. `personalization` can be NULL
+// if `personalization_len` is 0.
+ZendooError zendoo_poseidon_hash_new_constant_length(uintptr_t input_size,
+                                                     const ZendooFieldElement *const *personalization,
+                                                     uintptr_t personalization_len,
+                                                     ZendooPoseidonHash **out);
+
+// New Poseidon hash accepting any number of inputs, which must be a multiple of the rate
+// if `mod_rate` is true.
+ZendooError zendoo_poseidon_hash_new_variable_length(bool mod_rate,
+                                                     const ZendooFieldElement *const *personalization,
+                                                     uintptr_t personalization_len,
+                                                     ZendooPoseidonHash **out);
+
+ZendooError zendoo_poseidon_hash_update(ZendooPoseidonHash *h, const ZendooFieldElement *input);
+
+// Absorb `len` bytes, length prefixed and packed into field elements as PoseidonHash.updateBytes()
+// of the Java API does.
+ZendooError zendoo_poseidon_hash_update_bytes(ZendooPoseidonHash *h,
+                                              const uint8_t *bytes,
+                                              uintptr_t len);
+
+// Compute the digest. Fails with ZENDOO_ERROR_CRYPTO_ERROR if the number of inputs is not
+// the expected one.
+ZendooError zendoo_poseidon_hash_finalize(const ZendooPoseidonHash *h, ZendooFieldElement **out);
+
+ZendooError zendoo_poseidon_hash_reset(ZendooPoseidonHash *h,
+                                       const ZendooFieldElement *const *personalization,
+                                       uintptr_t personalization_len);
+
+void zendoo_poseidon_hash_free(ZendooPoseidonHash *h);
+
+// New Merkle tree of height `height`, optimized for appending leaves in batches of
+// `processing_step`.
+ZendooError zendoo_merkle_tree_new(uintptr_t height,
+                                   uintptr_t processing_step,
+                                   ZendooMerkleTree **out);
+
+// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree is full or finalized.
+ZendooError zendoo_merkle_tree_append(ZendooMerkleTree *tree, const ZendooFieldElement *leaf);
+
+// Finalize the tree: no more leaves can be appended, root and paths become available.
+ZendooError zendoo_merkle_tree_finalize(ZendooMerkleTree *tree);
+
+// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree is not finalized.
+ZendooError zendoo_merkle_tree_root(const ZendooMerkleTree *tree, ZendooFieldElement **out);
+
+// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree is not finalized or `leaf_index` is out
+// of range.
+ZendooError zendoo_merkle_tree_get_path(const ZendooMerkleTree *tree,
+                                        uint64_t leaf_index,
+                                        ZendooMerklePath **out);
+
+void zendoo_merkle_tree_free(ZendooMerkleTree *tree);
+
+// Check that `path` is the path of `leaf` in the tree of height `height` with root `root`.
+ZendooError zendoo_merkle_path_verify(const ZendooMerklePath *path,
+                                      uintptr_t height,
+                                      const ZendooFieldElement *leaf,
+                                      const ZendooFieldElement *root,
+                                      bool *out);
+
+ZendooError zendoo_merkle_path_to_bytes(const ZendooMerklePath *path, ZendooBuffer *out);
+
+ZendooError zendoo_merkle_path_from_bytes(const uint8_t *bytes,
+                                          uintptr_t len,
+                                          ZendooMerklePath **out);
+
+void zendoo_merkle_path_free(ZendooMerklePath *path);
+
+ZendooError zendoo_schnorr_generate_key(ZendooSchnorrPublicKey **out_public_key,
+                                        ZendooSchnorrSecretKey **out_secret_key);
+
+ZendooError zendoo_schnorr_get_public_key(const ZendooSchnorrSecretKey *secret_key,
+                                          ZendooSchnorrPublicKey **out);
+
+ZendooError zendoo_schnorr_verify_public_key(const ZendooSchnorrPublicKey *public_key, bool *out);
+
+ZendooError zendoo_schnorr_sign(const ZendooFieldElement *message,
+                                const ZendooSchnorrSecretKey *secret_key,
+                                const ZendooSchnorrPublicKey *public_key,
+                                ZendooSchnorrSignature **out);
+
+ZendooError zendoo_schnorr_verify(const ZendooFieldElement *message,
+                                  const ZendooSchnorrPublicKey *public_key,
+                                  const ZendooSchnorrSignature *signature,
+                                  bool *out);
+
+// Compressed serialization of a public key.
+ZendooError zendoo_schnorr_public_key_to_bytes(const ZendooSchnorrPublicKey *public_key,
+                                               ZendooBuffer *out);
+
+// Deserialize a compressed public key. Fails with ZENDOO_ERROR_INVALID_INPUT if the key is
+// not valid.
+ZendooError zendoo_schnorr_public_key_from_bytes(const uint8_t *bytes,
+                                                 uintptr_t len,
+                                                 ZendooSchnorrPublicKey **out);
+
+ZendooError zendoo_schnorr_signature_to_bytes(const ZendooSchnorrSignature *signature,
+                                              ZendooBuffer *out);
+
+ZendooError zendoo_schnorr_signature_from_bytes(const uint8_t *bytes,
+                                                uintptr_t len,
+                                                ZendooSchnorrSignature **out);
+
+// The memory of the key is wiped.
+void zendoo_schnorr_secret_key_free(ZendooSchnorrSecretKey *secret_key);
+
+void zendoo_schnorr_public_key_free(ZendooSchnorrPublicKey *public_key);
+
+void zendoo_schnorr_signature_free(ZendooSchnorrSignature *signature);
+
+ZendooError zendoo_vrf_generate_key(ZendooVrfPublicKey **out_public_key,
+                                    ZendooVrfSecretKey **out_secret_key);
+
+ZendooError zendoo_vrf_get_public_key(const ZendooVrfSecretKey *secret_key,
+                                      ZendooVrfPublicKey **out);
+
+ZendooError zendoo_vrf_verify_public_key(const ZendooVrfPublicKey *public_key, bool *out);
+
+// Compute the VRF proof for `message` and the corresponding output.
+ZendooError zendoo_vrf_prove(const ZendooFieldElement *message,
+                             const ZendooVrfSecretKey *secret_key,
+                             const ZendooVrfPublicKey *public_key,
+                             ZendooVrfProof **out_proof,
+                             ZendooFieldElement **out_output);
+
+// Verify `proof` and return the VRF output. Fails with ZENDOO_ERROR_CRYPTO_ERROR if the
+// proof is not valid.
+ZendooError zendoo_vrf_proof_to_hash(const ZendooFieldElement *message,
+                                     const ZendooVrfPublicKey *public_key,
+                                     const ZendooVrfProof *proof,
+                                     ZendooFieldElement **out);
+
+// Compressed serialization of a public key.
+ZendooError zendoo_vrf_public_key_to_bytes(const ZendooVrfPublicKey *public_key, ZendooBuffer *out);
+
+// Deserialize a compressed public key. Fails with ZENDOO_ERROR_INVALID_INPUT if the key is
+// not valid.
+ZendooError zendoo_vrf_public_key_from_bytes(const uint8_t *bytes,
+                                             uintptr_t len,
+                                             ZendooVrfPublicKey **out);
+
+// Compressed serialization of a proof.
+ZendooError zendoo_vrf_proof_to_bytes(const ZendooVrfProof *proof, ZendooBuffer *out);
+
+ZendooError zendoo_vrf_proof_from_bytes(const uint8_t *bytes, uintptr_t len, ZendooVrfProof **out);
+
+// The memory of the key is wiped.
+void zendoo_vrf_secret_key_free(ZendooVrfSecretKey *secret_key);
+
+void zendoo_vrf_public_key_free(ZendooVrfPublicKey *public_key);
+
+void zendoo_vrf_proof_free(ZendooVrfProof *proof);
+
+ZendooError zendoo_commitment_tree_new(ZendooCommitmentTree **out);
+
+// Add a sidechain creation. Custom configurations, custom creation data, constant and CSW
+// verification key are optional: pass NULL when absent.
+ZendooError zendoo_commitment_tree_add_scc(ZendooCommitmentTree *tree,
+                                           const ZendooFieldElement *sc_id,
+                                           uint64_t amount,
+                                           const uint8_t *pub_key,
+                                           const uint8_t *tx_hash,
+                                           uint32_t out_idx,
+                                           uint32_t withdrawal_epoch_length,
+                                           uint8_t mc_btr_request_data_length,
+                                           const uint8_t *custom_field_elements_configs,
+                                           uintptr_t custom_field_elements_configs_len,
+                                           const ZendooBitVectorElementsConfig *custom_bitvector_elements_configs,
+                                           uintptr_t custom_bitvector_elements_configs_len,
+                                           uint64_t btr_fee,
+                                           uint64_t ft_min_amount,
+                                           const uint8_t *custom_creation_data,
+                                           uintptr_t custom_creation_data_len,
+                                           const ZendooFieldElement *constant,
+                                           const uint8_t *cert_verification_key,
+                                           uintptr_t cert_verification_key_len,
+                                           const uint8_t *csw_verification_key,
+                                           uintptr_t csw_verification_key_len);
+
+ZendooError zendoo_commitment_tree_add_fwt(ZendooCommitmentTree *tree,
+                                           const ZendooFieldElement *sc_id,
+                                           uint64_t amount,
+                                           const uint8_t *pub_key,
+                                           const uint8_t *mc_return_address,
+                                           const uint8_t *tx_hash,
+                                           uint32_t out_idx);
+
+ZendooError zendoo_commitment_tree_add_bwtr(ZendooCommitmentTree *tree,
+                                            const ZendooFieldElement *sc_id,
+                                            uint64_t sc_fee,
+                                            const ZendooFieldElement *const *sc_request_data,
+                                            uintptr_t sc_request_data_len,
+                                            const uint8_t *mc_destination_address,
+                                            const uint8_t *tx_hash,
+                                            uint32_t out_idx);
+
+// Add a certificate. `bt_list` and `custom_fields` can be NULL when empty.
+ZendooError zendoo_commitment_tree_add_cert(ZendooCommitmentTree *tree,
+                                            const ZendooFieldElement *sc_id,
+                                            uint32_t epoch_number,
+                                            uint64_t quality,
+                                            const ZendooBackwardTransfer *bt_list,
+                                            uintptr_t bt_list_len,
+                                            const ZendooFieldElement *const *custom_fields,
+                                            uintptr_t custom_fields_len,
+                                            const ZendooFieldElement *end_cumulative_sc_tx_commitment_tree_root,
+                                            uint64_t btr_fee,
+                                            uint64_t ft_min_amount);
+
+ZendooError zendoo_commitment_tree_add_csw(ZendooCommitmentTree *tree,
+                                           const ZendooFieldElement *sc_id,
+                                           uint64_t amount,
+                                           const ZendooFieldElement *nullifier,
+                                           const uint8_t *mc_pk_hash);
+
+// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree has no transactions for `sc_id`.
+ZendooError zendoo_commitment_tree_get_sc_commitment(ZendooCommitmentTree *tree,
+                                                     const ZendooFieldElement *sc_id,
+                                                     ZendooFieldElement **out);
+
+ZendooError zendoo_commitment_tree_get_commitment(ZendooCommitmentTree *tree,
+                                                  ZendooFieldElement **out);
+
+// Serialize the transactions added to the tree, in the format of CommitmentTree.serialize()
+// of the Java API.
+ZendooError zendoo_commitment_tree_serialize_state(const ZendooCommitmentTree *tree,
+                                                   ZendooBuffer *out);
+
+ZendooError zendoo_commitment_tree_deserialize_state(const uint8_t *state,
+                                                     uintptr_t len,
+                                                     ZendooCommitmentTree **out);
+
+void zendoo_commitment_tree_free(ZendooCommitmentTree *tree);
+
+// Generate the DLOG keys, required before any setup, proof creation or verification.
+ZendooError zendoo_init_dlog_keys(uint32_t proving_system,
+                                  uintptr_t max_segment_size,
+                                  uintptr_t supported_segment_size);
+
+// Generate and write to the given paths the proving and verification keys of the naive
+// threshold signature circuit supporting `max_pks` public keys.
+ZendooError zendoo_naive_threshold_sig_setup(uint32_t proving_system,
+                                             uintptr_t max_pks,
+                                             const char *proving_key_path,
+                                             const char *verification_key_path,
+                                             bool zk,
+                                             uintptr_t max_proof_size,
+                                             uintptr_t max_vk_size,
+                                             bool compress_pk,
+                                             bool compress_vk);
+
+// Compute H(H(pks), threshold), the constant of the naive threshold signature circuit.
+ZendooError zendoo_compute_pks_threshold_hash(const ZendooSchnorrPublicKey *const *pks,
+                                              uintptr_t num_pks,
+                                              uint64_t threshold,
+                                              ZendooFieldElement **out);
+
+// Compute the message the signers of a certificate sign.
+ZendooError zendoo_compute_msg_to_sign(const ZendooFieldElement *sc_id,
+                                       uint32_t epoch_number,
+                                       const ZendooFieldElement *end_cumulative_sc_tx_commitment_tree_root,
+                                       uint64_t btr_fee,
+                                       uint64_t ft_min_amount,
+                                       const ZendooBackwardTransfer *bt_list,
+                                       uintptr_t bt_list_len,
+                                       ZendooFieldElement **out);
+
+// Create a naive threshold signature proof. `sigs` has one entry for each of the `num_pks`
+// public keys, NULL if the corresponding signature is missing. The proof is written to
+// `out_proof` and the number of valid signatures (the quality of the certificate) to
+// `out_quality`. Fails with ZENDOO_ERROR_INVALID_INPUT if the valid signatures are less
+// than `threshold`.
+ZendooError zendoo_naive_threshold_sig_create_proof(const ZendooSchnorrPublicKey *const *pks,
+                                                    const ZendooSchnorrSignature *const *sigs,
+                                                    uintptr_t num_pks,
+                                                    const ZendooFieldElement *sc_id,
+                                                    uint32_t epoch_number,
+                                                    const ZendooFieldElement *end_cumulative_sc_tx_commitment_tree_root,
+                                                    uint64_t btr_fee,
+                                                    uint64_t ft_min_amount,
+                                                    const ZendooBackwardTransfer *bt_list,
+                                                    uintptr_t bt_list_len,
+                                                    uint64_t threshold,
+                                                    const char *proving_key_path,
+                                                    bool enforce_membership,
+                                                    bool zk,
+                                                    bool compressed_pk,
+                                                    bool compress_proof,
+                                                    ZendooBuffer *out_proof,
+                                                    uint64_t *out_quality);
+
+// Verify a naive threshold signature proof of quality `valid_sigs`, where `constant` is the
+// one computed by zendoo_compute_pks_threshold_hash().
+ZendooError zendoo_naive_threshold_sig_verify_proof(const ZendooFieldElement *constant,
+                                                    const ZendooFieldElement *sc_id,
+                                                    uint32_t epoch_number,
+                                                    const ZendooFieldElement *end_cumulative_sc_tx_commitment_tree_root,
+                                                    uint64_t btr_fee,
+                                                    uint64_t ft_min_amount,
+                                                    const ZendooBackwardTransfer *bt_list,
+                                                    uintptr_t bt_list_len,
+                                                    uint64_t valid_sigs,
+                                                    const uint8_t *proof,
+                                                    uintptr_t proof_len,
+                                                    bool check_proof,
+                                                    bool compressed_proof,
+                                                    const char *verification_key_path,
+                                                    bool check_vk,
+                                                    bool compressed_vk,
+                                                    bool *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* ZENDOO_SC_H */
@@ -0,0 +1,1307 @@
+use algebra::{serialize::*, SemanticallyValid};
+use cctp_primitives::{
+    proving_system::{init_dlog_keys, ProvingSystem},
+    utils::{data_structures::*, mht::*, serialization::*},
+};
+use demo_circuit::{generate_circuit_keypair, get_instance_for_setup, type_mapping::*};
+
+use zendoo_sc_lib::{
+    cctp_calls::*, in_memory_mht::InMemoryOptimizedMerkleTree,
+    incremental_commitment_tree::IncrementalCommitmentTree,
+    resumable_poseidon::ResumablePoseidonHash, secret::*,
+};
+
+use std::{
+    convert::TryFrom,
+    ffi::CStr,
+    os::raw::c_char,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    ptr, slice,
+};
+
+//*****************************C API****************************************************************
+//
+// extern "C" surface of the library, for consumers other than the JVM. The header is generated by
+// cbindgen (see build.rs and cbindgen.toml) into include/zendoo_sc.h, when building with the
+// c-api-tests feature.
+//
+// Conventions:
+// - objects are opaque handles, allocated by the library and released by the corresponding
+//   zendoo_*_free function (which accepts NULL);
+// - every fallible function returns a ZendooError and writes its results through out pointers,
+//   which are left untouched on error. Panics never cross the FFI boundary: they are reported
+//   as ZENDOO_ERROR_PANIC;
+// - byte strings returned by the library are ZendooBuffers, released by zendoo_buffer_free;
+// - pointers to byte arrays of fixed size (sidechain ids, transaction hashes, ...) must point
+//   to at least that many bytes; arrays with an explicit length can be NULL when empty, and
+//   optional ones are NULL when absent.
+
+/// Error codes of the C API.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZendooError {
+    /// No error.
+    Ok = 0,
+    /// A required pointer was NULL.
+    NullPointer = 1,
+    /// An input was malformed or not valid for the operation (e.g. not deserializable,
+    /// semantically invalid, or rejected by the object it was passed to).
+    InvalidInput = 2,
+    /// A cryptographic operation failed.
+    CryptoError = 3,
+    /// An unexpected internal error happened.
+    Panic = 4,
+}
+
+type CResult<T> = Result<T, ZendooError>;
+
+/// Proving systems, with the same ordinals of the ProvingSystem Java enum. Functions take
+/// the ordinal as an uint32_t, and fail with ZENDOO_ERROR_INVALID_INPUT on unknown values.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZendooProvingSystem {
+    Undefined = 0,
+    Darlin = 1,
+    CoboundaryMarlin = 2,
+}
+
+// An enum received by value from C may hold any value, which is UB in Rust: proving systems
+// are passed as integers and converted here
+impl TryFrom<u32> for ZendooProvingSystem {
+    type Error = ZendooError;
+
+    fn try_from(ps: u32) -> CResult<Self> {
+        match ps {
+            0 => Ok(ZendooProvingSystem::Undefined),
+            1 => Ok(ZendooProvingSystem::Darlin),
+            2 => Ok(ZendooProvingSystem::CoboundaryMarlin),
+            _ => Err(ZendooError::InvalidInput),
+        }
+    }
+}
+
+impl From<ZendooProvingSystem> for ProvingSystem {
+    fn from(ps: ZendooProvingSystem) -> Self {
+        match ps {
+            ZendooProvingSystem::Undefined => ProvingSystem::Undefined,
+            ZendooProvingSystem::Darlin => ProvingSystem::Darlin,
+            ZendooProvingSystem::CoboundaryMarlin => ProvingSystem::CoboundaryMarlin,
+        }
+    }
+}
+
+/// Size in bytes of a mainchain public key hash.
+pub const ZENDOO_MC_PK_SIZE: usize = 20;
+
+/// Byte string allocated by the library.
+#[repr(C)]
+pub struct ZendooBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl ZendooBuffer {
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+        Self { data, len }
+    }
+}
+
+/// Backward transfer of a certificate.
+#[repr(C)]
+pub struct ZendooBackwardTransfer {
+    pub pk_dest: [u8; ZENDOO_MC_PK_SIZE],
+    pub amount: u64,
+}
+
+/// Configuration of a custom bit vector field of a sidechain.
+#[repr(C)]
+pub struct ZendooBitVectorElementsConfig {
+    pub bit_vector_size_bits: u32,
+    pub max_compressed_byte_size: u32,
+}
+
+pub struct ZendooFieldElement(FieldElement);
+pub struct ZendooPoseidonHash(ResumablePoseidonHash);
+pub struct ZendooMerkleTree(InMemoryOptimizedMerkleTree);
+pub struct ZendooMerklePath(GingerMHTPath);
+pub struct ZendooSchnorrSecretKey(ZeroizingSchnorrSk);
+pub struct ZendooSchnorrPublicKey(SchnorrPk);
+pub struct ZendooSchnorrSignature(SchnorrSig);
+pub struct ZendooVrfSecretKey(ZeroizingVRFSk);
+pub struct ZendooVrfPublicKey(VRFPk);
+pub struct ZendooVrfProof(VRFProof);
+pub struct ZendooCommitmentTree(IncrementalCommitmentTree);
+
+// Run `f`, converting its outcome, or a panic, into an error code
+fn c_call<F: FnOnce() -> CResult<()>>(f: F) -> ZendooError {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => ZendooError::Ok,
+        Ok(Err(e)) => e,
+        Err(_) => ZendooError::Panic,
+    }
+}
+
+fn read_ref<'a, T>(input: *const T) -> CResult<&'a T> {
+    unsafe { input.as_ref() }.ok_or(ZendooError::NullPointer)
+}
+
+fn read_mut<'a, T>(input: *mut T) -> CResult<&'a mut T> {
+    unsafe { input.as_mut() }.ok_or(ZendooError::NullPointer)
+}
+
+fn read_slice<'a, T>(input: *const T, len: usize) -> CResult<&'a [T]> {
+    if len == 0 {
+        Ok(&[])
+    } else if input.is_null() {
+        Err(ZendooError::NullPointer)
+    } else {
+        Ok(unsafe { slice::from_raw_parts(input, len) })
+    }
+}
+
+fn read_opt_slice<'a, T>(input: *const T, len: usize) -> CResult<Option<&'a [T]>> {
+    if input.is_null() {
+        Ok(None)
+    } else {
+        read_slice(input, len).map(Some)
+    }
+}
+
+fn read_array<'a, const N: usize>(input: *const u8) -> CResult<&'a [u8; N]> {
+    read_ref(input as *const [u8; N])
+}
+
+fn read_field_elements<'a>(
+    input: *const *const ZendooFieldElement,
+    len: usize,
+) -> CResult<Vec<&'a FieldElement>> {
+    read_slice(input, len)?
+        .iter()
+        .map(|&fe| read_ref(fe).map(|fe| &fe.0))
+        .collect()
+}
+
+fn read_path<'a>(input: *const c_char) -> CResult<&'a Path> {
+    if input.is_null() {
+        return Err(ZendooError::NullPointer);
+    }
+    unsafe { CStr::from_ptr(input) }
+        .to_str()
+        .map(Path::new)
+        .map_err(|_| ZendooError::InvalidInput)
+}
+
+fn read_bt_list(
+    bt_list: *const ZendooBackwardTransfer,
+    len: usize,
+) -> CResult<Vec<BackwardTransfer>> {
+    Ok(read_slice(bt_list, len)?
+        .iter()
+        .map(|bt| BackwardTransfer {
+            pk_dest: bt.pk_dest,
+            amount: bt.amount,
+        })
+        .collect())
+}
+
+fn deserialize_checked<T: CanonicalDeserialize + SemanticallyValid>(
+    bytes: *const u8,
+    len: usize,
+    compressed: Option<bool>,
+) -> CResult<T> {
+    deserialize_from_buffer(read_slice(bytes, len)?, Some(true), compressed)
+        .map_err(|_| ZendooError::InvalidInput)
+}
+
+fn serialize<T: CanonicalSerialize>(obj: &T, compressed: Option<bool>) -> CResult<Vec<u8>> {
+    serialize_to_buffer(obj, compressed).map_err(|_| ZendooError::CryptoError)
+}
+
+fn write_out<T>(out: *mut T, value: T) -> CResult<()> {
+    if out.is_null() {
+        return Err(ZendooError::NullPointer);
+    }
+    unsafe { out.write(value) };
+    Ok(())
+}
+
+fn write_handle<T>(out: *mut *mut T, value: T) -> CResult<()> {
+    if out.is_null() {
+        return Err(ZendooError::NullPointer);
+    }
+    unsafe { out.write(Box::into_raw(Box::new(value))) };
+    Ok(())
+}
+
+fn free_handle<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(unsafe { Box::from_raw(handle) });
+    }
+}
+
+fn check(condition: bool) -> CResult<()> {
+    if condition {
+        Ok(())
+    } else {
+        Err(ZendooError::InvalidInput)
+    }
+}
+
+fn crypto<T, E>(result: Result<T, E>) -> CResult<T> {
+    result.map_err(|_| ZendooError::CryptoError)
+}
+
+////////////BUFFER
+
+/// Release a buffer returned by the library.
+#[no_mangle]
+pub extern "C" fn zendoo_buffer_free(buffer: ZendooBuffer) {
+    if !buffer.data.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)) });
+    }
+}
+
+////////////FIELD ELEMENT
+
+/// Size in bytes of a serialized field element.
+#[no_mangle]
+pub extern "C" fn zendoo_field_element_size() -> usize {
+    FIELD_SIZE
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_field_element_from_u64(
+    value: u64,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| write_handle(out, ZendooFieldElement(FieldElement::from(value))))
+}
+
+/// Deserialize a field element from `len` bytes, which must be zendoo_field_element_size().
+#[no_mangle]
+pub extern "C" fn zendoo_field_element_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        check(len == FIELD_SIZE)?;
+        let fe = deserialize_checked(bytes, len, None)?;
+        write_handle(out, ZendooFieldElement(fe))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_field_element_to_bytes(
+    fe: *const ZendooFieldElement,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = serialize(&read_ref(fe)?.0, None)?;
+        write_out(out, ZendooBuffer::from_vec(bytes))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_field_element_equals(
+    fe_1: *const ZendooFieldElement,
+    fe_2: *const ZendooFieldElement,
+    out: *mut bool,
+) -> ZendooError {
+    c_call(|| write_out(out, read_ref(fe_1)?.0 == read_ref(fe_2)?.0))
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_field_element_free(fe: *mut ZendooFieldElement) {
+    free_handle(fe)
+}
+
+////////////POSEIDON HASH
+
+/// New Poseidon hash expecting exactly `input_size` inputs. `personalization` can be NULL
+/// if `personalization_len` is 0.
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_new_constant_length(
+    input_size: usize,
+    personalization: *const *const ZendooFieldElement,
+    personalization_len: usize,
+    out: *mut *mut ZendooPoseidonHash,
+) -> ZendooError {
+    c_call(|| {
+        let personalization = read_field_elements(personalization, personalization_len)?;
+        let h = ResumablePoseidonHash::constant_length(
+            input_size,
+            if personalization.is_empty() {
+                None
+            } else {
+                Some(personalization)
+            },
+        );
+        write_handle(out, ZendooPoseidonHash(h))
+    })
+}
+
+/// New Poseidon hash accepting any number of inputs, which must be a multiple of the rate
+/// if `mod_rate` is true.
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_new_variable_length(
+    mod_rate: bool,
+    personalization: *const *const ZendooFieldElement,
+    personalization_len: usize,
+    out: *mut *mut ZendooPoseidonHash,
+) -> ZendooError {
+    c_call(|| {
+        let personalization = read_field_elements(personalization, personalization_len)?;
+        let h = ResumablePoseidonHash::variable_length(
+            mod_rate,
+            if personalization.is_empty() {
+                None
+            } else {
+                Some(personalization)
+            },
+        );
+        write_handle(out, ZendooPoseidonHash(h))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_update(
+    h: *mut ZendooPoseidonHash,
+    input: *const ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let input = read_ref(input)?;
+        read_mut(h)?.0.update(&input.0);
+        Ok(())
+    })
+}
+
+/// Absorb `len` bytes, length prefixed and packed into field elements as PoseidonHash.updateBytes()
+/// of the Java API does.
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_update_bytes(
+    h: *mut ZendooPoseidonHash,
+    bytes: *const u8,
+    len: usize,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = read_slice(bytes, len)?;
+        crypto(read_mut(h)?.0.update_bytes(bytes))
+    })
+}
+
+/// Compute the digest. Fails with ZENDOO_ERROR_CRYPTO_ERROR if the number of inputs is not
+/// the expected one.
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_finalize(
+    h: *const ZendooPoseidonHash,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let digest = crypto(read_ref(h)?.0.finalize())?;
+        write_handle(out, ZendooFieldElement(digest))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_reset(
+    h: *mut ZendooPoseidonHash,
+    personalization: *const *const ZendooFieldElement,
+    personalization_len: usize,
+) -> ZendooError {
+    c_call(|| {
+        let personalization = read_field_elements(personalization, personalization_len)?;
+        read_mut(h)?.0.reset(if personalization.is_empty() {
+            None
+        } else {
+            Some(personalization)
+        });
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_poseidon_hash_free(h: *mut ZendooPoseidonHash) {
+    free_handle(h)
+}
+
+////////////MERKLE TREE
+
+/// New Merkle tree of height `height`, optimized for appending leaves in batches of
+/// `processing_step`.
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_tree_new(
+    height: usize,
+    processing_step: usize,
+    out: *mut *mut ZendooMerkleTree,
+) -> ZendooError {
+    c_call(|| {
+        let tree = InMemoryOptimizedMerkleTree::new(height, processing_step)
+            .map_err(|_| ZendooError::InvalidInput)?;
+        write_handle(out, ZendooMerkleTree(tree))
+    })
+}
+
+/// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree is full or finalized.
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_tree_append(
+    tree: *mut ZendooMerkleTree,
+    leaf: *const ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let leaf = read_ref(leaf)?;
+        read_mut(tree)?
+            .0
+            .append(&leaf.0)
+            .map_err(|_| ZendooError::InvalidInput)
+    })
+}
+
+/// Finalize the tree: no more leaves can be appended, root and paths become available.
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_tree_finalize(tree: *mut ZendooMerkleTree) -> ZendooError {
+    c_call(|| crypto(read_mut(tree)?.0.finalize_in_place()))
+}
+
+/// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree is not finalized.
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_tree_root(
+    tree: *const ZendooMerkleTree,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let root = get_ginger_mht_root(&read_ref(tree)?.0).ok_or(ZendooError::InvalidInput)?;
+        write_handle(out, ZendooFieldElement(root))
+    })
+}
+
+/// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree is not finalized or `leaf_index` is out
+/// of range.
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_tree_get_path(
+    tree: *const ZendooMerkleTree,
+    leaf_index: u64,
+    out: *mut *mut ZendooMerklePath,
+) -> ZendooError {
+    c_call(|| {
+        let path = get_ginger_mht_path(&read_ref(tree)?.0, leaf_index)
+            .ok_or(ZendooError::InvalidInput)?;
+        write_handle(out, ZendooMerklePath(path))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_tree_free(tree: *mut ZendooMerkleTree) {
+    free_handle(tree)
+}
+
+/// Check that `path` is the path of `leaf` in the tree of height `height` with root `root`.
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_path_verify(
+    path: *const ZendooMerklePath,
+    height: usize,
+    leaf: *const ZendooFieldElement,
+    root: *const ZendooFieldElement,
+    out: *mut bool,
+) -> ZendooError {
+    c_call(|| {
+        let path = &read_ref(path)?.0;
+        let leaf = &read_ref(leaf)?.0;
+        let root = &read_ref(root)?.0;
+        let verified =
+            path.is_valid() && crypto(verify_ginger_merkle_path(path, height, leaf, root))?;
+        write_out(out, verified)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_path_to_bytes(
+    path: *const ZendooMerklePath,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = serialize(&read_ref(path)?.0, None)?;
+        write_out(out, ZendooBuffer::from_vec(bytes))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_path_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut ZendooMerklePath,
+) -> ZendooError {
+    c_call(|| {
+        let path = deserialize_checked(bytes, len, None)?;
+        write_handle(out, ZendooMerklePath(path))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_merkle_path_free(path: *mut ZendooMerklePath) {
+    free_handle(path)
+}
+
+////////////SCHNORR
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_generate_key(
+    out_public_key: *mut *mut ZendooSchnorrPublicKey,
+    out_secret_key: *mut *mut ZendooSchnorrSecretKey,
+) -> ZendooError {
+    c_call(|| {
+        if out_public_key.is_null() || out_secret_key.is_null() {
+            return Err(ZendooError::NullPointer);
+        }
+        let (pk, sk) = schnorr_generate_key();
+        write_handle(out_public_key, ZendooSchnorrPublicKey(pk))?;
+        write_handle(out_secret_key, ZendooSchnorrSecretKey(sk))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_get_public_key(
+    secret_key: *const ZendooSchnorrSecretKey,
+    out: *mut *mut ZendooSchnorrPublicKey,
+) -> ZendooError {
+    c_call(|| {
+        let pk = schnorr_get_public_key(&read_ref(secret_key)?.0);
+        write_handle(out, ZendooSchnorrPublicKey(pk))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_verify_public_key(
+    public_key: *const ZendooSchnorrPublicKey,
+    out: *mut bool,
+) -> ZendooError {
+    c_call(|| write_out(out, schnorr_verify_public_key(&read_ref(public_key)?.0)))
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_sign(
+    message: *const ZendooFieldElement,
+    secret_key: *const ZendooSchnorrSecretKey,
+    public_key: *const ZendooSchnorrPublicKey,
+    out: *mut *mut ZendooSchnorrSignature,
+) -> ZendooError {
+    c_call(|| {
+        let sig = crypto(schnorr_sign(
+            &read_ref(message)?.0,
+            &read_ref(secret_key)?.0,
+            &read_ref(public_key)?.0,
+        ))?;
+        write_handle(out, ZendooSchnorrSignature(sig))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_verify(
+    message: *const ZendooFieldElement,
+    public_key: *const ZendooSchnorrPublicKey,
+    signature: *const ZendooSchnorrSignature,
+    out: *mut bool,
+) -> ZendooError {
+    c_call(|| {
+        let verified = crypto(schnorr_verify_signature(
+            &read_ref(message)?.0,
+            &read_ref(public_key)?.0,
+            &read_ref(signature)?.0,
+        ))?;
+        write_out(out, verified)
+    })
+}
+
+/// Compressed serialization of a public key.
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_public_key_to_bytes(
+    public_key: *const ZendooSchnorrPublicKey,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = serialize(&read_ref(public_key)?.0, Some(true))?;
+        write_out(out, ZendooBuffer::from_vec(bytes))
+    })
+}
+
+/// Deserialize a compressed public key. Fails with ZENDOO_ERROR_INVALID_INPUT if the key is
+/// not valid.
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_public_key_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut ZendooSchnorrPublicKey,
+) -> ZendooError {
+    c_call(|| {
+        let pk = deserialize_checked(bytes, len, Some(true))?;
+        write_handle(out, ZendooSchnorrPublicKey(pk))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_signature_to_bytes(
+    signature: *const ZendooSchnorrSignature,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = serialize(&read_ref(signature)?.0, None)?;
+        write_out(out, ZendooBuffer::from_vec(bytes))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_signature_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut ZendooSchnorrSignature,
+) -> ZendooError {
+    c_call(|| {
+        let sig = deserialize_checked(bytes, len, None)?;
+        write_handle(out, ZendooSchnorrSignature(sig))
+    })
+}
+
+/// The memory of the key is wiped.
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_secret_key_free(secret_key: *mut ZendooSchnorrSecretKey) {
+    free_handle(secret_key)
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_public_key_free(public_key: *mut ZendooSchnorrPublicKey) {
+    free_handle(public_key)
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_schnorr_signature_free(signature: *mut ZendooSchnorrSignature) {
+    free_handle(signature)
+}
+
+////////////VRF
+
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_generate_key(
+    out_public_key: *mut *mut ZendooVrfPublicKey,
+    out_secret_key: *mut *mut ZendooVrfSecretKey,
+) -> ZendooError {
+    c_call(|| {
+        if out_public_key.is_null() || out_secret_key.is_null() {
+            return Err(ZendooError::NullPointer);
+        }
+        let (pk, sk) = vrf_generate_key();
+        write_handle(out_public_key, ZendooVrfPublicKey(pk))?;
+        write_handle(out_secret_key, ZendooVrfSecretKey(sk))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_get_public_key(
+    secret_key: *const ZendooVrfSecretKey,
+    out: *mut *mut ZendooVrfPublicKey,
+) -> ZendooError {
+    c_call(|| {
+        let pk = vrf_get_public_key(&read_ref(secret_key)?.0);
+        write_handle(out, ZendooVrfPublicKey(pk))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_verify_public_key(
+    public_key: *const ZendooVrfPublicKey,
+    out: *mut bool,
+) -> ZendooError {
+    c_call(|| write_out(out, vrf_verify_public_key(&read_ref(public_key)?.0)))
+}
+
+/// Compute the VRF proof for `message` and the corresponding output.
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_prove(
+    message: *const ZendooFieldElement,
+    secret_key: *const ZendooVrfSecretKey,
+    public_key: *const ZendooVrfPublicKey,
+    out_proof: *mut *mut ZendooVrfProof,
+    out_output: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        if out_proof.is_null() || out_output.is_null() {
+            return Err(ZendooError::NullPointer);
+        }
+        let (proof, output) = crypto(vrf_prove(
+            &read_ref(message)?.0,
+            &read_ref(secret_key)?.0,
+            &read_ref(public_key)?.0,
+        ))?;
+        write_handle(out_proof, ZendooVrfProof(proof))?;
+        write_handle(out_output, ZendooFieldElement(output))
+    })
+}
+
+/// Verify `proof` and return the VRF output. Fails with ZENDOO_ERROR_CRYPTO_ERROR if the
+/// proof is not valid.
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_proof_to_hash(
+    message: *const ZendooFieldElement,
+    public_key: *const ZendooVrfPublicKey,
+    proof: *const ZendooVrfProof,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let output = crypto(vrf_proof_to_hash(
+            &read_ref(message)?.0,
+            &read_ref(public_key)?.0,
+            &read_ref(proof)?.0,
+        ))?;
+        write_handle(out, ZendooFieldElement(output))
+    })
+}
+
+/// Compressed serialization of a public key.
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_public_key_to_bytes(
+    public_key: *const ZendooVrfPublicKey,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = serialize(&read_ref(public_key)?.0, Some(true))?;
+        write_out(out, ZendooBuffer::from_vec(bytes))
+    })
+}
+
+/// Deserialize a compressed public key. Fails with ZENDOO_ERROR_INVALID_INPUT if the key is
+/// not valid.
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_public_key_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut ZendooVrfPublicKey,
+) -> ZendooError {
+    c_call(|| {
+        let pk = deserialize_checked(bytes, len, Some(true))?;
+        write_handle(out, ZendooVrfPublicKey(pk))
+    })
+}
+
+/// Compressed serialization of a proof.
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_proof_to_bytes(
+    proof: *const ZendooVrfProof,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let bytes = serialize(&read_ref(proof)?.0, Some(true))?;
+        write_out(out, ZendooBuffer::from_vec(bytes))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_proof_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut ZendooVrfProof,
+) -> ZendooError {
+    c_call(|| {
+        let proof = deserialize_checked(bytes, len, Some(true))?;
+        write_handle(out, ZendooVrfProof(proof))
+    })
+}
+
+/// The memory of the key is wiped.
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_secret_key_free(secret_key: *mut ZendooVrfSecretKey) {
+    free_handle(secret_key)
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_public_key_free(public_key: *mut ZendooVrfPublicKey) {
+    free_handle(public_key)
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_vrf_proof_free(proof: *mut ZendooVrfProof) {
+    free_handle(proof)
+}
+
+////////////COMMITMENT TREE
+
+// Sidechain ids and transaction hashes are FIELD_SIZE bytes, public keys of forward transfers
+// FIELD_SIZE bytes, mainchain addresses ZENDOO_MC_PK_SIZE bytes. The add functions fail with
+// ZENDOO_ERROR_INVALID_INPUT if the transaction is rejected by the tree.
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_new(out: *mut *mut ZendooCommitmentTree) -> ZendooError {
+    c_call(|| write_handle(out, ZendooCommitmentTree(IncrementalCommitmentTree::create())))
+}
+
+/// Add a sidechain creation. Custom configurations, custom creation data, constant and CSW
+/// verification key are optional: pass NULL when absent.
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_add_scc(
+    tree: *mut ZendooCommitmentTree,
+    sc_id: *const ZendooFieldElement,
+    amount: u64,
+    pub_key: *const u8,
+    tx_hash: *const u8,
+    out_idx: u32,
+    withdrawal_epoch_length: u32,
+    mc_btr_request_data_length: u8,
+    custom_field_elements_configs: *const u8,
+    custom_field_elements_configs_len: usize,
+    custom_bitvector_elements_configs: *const ZendooBitVectorElementsConfig,
+    custom_bitvector_elements_configs_len: usize,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    custom_creation_data: *const u8,
+    custom_creation_data_len: usize,
+    constant: *const ZendooFieldElement,
+    cert_verification_key: *const u8,
+    cert_verification_key_len: usize,
+    csw_verification_key: *const u8,
+    csw_verification_key_len: usize,
+) -> ZendooError {
+    c_call(|| {
+        let custom_bitvector_elements_configs = read_opt_slice(
+            custom_bitvector_elements_configs,
+            custom_bitvector_elements_configs_len,
+        )?
+        .map(|configs| {
+            configs
+                .iter()
+                .map(|c| BitVectorElementsConfig {
+                    bit_vector_size_bits: c.bit_vector_size_bits,
+                    max_compressed_byte_size: c.max_compressed_byte_size,
+                })
+                .collect::<Vec<_>>()
+        });
+        let constant = if constant.is_null() {
+            None
+        } else {
+            Some(&read_ref(constant)?.0)
+        };
+
+        check(read_mut(tree)?.0.add_scc(
+            &read_ref(sc_id)?.0,
+            amount,
+            read_array(pub_key)?,
+            read_array(tx_hash)?,
+            out_idx,
+            withdrawal_epoch_length,
+            mc_btr_request_data_length,
+            read_opt_slice(
+                custom_field_elements_configs,
+                custom_field_elements_configs_len,
+            )?,
+            custom_bitvector_elements_configs.as_deref(),
+            btr_fee,
+            ft_min_amount,
+            read_opt_slice(custom_creation_data, custom_creation_data_len)?,
+            constant,
+            read_slice(cert_verification_key, cert_verification_key_len)?,
+            read_opt_slice(csw_verification_key, csw_verification_key_len)?,
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_add_fwt(
+    tree: *mut ZendooCommitmentTree,
+    sc_id: *const ZendooFieldElement,
+    amount: u64,
+    pub_key: *const u8,
+    mc_return_address: *const u8,
+    tx_hash: *const u8,
+    out_idx: u32,
+) -> ZendooError {
+    c_call(|| {
+        check(read_mut(tree)?.0.add_fwt(
+            &read_ref(sc_id)?.0,
+            amount,
+            read_array(pub_key)?,
+            read_array(mc_return_address)?,
+            read_array(tx_hash)?,
+            out_idx,
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_add_bwtr(
+    tree: *mut ZendooCommitmentTree,
+    sc_id: *const ZendooFieldElement,
+    sc_fee: u64,
+    sc_request_data: *const *const ZendooFieldElement,
+    sc_request_data_len: usize,
+    mc_destination_address: *const u8,
+    tx_hash: *const u8,
+    out_idx: u32,
+) -> ZendooError {
+    c_call(|| {
+        check(read_mut(tree)?.0.add_bwtr(
+            &read_ref(sc_id)?.0,
+            sc_fee,
+            read_field_elements(sc_request_data, sc_request_data_len)?,
+            read_array(mc_destination_address)?,
+            read_array(tx_hash)?,
+            out_idx,
+        ))
+    })
+}
+
+/// Add a certificate. `bt_list` and `custom_fields` can be NULL when empty.
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_add_cert(
+    tree: *mut ZendooCommitmentTree,
+    sc_id: *const ZendooFieldElement,
+    epoch_number: u32,
+    quality: u64,
+    bt_list: *const ZendooBackwardTransfer,
+    bt_list_len: usize,
+    custom_fields: *const *const ZendooFieldElement,
+    custom_fields_len: usize,
+    end_cumulative_sc_tx_commitment_tree_root: *const ZendooFieldElement,
+    btr_fee: u64,
+    ft_min_amount: u64,
+) -> ZendooError {
+    c_call(|| {
+        let bt_list = read_bt_list(bt_list, bt_list_len)?;
+        let custom_fields = read_field_elements(custom_fields, custom_fields_len)?;
+
+        check(read_mut(tree)?.0.add_cert(
+            &read_ref(sc_id)?.0,
+            epoch_number,
+            quality,
+            if bt_list.is_empty() {
+                None
+            } else {
+                Some(bt_list.as_slice())
+            },
+            if custom_fields.is_empty() {
+                None
+            } else {
+                Some(custom_fields)
+            },
+            &read_ref(end_cumulative_sc_tx_commitment_tree_root)?.0,
+            btr_fee,
+            ft_min_amount,
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_add_csw(
+    tree: *mut ZendooCommitmentTree,
+    sc_id: *const ZendooFieldElement,
+    amount: u64,
+    nullifier: *const ZendooFieldElement,
+    mc_pk_hash: *const u8,
+) -> ZendooError {
+    c_call(|| {
+        check(read_mut(tree)?.0.add_csw(
+            &read_ref(sc_id)?.0,
+            amount,
+            &read_ref(nullifier)?.0,
+            read_array(mc_pk_hash)?,
+        ))
+    })
+}
+
+/// Fails with ZENDOO_ERROR_INVALID_INPUT if the tree has no transactions for `sc_id`.
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_get_sc_commitment(
+    tree: *mut ZendooCommitmentTree,
+    sc_id: *const ZendooFieldElement,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let sc_id = &read_ref(sc_id)?.0;
+        let sc_commitment = read_mut(tree)?
+            .0
+            .get_sc_commitment(sc_id)
+            .ok_or(ZendooError::InvalidInput)?;
+        write_handle(out, ZendooFieldElement(sc_commitment))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_get_commitment(
+    tree: *mut ZendooCommitmentTree,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let commitment = read_mut(tree)?
+            .0
+            .get_commitment()
+            .ok_or(ZendooError::CryptoError)?;
+        write_handle(out, ZendooFieldElement(commitment))
+    })
+}
+
+/// Serialize the transactions added to the tree, in the format of CommitmentTree.serialize()
+/// of the Java API.
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_serialize_state(
+    tree: *const ZendooCommitmentTree,
+    out: *mut ZendooBuffer,
+) -> ZendooError {
+    c_call(|| {
+        let state = crypto(read_ref(tree)?.0.serialize_state())?;
+        write_out(out, ZendooBuffer::from_vec(state))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_deserialize_state(
+    state: *const u8,
+    len: usize,
+    out: *mut *mut ZendooCommitmentTree,
+) -> ZendooError {
+    c_call(|| {
+        let tree = IncrementalCommitmentTree::deserialize_state(read_slice(state, len)?)
+            .map_err(|_| ZendooError::InvalidInput)?;
+        write_handle(out, ZendooCommitmentTree(tree))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn zendoo_commitment_tree_free(tree: *mut ZendooCommitmentTree) {
+    free_handle(tree)
+}
+
+////////////NAIVE THRESHOLD SIGNATURE PROOF
+
+/// Generate the DLOG keys, required before any setup, proof creation or verification.
+#[no_mangle]
+pub extern "C" fn zendoo_init_dlog_keys(
+    proving_system: u32,
+    max_segment_size: usize,
+    supported_segment_size: usize,
+) -> ZendooError {
+    c_call(|| {
+        crypto(init_dlog_keys(
+            ZendooProvingSystem::try_from(proving_system)?.into(),
+            max_segment_size,
+            supported_segment_size,
+        ))
+    })
+}
+
+/// Generate and write to the given paths the proving and verification keys of the naive
+/// threshold signature circuit supporting `max_pks` public keys.
+#[no_mangle]
+pub extern "C" fn zendoo_naive_threshold_sig_setup(
+    proving_system: u32,
+    max_pks: usize,
+    proving_key_path: *const c_char,
+    verification_key_path: *const c_char,
+    zk: bool,
+    max_proof_size: usize,
+    max_vk_size: usize,
+    compress_pk: bool,
+    compress_vk: bool,
+) -> ZendooError {
+    c_call(|| {
+        crypto(generate_circuit_keypair(
+            get_instance_for_setup(max_pks),
+            ZendooProvingSystem::try_from(proving_system)?.into(),
+            read_path(proving_key_path)?,
+            read_path(verification_key_path)?,
+            max_proof_size,
+            max_vk_size,
+            zk,
+            Some(compress_pk),
+            Some(compress_vk),
+        ))
+    })
+}
+
+/// Compute H(H(pks), threshold), the constant of the naive threshold signature circuit.
+#[no_mangle]
+pub extern "C" fn zendoo_compute_pks_threshold_hash(
+    pks: *const *const ZendooSchnorrPublicKey,
+    num_pks: usize,
+    threshold: u64,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let pks = read_slice(pks, num_pks)?
+            .iter()
+            .map(|&pk| read_ref(pk).map(|pk| pk.0))
+            .collect::<CResult<Vec<_>>>()?;
+        let hash = crypto(compute_pks_threshold_hash(pks.as_slice(), threshold))?;
+        write_handle(out, ZendooFieldElement(hash))
+    })
+}
+
+/// Compute the message the signers of a certificate sign.
+#[no_mangle]
+pub extern "C" fn zendoo_compute_msg_to_sign(
+    sc_id: *const ZendooFieldElement,
+    epoch_number: u32,
+    end_cumulative_sc_tx_commitment_tree_root: *const ZendooFieldElement,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: *const ZendooBackwardTransfer,
+    bt_list_len: usize,
+    out: *mut *mut ZendooFieldElement,
+) -> ZendooError {
+    c_call(|| {
+        let (_, msg) = crypto(compute_msg_to_sign(
+            &read_ref(sc_id)?.0,
+            epoch_number,
+            &read_ref(end_cumulative_sc_tx_commitment_tree_root)?.0,
+            btr_fee,
+            ft_min_amount,
+            read_bt_list(bt_list, bt_list_len)?,
+        ))?;
+        write_handle(out, ZendooFieldElement(msg))
+    })
+}
+
+/// Create a naive threshold signature proof. `sigs` has one entry for each of the `num_pks`
+/// public keys, NULL if the corresponding signature is missing. The proof is written to
+/// `out_proof` and the number of valid signatures (the quality of the certificate) to
+/// `out_quality`. Fails with ZENDOO_ERROR_INVALID_INPUT if the valid signatures are less
+/// than `threshold`.
+#[no_mangle]
+pub extern "C" fn zendoo_naive_threshold_sig_create_proof(
+    pks: *const *const ZendooSchnorrPublicKey,
+    sigs: *const *const ZendooSchnorrSignature,
+    num_pks: usize,
+    sc_id: *const ZendooFieldElement,
+    epoch_number: u32,
+    end_cumulative_sc_tx_commitment_tree_root: *const ZendooFieldElement,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: *const ZendooBackwardTransfer,
+    bt_list_len: usize,
+    threshold: u64,
+    proving_key_path: *const c_char,
+    enforce_membership: bool,
+    zk: bool,
+    compressed_pk: bool,
+    compress_proof: bool,
+    out_proof: *mut ZendooBuffer,
+    out_quality: *mut u64,
+) -> ZendooError {
+    c_call(|| {
+        if out_proof.is_null() || out_quality.is_null() {
+            return Err(ZendooError::NullPointer);
+        }
+
+        let pks = read_slice(pks, num_pks)?
+            .iter()
+            .map(|&pk| read_ref(pk).map(|pk| pk.0))
+            .collect::<CResult<Vec<_>>>()?;
+        let sigs = read_slice(sigs, num_pks)?
+            .iter()
+            .map(|&sig| unsafe { sig.as_ref() }.map(|sig| sig.0))
+            .collect::<Vec<_>>();
+        let sc_id = &read_ref(sc_id)?.0;
+        let end_cumulative_sc_tx_commitment_tree_root =
+            &read_ref(end_cumulative_sc_tx_commitment_tree_root)?.0;
+
+        // Check the signatures here, so that not reaching the threshold is reported as an
+        // invalid input rather than as a failure of the prover
+        let (_, msg) = crypto(compute_msg_to_sign(
+            sc_id,
+            epoch_number,
+            end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee,
+            ft_min_amount,
+            read_bt_list(bt_list, bt_list_len)?,
+        ))?;
+        let mut valid_sigs = 0;
+        for (pk, sig) in pks.iter().zip(sigs.iter()) {
+            if let Some(sig) = sig {
+                if crypto(schnorr_verify_signature(&msg, pk, sig))? {
+                    valid_sigs += 1;
+                }
+            }
+        }
+        check(valid_sigs >= threshold)?;
+
+        let (proof, quality) = crypto(create_naive_threshold_sig_proof(
+            pks.as_slice(),
+            sigs,
+            sc_id,
+            epoch_number,
+            end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee,
+            ft_min_amount,
+            read_bt_list(bt_list, bt_list_len)?,
+            threshold,
+            read_path(proving_key_path)?,
+            enforce_membership,
+            zk,
+            compressed_pk,
+            compress_proof,
+        ))?;
+
+        write_out(out_proof, ZendooBuffer::from_vec(proof))?;
+        write_out(out_quality, quality)
+    })
+}
+
+/// Verify a naive threshold signature proof of quality `valid_sigs`, where `constant` is the
+/// one computed by zendoo_compute_pks_threshold_hash().
+#[no_mangle]
+pub extern "C" fn zendoo_naive_threshold_sig_verify_proof(
+    constant: *const ZendooFieldElement,
+    sc_id: *const ZendooFieldElement,
+    epoch_number: u32,
+    end_cumulative_sc_tx_commitment_tree_root: *const ZendooFieldElement,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: *const ZendooBackwardTransfer,
+    bt_list_len: usize,
+    valid_sigs: u64,
+    proof: *const u8,
+    proof_len: usize,
+    check_proof: bool,
+    compressed_proof: bool,
+    verification_key_path: *const c_char,
+    check_vk: bool,
+    compressed_vk: bool,
+    out: *mut bool,
+) -> ZendooError {
+    c_call(|| {
+        let proof = read_slice(proof, proof_len)?;
+        check(!proof.is_empty())?;
+
+        let verified = crypto(verify_naive_threshold_sig_proof(
+            &read_ref(constant)?.0,
+            &read_ref(sc_id)?.0,
+            epoch_number,
+            &read_ref(end_cumulative_sc_tx_commitment_tree_root)?.0,
+            btr_fee,
+            ft_min_amount,
+            read_bt_list(bt_list, bt_list_len)?,
+            valid_sigs,
+            proof.to_vec(),
+            check_proof,
+            compressed_proof,
+            read_path(verification_key_path)?,
+            check_vk,
+            compressed_vk,
+        ))?;
+        write_out(out, verified)
+    })
+}
+
+#[cfg(all(test, feature = "c-api-tests"))]
+mod test {
+    use std::os::raw::c_int;
+
+    #[link(name = "zendoo_sc_c_tests", kind = "static")]
+    extern "C" {
+        fn zendoo_c_api_tests() -> c_int;
+    }
+
+    // Run the C tests in tests/c, compiled by build.rs against the generated header
+    #[test]
+    fn c_api() {
+        assert_eq!(unsafe { zendoo_c_api_tests() }, 0);
+    }
+}
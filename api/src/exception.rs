@@ -124,6 +124,36 @@ macro_rules! ffi_export {
         }
     );
 
+    // For functions returning jstring
+    (
+        $(#[$attr:meta])*
+        fn $fn_name:ident (
+            $env:ident : $env_ty: ty, $($arg:ident : $arg_ty:ty),* $(,)*
+        ) -> jstring $body:block
+    ) => (
+        #[no_mangle]
+        $(#[$attr])*
+        pub extern "system" fn $fn_name($env: $env_ty, $($arg : $arg_ty),*) -> jobject {
+            match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(move || $body)) {
+                Ok(x) => return x,
+                Err(e1) => {
+                    match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(
+                        move || throw!(&$env, "java/lang/RuntimeException", &any_to_string(e1), std::ptr::null::<jobject>() as jobject)
+                    )) {
+                        Ok(default) => default,
+                        Err(e2) => {
+                            // At this level, _throw_inner call shouldn't panic. But if, for some reason,
+                            // it panics again, then we have no choice but to abort the process (to avoid
+                            // unwinding across the FFI)
+                            eprintln!("{:?}", &any_to_string(e2));
+                            std::process::abort();
+                        }
+                    }
+                }
+            }
+        }
+    );
+
     // For functions returning a type implementing Default
     (
         $(#[$attr:meta])*
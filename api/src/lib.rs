@@ -15,13 +15,14 @@ extern crate jni;
 use algebra::{serialize::*, SemanticallyValid};
 use cctp_primitives::{
     proving_system::{init_dlog_keys, ProvingSystem},
-    utils::{data_structures::*, mht::*, poseidon_hash::*, serialization::*},
+    utils::{data_structures::*, mht::*, serialization::*},
 };
 use demo_circuit::{generate_circuit_keypair, get_instance_for_setup, type_mapping::*};
 use std::{any::type_name, path::Path};
 
-mod cctp_calls;
-use cctp_calls::*;
+use zendoo_sc_lib::*;
+
+mod c_api;
 
 #[macro_use]
 mod exception;
@@ -120,29 +121,71 @@ fn serialize_from_jobject<T: CanonicalSerialize>(
         .expect("Cannot write object.")
 }
 
-fn parse_jbyte_array_to_vec(_env: &JNIEnv, java_byte_array: &jbyteArray, length: usize) -> Vec<u8> {
-    let vec = _env
-        .convert_byte_array(*java_byte_array)
-        .expect("Should be able to convert to Rust array");
+fn serialize_secret_from_jobject<T: CanonicalSerialize + Zeroize>(
+    _env: &JNIEnv,
+    obj: JObject,
+    ptr_name: &str,
+) -> jbyteArray {
+    let pointer = _env
+        .get_field(obj, ptr_name, "J")
+        .expect("Cannot get object raw pointer.");
+
+    let secret = read_raw_pointer(&_env, pointer.j().unwrap() as *const Zeroizing<T>);
 
-    if vec.len() != length {
-        panic!(
-            "Retrieved array size {} expected to be {}.",
-            vec.len(),
-            length
-        );
+    // Intermediate buffer is wiped when dropped
+    let secret_bytes = serialize_secret(&**secret)
+        .unwrap_or_else(|_| panic!("unable to write {} to buffer", type_name::<T>()));
+
+    _env.byte_array_from_slice(secret_bytes.as_slice())
+        .expect("Cannot write object.")
+}
+
+fn deserialize_secret_to_jobject<T: CanonicalDeserialize + SemanticallyValid + Zeroize>(
+    _env: &JNIEnv,
+    obj_bytes: jbyteArray,
+    class_path: &str,
+) -> jobject {
+    let obj_bytes = Zeroizing::new(
+        _env.convert_byte_array(obj_bytes)
+            .expect("Cannot read bytes."),
+    );
+
+    match deserialize_secret::<T>(obj_bytes.as_slice()) {
+        Ok(secret) => *return_jobject(&_env, secret, class_path),
+        Err(_) => std::ptr::null::<jobject>() as jobject,
     }
+}
+
+fn json_from_jobject<F: JsonFormat>(_env: &JNIEnv, obj: JObject, ptr_name: &str) -> jstring {
+    let pointer = _env
+        .get_field(obj, ptr_name, "J")
+        .expect("Cannot get object raw pointer.");
 
-    vec
+    let json = F::to_json(read_raw_pointer(
+        &_env,
+        pointer.j().unwrap() as *const F::Object,
+    ))
+    .unwrap_or_else(|_| panic!("unable to write {} to JSON", F::JSON_TYPE));
+
+    _env.new_string(json)
+        .expect("Cannot write JSON string.")
+        .into_inner()
 }
 
-fn get_byte_array(_env: &JNIEnv, java_byte_array: &jbyteArray, buffer: &mut [u8]) {
-    let vec = _env
-        .convert_byte_array(*java_byte_array)
-        .expect("Should be able to convert to Rust array");
+fn json_to_jobject<F: JsonFormat>(_env: &JNIEnv, json: JString, class_path: &str) -> jobject {
+    let json: String = _env
+        .get_string(json)
+        .expect("Should be able to read jstring as Rust String")
+        .into();
 
-    for (pos, e) in vec.iter().enumerate() {
-        buffer[pos] = *e;
+    match F::from_json(&json) {
+        Ok(obj) => *return_jobject(&_env, obj, class_path),
+        Err(e) => throw!(
+            _env,
+            "java/lang/IllegalArgumentException",
+            &format!("Invalid {} JSON: {}", F::JSON_TYPE, e),
+            JObject::null().into_inner()
+        ),
     }
 }
 
@@ -151,12 +194,28 @@ use cctp_primitives::bit_vector::merkle_tree::{
 };
 use cctp_primitives::proving_system::{check_proof_vk_size, ZendooVerifierKey};
 use cctp_primitives::utils::compute_sc_id;
-use jni::objects::{JClass, JObject, JString, JValue};
-use jni::sys::{jboolean, jbyte, jbyteArray, jint, jlong, jobject, jobjectArray};
+use jni::objects::{JClass, JObject, JString, JThrowable, JValue};
+use jni::sys::{
+    jboolean, jbyte, jbyteArray, jint, jlong, jlongArray, jobject, jobjectArray, jstring,
+};
 use jni::sys::{JNI_FALSE, JNI_TRUE};
 use jni::JNIEnv;
 use std::convert::TryInto;
 
+fn into_i8(v: Vec<u8>) -> Vec<i8> {
+    // first, make sure v's destructor doesn't free the data
+    // it thinks it owns when it goes out of scope
+    let mut v = std::mem::ManuallyDrop::new(v);
+
+    // then, pick apart the existing Vec
+    let p = v.as_mut_ptr();
+    let len = v.len();
+    let cap = v.capacity();
+
+    // finally, adopt the data into a new Vec
+    unsafe { Vec::from_raw_parts(p as *mut i8, len, cap) }
+}
+
 //Field element related functions
 
 fn return_field_element(_env: &JNIEnv, fe: FieldElement) -> jobject {
@@ -206,6 +265,29 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_FieldElement_nativeToJson(
+        _env: JNIEnv,
+        _field_element: JObject,
+    ) -> jstring {
+        json_from_jobject::<FieldElementJson>(&_env, _field_element, "fieldElementPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_FieldElement_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<FieldElementJson>(
+            &_env,
+            _json,
+            "com/horizen/librustsidechains/FieldElement",
+        )
+    }
+);
+
 ffi_export!(
     fn Java_com_horizen_librustsidechains_FieldElement_nativeCreateRandom(
         _env: JNIEnv,
@@ -347,6 +429,29 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrPublicKey_nativeToJson(
+        _env: JNIEnv,
+        _public_key: JObject,
+    ) -> jstring {
+        json_from_jobject::<SchnorrPublicKeyJson>(&_env, _public_key, "publicKeyPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrPublicKey_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<SchnorrPublicKeyJson>(
+            &_env,
+            _json,
+            "com/horizen/schnorrnative/SchnorrPublicKey",
+        )
+    }
+);
+
 ffi_export!(
     fn Java_com_horizen_schnorrnative_SchnorrPublicKey_nativeFreePublicKey(
         _env: JNIEnv,
@@ -379,7 +484,7 @@ ffi_export!(
         _env: JNIEnv,
         _schnorr_secret_key: JObject,
     ) -> jbyteArray {
-        serialize_from_jobject::<SchnorrSk>(&_env, _schnorr_secret_key, "secretKeyPointer", None)
+        serialize_secret_from_jobject::<SchnorrSk>(&_env, _schnorr_secret_key, "secretKeyPointer")
     }
 );
 
@@ -389,13 +494,7 @@ ffi_export!(
         _schnorr_secret_key_class: JClass,
         _secret_key_bytes: jbyteArray,
     ) -> jobject {
-        deserialize_to_jobject::<SchnorrSk>(
-            &_env,
-            _secret_key_bytes,
-            None,
-            None,
-            "com/horizen/schnorrnative/SchnorrSecretKey",
-        )
+        deserialize_secret_to_jobject::<SchnorrSk>(&_env, _secret_key_bytes, "com/horizen/schnorrnative/SchnorrSecretKey")
     }
 );
 
@@ -408,7 +507,7 @@ ffi_export!(
             .get_field(_schnorr_secret_key, "secretKeyPointer", "J")
             .expect("Cannot get secret key pointer.");
 
-        let secret_key = secret_key_pointer.j().unwrap() as *mut SchnorrSk;
+        let secret_key = secret_key_pointer.j().unwrap() as *mut ZeroizingSchnorrSk;
 
         if secret_key.is_null() {
             return;
@@ -417,6 +516,95 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrSecretKey_nativeEquals(
+        _env: JNIEnv,
+        _schnorr_secret_key_1: JObject,
+        _schnorr_secret_key_2: JObject,
+    ) -> jboolean {
+        let secret_key_1 = {
+            let s = _env
+                .get_field(_schnorr_secret_key_1, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer_1");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingSchnorrSk)
+        };
+
+        let secret_key_2 = {
+            let s = _env
+                .get_field(_schnorr_secret_key_2, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer_2");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingSchnorrSk)
+        };
+
+        // Constant time comparison
+        match secret_key_1 == secret_key_2 {
+            true => JNI_TRUE,
+            false => JNI_FALSE,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrSecretKey_nativeGetKeystoreSize(
+        _env: JNIEnv,
+        _schnorr_secret_key_class: JClass,
+    ) -> jint {
+        KEYSTORE_SIZE as jint
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrSecretKey_nativeEncrypt(
+        _env: JNIEnv,
+        _schnorr_secret_key: JObject,
+        _password: jbyteArray,
+    ) -> jbyteArray {
+        let secret_key = {
+            let s = _env
+                .get_field(_schnorr_secret_key, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingSchnorrSk)
+        };
+
+        let password = Zeroizing::new(
+            _env.convert_byte_array(_password)
+                .expect("Cannot read password bytes."),
+        );
+
+        let keystore = schnorr_encrypt_secret_key(secret_key, password.as_slice())
+            .expect("Should be able to encrypt secret key");
+
+        _env.byte_array_from_slice(keystore.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrSecretKey_nativeDecrypt(
+        _env: JNIEnv,
+        _schnorr_secret_key_class: JClass,
+        _keystore: jbyteArray,
+        _password: jbyteArray,
+    ) -> jobject {
+        let keystore = _env
+            .convert_byte_array(_keystore)
+            .expect("Cannot read keystore bytes.");
+
+        let password = Zeroizing::new(
+            _env.convert_byte_array(_password)
+                .expect("Cannot read password bytes."),
+        );
+
+        match schnorr_decrypt_secret_key(keystore.as_slice(), password.as_slice()) {
+            Ok(secret_key) => *return_jobject(&_env, secret_key, "com/horizen/schnorrnative/SchnorrSecretKey"),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
 //Public VRF key utility functions
 ffi_export!(
     fn Java_com_horizen_vrfnative_VRFPublicKey_nativeGetPublicKeySize(
@@ -460,6 +648,25 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFPublicKey_nativeToJson(
+        _env: JNIEnv,
+        _public_key: JObject,
+    ) -> jstring {
+        json_from_jobject::<VRFPublicKeyJson>(&_env, _public_key, "publicKeyPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFPublicKey_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<VRFPublicKeyJson>(&_env, _json, "com/horizen/vrfnative/VRFPublicKey")
+    }
+);
+
 ffi_export!(
     fn Java_com_horizen_vrfnative_VRFPublicKey_nativeFreePublicKey(
         _env: JNIEnv,
@@ -493,7 +700,7 @@ ffi_export!(
         _env: JNIEnv,
         _vrf_secret_key: JObject,
     ) -> jbyteArray {
-        serialize_from_jobject::<VRFSk>(&_env, _vrf_secret_key, "secretKeyPointer", None)
+        serialize_secret_from_jobject::<VRFSk>(&_env, _vrf_secret_key, "secretKeyPointer")
     }
 );
 
@@ -503,13 +710,7 @@ ffi_export!(
         _vrf_secret_key_class: JClass,
         _secret_key_bytes: jbyteArray,
     ) -> jobject {
-        deserialize_to_jobject::<VRFSk>(
-            &_env,
-            _secret_key_bytes,
-            None,
-            None,
-            "com/horizen/vrfnative/VRFSecretKey",
-        )
+        deserialize_secret_to_jobject::<VRFSk>(&_env, _secret_key_bytes, "com/horizen/vrfnative/VRFSecretKey")
     }
 );
 
@@ -522,7 +723,7 @@ ffi_export!(
             .get_field(_vrf_secret_key, "secretKeyPointer", "J")
             .expect("Cannot get secret key pointer.");
 
-        let secret_key = secret_key_pointer.j().unwrap() as *mut SchnorrSk;
+        let secret_key = secret_key_pointer.j().unwrap() as *mut ZeroizingVRFSk;
 
         if secret_key.is_null() {
             return;
@@ -531,6 +732,95 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFSecretKey_nativeEquals(
+        _env: JNIEnv,
+        _vrf_secret_key_1: JObject,
+        _vrf_secret_key_2: JObject,
+    ) -> jboolean {
+        let secret_key_1 = {
+            let s = _env
+                .get_field(_vrf_secret_key_1, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer_1");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingVRFSk)
+        };
+
+        let secret_key_2 = {
+            let s = _env
+                .get_field(_vrf_secret_key_2, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer_2");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingVRFSk)
+        };
+
+        // Constant time comparison
+        match secret_key_1 == secret_key_2 {
+            true => JNI_TRUE,
+            false => JNI_FALSE,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFSecretKey_nativeGetKeystoreSize(
+        _env: JNIEnv,
+        _vrf_secret_key_class: JClass,
+    ) -> jint {
+        KEYSTORE_SIZE as jint
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFSecretKey_nativeEncrypt(
+        _env: JNIEnv,
+        _vrf_secret_key: JObject,
+        _password: jbyteArray,
+    ) -> jbyteArray {
+        let secret_key = {
+            let s = _env
+                .get_field(_vrf_secret_key, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingVRFSk)
+        };
+
+        let password = Zeroizing::new(
+            _env.convert_byte_array(_password)
+                .expect("Cannot read password bytes."),
+        );
+
+        let keystore = vrf_encrypt_secret_key(secret_key, password.as_slice())
+            .expect("Should be able to encrypt secret key");
+
+        _env.byte_array_from_slice(keystore.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFSecretKey_nativeDecrypt(
+        _env: JNIEnv,
+        _vrf_secret_key_class: JClass,
+        _keystore: jbyteArray,
+        _password: jbyteArray,
+    ) -> jobject {
+        let keystore = _env
+            .convert_byte_array(_keystore)
+            .expect("Cannot read keystore bytes.");
+
+        let password = Zeroizing::new(
+            _env.convert_byte_array(_password)
+                .expect("Cannot read password bytes."),
+        );
+
+        match vrf_decrypt_secret_key(keystore.as_slice(), password.as_slice()) {
+            Ok(secret_key) => *return_jobject(&_env, secret_key, "com/horizen/vrfnative/VRFSecretKey"),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
 //Schnorr signature utility functions
 ffi_export!(
     fn Java_com_horizen_schnorrnative_SchnorrSignature_nativeGetSignatureSize(
@@ -567,6 +857,29 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrSignature_nativeToJson(
+        _env: JNIEnv,
+        _signature: JObject,
+    ) -> jstring {
+        json_from_jobject::<SchnorrSignatureJson>(&_env, _signature, "signaturePointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_schnorrnative_SchnorrSignature_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<SchnorrSignatureJson>(
+            &_env,
+            _json,
+            "com/horizen/schnorrnative/SchnorrSignature",
+        )
+    }
+);
+
 ffi_export!(
     fn Java_com_horizen_schnorrnative_SchnorrSignature_nativeIsValidSignature(
         _env: JNIEnv,
@@ -611,8 +924,11 @@ ffi_export!(
     ) -> jobject {
         let (pk, sk) = schnorr_generate_key();
 
-        let secret_key_object =
-            return_jobject(&_env, sk, "com/horizen/schnorrnative/SchnorrSecretKey");
+        let secret_key_object = return_jobject(
+            &_env,
+            sk,
+            "com/horizen/schnorrnative/SchnorrSecretKey",
+        );
         let public_key_object =
             return_jobject(&_env, pk, "com/horizen/schnorrnative/SchnorrPublicKey");
 
@@ -651,7 +967,7 @@ ffi_export!(
                 .get_field(sk_object, "secretKeyPointer", "J")
                 .expect("Should be able to get field secretKeyPointer");
 
-            read_raw_pointer(&_env, s.j().unwrap() as *const SchnorrSk)
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingSchnorrSk)
         };
 
         //Read pk
@@ -725,7 +1041,7 @@ ffi_export!(
             .get_field(_secret_key, "secretKeyPointer", "J")
             .expect("Should be able to get field secretKeyPointer")
             .j()
-            .unwrap() as *const SchnorrSk;
+            .unwrap() as *const ZeroizingSchnorrSk;
 
         let secret_key = read_raw_pointer(&_env, sk);
 
@@ -798,6 +1114,7 @@ ffi_export!(
         _class: JClass,
         _input_size: jint,
         _personalization: jobjectArray,
+        _resumable: jboolean,
     ) -> jobject {
         //Read _personalization as array of FieldElement
         let personalization_len = _env
@@ -820,15 +1137,18 @@ ffi_export!(
             personalization.push(field);
         }
 
+        let personalization = if personalization.is_empty() {
+            None
+        } else {
+            Some(personalization)
+        };
+
         //Instantiate PoseidonHash
-        let h = get_poseidon_hash_constant_length(
-            _input_size as usize,
-            if personalization.is_empty() {
-                None
-            } else {
-                Some(personalization)
-            },
-        );
+        let h = if _resumable == JNI_TRUE {
+            ResumablePoseidonHash::resumable_constant_length(_input_size as usize, personalization)
+        } else {
+            ResumablePoseidonHash::constant_length(_input_size as usize, personalization)
+        };
 
         //Return PoseidonHash instance
         return_jobject(&_env, h, "com/horizen/poseidonnative/PoseidonHash").into_inner()
@@ -841,6 +1161,7 @@ ffi_export!(
         _class: JClass,
         _mod_rate: jboolean,
         _personalization: jobjectArray,
+        _resumable: jboolean,
     ) -> jobject {
         //Read _personalization as array of FieldElement
         let personalization_len = _env
@@ -863,15 +1184,18 @@ ffi_export!(
             personalization.push(field);
         }
 
+        let personalization = if personalization.is_empty() {
+            None
+        } else {
+            Some(personalization)
+        };
+
         //Instantiate PoseidonHash
-        let h = get_poseidon_hash_variable_length(
-            _mod_rate == JNI_TRUE,
-            if personalization.is_empty() {
-                None
-            } else {
-                Some(personalization)
-            },
-        );
+        let h = if _resumable == JNI_TRUE {
+            ResumablePoseidonHash::resumable_variable_length(_mod_rate == JNI_TRUE, personalization)
+        } else {
+            ResumablePoseidonHash::variable_length(_mod_rate == JNI_TRUE, personalization)
+        };
 
         //Return PoseidonHash instance
         return_jobject(&_env, h, "com/horizen/poseidonnative/PoseidonHash").into_inner()
@@ -890,7 +1214,7 @@ ffi_export!(
                 .get_field(_h, "poseidonHashPointer", "J")
                 .expect("Should be able to get field poseidonHashPointer");
 
-            read_mut_raw_pointer(&_env, h.j().unwrap() as *mut FieldHash)
+            read_mut_raw_pointer(&_env, h.j().unwrap() as *mut ResumablePoseidonHash)
         };
 
         //Read input
@@ -902,7 +1226,7 @@ ffi_export!(
             read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
         };
 
-        update_poseidon_hash(digest, input);
+        digest.update(input);
     }
 );
 
@@ -917,11 +1241,11 @@ ffi_export!(
                 .get_field(_h, "poseidonHashPointer", "J")
                 .expect("Should be able to get field poseidonHashPointer");
 
-            read_raw_pointer(&_env, h.j().unwrap() as *const FieldHash)
+            read_raw_pointer(&_env, h.j().unwrap() as *const ResumablePoseidonHash)
         };
 
         //Get digest
-        let fe = match finalize_poseidon_hash(digest) {
+        let fe = match digest.finalize() {
             Ok(fe) => fe,
             Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
         };
@@ -942,7 +1266,7 @@ ffi_export!(
                 .get_field(_h, "poseidonHashPointer", "J")
                 .expect("Should be able to get field poseidonHashPointer");
 
-            read_mut_raw_pointer(&_env, h.j().unwrap() as *mut FieldHash)
+            read_mut_raw_pointer(&_env, h.j().unwrap() as *mut ResumablePoseidonHash)
         };
 
         //Read _personalization as array of FieldElement
@@ -972,40 +1296,330 @@ ffi_export!(
             Some(personalization)
         };
 
-        reset_poseidon_hash(digest, personalization)
+        digest.reset(personalization)
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeFreePoseidonHash(
+    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeUpdateBytes(
         _env: JNIEnv,
         _h: JObject,
+        _input: jbyteArray,
     ) {
-        let h_pointer = _env
-            .get_field(_h, "poseidonHashPointer", "J")
-            .expect("Cannot get poseidonHashPointer");
+        //Read PoseidonHash instance
+        let digest = {
+            let h = _env
+                .get_field(_h, "poseidonHashPointer", "J")
+                .expect("Should be able to get field poseidonHashPointer");
+
+            read_mut_raw_pointer(&_env, h.j().unwrap() as *mut ResumablePoseidonHash)
+        };
 
-        let h = h_pointer.j().unwrap() as *mut FieldHash;
+        //Read input
+        let input = _env
+            .convert_byte_array(_input)
+            .expect("Cannot read input bytes.");
 
-        if h.is_null() {
-            return;
-        }
-        drop(unsafe { Box::from_raw(h) });
+        digest
+            .update_bytes(input.as_slice())
+            .expect("Should be able to update PoseidonHash with bytes");
     }
 );
 
-//Merkle tree functions
-
-//////////// MERKLE PATH
-
 ffi_export!(
-    fn Java_com_horizen_merkletreenative_MerklePath_nativeVerify(
+    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeHashBytes(
         _env: JNIEnv,
-        _path: JObject,
-        _height: jint,
-        _leaf: JObject,
-        _root: JObject,
-    ) -> jboolean {
+        _class: JClass,
+        _domain: jbyteArray,
+        _data: jbyteArray,
+    ) -> jobject {
+        //Read domain and data
+        let domain = _env
+            .convert_byte_array(_domain)
+            .expect("Cannot read domain bytes.");
+
+        let data = _env
+            .convert_byte_array(_data)
+            .expect("Cannot read data bytes.");
+
+        //Get digest
+        let fe = match poseidon_hash_bytes(domain.as_slice(), data.as_slice()) {
+            Ok(fe) => fe,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        return_field_element(&_env, fe)
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeClone(
+        _env: JNIEnv,
+        _h: JObject,
+    ) -> jobject {
+        //Read PoseidonHash instance
+        let digest = {
+            let h = _env
+                .get_field(_h, "poseidonHashPointer", "J")
+                .expect("Should be able to get field poseidonHashPointer");
+
+            read_raw_pointer(&_env, h.j().unwrap() as *const ResumablePoseidonHash)
+        };
+
+        //Return a copy of the PoseidonHash instance
+        match digest.try_clone() {
+            Ok(cloned) => {
+                return_jobject(&_env, cloned, "com/horizen/poseidonnative/PoseidonHash")
+                    .into_inner()
+            }
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalStateException",
+                &e.to_string(),
+                JObject::null().into_inner()
+            ),
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeSerializeState(
+        _env: JNIEnv,
+        _h: JObject,
+    ) -> jbyteArray {
+        //Read PoseidonHash instance
+        let digest = {
+            let h = _env
+                .get_field(_h, "poseidonHashPointer", "J")
+                .expect("Should be able to get field poseidonHashPointer");
+
+            read_raw_pointer(&_env, h.j().unwrap() as *const ResumablePoseidonHash)
+        };
+
+        let state = match digest.serialize_state() {
+            Ok(state) => state,
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalStateException",
+                &e.to_string(),
+                JObject::null().into_inner()
+            ),
+        };
+
+        _env.byte_array_from_slice(state.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeDeserializeState(
+        _env: JNIEnv,
+        _class: JClass,
+        _state: jbyteArray,
+    ) -> jobject {
+        //Read state
+        let state = _env
+            .convert_byte_array(_state)
+            .expect("Cannot read state bytes.");
+
+        //Restore PoseidonHash instance
+        let h = match ResumablePoseidonHash::deserialize_state(state.as_slice()) {
+            Ok(h) => h,
+            Err(_) => return std::ptr::null::<jobject>() as jobject,
+        };
+
+        return_jobject(&_env, h, "com/horizen/poseidonnative/PoseidonHash").into_inner()
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonHash_nativeFreePoseidonHash(
+        _env: JNIEnv,
+        _h: JObject,
+    ) {
+        let h_pointer = _env
+            .get_field(_h, "poseidonHashPointer", "J")
+            .expect("Cannot get poseidonHashPointer");
+
+        let h = h_pointer.j().unwrap() as *mut ResumablePoseidonHash;
+
+        if h.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(h) });
+    }
+);
+
+//Poseidon commitment, PRF and KDF functions
+
+fn read_field_elements(_env: &JNIEnv, _fes: jobjectArray) -> Vec<FieldElement> {
+    let fes_len = _env
+        .get_array_length(_fes)
+        .expect("Should be able to get field elements array size");
+
+    let mut fes = Vec::with_capacity(fes_len as usize);
+    for i in 0..fes_len {
+        let fe_object = _env
+            .get_object_array_element(_fes, i)
+            .unwrap_or_else(|_| panic!("Should be able to get elem {} of field elements array", i));
+
+        let fe = {
+            let f = _env
+                .get_field(fe_object, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        fes.push(*fe);
+    }
+    fes
+}
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonPrimitives_nativeCommit(
+        _env: JNIEnv,
+        _class: JClass,
+        _message: jobjectArray,
+        _randomness: JObject,
+    ) -> jobject {
+        //Read message and randomness
+        let message = read_field_elements(&_env, _message);
+
+        let randomness = {
+            let r = _env
+                .get_field(_randomness, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, r.j().unwrap() as *const FieldElement)
+        };
+
+        //Compute commitment
+        let commitment = match poseidon_commit(message.as_slice(), randomness) {
+            Ok(commitment) => commitment,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        return_field_element(&_env, commitment)
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonPrimitives_nativeVerifyCommitment(
+        _env: JNIEnv,
+        _class: JClass,
+        _commitment: JObject,
+        _message: jobjectArray,
+        _randomness: JObject,
+    ) -> jboolean {
+        //Read commitment, message and randomness
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, c.j().unwrap() as *const FieldElement)
+        };
+
+        let message = read_field_elements(&_env, _message);
+
+        let randomness = {
+            let r = _env
+                .get_field(_randomness, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, r.j().unwrap() as *const FieldElement)
+        };
+
+        //Verify opening
+        match verify_poseidon_commitment(commitment, message.as_slice(), randomness) {
+            Ok(true) => JNI_TRUE,
+            _ => JNI_FALSE,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonPrimitives_nativePrf(
+        _env: JNIEnv,
+        _class: JClass,
+        _key: JObject,
+        _input: JObject,
+    ) -> jobject {
+        //Read key and input
+        let key = {
+            let k = _env
+                .get_field(_key, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, k.j().unwrap() as *const FieldElement)
+        };
+
+        let input = {
+            let i = _env
+                .get_field(_input, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
+        };
+
+        //Compute PRF
+        let output = match poseidon_prf(key, input) {
+            Ok(output) => output,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        return_field_element(&_env, output)
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_poseidonnative_PoseidonPrimitives_nativeKdf(
+        _env: JNIEnv,
+        _class: JClass,
+        _key: JObject,
+        _domain: JObject,
+        _index: jlong,
+    ) -> jobject {
+        //Read key and domain
+        let key = {
+            let k = _env
+                .get_field(_key, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, k.j().unwrap() as *const FieldElement)
+        };
+
+        let domain = {
+            let d = _env
+                .get_field(_domain, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, d.j().unwrap() as *const FieldElement)
+        };
+
+        //Derive key
+        let derived_key = match poseidon_kdf(key, domain, _index as u64) {
+            Ok(derived_key) => derived_key,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        return_field_element(&_env, derived_key)
+    }
+);
+
+//Merkle tree functions
+
+//////////// MERKLE PATH
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerklePath_nativeVerify(
+        _env: JNIEnv,
+        _path: JObject,
+        _height: jint,
+        _leaf: JObject,
+        _root: JObject,
+    ) -> jboolean {
         let leaf = {
             let fe = _env
                 .get_field(_leaf, "fieldElementPointer", "J")
@@ -1212,6 +1826,25 @@ ffi_export!(
     }
 );
 
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerklePath_nativeToJson(
+        _env: JNIEnv,
+        _path: JObject,
+    ) -> jstring {
+        json_from_jobject::<MerklePathJson>(&_env, _path, "merklePathPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerklePath_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<MerklePathJson>(&_env, _json, "com/horizen/merkletreenative/MerklePath")
+    }
+);
+
 ffi_export!(
     fn Java_com_horizen_merkletreenative_MerklePath_nativeFreeMerklePath(
         _env: JNIEnv,
@@ -1225,79 +1858,258 @@ ffi_export!(
     }
 );
 
+//////////// MERKLE MULTI-PROOF
+
 ffi_export!(
-    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeInit(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeGetHeight(
         _env: JNIEnv,
-        _class: JClass,
-        _height: jint,
-        _processing_step: jlong,
-    ) -> jobject {
-        // Create new InMemoryOptimizedMerkleTree Rust side
-        let mt = new_ginger_mht(_height as usize, _processing_step as usize);
+        _proof: JObject,
+    ) -> jint {
+        let proof = {
+            let p = _env
+                .get_field(_proof, "merkleMultiProofPointer", "J")
+                .expect("Should be able to get field merkleMultiProofPointer");
 
-        // Create and return new InMemoryOptimizedMerkleTree Java side
-        match mt {
-            Ok(mt) => return_jobject(
-                &_env,
-                mt,
-                "com/horizen/merkletreenative/InMemoryOptimizedMerkleTree",
-            )
-            .into_inner(),
-            Err(_) => std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
-        }
+            read_raw_pointer(&_env, p.j().unwrap() as *const MerkleMultiProof)
+        };
+
+        proof.height() as jint
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeAppend(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeGetLeafIndices(
         _env: JNIEnv,
-        _tree: JObject,
-        _leaf: JObject,
-    ) -> jboolean {
-        let leaf = {
-            let fe = _env
-                .get_field(_leaf, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer");
+        _proof: JObject,
+    ) -> jlongArray {
+        let proof = {
+            let p = _env
+                .get_field(_proof, "merkleMultiProofPointer", "J")
+                .expect("Should be able to get field merkleMultiProofPointer");
 
-            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, p.j().unwrap() as *const MerkleMultiProof)
         };
 
-        let tree = {
-            let t = _env
-                .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
-                .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
+        let leaf_indices = proof
+            .leaf_indices()
+            .iter()
+            .map(|&i| i as jlong)
+            .collect::<Vec<_>>();
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut GingerMHT)
-        };
+        let result = _env
+            .new_long_array(leaf_indices.len() as i32)
+            .expect("Should be able to create leaf indices array");
+        _env.set_long_array_region(result, 0, leaf_indices.as_slice())
+            .expect("Should be able to write leaf indices");
 
-        match append_leaf_to_ginger_mht(tree, leaf) {
-            Ok(_) => JNI_TRUE,
-            Err(_) => JNI_FALSE,
-        }
+        result
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeFinalize(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeVerify(
         _env: JNIEnv,
-        _tree: JObject,
-    ) -> jobject {
-        let tree = {
-            let t = _env
-                .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
-                .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
+        _proof: JObject,
+        _leaves: jobjectArray,
+        _root: JObject,
+    ) -> jboolean {
+        let proof = {
+            let p = _env
+                .get_field(_proof, "merkleMultiProofPointer", "J")
+                .expect("Should be able to get field merkleMultiProofPointer");
 
-            read_raw_pointer(&_env, t.j().unwrap() as *const GingerMHT)
+            read_raw_pointer(&_env, p.j().unwrap() as *const MerkleMultiProof)
         };
 
-        match finalize_ginger_mht(tree) {
-            Ok(tree_copy) => return_jobject(
-                &_env,
-                tree_copy,
-                "com/horizen/merkletreenative/InMemoryOptimizedMerkleTree",
-            )
-            .into_inner(),
-            Err(_) => std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        let leaves_len = _env
+            .get_array_length(_leaves)
+            .expect("Should be able to get leaves array size");
+
+        let mut leaves = Vec::with_capacity(leaves_len as usize);
+        for i in 0..leaves_len {
+            let leaf_object = _env
+                .get_object_array_element(_leaves, i)
+                .unwrap_or_else(|_| panic!("Should be able to get elem {} of leaves array", i));
+
+            let leaf = {
+                let fe = _env
+                    .get_field(leaf_object, "fieldElementPointer", "J")
+                    .expect("Should be able to get field fieldElementPointer");
+
+                read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+            };
+
+            leaves.push(*leaf);
+        }
+
+        let root = {
+            let fe = _env
+                .get_field(_root, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
+
+        match proof.verify(leaves.as_slice(), root) {
+            Ok(true) => JNI_TRUE,
+            _ => JNI_FALSE,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeSerialize(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jbyteArray {
+        let proof = {
+            let p = _env
+                .get_field(_proof, "merkleMultiProofPointer", "J")
+                .expect("Should be able to get field merkleMultiProofPointer");
+
+            read_raw_pointer(&_env, p.j().unwrap() as *const MerkleMultiProof)
+        };
+
+        let proof_bytes = proof
+            .to_bytes()
+            .expect("Should be able to serialize MerkleMultiProof");
+
+        _env.byte_array_from_slice(proof_bytes.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof_bytes: jbyteArray,
+    ) -> jobject {
+        let proof_bytes = _env
+            .convert_byte_array(_proof_bytes)
+            .expect("Cannot read proof bytes.");
+
+        match MerkleMultiProof::from_bytes(proof_bytes.as_slice()) {
+            Ok(proof) => return_jobject(
+                &_env,
+                proof,
+                "com/horizen/merkletreenative/MerkleMultiProof",
+            )
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeToJson(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jstring {
+        json_from_jobject::<MerkleMultiProofJson>(&_env, _proof, "merkleMultiProofPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<MerkleMultiProofJson>(
+            &_env,
+            _json,
+            "com/horizen/merkletreenative/MerkleMultiProof",
+        )
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_MerkleMultiProof_nativeFreeMerkleMultiProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof: *mut MerkleMultiProof,
+    ) {
+        if _proof.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_proof) });
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeInit(
+        _env: JNIEnv,
+        _class: JClass,
+        _height: jint,
+        _processing_step: jlong,
+    ) -> jobject {
+        // Create new InMemoryOptimizedMerkleTree Rust side
+        let mt = InMemoryOptimizedMerkleTree::new(_height as usize, _processing_step as usize);
+
+        // Create and return new InMemoryOptimizedMerkleTree Java side
+        match mt {
+            Ok(mt) => return_jobject(
+                &_env,
+                mt,
+                "com/horizen/merkletreenative/InMemoryOptimizedMerkleTree",
+            )
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeAppend(
+        _env: JNIEnv,
+        _tree: JObject,
+        _leaf: JObject,
+    ) -> jboolean {
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
+
+        let tree = {
+            let t = _env
+                .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
+                .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut InMemoryOptimizedMerkleTree)
+        };
+
+        match tree.append(leaf) {
+            Ok(_) => JNI_TRUE,
+            Err(_) => JNI_FALSE,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeFinalize(
+        _env: JNIEnv,
+        _tree: JObject,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
+                .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
+
+            read_raw_pointer(&_env, t.j().unwrap() as *const InMemoryOptimizedMerkleTree)
+        };
+
+        match tree.finalize() {
+            Ok(tree_copy) => return_jobject(
+                &_env,
+                tree_copy,
+                "com/horizen/merkletreenative/InMemoryOptimizedMerkleTree",
+            )
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
         }
     }
 );
@@ -1312,10 +2124,10 @@ ffi_export!(
                 .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
                 .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut GingerMHT)
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut InMemoryOptimizedMerkleTree)
         };
 
-        match finalize_ginger_mht_in_place(tree) {
+        match tree.finalize_in_place() {
             Ok(_) => JNI_TRUE,
             Err(_) => JNI_FALSE,
         }
@@ -1332,7 +2144,7 @@ ffi_export!(
                 .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
                 .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
 
-            read_raw_pointer(&_env, t.j().unwrap() as *const GingerMHT)
+            read_raw_pointer(&_env, t.j().unwrap() as *const InMemoryOptimizedMerkleTree)
         };
 
         match get_ginger_mht_root(tree) {
@@ -1353,7 +2165,7 @@ ffi_export!(
                 .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
                 .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
 
-            read_raw_pointer(&_env, t.j().unwrap() as *const GingerMHT)
+            read_raw_pointer(&_env, t.j().unwrap() as *const InMemoryOptimizedMerkleTree)
         };
 
         match get_ginger_mht_path(tree, _leaf_index as u64) {
@@ -1366,486 +2178,425 @@ ffi_export!(
 );
 
 ffi_export!(
-    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeReset(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeGetMerkleMultiProof(
         _env: JNIEnv,
         _tree: JObject,
-    ) {
+        _leaf_indices: jlongArray,
+    ) -> jobject {
         let tree = {
             let t = _env
                 .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
                 .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut GingerMHT)
+            read_raw_pointer(&_env, t.j().unwrap() as *const InMemoryOptimizedMerkleTree)
+        };
+
+        let leaf_indices = {
+            let len = _env
+                .get_array_length(_leaf_indices)
+                .expect("Should be able to get leaf indices array size");
+            let mut indices = vec![0 as jlong; len as usize];
+            _env.get_long_array_region(_leaf_indices, 0, indices.as_mut_slice())
+                .expect("Should be able to read leaf indices");
+            indices.into_iter().map(|i| i as u64).collect::<Vec<_>>()
         };
 
-        reset_ginger_mht(tree);
+        match MerkleMultiProof::new(tree, leaf_indices.as_slice()) {
+            Ok(proof) => return_jobject(
+                &_env,
+                proof,
+                "com/horizen/merkletreenative/MerkleMultiProof",
+            )
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeFreeInMemoryOptimizedMerkleTree(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeReset(
         _env: JNIEnv,
-        _class: JClass,
-        _tree: *mut GingerMHT,
+        _tree: JObject,
     ) {
-        if _tree.is_null() {
-            return;
-        }
-        drop(unsafe { Box::from_raw(_tree) });
-    }
-);
+        let tree = {
+            let t = _env
+                .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
+                .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
 
-//VRF utility functions
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut InMemoryOptimizedMerkleTree)
+        };
 
-ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFProof_nativeGetProofSize(
-        _env: JNIEnv,
-        _class: JClass,
-    ) -> jint {
-        VRF_PROOF_SIZE as jint
+        tree.reset();
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFProof_nativeSerializeProof(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeSerialize(
         _env: JNIEnv,
-        _proof: JObject,
-        _compressed: jboolean,
+        _tree: JObject,
     ) -> jbyteArray {
-        serialize_from_jobject::<VRFProof>(&_env, _proof, "proofPointer", Some(_compressed))
+        let tree = {
+            let t = _env
+                .get_field(_tree, "inMemoryOptimizedMerkleTreePointer", "J")
+                .expect("Should be able to get field inMemoryOptimizedMerkleTreePointer");
+
+            read_raw_pointer(&_env, t.j().unwrap() as *const InMemoryOptimizedMerkleTree)
+        };
+
+        let snapshot = tree
+            .serialize_snapshot()
+            .expect("Should be able to serialize tree");
+
+        _env.byte_array_from_slice(snapshot.as_slice())
+            .expect("Cannot write object.")
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFProof_nativeDeserializeProof(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeDeserialize(
         _env: JNIEnv,
         _class: JClass,
-        _proof_bytes: jbyteArray,
-        _check_proof: jboolean,
-        _compressed: jboolean,
+        _snapshot: jbyteArray,
     ) -> jobject {
-        deserialize_to_jobject::<VRFProof>(
-            &_env,
-            _proof_bytes,
-            Some(_check_proof),
-            Some(_compressed),
-            "com/horizen/vrfnative/VRFProof",
-        )
-    }
-);
-
-ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFProof_nativeIsValidVRFProof(
-        _env: JNIEnv,
-        _vrf_proof: JObject,
-    ) -> jboolean {
-        let proof = _env
-            .get_field(_vrf_proof, "proofPointer", "J")
-            .expect("Should be able to get field proofPointer")
-            .j()
-            .unwrap() as *const VRFProof;
+        let snapshot = _env
+            .convert_byte_array(_snapshot)
+            .expect("Cannot read snapshot bytes.");
 
-        if is_valid(read_raw_pointer(&_env, proof)) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
+        match InMemoryOptimizedMerkleTree::deserialize_snapshot(snapshot.as_slice()) {
+            Ok(tree) => return_jobject(
+                &_env,
+                tree,
+                "com/horizen/merkletreenative/InMemoryOptimizedMerkleTree",
+            )
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFProof_nativeFreeProof(
+    fn Java_com_horizen_merkletreenative_InMemoryOptimizedMerkleTree_nativeFreeInMemoryOptimizedMerkleTree(
         _env: JNIEnv,
         _class: JClass,
-        _proof: *mut VRFProof,
+        _tree: *mut InMemoryOptimizedMerkleTree,
     ) {
-        if _proof.is_null() {
+        if _tree.is_null() {
             return;
         }
-        drop(unsafe { Box::from_raw(_proof) });
+        drop(unsafe { Box::from_raw(_tree) });
     }
 );
 
-//VRF functions
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFKeyPair_nativeGenerate(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeInit(
         _env: JNIEnv,
-        // this is the class that owns our
-        // static method. Not going to be
-        // used, but still needs to have
-        // an argument slot
         _class: JClass,
+        _path: JString,
+        _height: jint,
     ) -> jobject {
-        let (pk, sk) = vrf_generate_key();
-
-        let secret_key_object = return_jobject(&_env, sk, "com/horizen/vrfnative/VRFSecretKey");
-        let public_key_object = return_jobject(&_env, pk, "com/horizen/vrfnative/VRFPublicKey");
+        let path = _env
+            .get_string(_path)
+            .expect("Should be able to read jstring as Rust String");
 
-        let class = _env
-            .find_class("com/horizen/vrfnative/VRFKeyPair")
-            .expect("Should be able to find VRFKeyPair class");
+        // Negative or too big heights are rejected by open()
+        let height = _height.try_into().unwrap_or(0);
 
-        let result = _env
-            .new_object(
-                class,
-                "(Lcom/horizen/vrfnative/VRFSecretKey;Lcom/horizen/vrfnative/VRFPublicKey;)V",
-                &[
-                    JValue::Object(secret_key_object),
-                    JValue::Object(public_key_object),
-                ],
+        match PersistentMerkleTree::open(path.to_str().unwrap(), height) {
+            Ok(tree) => return_jobject(
+                &_env,
+                tree,
+                "com/horizen/merkletreenative/PersistentMerkleTree",
             )
-            .expect("Should be able to create new (VRFSecretKey, VRFPublicKey) object");
-
-        *result
-    }
-);
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFKeyPair_nativeProve(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeAppend(
         _env: JNIEnv,
-        _vrf_key_pair: JObject,
-        _message: JObject,
-    ) -> jobject {
-        //Read sk
-        let sk_object = _env
-            .get_field(
-                _vrf_key_pair,
-                "secretKey",
-                "Lcom/horizen/vrfnative/VRFSecretKey;",
-            )
-            .expect("Should be able to get field vrfKey")
-            .l()
-            .unwrap();
-
-        let secret_key = {
-            let s = _env
-                .get_field(sk_object, "secretKeyPointer", "J")
-                .expect("Should be able to get field secretKeyPointer");
+        _tree: JObject,
+        _leaf: JObject,
+    ) -> jlong {
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-            read_raw_pointer(&_env, s.j().unwrap() as *const VRFSk)
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
         };
 
-        //Read pk
-        let pk_object = _env
-            .get_field(
-                _vrf_key_pair,
-                "publicKey",
-                "Lcom/horizen/vrfnative/VRFPublicKey;",
-            )
-            .expect("Should be able to get field publicKey")
-            .l()
-            .unwrap();
-
-        let public_key = {
-            let p = _env
-                .get_field(pk_object, "publicKeyPointer", "J")
-                .expect("Should be able to get field publicKeyPointer");
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
 
-            read_raw_pointer(&_env, p.j().unwrap() as *const VRFPk)
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut PersistentMerkleTree)
         };
 
-        //Read message
-        let message = {
-            let m = _env
-                .get_field(_message, "fieldElementPointer", "J")
+        match tree.append(leaf) {
+            Ok(index) => index as jlong,
+            Err(_) => -1,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeUpdate(
+        _env: JNIEnv,
+        _tree: JObject,
+        _leaf_index: jlong,
+        _leaf: JObject,
+    ) -> jboolean {
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
                 .expect("Should be able to get field fieldElementPointer");
 
-            read_raw_pointer(&_env, m.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
         };
 
-        //Compute vrf proof
-        let (proof, vrf_out) = match vrf_prove(message, secret_key, public_key) {
-            Ok((proof, vrf_out)) => (
-                return_jobject(&_env, proof, "com/horizen/vrfnative/VRFProof"),
-                return_jobject(&_env, vrf_out, "com/horizen/librustsidechains/FieldElement"),
-            ),
-            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut PersistentMerkleTree)
         };
 
-        //Create and return VRFProveResult instance
-        let class = _env
-            .find_class("com/horizen/vrfnative/VRFProveResult")
-            .expect("Should be able to find VRFProveResult class");
+        match tree.update(_leaf_index as u64, leaf) {
+            Ok(_) => JNI_TRUE,
+            Err(_) => JNI_FALSE,
+        }
+    }
+);
 
-        let result = _env
-            .new_object(
-                class,
-                "(Lcom/horizen/vrfnative/VRFProof;Lcom/horizen/librustsidechains/FieldElement;)V",
-                &[JValue::Object(proof), JValue::Object(vrf_out)],
-            )
-            .expect("Should be able to create new VRFProveResult:(VRFProof, FieldElement) object");
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeGetLeaf(
+        _env: JNIEnv,
+        _tree: JObject,
+        _leaf_index: jlong,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
 
-        *result
+            read_raw_pointer(&_env, t.j().unwrap() as *const PersistentMerkleTree)
+        };
+
+        match tree.get_leaf(_leaf_index as u64) {
+            Ok(leaf) => return_field_element(&_env, leaf),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFSecretKey_nativeGetPublicKey(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeGetNumLeaves(
         _env: JNIEnv,
-        _vrf_secret_key: JObject,
-    ) -> jobject {
-        let sk = _env
-            .get_field(_vrf_secret_key, "secretKeyPointer", "J")
-            .expect("Should be able to get field secretKeyPointer")
-            .j()
-            .unwrap() as *const VRFSk;
+        _tree: JObject,
+    ) -> jlong {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
 
-        let secret_key = read_raw_pointer(&_env, sk);
+            read_raw_pointer(&_env, t.j().unwrap() as *const PersistentMerkleTree)
+        };
 
-        let pk = vrf_get_public_key(secret_key);
-        return_jobject(&_env, pk, "com/horizen/vrfnative/VRFPublicKey").into_inner()
+        tree.num_leaves() as jlong
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFPublicKey_nativeVerifyKey(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeRoot(
         _env: JNIEnv,
-        _vrf_public_key: JObject,
-    ) -> jboolean {
-        let pk = _env
-            .get_field(_vrf_public_key, "publicKeyPointer", "J")
-            .expect("Should be able to get field publicKeyPointer")
-            .j()
-            .unwrap() as *const VRFPk;
+        _tree: JObject,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
 
-        if vrf_verify_public_key(read_raw_pointer(&_env, pk)) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
+            read_raw_pointer(&_env, t.j().unwrap() as *const PersistentMerkleTree)
+        };
+
+        match tree.root() {
+            Ok(root) => return_field_element(&_env, root),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_vrfnative_VRFPublicKey_nativeProofToHash(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeGetMerklePath(
         _env: JNIEnv,
-        _vrf_public_key: JObject,
-        _proof: JObject,
-        _message: JObject,
+        _tree: JObject,
+        _leaf_index: jlong,
     ) -> jobject {
-        let public_key = {
-            let p = _env
-                .get_field(_vrf_public_key, "publicKeyPointer", "J")
-                .expect("Should be able to get field publicKeyPointer");
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
 
-            read_raw_pointer(&_env, p.j().unwrap() as *const VRFPk)
+            read_raw_pointer(&_env, t.j().unwrap() as *const PersistentMerkleTree)
         };
 
-        //Read message
-        let message = {
-            let m = _env
-                .get_field(_message, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer");
+        match tree.get_merkle_path(_leaf_index as u64) {
+            Ok(path) => {
+                return_jobject(&_env, path, "com/horizen/merkletreenative/MerklePath").into_inner()
+            }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
-            read_raw_pointer(&_env, m.j().unwrap() as *const FieldElement)
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeCommit(
+        _env: JNIEnv,
+        _tree: JObject,
+    ) -> jboolean {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut PersistentMerkleTree)
         };
 
-        //Read proof
-        let proof = {
-            let p = _env
-                .get_field(_proof, "proofPointer", "J")
-                .expect("Should be able to get field proofPointer");
+        match tree.commit() {
+            Ok(_) => JNI_TRUE,
+            Err(_) => JNI_FALSE,
+        }
+    }
+);
 
-            read_raw_pointer(&_env, p.j().unwrap() as *const VRFProof)
-        };
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeRollback(
+        _env: JNIEnv,
+        _tree: JObject,
+    ) {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "persistentMerkleTreePointer", "J")
+                .expect("Should be able to get field persistentMerkleTreePointer");
 
-        //Verify vrf proof and get vrf output
-        let vrf_out = match vrf_proof_to_hash(message, public_key, proof) {
-            Ok(result) => result,
-            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut PersistentMerkleTree)
         };
 
-        //Return vrf output
-        return_field_element(&_env, vrf_out)
+        tree.rollback();
     }
 );
 
-//Naive threshold signature proof functions
-
 ffi_export!(
-    fn Java_com_horizen_sigproofnative_BackwardTransfer_nativeGetMcPkHashSize(
+    fn Java_com_horizen_merkletreenative_PersistentMerkleTree_nativeFreePersistentMerkleTree(
         _env: JNIEnv,
         _class: JClass,
-    ) -> jint {
-        MC_PK_SIZE as jint
+        _tree: *mut PersistentMerkleTree,
+    ) {
+        if _tree.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_tree) });
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeGetConstant(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeInit(
         _env: JNIEnv,
-        // this is the class that owns our
-        // static method. Not going to be
-        // used, but still needs to have
-        // an argument slot
         _class: JClass,
-        _schnorr_pks_list: jobjectArray,
-        _threshold: jlong,
+        _height: jint,
     ) -> jobject {
-        //Extract Schnorr pks
-        let mut pks = vec![];
-
-        let pks_list_size = _env
-            .get_array_length(_schnorr_pks_list)
-            .expect("Should be able to get schnorr_pks_list size");
-
-        for i in 0..pks_list_size {
-            let pk_object = _env
-                .get_object_array_element(_schnorr_pks_list, i)
-                .unwrap_or_else(|_| panic!("Should be able to get elem {} of schnorr_pks_list", i));
-
-            let pk = _env
-                .get_field(pk_object, "publicKeyPointer", "J")
-                .expect("Should be able to get field publicKeyPointer");
-
-            pks.push(*read_raw_pointer(
-                &_env,
-                pk.j().unwrap() as *const SchnorrPk,
-            ));
-        }
+        // Negative or too big heights are rejected by new()
+        let height = _height.try_into().unwrap_or(0);
 
-        //Extract threshold
-        let threshold = _threshold as u64;
-
-        //Compute constant
-        match compute_pks_threshold_hash(pks.as_slice(), threshold) {
-            Ok(constant) => return_field_element(&_env, constant),
-            Err(_) => std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        match SparseMerkleTree::new(height) {
+            Ok(tree) => {
+                return_jobject(&_env, tree, "com/horizen/merkletreenative/SparseMerkleTree")
+                    .into_inner()
+            }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeCreateMsgToSign(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeInsert(
         _env: JNIEnv,
-        // this is the class that owns our
-        // static method. Not going to be
-        // used, but still needs to have
-        // an argument slot
-        _class: JClass,
-        _bt_list: jobjectArray,
-        _sc_id: JObject,
-        _epoch_number: jint,
-        _end_cumulative_sc_tx_comm_tree_root: JObject,
-        _btr_fee: jlong,
-        _ft_min_amount: jlong,
-    ) -> jobject {
-        //Extract backward transfers
-        let mut bt_list = vec![];
-
-        let bt_list_size = _env
-            .get_array_length(_bt_list)
-            .expect("Should be able to get bt_list size");
-
-        if bt_list_size > 0 {
-            for i in 0..bt_list_size {
-                let o = _env
-                    .get_object_array_element(_bt_list, i)
-                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+        _tree: JObject,
+        _position: jlong,
+        _leaf: JObject,
+    ) -> jboolean {
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-                let p = _env
-                    .call_method(o, "getPublicKeyHash", "()[B", &[])
-                    .expect("Should be able to call getPublicKeyHash method")
-                    .l()
-                    .unwrap()
-                    .cast();
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
 
-                let pk: [u8; 20] = _env
-                    .convert_byte_array(p)
-                    .expect("Should be able to convert to Rust byte array")
-                    .try_into()
-                    .expect("Should be able to write into fixed buffer of size 20");
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-                let a = _env
-                    .call_method(o, "getAmount", "()J", &[])
-                    .expect("Should be able to call getAmount method")
-                    .j()
-                    .unwrap() as u64;
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut SparseMerkleTree)
+        };
 
-                bt_list.push((a, pk));
-            }
+        match tree.insert(_position as u64, leaf) {
+            Ok(_) => JNI_TRUE,
+            Err(_) => JNI_FALSE,
         }
+    }
+);
 
-        let bt_list = bt_list
-            .into_iter()
-            .map(|bt_raw| BackwardTransfer {
-                pk_dest: bt_raw.1,
-                amount: bt_raw.0,
-            })
-            .collect::<Vec<_>>();
-
-        let sc_id = {
-            let f = _env
-                .get_field(_sc_id, "fieldElementPointer", "J")
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeUpdate(
+        _env: JNIEnv,
+        _tree: JObject,
+        _position: jlong,
+        _leaf: JObject,
+    ) -> jboolean {
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
                 .expect("Should be able to get field fieldElementPointer");
 
-            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
         };
 
-        let end_cumulative_sc_tx_comm_tree_root = {
-            let f = _env
-                .get_field(
-                    _end_cumulative_sc_tx_comm_tree_root,
-                    "fieldElementPointer",
-                    "J",
-                )
-                .expect("Should be able to get field fieldElementPointer");
-
-            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
-        };
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-        //Compute message to sign:
-        let msg = match compute_msg_to_sign(
-            sc_id,
-            _epoch_number as u32,
-            end_cumulative_sc_tx_comm_tree_root,
-            _btr_fee as u64,
-            _ft_min_amount as u64,
-            bt_list,
-        ) {
-            Ok((_, msg)) => msg,
-            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut SparseMerkleTree)
         };
 
-        //Return msg
-        return_field_element(&_env, msg)
+        match tree.update(_position as u64, leaf) {
+            Ok(_) => JNI_TRUE,
+            Err(_) => JNI_FALSE,
+        }
     }
 );
 
-fn get_proving_system_type(_env: &JNIEnv, _proving_system: JObject) -> ProvingSystem {
-    // Extract proving system type
-    let proving_system = _env
-        .call_method(_proving_system, "ordinal", "()I", &[])
-        .expect("Should be able to call ordinal() on ProvingSystem enum")
-        .i()
-        .unwrap() as usize;
-
-    // Convert to Rust enum
-    match proving_system {
-        0 => ProvingSystem::Undefined,
-        1 => ProvingSystem::Darlin,
-        2 => ProvingSystem::CoboundaryMarlin,
-        _ => unreachable!(),
-    }
-}
-
 ffi_export!(
-    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGenerateDLogKeys(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeRemove(
         _env: JNIEnv,
-        _class: JClass,
-        _proving_system: JObject,
-        _max_segment_size: jint,
-        _supported_segment_size: jint,
+        _tree: JObject,
+        _position: jlong,
     ) -> jboolean {
-        // Get proving system type
-        let proving_system = get_proving_system_type(&_env, _proving_system);
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-        // Generate DLOG keypair
-        match init_dlog_keys(
-            proving_system,
-            _max_segment_size as usize,
-            _supported_segment_size as usize,
-        ) {
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut SparseMerkleTree)
+        };
+
+        match tree.remove(_position as u64) {
             Ok(_) => JNI_TRUE,
             Err(_) => JNI_FALSE,
         }
@@ -1853,1009 +2604,3810 @@ ffi_export!(
 );
 
 ffi_export!(
-    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeCheckProofVkSize(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeGetLeaf(
         _env: JNIEnv,
-        _class: JClass,
-        _zk: jboolean,
-        _supported_segment_size: jint,
-        _max_proof_size: jint,
-        _max_vk_size: jint,
-        _verification_key_path: JString,
-    ) -> jboolean {
-        // Read vk from file
-
-        //Extract vk path
-        let vk_path = _env
-            .get_string(_verification_key_path)
-            .expect("Should be able to read jstring as Rust String");
+        _tree: JObject,
+        _position: jlong,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-        // Deserialize vk
-        let vk: ZendooVerifierKey = match read_from_file(
-            Path::new(vk_path.to_str().unwrap()),
-            Some(false),
-            Some(true),
-        ) {
-            Ok(vk) => vk,
-            Err(e) => {
-                println!("{:?}", e);
-                return JNI_FALSE;
-            }
+            read_raw_pointer(&_env, t.j().unwrap() as *const SparseMerkleTree)
         };
 
-        // Read zk value
-        let zk = _zk == JNI_TRUE;
+        match tree.get_leaf(_position as u64) {
+            Some(leaf) => return_field_element(&_env, leaf),
+            None => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
-        // Get ps type from vk
-        let ps_type = vk.get_proving_system_type();
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeGetNumLeaves(
+        _env: JNIEnv,
+        _tree: JObject,
+    ) -> jlong {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-        // Get index info from vk
-        let index_info = match vk {
-            ZendooVerifierKey::CoboundaryMarlin(cob_marlin_vk) => cob_marlin_vk.index_info,
-            ZendooVerifierKey::Darlin(darlin_vk) => darlin_vk.index_info,
+            read_raw_pointer(&_env, t.j().unwrap() as *const SparseMerkleTree)
         };
 
-        // Perform check
-        let result = check_proof_vk_size(
-            _supported_segment_size as usize,
-            index_info,
-            zk,
-            ps_type,
-            _max_proof_size as usize,
-            _max_vk_size as usize,
-        );
-
-        if result {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
-        }
+        tree.num_leaves() as jlong
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeSetup(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeRoot(
         _env: JNIEnv,
-        _class: JClass,
-        _proving_system: JObject,
-        _max_pks: jlong,
-        _proving_key_path: JString,
-        _verification_key_path: JString,
-        _zk: jboolean,
-        _max_proof_size: jint,
-        _max_vk_size: jint,
-        _compress_pk: jboolean,
-        _compress_vk: jboolean,
-    ) -> jboolean {
-        // Get proving system type
-        let proving_system = get_proving_system_type(&_env, _proving_system);
-
-        // Read paths
-        let proving_key_path = _env
-            .get_string(_proving_key_path)
-            .expect("Should be able to read jstring as Rust String");
+        _tree: JObject,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-        let verification_key_path = _env
-            .get_string(_verification_key_path)
-            .expect("Should be able to read jstring as Rust String");
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut SparseMerkleTree)
+        };
 
-        let max_pks = _max_pks as usize;
+        match tree.root() {
+            Ok(root) => return_field_element(&_env, root),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
-        let circ = get_instance_for_setup(max_pks);
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeGetMembershipProof(
+        _env: JNIEnv,
+        _tree: JObject,
+        _position: jlong,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-        // Read zk value
-        let zk = _zk == JNI_TRUE;
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut SparseMerkleTree)
+        };
 
-        // Generate snark keypair
-        match generate_circuit_keypair(
-            circ,
-            proving_system,
-            Path::new(proving_key_path.to_str().unwrap()),
-            Path::new(verification_key_path.to_str().unwrap()),
-            _max_proof_size as usize,
-            _max_vk_size as usize,
-            zk,
-            Some(_compress_pk == JNI_TRUE),
-            Some(_compress_vk == JNI_TRUE),
-        ) {
-            Ok(_) => JNI_TRUE,
-            Err(e) => {
-                println!("{:?}", e);
-                JNI_FALSE
+        match tree.get_membership_proof(_position as u64) {
+            Ok(path) => {
+                return_jobject(&_env, path, "com/horizen/merkletreenative/MerklePath").into_inner()
             }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
-fn get_proving_system_type_as_jint(_env: &JNIEnv, ps: ProvingSystem) -> jint {
-    match ps {
-        ProvingSystem::Undefined => 0_i32,
-        ProvingSystem::Darlin => 1_i32,
-        ProvingSystem::CoboundaryMarlin => 2_i32,
-    }
-}
-
 ffi_export!(
-    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGetProverKeyProvingSystemType(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeGetNonMembershipProof(
         _env: JNIEnv,
-        _class: JClass,
-        _proving_key_path: JString,
-    ) -> jint {
-        // Read paths
-        let proving_key_path = _env
-            .get_string(_proving_key_path)
-            .expect("Should be able to read jstring as Rust String");
-
-        match read_from_file::<ProvingSystem>(
-            Path::new(proving_key_path.to_str().unwrap()),
-            None,
-            None,
-        ) {
-            Ok(ps) => get_proving_system_type_as_jint(&_env, ps),
-            Err(_) => 1_i32,
-        }
-    }
-);
+        _tree: JObject,
+        _position: jlong,
+    ) -> jobject {
+        let tree = {
+            let t = _env
+                .get_field(_tree, "sparseMerkleTreePointer", "J")
+                .expect("Should be able to get field sparseMerkleTreePointer");
 
-ffi_export!(
-    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGetVerifierKeyProvingSystemType(
-        _env: JNIEnv,
-        _class: JClass,
-        _verifier_key_path: JString,
-    ) -> jint {
-        // Read paths
-        let verifier_key_path = _env
-            .get_string(_verifier_key_path)
-            .expect("Should be able to read jstring as Rust String");
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut SparseMerkleTree)
+        };
 
-        match read_from_file::<ProvingSystem>(
-            Path::new(verifier_key_path.to_str().unwrap()),
-            None,
-            None,
-        ) {
-            Ok(ps) => get_proving_system_type_as_jint(&_env, ps),
-            Err(_) => 1_i32,
+        match tree.get_non_membership_proof(_position as u64) {
+            Ok(path) => {
+                return_jobject(&_env, path, "com/horizen/merkletreenative/MerklePath").into_inner()
+            }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeCreateProof(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeVerifyMembershipProof(
         _env: JNIEnv,
-        // this is the class that owns our
-        // static method. Not going to be
-        // used, but still needs to have
-        // an argument slot
         _class: JClass,
-        _bt_list: jobjectArray,
-        _sc_id: JObject,
-        _epoch_number: jint,
-        _end_cumulative_sc_tx_comm_tree_root: JObject,
-        _btr_fee: jlong,
-        _ft_min_amount: jlong,
-        _schnorr_sigs_list: jobjectArray,
-        _schnorr_pks_list: jobjectArray,
-        _threshold: jlong,
-        _proving_key_path: JString,
-        _check_proving_key: jboolean,
-        _zk: jboolean,
-        _compressed_pk: jboolean,
-        _compress_proof: jboolean,
-    ) -> jobject {
-        // Extract backward transfers
-        let mut bt_list = vec![];
-
-        let bt_list_size = _env
-            .get_array_length(_bt_list)
-            .expect("Should be able to get bt_list size");
-
-        if bt_list_size > 0 {
-            for i in 0..bt_list_size {
-                let o = _env
-                    .get_object_array_element(_bt_list, i)
-                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
-
-                let p = _env
-                    .call_method(o, "getPublicKeyHash", "()[B", &[])
-                    .expect("Should be able to call getPublicKeyHash method")
-                    .l()
-                    .unwrap()
-                    .cast();
-
-                let pk: [u8; 20] = _env
-                    .convert_byte_array(p)
-                    .expect("Should be able to convert to Rust byte array")
-                    .try_into()
-                    .expect("Should be able to write into fixed buffer of size 20");
-
-                let a = _env
-                    .call_method(o, "getAmount", "()J", &[])
-                    .expect("Should be able to call getAmount method")
-                    .j()
-                    .unwrap() as u64;
-
-                bt_list.push((a, pk));
-            }
-        }
-
-        let bt_list = bt_list
-            .into_iter()
-            .map(|bt_raw| BackwardTransfer {
-                pk_dest: bt_raw.1,
-                amount: bt_raw.0,
-            })
-            .collect::<Vec<_>>();
-
-        //Extract Schnorr signatures and the corresponding Schnorr pks
-        let mut sigs = vec![];
-        let mut pks = vec![];
-
-        let sigs_list_size = _env
-            .get_array_length(_schnorr_sigs_list)
-            .expect("Should be able to get schnorr_sigs_list size");
-
-        let pks_list_size = _env
-            .get_array_length(_schnorr_pks_list)
-            .expect("Should be able to get schnorr_pks_list size");
+        _proof: JObject,
+        _height: jint,
+        _position: jlong,
+        _leaf: JObject,
+        _root: JObject,
+    ) -> jboolean {
+        let proof = {
+            let t = _env
+                .get_field(_proof, "merklePathPointer", "J")
+                .expect("Should be able to get field merklePathPointer");
 
-        assert_eq!(sigs_list_size, pks_list_size);
+            read_raw_pointer(&_env, t.j().unwrap() as *const GingerMHTPath)
+        };
 
-        for i in 0..sigs_list_size {
-            //Get i-th sig
-            let sig_object = _env
-                .get_object_array_element(_schnorr_sigs_list, i)
-                .unwrap_or_else(|_| panic!("Should be able to get elem {} of schnorr_sigs_list", i));
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-            let pk_object = _env
-                .get_object_array_element(_schnorr_pks_list, i)
-                .unwrap_or_else(|_| panic!("Should be able to get elem {} of schnorr_pks_list", i));
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
 
-            let signature = {
-                let sig = _env
-                    .get_field(sig_object, "signaturePointer", "J")
-                    .expect("Should be able to get field signaturePointer");
+        let root = {
+            let fe = _env
+                .get_field(_root, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-                read_nullable_raw_pointer(sig.j().unwrap() as *const SchnorrSig).copied()
-            };
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
 
-            let public_key = {
-                let pk = _env
-                    .get_field(pk_object, "publicKeyPointer", "J")
-                    .expect("Should be able to get field publicKeyPointer");
+        let height = match _height.try_into() {
+            Ok(height) => height,
+            Err(_) => return JNI_FALSE,
+        };
 
-                read_raw_pointer(&_env, pk.j().unwrap() as *const SchnorrPk)
-            };
+        if !proof.is_valid() {
+            return JNI_FALSE;
+        }
 
-            sigs.push(signature);
-            pks.push(*public_key);
+        match verify_sparse_mht_membership_proof(proof, height, _position as u64, leaf, root) {
+            Ok(true) => JNI_TRUE,
+            _ => JNI_FALSE,
         }
+    }
+);
 
-        let sc_id = {
-            let f = _env
-                .get_field(_sc_id, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer");
+ffi_export!(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeVerifyNonMembershipProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof: JObject,
+        _height: jint,
+        _position: jlong,
+        _root: JObject,
+    ) -> jboolean {
+        let proof = {
+            let t = _env
+                .get_field(_proof, "merklePathPointer", "J")
+                .expect("Should be able to get field merklePathPointer");
 
-            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, t.j().unwrap() as *const GingerMHTPath)
         };
 
-        let end_cumulative_sc_tx_comm_tree_root = {
-            let f = _env
-                .get_field(
-                    _end_cumulative_sc_tx_comm_tree_root,
-                    "fieldElementPointer",
-                    "J",
-                )
+        let root = {
+            let fe = _env
+                .get_field(_root, "fieldElementPointer", "J")
                 .expect("Should be able to get field fieldElementPointer");
 
-            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
         };
 
-        //Extract params_path str
-        let proving_key_path = _env
-            .get_string(_proving_key_path)
-            .expect("Should be able to read jstring as Rust String");
-
-        //create proof
-        let (proof, quality) = match create_naive_threshold_sig_proof(
-            pks.as_slice(),
-            sigs,
-            sc_id,
-            _epoch_number as u32,
-            end_cumulative_sc_tx_comm_tree_root,
-            _btr_fee as u64,
-            _ft_min_amount as u64,
-            bt_list,
-            _threshold as u64,
-            Path::new(proving_key_path.to_str().unwrap()),
-            _check_proving_key == JNI_TRUE,
-            _zk == JNI_TRUE,
-            _compressed_pk == JNI_TRUE,
-            _compress_proof == JNI_TRUE,
-        ) {
-            Ok(proof) => proof,
-            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR or IO_ERROR
+        let height = match _height.try_into() {
+            Ok(height) => height,
+            Err(_) => return JNI_FALSE,
         };
 
-        //Return proof serialized
-        let proof_serialized = _env
-            .byte_array_from_slice(proof.as_slice())
-            .expect("Should be able to convert Rust slice into jbytearray");
-
-        //Create new CreateProofResult object
-        let proof_result_class = _env
-            .find_class("com/horizen/sigproofnative/CreateProofResult")
-            .expect("Should be able to find CreateProofResult class");
-
-        let result = _env
-            .new_object(
-                proof_result_class,
-                "([BJ)V",
-                &[
-                    JValue::Object(JObject::from(proof_serialized)),
-                    JValue::Long(quality as i64),
-                ],
-            )
-            .expect("Should be able to create new CreateProofResult:(byte[], long) object");
+        if !proof.is_valid() {
+            return JNI_FALSE;
+        }
 
-        *result
+        match verify_sparse_mht_non_membership_proof(proof, height, _position as u64, root) {
+            Ok(true) => JNI_TRUE,
+            _ => JNI_FALSE,
+        }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGetProofProvingSystemType(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeGetPositionFromKey(
         _env: JNIEnv,
         _class: JClass,
-        _proof: jbyteArray,
-    ) -> jint {
-        //Extract proof
-        let proof_bytes = _env
-            .convert_byte_array(_proof)
-            .expect("Should be able to convert to Rust byte array");
+        _key: JObject,
+        _height: jint,
+    ) -> jlong {
+        let key = {
+            let fe = _env
+                .get_field(_key, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-        match deserialize_from_buffer::<ProvingSystem>(&proof_bytes[..1], None, None) {
-            Ok(ps) => get_proving_system_type_as_jint(&_env, ps),
-            Err(_) => 1_i32,
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
+
+        match _height
+            .try_into()
+            .map(|height| sparse_mht_position_from_key(key, height))
+        {
+            Ok(Ok(position)) => position as jlong,
+            _ => -1,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeVerifyProof(
+    fn Java_com_horizen_merkletreenative_SparseMerkleTree_nativeFreeSparseMerkleTree(
+        _env: JNIEnv,
+        _class: JClass,
+        _tree: *mut SparseMerkleTree,
+    ) {
+        if _tree.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_tree) });
+    }
+);
+
+//VRF utility functions
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeGetProofSize(
+        _env: JNIEnv,
+        _class: JClass,
+    ) -> jint {
+        VRF_PROOF_SIZE as jint
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeSerializeProof(
+        _env: JNIEnv,
+        _proof: JObject,
+        _compressed: jboolean,
+    ) -> jbyteArray {
+        serialize_from_jobject::<VRFProof>(&_env, _proof, "proofPointer", Some(_compressed))
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeDeserializeProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof_bytes: jbyteArray,
+        _check_proof: jboolean,
+        _compressed: jboolean,
+    ) -> jobject {
+        deserialize_to_jobject::<VRFProof>(
+            &_env,
+            _proof_bytes,
+            Some(_check_proof),
+            Some(_compressed),
+            "com/horizen/vrfnative/VRFProof",
+        )
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeToJson(_env: JNIEnv, _proof: JObject) -> jstring {
+        json_from_jobject::<VRFProofJson>(&_env, _proof, "proofPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<VRFProofJson>(&_env, _json, "com/horizen/vrfnative/VRFProof")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeIsValidVRFProof(
+        _env: JNIEnv,
+        _vrf_proof: JObject,
+    ) -> jboolean {
+        let proof = _env
+            .get_field(_vrf_proof, "proofPointer", "J")
+            .expect("Should be able to get field proofPointer")
+            .j()
+            .unwrap() as *const VRFProof;
+
+        if is_valid(read_raw_pointer(&_env, proof)) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFProof_nativeFreeProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof: *mut VRFProof,
+    ) {
+        if _proof.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_proof) });
+    }
+);
+
+//VRF functions
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFKeyPair_nativeGenerate(
         _env: JNIEnv,
         // this is the class that owns our
         // static method. Not going to be
         // used, but still needs to have
         // an argument slot
         _class: JClass,
-        _bt_list: jobjectArray,
-        _sc_id: JObject,
-        _epoch_number: jint,
-        _end_cumulative_sc_tx_comm_tree_root: JObject,
-        _btr_fee: jlong,
-        _ft_min_amount: jlong,
-        _constant: JObject,
-        _quality: jlong,
-        _sc_proof_bytes: jbyteArray,
-        _check_proof: jboolean,
-        _compressed_proof: jboolean,
-        _verification_key_path: JString,
-        _check_vk: jboolean,
-        _compressed_vk: jboolean,
-    ) -> jboolean {
-        //Extract backward transfers
-        let mut bt_list = vec![];
+    ) -> jobject {
+        let (pk, sk) = vrf_generate_key();
 
-        let bt_list_size = _env
-            .get_array_length(_bt_list)
-            .expect("Should be able to get bt_list size");
+        let secret_key_object =
+            return_jobject(&_env, sk, "com/horizen/vrfnative/VRFSecretKey");
+        let public_key_object = return_jobject(&_env, pk, "com/horizen/vrfnative/VRFPublicKey");
 
-        if bt_list_size > 0 {
-            for i in 0..bt_list_size {
-                let o = _env
-                    .get_object_array_element(_bt_list, i)
-                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+        let class = _env
+            .find_class("com/horizen/vrfnative/VRFKeyPair")
+            .expect("Should be able to find VRFKeyPair class");
 
-                let p = _env
-                    .call_method(o, "getPublicKeyHash", "()[B", &[])
-                    .expect("Should be able to call getPublicKeyHash method")
-                    .l()
-                    .unwrap()
-                    .cast();
+        let result = _env
+            .new_object(
+                class,
+                "(Lcom/horizen/vrfnative/VRFSecretKey;Lcom/horizen/vrfnative/VRFPublicKey;)V",
+                &[
+                    JValue::Object(secret_key_object),
+                    JValue::Object(public_key_object),
+                ],
+            )
+            .expect("Should be able to create new (VRFSecretKey, VRFPublicKey) object");
+
+        *result
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFKeyPair_nativeProve(
+        _env: JNIEnv,
+        _vrf_key_pair: JObject,
+        _message: JObject,
+    ) -> jobject {
+        //Read sk
+        let sk_object = _env
+            .get_field(
+                _vrf_key_pair,
+                "secretKey",
+                "Lcom/horizen/vrfnative/VRFSecretKey;",
+            )
+            .expect("Should be able to get field vrfKey")
+            .l()
+            .unwrap();
+
+        let secret_key = {
+            let s = _env
+                .get_field(sk_object, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingVRFSk)
+        };
+
+        //Read pk
+        let pk_object = _env
+            .get_field(
+                _vrf_key_pair,
+                "publicKey",
+                "Lcom/horizen/vrfnative/VRFPublicKey;",
+            )
+            .expect("Should be able to get field publicKey")
+            .l()
+            .unwrap();
+
+        let public_key = {
+            let p = _env
+                .get_field(pk_object, "publicKeyPointer", "J")
+                .expect("Should be able to get field publicKeyPointer");
+
+            read_raw_pointer(&_env, p.j().unwrap() as *const VRFPk)
+        };
+
+        //Read message
+        let message = {
+            let m = _env
+                .get_field(_message, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, m.j().unwrap() as *const FieldElement)
+        };
+
+        //Compute vrf proof
+        let (proof, vrf_out) = match vrf_prove(message, secret_key, public_key) {
+            Ok((proof, vrf_out)) => (
+                return_jobject(&_env, proof, "com/horizen/vrfnative/VRFProof"),
+                return_jobject(&_env, vrf_out, "com/horizen/librustsidechains/FieldElement"),
+            ),
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        //Create and return VRFProveResult instance
+        let class = _env
+            .find_class("com/horizen/vrfnative/VRFProveResult")
+            .expect("Should be able to find VRFProveResult class");
+
+        let result = _env
+            .new_object(
+                class,
+                "(Lcom/horizen/vrfnative/VRFProof;Lcom/horizen/librustsidechains/FieldElement;)V",
+                &[JValue::Object(proof), JValue::Object(vrf_out)],
+            )
+            .expect("Should be able to create new VRFProveResult:(VRFProof, FieldElement) object");
+
+        *result
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFSecretKey_nativeGetPublicKey(
+        _env: JNIEnv,
+        _vrf_secret_key: JObject,
+    ) -> jobject {
+        let sk = _env
+            .get_field(_vrf_secret_key, "secretKeyPointer", "J")
+            .expect("Should be able to get field secretKeyPointer")
+            .j()
+            .unwrap() as *const ZeroizingVRFSk;
+
+        let secret_key = read_raw_pointer(&_env, sk);
+
+        let pk = vrf_get_public_key(secret_key);
+        return_jobject(&_env, pk, "com/horizen/vrfnative/VRFPublicKey").into_inner()
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFPublicKey_nativeVerifyKey(
+        _env: JNIEnv,
+        _vrf_public_key: JObject,
+    ) -> jboolean {
+        let pk = _env
+            .get_field(_vrf_public_key, "publicKeyPointer", "J")
+            .expect("Should be able to get field publicKeyPointer")
+            .j()
+            .unwrap() as *const VRFPk;
+
+        if vrf_verify_public_key(read_raw_pointer(&_env, pk)) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_vrfnative_VRFPublicKey_nativeProofToHash(
+        _env: JNIEnv,
+        _vrf_public_key: JObject,
+        _proof: JObject,
+        _message: JObject,
+    ) -> jobject {
+        let public_key = {
+            let p = _env
+                .get_field(_vrf_public_key, "publicKeyPointer", "J")
+                .expect("Should be able to get field publicKeyPointer");
+
+            read_raw_pointer(&_env, p.j().unwrap() as *const VRFPk)
+        };
+
+        //Read message
+        let message = {
+            let m = _env
+                .get_field(_message, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, m.j().unwrap() as *const FieldElement)
+        };
+
+        //Read proof
+        let proof = {
+            let p = _env
+                .get_field(_proof, "proofPointer", "J")
+                .expect("Should be able to get field proofPointer");
+
+            read_raw_pointer(&_env, p.j().unwrap() as *const VRFProof)
+        };
+
+        //Verify vrf proof and get vrf output
+        let vrf_out = match vrf_proof_to_hash(message, public_key, proof) {
+            Ok(result) => result,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        //Return vrf output
+        return_field_element(&_env, vrf_out)
+    }
+);
+
+//Naive threshold signature proof functions
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_BackwardTransfer_nativeGetMcPkHashSize(
+        _env: JNIEnv,
+        _class: JClass,
+    ) -> jint {
+        MC_PK_SIZE as jint
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeGetConstant(
+        _env: JNIEnv,
+        // this is the class that owns our
+        // static method. Not going to be
+        // used, but still needs to have
+        // an argument slot
+        _class: JClass,
+        _schnorr_pks_list: jobjectArray,
+        _threshold: jlong,
+    ) -> jobject {
+        //Extract Schnorr pks
+        let mut pks = vec![];
+
+        let pks_list_size = _env
+            .get_array_length(_schnorr_pks_list)
+            .expect("Should be able to get schnorr_pks_list size");
+
+        for i in 0..pks_list_size {
+            let pk_object = _env
+                .get_object_array_element(_schnorr_pks_list, i)
+                .unwrap_or_else(|_| panic!("Should be able to get elem {} of schnorr_pks_list", i));
+
+            let pk = _env
+                .get_field(pk_object, "publicKeyPointer", "J")
+                .expect("Should be able to get field publicKeyPointer");
+
+            pks.push(*read_raw_pointer(
+                &_env,
+                pk.j().unwrap() as *const SchnorrPk,
+            ));
+        }
+
+        //Extract threshold
+        let threshold = _threshold as u64;
+
+        //Compute constant
+        match compute_pks_threshold_hash(pks.as_slice(), threshold) {
+            Ok(constant) => return_field_element(&_env, constant),
+            Err(_) => std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeCreateMsgToSign(
+        _env: JNIEnv,
+        // this is the class that owns our
+        // static method. Not going to be
+        // used, but still needs to have
+        // an argument slot
+        _class: JClass,
+        _bt_list: jobjectArray,
+        _sc_id: JObject,
+        _epoch_number: jint,
+        _end_cumulative_sc_tx_comm_tree_root: JObject,
+        _btr_fee: jlong,
+        _ft_min_amount: jlong,
+    ) -> jobject {
+        //Extract backward transfers
+        let mut bt_list = vec![];
+
+        let bt_list_size = _env
+            .get_array_length(_bt_list)
+            .expect("Should be able to get bt_list size");
+
+        if bt_list_size > 0 {
+            for i in 0..bt_list_size {
+                let o = _env
+                    .get_object_array_element(_bt_list, i)
+                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+
+                let p = _env
+                    .call_method(o, "getPublicKeyHash", "()[B", &[])
+                    .expect("Should be able to call getPublicKeyHash method")
+                    .l()
+                    .unwrap()
+                    .cast();
+
+                let pk: [u8; 20] = _env
+                    .convert_byte_array(p)
+                    .expect("Should be able to convert to Rust byte array")
+                    .try_into()
+                    .expect("Should be able to write into fixed buffer of size 20");
+
+                let a = _env
+                    .call_method(o, "getAmount", "()J", &[])
+                    .expect("Should be able to call getAmount method")
+                    .j()
+                    .unwrap() as u64;
+
+                bt_list.push((a, pk));
+            }
+        }
+
+        let bt_list = bt_list
+            .into_iter()
+            .map(|bt_raw| BackwardTransfer {
+                pk_dest: bt_raw.1,
+                amount: bt_raw.0,
+            })
+            .collect::<Vec<_>>();
+
+        let sc_id = {
+            let f = _env
+                .get_field(_sc_id, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        let end_cumulative_sc_tx_comm_tree_root = {
+            let f = _env
+                .get_field(
+                    _end_cumulative_sc_tx_comm_tree_root,
+                    "fieldElementPointer",
+                    "J",
+                )
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        //Compute message to sign:
+        let msg = match compute_msg_to_sign(
+            sc_id,
+            _epoch_number as u32,
+            end_cumulative_sc_tx_comm_tree_root,
+            _btr_fee as u64,
+            _ft_min_amount as u64,
+            bt_list,
+        ) {
+            Ok((_, msg)) => msg,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR
+        };
+
+        //Return msg
+        return_field_element(&_env, msg)
+    }
+);
+
+fn get_proving_system_type(_env: &JNIEnv, _proving_system: JObject) -> ProvingSystem {
+    // Extract proving system type
+    let proving_system = _env
+        .call_method(_proving_system, "ordinal", "()I", &[])
+        .expect("Should be able to call ordinal() on ProvingSystem enum")
+        .i()
+        .unwrap() as usize;
+
+    // Convert to Rust enum
+    match proving_system {
+        0 => ProvingSystem::Undefined,
+        1 => ProvingSystem::Darlin,
+        2 => ProvingSystem::CoboundaryMarlin,
+        _ => unreachable!(),
+    }
+}
+
+ffi_export!(
+    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGenerateDLogKeys(
+        _env: JNIEnv,
+        _class: JClass,
+        _proving_system: JObject,
+        _max_segment_size: jint,
+        _supported_segment_size: jint,
+    ) -> jboolean {
+        // Get proving system type
+        let proving_system = get_proving_system_type(&_env, _proving_system);
+
+        // Generate DLOG keypair
+        match init_dlog_keys(
+            proving_system,
+            _max_segment_size as usize,
+            _supported_segment_size as usize,
+        ) {
+            Ok(_) => JNI_TRUE,
+            Err(_) => JNI_FALSE,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeCheckProofVkSize(
+        _env: JNIEnv,
+        _class: JClass,
+        _zk: jboolean,
+        _supported_segment_size: jint,
+        _max_proof_size: jint,
+        _max_vk_size: jint,
+        _verification_key_path: JString,
+    ) -> jboolean {
+        // Read vk from file
+
+        //Extract vk path
+        let vk_path = _env
+            .get_string(_verification_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        // Deserialize vk
+        let vk: ZendooVerifierKey = match read_artifact_from_file(
+            Path::new(vk_path.to_str().unwrap()),
+            ArtifactType::VerifierKey,
+            Some(false),
+            Some(true),
+        ) {
+            Ok(vk) => vk,
+            Err(e) => {
+                println!("{:?}", e);
+                return JNI_FALSE;
+            }
+        };
+
+        // Read zk value
+        let zk = _zk == JNI_TRUE;
+
+        // Get ps type from vk
+        let ps_type = vk.get_proving_system_type();
+
+        // Get index info from vk
+        let index_info = match vk {
+            ZendooVerifierKey::CoboundaryMarlin(cob_marlin_vk) => cob_marlin_vk.index_info,
+            ZendooVerifierKey::Darlin(darlin_vk) => darlin_vk.index_info,
+        };
+
+        // Perform check
+        let result = check_proof_vk_size(
+            _supported_segment_size as usize,
+            index_info,
+            zk,
+            ps_type,
+            _max_proof_size as usize,
+            _max_vk_size as usize,
+        );
+
+        if result {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeSetup(
+        _env: JNIEnv,
+        _class: JClass,
+        _proving_system: JObject,
+        _max_pks: jlong,
+        _proving_key_path: JString,
+        _verification_key_path: JString,
+        _zk: jboolean,
+        _max_proof_size: jint,
+        _max_vk_size: jint,
+        _compress_pk: jboolean,
+        _compress_vk: jboolean,
+    ) -> jboolean {
+        // Get proving system type
+        let proving_system = get_proving_system_type(&_env, _proving_system);
+
+        // Read paths
+        let proving_key_path = _env
+            .get_string(_proving_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        let verification_key_path = _env
+            .get_string(_verification_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        let max_pks = _max_pks as usize;
+
+        let circ = get_instance_for_setup(max_pks);
+
+        // Read zk value
+        let zk = _zk == JNI_TRUE;
+
+        // Generate snark keypair
+        match generate_circuit_keypair(
+            circ,
+            proving_system,
+            Path::new(proving_key_path.to_str().unwrap()),
+            Path::new(verification_key_path.to_str().unwrap()),
+            _max_proof_size as usize,
+            _max_vk_size as usize,
+            zk,
+            Some(_compress_pk == JNI_TRUE),
+            Some(_compress_vk == JNI_TRUE),
+        ) {
+            Ok(_) => JNI_TRUE,
+            Err(e) => {
+                println!("{:?}", e);
+                JNI_FALSE
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeGetCircuitSizeInfo(
+        _env: JNIEnv,
+        _class: JClass,
+        _proving_system: JObject,
+        _max_pks: jlong,
+        _zk: jboolean,
+        _supported_segment_size: jint,
+        _max_proof_size: jint,
+        _max_vk_size: jint,
+    ) -> jobject {
+        // Get proving system type
+        let proving_system = get_proving_system_type(&_env, _proving_system);
+
+        // Negative sizes are invalid
+        let (max_pks, supported_segment_size, max_proof_size, max_vk_size) = match (
+            _max_pks.try_into(),
+            _supported_segment_size.try_into(),
+            _max_proof_size.try_into(),
+            _max_vk_size.try_into(),
+        ) {
+            (Ok(max_pks), Ok(segment_size), Ok(max_proof_size), Ok(max_vk_size)) => {
+                (max_pks, segment_size, max_proof_size, max_vk_size)
+            }
+            _ => return std::ptr::null::<jobject>() as jobject,
+        };
+
+        // Compute circuit size info
+        let info = match get_naive_threshold_sig_circuit_size_info(
+            max_pks,
+            proving_system,
+            supported_segment_size,
+            _zk == JNI_TRUE,
+        ) {
+            Ok(info) => info,
+            Err(_) => return std::ptr::null::<jobject>() as jobject,
+        };
+
+        let fits = info.fits(max_proof_size, max_vk_size);
+
+        //Create new CircuitSizeInfo object
+        let info_class = _env
+            .find_class("com/horizen/sigproofnative/CircuitSizeInfo")
+            .expect("Should be able to find CircuitSizeInfo class");
+
+        let result = _env
+            .new_object(
+                info_class,
+                "(JIIZ)V",
+                &[
+                    JValue::Long(info.num_constraints as i64),
+                    JValue::Int(info.proof_size as i32),
+                    JValue::Int(info.vk_size as i32),
+                    JValue::Bool(fits as jboolean),
+                ],
+            )
+            .expect("Should be able to create new CircuitSizeInfo:(long, int, int, boolean) object");
+
+        *result
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeGetMaxSupportedPks(
+        _env: JNIEnv,
+        _class: JClass,
+        _proving_system: JObject,
+        _zk: jboolean,
+        _supported_segment_size: jint,
+        _max_proof_size: jint,
+        _max_vk_size: jint,
+        _max_pks_bound: jlong,
+    ) -> jlong {
+        // Get proving system type
+        let proving_system = get_proving_system_type(&_env, _proving_system);
+
+        // Negative sizes are invalid
+        let (supported_segment_size, max_proof_size, max_vk_size, max_pks_bound) = match (
+            _supported_segment_size.try_into(),
+            _max_proof_size.try_into(),
+            _max_vk_size.try_into(),
+            _max_pks_bound.try_into(),
+        ) {
+            (Ok(segment_size), Ok(max_proof_size), Ok(max_vk_size), Ok(max_pks_bound)) => {
+                (segment_size, max_proof_size, max_vk_size, max_pks_bound)
+            }
+            _ => return -1,
+        };
+
+        // Search the max number of pks fitting the limits
+        match get_naive_threshold_sig_circuit_max_pks(
+            proving_system,
+            supported_segment_size,
+            _zk == JNI_TRUE,
+            max_proof_size,
+            max_vk_size,
+            max_pks_bound,
+        ) {
+            Ok(Some(max_pks)) => max_pks as jlong,
+            Ok(None) => 0,
+            Err(_) => -1,
+        }
+    }
+);
+
+fn get_proving_system_type_as_jint(_env: &JNIEnv, ps: ProvingSystem) -> jint {
+    match ps {
+        ProvingSystem::Undefined => 0_i32,
+        ProvingSystem::Darlin => 1_i32,
+        ProvingSystem::CoboundaryMarlin => 2_i32,
+    }
+}
+
+ffi_export!(
+    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGetProverKeyProvingSystemType(
+        _env: JNIEnv,
+        _class: JClass,
+        _proving_key_path: JString,
+    ) -> jint {
+        // Read paths
+        let proving_key_path = _env
+            .get_string(_proving_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        match read_artifact_proving_system(
+            Path::new(proving_key_path.to_str().unwrap()),
+            ArtifactType::ProverKey,
+        ) {
+            Ok(ps) => get_proving_system_type_as_jint(&_env, ps),
+            Err(_) => 1_i32,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGetVerifierKeyProvingSystemType(
+        _env: JNIEnv,
+        _class: JClass,
+        _verifier_key_path: JString,
+    ) -> jint {
+        // Read paths
+        let verifier_key_path = _env
+            .get_string(_verifier_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        match read_artifact_proving_system(
+            Path::new(verifier_key_path.to_str().unwrap()),
+            ArtifactType::VerifierKey,
+        ) {
+            Ok(ps) => get_proving_system_type_as_jint(&_env, ps),
+            Err(_) => 1_i32,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeCreateProof(
+        _env: JNIEnv,
+        // this is the class that owns our
+        // static method. Not going to be
+        // used, but still needs to have
+        // an argument slot
+        _class: JClass,
+        _bt_list: jobjectArray,
+        _sc_id: JObject,
+        _epoch_number: jint,
+        _end_cumulative_sc_tx_comm_tree_root: JObject,
+        _btr_fee: jlong,
+        _ft_min_amount: jlong,
+        _schnorr_sigs_list: jobjectArray,
+        _schnorr_pks_list: jobjectArray,
+        _threshold: jlong,
+        _proving_key_path: JString,
+        _check_proving_key: jboolean,
+        _zk: jboolean,
+        _compressed_pk: jboolean,
+        _compress_proof: jboolean,
+    ) -> jobject {
+        // Extract backward transfers
+        let mut bt_list = vec![];
+
+        let bt_list_size = _env
+            .get_array_length(_bt_list)
+            .expect("Should be able to get bt_list size");
+
+        if bt_list_size > 0 {
+            for i in 0..bt_list_size {
+                let o = _env
+                    .get_object_array_element(_bt_list, i)
+                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+
+                let p = _env
+                    .call_method(o, "getPublicKeyHash", "()[B", &[])
+                    .expect("Should be able to call getPublicKeyHash method")
+                    .l()
+                    .unwrap()
+                    .cast();
+
+                let pk: [u8; 20] = _env
+                    .convert_byte_array(p)
+                    .expect("Should be able to convert to Rust byte array")
+                    .try_into()
+                    .expect("Should be able to write into fixed buffer of size 20");
+
+                let a = _env
+                    .call_method(o, "getAmount", "()J", &[])
+                    .expect("Should be able to call getAmount method")
+                    .j()
+                    .unwrap() as u64;
+
+                bt_list.push((a, pk));
+            }
+        }
+
+        let bt_list = bt_list
+            .into_iter()
+            .map(|bt_raw| BackwardTransfer {
+                pk_dest: bt_raw.1,
+                amount: bt_raw.0,
+            })
+            .collect::<Vec<_>>();
+
+        //Extract Schnorr signatures and the corresponding Schnorr pks
+        let mut sigs = vec![];
+        let mut pks = vec![];
+
+        let sigs_list_size = _env
+            .get_array_length(_schnorr_sigs_list)
+            .expect("Should be able to get schnorr_sigs_list size");
+
+        let pks_list_size = _env
+            .get_array_length(_schnorr_pks_list)
+            .expect("Should be able to get schnorr_pks_list size");
+
+        assert_eq!(sigs_list_size, pks_list_size);
+
+        for i in 0..sigs_list_size {
+            //Get i-th sig
+            let sig_object = _env
+                .get_object_array_element(_schnorr_sigs_list, i)
+                .unwrap_or_else(|_| panic!("Should be able to get elem {} of schnorr_sigs_list", i));
+
+            let pk_object = _env
+                .get_object_array_element(_schnorr_pks_list, i)
+                .unwrap_or_else(|_| panic!("Should be able to get elem {} of schnorr_pks_list", i));
+
+            let signature = {
+                let sig = _env
+                    .get_field(sig_object, "signaturePointer", "J")
+                    .expect("Should be able to get field signaturePointer");
+
+                read_nullable_raw_pointer(sig.j().unwrap() as *const SchnorrSig).copied()
+            };
+
+            let public_key = {
+                let pk = _env
+                    .get_field(pk_object, "publicKeyPointer", "J")
+                    .expect("Should be able to get field publicKeyPointer");
+
+                read_raw_pointer(&_env, pk.j().unwrap() as *const SchnorrPk)
+            };
+
+            sigs.push(signature);
+            pks.push(*public_key);
+        }
+
+        let sc_id = {
+            let f = _env
+                .get_field(_sc_id, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        let end_cumulative_sc_tx_comm_tree_root = {
+            let f = _env
+                .get_field(
+                    _end_cumulative_sc_tx_comm_tree_root,
+                    "fieldElementPointer",
+                    "J",
+                )
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        //Extract params_path str
+        let proving_key_path = _env
+            .get_string(_proving_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        //create proof
+        let (proof, quality) = match create_naive_threshold_sig_proof(
+            pks.as_slice(),
+            sigs,
+            sc_id,
+            _epoch_number as u32,
+            end_cumulative_sc_tx_comm_tree_root,
+            _btr_fee as u64,
+            _ft_min_amount as u64,
+            bt_list,
+            _threshold as u64,
+            Path::new(proving_key_path.to_str().unwrap()),
+            _check_proving_key == JNI_TRUE,
+            _zk == JNI_TRUE,
+            _compressed_pk == JNI_TRUE,
+            _compress_proof == JNI_TRUE,
+        ) {
+            Ok(proof) => proof,
+            Err(_) => return std::ptr::null::<jobject>() as jobject, //CRYPTO_ERROR or IO_ERROR
+        };
+
+        //Return proof serialized
+        let proof_serialized = _env
+            .byte_array_from_slice(proof.as_slice())
+            .expect("Should be able to convert Rust slice into jbytearray");
+
+        //Create new CreateProofResult object
+        let proof_result_class = _env
+            .find_class("com/horizen/sigproofnative/CreateProofResult")
+            .expect("Should be able to find CreateProofResult class");
+
+        let result = _env
+            .new_object(
+                proof_result_class,
+                "([BJ)V",
+                &[
+                    JValue::Object(JObject::from(proof_serialized)),
+                    JValue::Long(quality as i64),
+                ],
+            )
+            .expect("Should be able to create new CreateProofResult:(byte[], long) object");
+
+        *result
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_provingsystemnative_ProvingSystem_nativeGetProofProvingSystemType(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof: jbyteArray,
+    ) -> jint {
+        //Extract proof
+        let proof_bytes = _env
+            .convert_byte_array(_proof)
+            .expect("Should be able to convert to Rust byte array");
+
+        match deserialize_from_buffer::<ProvingSystem>(&proof_bytes[..1], None, None) {
+            Ok(ps) => get_proving_system_type_as_jint(&_env, ps),
+            Err(_) => 1_i32,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_NaiveThresholdSigProof_nativeVerifyProof(
+        _env: JNIEnv,
+        // this is the class that owns our
+        // static method. Not going to be
+        // used, but still needs to have
+        // an argument slot
+        _class: JClass,
+        _bt_list: jobjectArray,
+        _sc_id: JObject,
+        _epoch_number: jint,
+        _end_cumulative_sc_tx_comm_tree_root: JObject,
+        _btr_fee: jlong,
+        _ft_min_amount: jlong,
+        _constant: JObject,
+        _quality: jlong,
+        _sc_proof_bytes: jbyteArray,
+        _check_proof: jboolean,
+        _compressed_proof: jboolean,
+        _verification_key_path: JString,
+        _check_vk: jboolean,
+        _compressed_vk: jboolean,
+    ) -> jboolean {
+        //Extract backward transfers
+        let mut bt_list = vec![];
+
+        let bt_list_size = _env
+            .get_array_length(_bt_list)
+            .expect("Should be able to get bt_list size");
+
+        if bt_list_size > 0 {
+            for i in 0..bt_list_size {
+                let o = _env
+                    .get_object_array_element(_bt_list, i)
+                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+
+                let p = _env
+                    .call_method(o, "getPublicKeyHash", "()[B", &[])
+                    .expect("Should be able to call getPublicKeyHash method")
+                    .l()
+                    .unwrap()
+                    .cast();
+
+                let pk: [u8; 20] = _env
+                    .convert_byte_array(p)
+                    .expect("Should be able to convert to Rust byte array")
+                    .try_into()
+                    .expect("Should be able to write into fixed buffer of size 20");
+
+                let a = _env
+                    .call_method(o, "getAmount", "()J", &[])
+                    .expect("Should be able to call getAmount method")
+                    .j()
+                    .unwrap() as u64;
+
+                bt_list.push((a, pk));
+            }
+        }
+
+        let bt_list = bt_list
+            .into_iter()
+            .map(|bt_raw| BackwardTransfer {
+                pk_dest: bt_raw.1,
+                amount: bt_raw.0,
+            })
+            .collect::<Vec<_>>();
+
+        let sc_id = {
+            let f = _env
+                .get_field(_sc_id, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        let end_cumulative_sc_tx_comm_tree_root = {
+            let f = _env
+                .get_field(
+                    _end_cumulative_sc_tx_comm_tree_root,
+                    "fieldElementPointer",
+                    "J",
+                )
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        //Extract constant
+        let constant = {
+            let c = _env
+                .get_field(_constant, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, c.j().unwrap() as *const FieldElement)
+        };
+
+        //Extract proof
+        let proof_bytes = _env
+            .convert_byte_array(_sc_proof_bytes)
+            .expect("Should be able to convert to Rust byte array");
+
+        //Extract vk path
+        let vk_path = _env
+            .get_string(_verification_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        //Verify proof
+        match verify_naive_threshold_sig_proof(
+            constant,
+            sc_id,
+            _epoch_number as u32,
+            end_cumulative_sc_tx_comm_tree_root,
+            _btr_fee as u64,
+            _ft_min_amount as u64,
+            bt_list,
+            _quality as u64,
+            proof_bytes,
+            _check_proof == JNI_TRUE,
+            _compressed_proof == JNI_TRUE,
+            Path::new(vk_path.to_str().unwrap()),
+            _check_vk == JNI_TRUE,
+            _compressed_vk == JNI_TRUE,
+        ) {
+            Ok(result) => {
+                if result {
+                    JNI_TRUE
+                } else {
+                    JNI_FALSE
+                }
+            }
+            Err(_) => JNI_FALSE, // CRYPTO_ERROR or IO_ERROR
+        }
+    }
+);
+
+///////// CERTIFICATE
+
+fn read_backward_transfers(_env: &JNIEnv, _bt_list: jobjectArray) -> Vec<BackwardTransfer> {
+    let bt_list_size = _env
+        .get_array_length(_bt_list)
+        .expect("Should be able to get bt_list size");
+
+    (0..bt_list_size)
+        .map(|i| {
+            let o = _env
+                .get_object_array_element(_bt_list, i)
+                .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+
+            let p = _env
+                .call_method(o, "getPublicKeyHash", "()[B", &[])
+                .expect("Should be able to call getPublicKeyHash method")
+                .l()
+                .unwrap()
+                .cast();
+
+            let pk_dest: [u8; MC_PK_SIZE] = _env
+                .convert_byte_array(p)
+                .expect("Should be able to convert to Rust byte array")
+                .try_into()
+                .expect("Should be able to write into fixed buffer of size MC_PK_SIZE");
+
+            let amount = _env
+                .call_method(o, "getAmount", "()J", &[])
+                .expect("Should be able to call getAmount method")
+                .j()
+                .unwrap() as u64;
+
+            BackwardTransfer { pk_dest, amount }
+        })
+        .collect()
+}
+
+fn read_certificate<'a>(_env: &JNIEnv, _cert: JObject) -> &'a Certificate {
+    let c = _env
+        .get_field(_cert, "certificatePointer", "J")
+        .expect("Should be able to get field certificatePointer");
+
+    read_raw_pointer(&_env, c.j().unwrap() as *const Certificate)
+}
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeCreate(
+        _env: JNIEnv,
+        _class: JClass,
+        _sc_id: JObject,
+        _epoch_number: jint,
+        _quality: jlong,
+        _end_cumulative_sc_tx_comm_tree_root: JObject,
+        _bt_list: jobjectArray,
+        _custom_fields: jobjectArray,
+        _btr_fee: jlong,
+        _ft_min_amount: jlong,
+        _proof: jbyteArray,
+    ) -> jobject {
+        let sc_id = {
+            let f = _env
+                .get_field(_sc_id, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        let end_cumulative_sc_tx_comm_tree_root = {
+            let f = _env
+                .get_field(
+                    _end_cumulative_sc_tx_comm_tree_root,
+                    "fieldElementPointer",
+                    "J",
+                )
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+        };
+
+        let proof = _env
+            .convert_byte_array(_proof)
+            .expect("Should be able to convert to Rust byte array");
+
+        let cert = CertificateBuilder::new()
+            .sc_id(*sc_id)
+            .epoch_number(_epoch_number as u32)
+            .quality(_quality as u64)
+            .end_cumulative_sc_tx_commitment_tree_root(*end_cumulative_sc_tx_comm_tree_root)
+            .bt_list(read_backward_transfers(&_env, _bt_list))
+            .custom_fields(read_field_elements(&_env, _custom_fields))
+            .btr_fee(_btr_fee as u64)
+            .ft_min_amount(_ft_min_amount as u64)
+            .proof(proof)
+            .build();
+
+        match cert {
+            Ok(cert) => *return_jobject(&_env, cert, "com/horizen/sigproofnative/Certificate"),
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &format!("Invalid certificate: {}", e),
+                JObject::null().into_inner()
+            ),
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeGetQuality(
+        _env: JNIEnv,
+        _cert: JObject,
+    ) -> jlong {
+        read_certificate(&_env, _cert).quality() as jlong
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeGetProof(
+        _env: JNIEnv,
+        _cert: JObject,
+    ) -> jbyteArray {
+        _env.byte_array_from_slice(read_certificate(&_env, _cert).proof())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeVerify(
+        _env: JNIEnv,
+        _cert: JObject,
+        _constant_nullable: JObject, // can be null if there is no constant
+        _check_proof: jboolean,
+        _compressed_proof: jboolean,
+        _verification_key_path: JString,
+        _check_vk: jboolean,
+        _compressed_vk: jboolean,
+    ) -> jboolean {
+        let cert = read_certificate(&_env, _cert);
+
+        let constant = if _constant_nullable.is_null() {
+            None
+        } else {
+            let c = _env
+                .get_field(_constant_nullable, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            Some(read_raw_pointer(&_env, c.j().unwrap() as *const FieldElement))
+        };
+
+        let vk_path = _env
+            .get_string(_verification_key_path)
+            .expect("Should be able to read jstring as Rust String");
+
+        match cert.verify(
+            constant,
+            _check_proof == JNI_TRUE,
+            _compressed_proof == JNI_TRUE,
+            Path::new(vk_path.to_str().unwrap()),
+            _check_vk == JNI_TRUE,
+            _compressed_vk == JNI_TRUE,
+        ) {
+            Ok(true) => JNI_TRUE,
+            Ok(false) => JNI_FALSE,
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &format!("Unable to verify certificate: {}", e),
+                JNI_FALSE
+            ),
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeSerialize(
+        _env: JNIEnv,
+        _cert: JObject,
+    ) -> jbyteArray {
+        let cert_bytes = read_certificate(&_env, _cert)
+            .to_bytes()
+            .expect("Should be able to serialize Certificate");
+
+        _env.byte_array_from_slice(cert_bytes.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _cert_bytes: jbyteArray,
+    ) -> jobject {
+        let cert_bytes = _env
+            .convert_byte_array(_cert_bytes)
+            .expect("Cannot read certificate bytes.");
+
+        match Certificate::from_bytes(cert_bytes.as_slice()) {
+            Ok(cert) => *return_jobject(&_env, cert, "com/horizen/sigproofnative/Certificate"),
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &e.to_string(),
+                JObject::null().into_inner()
+            ),
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_sigproofnative_Certificate_nativeFreeCertificate(
+        _env: JNIEnv,
+        _class: JClass,
+        _cert: *mut Certificate,
+    ) {
+        if _cert.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_cert) });
+    }
+);
+
+///////// COMMITMENT TREE
+
+const COMMITMENT_TREE_INPUT_EXCEPTION: &str =
+    "com/horizen/commitmenttree/CommitmentTreeInputException";
+
+// Malformed input of a CommitmentTree function: name of the (Java side) parameter and reason
+struct MalformedInput {
+    field: String,
+    reason: String,
+}
+
+impl MalformedInput {
+    fn new<S: Into<String>>(field: &str, reason: S) -> Self {
+        Self {
+            field: field.to_owned(),
+            reason: reason.into(),
+        }
+    }
+}
+
+fn throw_malformed_input(_env: &JNIEnv, input: MalformedInput) {
+    // Do nothing if there is a pending Java-exception
+    if _env.exception_check().unwrap() {
+        return;
+    }
+
+    let field = _env
+        .new_string(&input.field)
+        .expect("Should be able to create field name string");
+    let reason = _env
+        .new_string(format!("Malformed {}: {}", input.field, input.reason))
+        .expect("Should be able to create reason string");
+
+    let exception = _env
+        .new_object(
+            COMMITMENT_TREE_INPUT_EXCEPTION,
+            "(Ljava/lang/String;Ljava/lang/String;)V",
+            &[JValue::Object(field.into()), JValue::Object(reason.into())],
+        )
+        .expect("Should be able to create CommitmentTreeInputException");
+
+    _env.throw(JThrowable::from(exception))
+        .expect("Should be able to throw CommitmentTreeInputException");
+}
+
+/// Unwrap the result of reading a CommitmentTree input or, if the input is malformed,
+/// throw a CommitmentTreeInputException and return $default from the calling function
+macro_rules! read_ct_input {
+    ($env:expr, $read:expr, $default:expr) => {
+        match $read {
+            Ok(input) => input,
+            Err(e) => {
+                throw_malformed_input($env, e);
+                return $default;
+            }
+        }
+    };
+}
+
+fn ct_read_bytes(
+    _env: &JNIEnv,
+    array: jbyteArray,
+    field: &str,
+) -> Result<Vec<u8>, MalformedInput> {
+    if array.is_null() {
+        return Err(MalformedInput::new(field, "must not be null"));
+    }
+    _env.convert_byte_array(array)
+        .map_err(|e| MalformedInput::new(field, format!("unable to read bytes ({})", e)))
+}
+
+fn ct_read_fixed_bytes<const N: usize>(
+    _env: &JNIEnv,
+    array: jbyteArray,
+    field: &str,
+) -> Result<[u8; N], MalformedInput> {
+    let bytes = ct_read_bytes(_env, array, field)?;
+    bytes.as_slice().try_into().map_err(|_| {
+        MalformedInput::new(
+            field,
+            format!("expected {} bytes, found {}", N, bytes.len()),
+        )
+    })
+}
+
+fn ct_read_field_element(
+    _env: &JNIEnv,
+    array: jbyteArray,
+    field: &str,
+) -> Result<FieldElement, MalformedInput> {
+    let bytes = ct_read_fixed_bytes::<FIELD_SIZE>(_env, array, field)?;
+    FieldElement::deserialize(&bytes[..])
+        .map_err(|_| MalformedInput::new(field, "not a valid field element"))
+}
+
+// Read the elements of a Java array, reporting errors on the element at position `i`
+// as errors on `field[i]`
+fn ct_read_object_array<T, F>(
+    _env: &JNIEnv,
+    array: jobjectArray,
+    field: &str,
+    mut read: F,
+) -> Result<Vec<T>, MalformedInput>
+where
+    F: FnMut(JObject, &str) -> Result<T, MalformedInput>,
+{
+    if array.is_null() {
+        return Err(MalformedInput::new(field, "must not be null"));
+    }
+    let len = _env
+        .get_array_length(array)
+        .map_err(|e| MalformedInput::new(field, format!("unable to read array ({})", e)))?;
+
+    (0..len)
+        .map(|i| {
+            let element_field = format!("{}[{}]", field, i);
+            let element = _env.get_object_array_element(array, i).map_err(|e| {
+                MalformedInput::new(&element_field, format!("unable to read element ({})", e))
+            })?;
+            if element.is_null() {
+                return Err(MalformedInput::new(&element_field, "must not be null"));
+            }
+            read(element, &element_field)
+        })
+        .collect()
+}
+
+fn ct_read_field_element_array(
+    _env: &JNIEnv,
+    array: jobjectArray,
+    field: &str,
+) -> Result<Vec<FieldElement>, MalformedInput> {
+    ct_read_object_array(_env, array, field, |element, element_field| {
+        ct_read_field_element(_env, element.into_inner(), element_field)
+    })
+}
+
+fn ct_call_getter<'a>(
+    _env: &JNIEnv<'a>,
+    obj: JObject<'a>,
+    getter: &str,
+    sig: &str,
+    field: &str,
+) -> Result<JValue<'a>, MalformedInput> {
+    _env.call_method(obj, getter, sig, &[])
+        .map_err(|e| MalformedInput::new(field, format!("unable to call {} ({})", getter, e)))
+}
+
+fn ct_read_field_elements_configs(
+    _env: &JNIEnv,
+    array: jobjectArray,
+) -> Result<Vec<u8>, MalformedInput> {
+    ct_read_object_array(
+        _env,
+        array,
+        "customFieldElementsConfigs",
+        |config, field| {
+            Ok(ct_call_getter(_env, config, "getBits", "()B", field)?
+                .b()
+                .unwrap() as u8)
+        },
+    )
+}
+
+fn ct_read_bitvector_elements_configs(
+    _env: &JNIEnv,
+    array: jobjectArray,
+) -> Result<Vec<BitVectorElementsConfig>, MalformedInput> {
+    ct_read_object_array(
+        _env,
+        array,
+        "customBitvectorElementsConfigs",
+        |config, field| {
+            let bit_vector_size_bits =
+                ct_call_getter(_env, config, "getBitVectorSizeBits", "()I", field)?
+                    .i()
+                    .unwrap() as u32;
+
+            let max_compressed_byte_size =
+                ct_call_getter(_env, config, "getMaxCompressedByteSize", "()I", field)?
+                    .i()
+                    .unwrap() as u32;
+
+            Ok(BitVectorElementsConfig {
+                bit_vector_size_bits,
+                max_compressed_byte_size,
+            })
+        },
+    )
+}
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeInit(
+        _env: JNIEnv,
+        _class: JClass,
+    ) -> jobject {
+        // Create new CommitmentTree Rust side
+        let commitment_tree = IncrementalCommitmentTree::create();
+
+        // Create and return new CommitmentTree Java side
+        let commitment_tree_ptr: jlong =
+            Box::into_raw(Box::new(commitment_tree)) as i64;
+
+        _env.new_object(_class, "(J)V", &[JValue::Long(commitment_tree_ptr)])
+            .expect("Should be able to create new CommitmentTree object")
+            .into_inner()
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeFreeCommitmentTree(
+        _env: JNIEnv,
+        _class: JClass,
+        _commitment_tree: *mut IncrementalCommitmentTree,
+    ) {
+        if _commitment_tree.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_commitment_tree) });
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeSerialize(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+    ) -> jbyteArray {
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_raw_pointer(&_env, t.j().unwrap() as *const IncrementalCommitmentTree)
+        };
+
+        let state = commitment_tree
+            .serialize_state()
+            .expect("Should be able to serialize commitment tree");
+
+        _env.byte_array_from_slice(state.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _state: jbyteArray,
+    ) -> jobject {
+        let state = read_ct_input!(
+            &_env,
+            ct_read_bytes(&_env, _state, "state"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        match IncrementalCommitmentTree::deserialize_state(state.as_slice()) {
+            Ok(commitment_tree) => return_jobject(
+                &_env,
+                commitment_tree,
+                "com/horizen/commitmenttree/CommitmentTree",
+            )
+            .into_inner(),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddScCr(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _amount: jlong,
+        _pub_key: jbyteArray,
+        _tx_hash: jbyteArray,
+        _out_idx: jint,
+        _withdrawal_epoch_length: jint,
+        _mc_btr_request_data_length: jbyte,
+        _custom_field_elements_configs: jobjectArray,
+        _custom_bitvector_elements_configs: jobjectArray,
+        _btr_fee: jlong,
+        _ft_min_amount: jlong,
+        _custom_creation_data: jbyteArray,
+        _constant_nullable: jbyteArray, // can be null if there is no constant
+        _cert_verification_key: jbyteArray,
+        _csw_verification_key_nullable: jbyteArray, // can be null if there is no key for CSWs
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let amount = _amount as u64;
+
+        let pub_key = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _pub_key, "pubKey"),
+            JNI_FALSE
+        );
+
+        let tx_hash = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _tx_hash, "txHash"),
+            JNI_FALSE
+        );
+
+        let out_idx = _out_idx as u32;
+
+        let withdrawal_epoch_length = _withdrawal_epoch_length as u32;
+
+        let mc_btr_request_data_length = _mc_btr_request_data_length as u8;
+
+        let custom_field_elements_configs = read_ct_input!(
+            &_env,
+            ct_read_field_elements_configs(&_env, _custom_field_elements_configs),
+            JNI_FALSE
+        );
+        let custom_field_elements_configs_opt = if !custom_field_elements_configs.is_empty() {
+            Some(custom_field_elements_configs.as_slice())
+        } else {
+            None
+        };
+
+        let custom_bitvector_elements_configs = read_ct_input!(
+            &_env,
+            ct_read_bitvector_elements_configs(&_env, _custom_bitvector_elements_configs),
+            JNI_FALSE
+        );
+        let custom_bitvector_elements_configs_opt = if !custom_bitvector_elements_configs.is_empty() {
+            Some(custom_bitvector_elements_configs.as_slice())
+        } else {
+            None
+        };
+
+        let btr_fee = _btr_fee as u64;
+
+        let ft_min_amount = _ft_min_amount as u64;
+
+        let custom_creation_data = read_ct_input!(
+            &_env,
+            ct_read_bytes(&_env, _custom_creation_data, "customCreationData"),
+            JNI_FALSE
+        );
+        let custom_creation_data_opt = if !custom_creation_data.is_empty() {
+            Some(custom_creation_data.as_slice())
+        } else {
+            None
+        };
+
+        let constant = if _constant_nullable.is_null() {
+            None
+        } else {
+            Some(read_ct_input!(
+                &_env,
+                ct_read_field_element(&_env, _constant_nullable, "constant"),
+                JNI_FALSE
+            ))
+        };
+
+        let cert_verification_key = read_ct_input!(
+            &_env,
+            ct_read_bytes(&_env, _cert_verification_key, "certVerificationKey"),
+            JNI_FALSE
+        );
+
+        let csw_verification_key = if _csw_verification_key_nullable.is_null() {
+            None
+        } else {
+            Some(read_ct_input!(
+                &_env,
+                ct_read_bytes(&_env, _csw_verification_key_nullable, "cswVerificationKey"),
+                JNI_FALSE
+            ))
+        };
+        let csw_verification_key_opt = csw_verification_key.as_deref();
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_scc(
+            &sc_id,
+            amount,
+            &pub_key,
+            &tx_hash,
+            out_idx,
+            withdrawal_epoch_length,
+            mc_btr_request_data_length,
+            custom_field_elements_configs_opt,
+            custom_bitvector_elements_configs_opt,
+            btr_fee,
+            ft_min_amount,
+            custom_creation_data_opt,
+            constant.as_ref(),
+            cert_verification_key.as_slice(),
+            csw_verification_key_opt,
+        ) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddScCrWithParams(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _tx_hash: jbyteArray,
+        _out_idx: jint,
+        _params: JObject,
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let tx_hash = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _tx_hash, "txHash"),
+            JNI_FALSE
+        );
+
+        let params = read_sc_creation_params(&_env, _params);
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_sc_creation(&sc_id, &tx_hash, _out_idx as u32, params) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddFwt(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _amount: jlong,
+        _pub_key: jbyteArray,
+        _mc_return_address: jbyteArray,
+        _tx_hash: jbyteArray,
+        _out_idx: jint,
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let amount = _amount as u64;
+
+        let pub_key = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _pub_key, "pubKey"),
+            JNI_FALSE
+        );
+
+        let mc_return_address = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<MC_PK_SIZE>(&_env, _mc_return_address, "mcReturnAddress"),
+            JNI_FALSE
+        );
+
+        let tx_hash = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _tx_hash, "txHash"),
+            JNI_FALSE
+        );
+
+        let out_idx = _out_idx as u32;
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_fwt(
+            &sc_id,
+            amount,
+            &pub_key,
+            &mc_return_address,
+            &tx_hash,
+            out_idx,
+        ) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddBtr(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _sc_fee: jlong,
+        _mc_destination_address: jbyteArray,
+        _sc_request_data: jobjectArray,
+        _tx_hash: jbyteArray,
+        _out_idx: jint,
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let sc_fee = _sc_fee as u64;
+
+        let mc_destination_address = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<MC_PK_SIZE>(&_env, _mc_destination_address, "mcDestinationAddress"),
+            JNI_FALSE
+        );
+
+        let sc_request_data = read_ct_input!(
+            &_env,
+            ct_read_field_element_array(&_env, _sc_request_data, "scRequestData"),
+            JNI_FALSE
+        );
+
+        let tx_hash = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _tx_hash, "txHash"),
+            JNI_FALSE
+        );
+
+        let out_idx = _out_idx as u32;
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_bwtr(
+            &sc_id,
+            sc_fee,
+            sc_request_data.iter().collect(),
+            &mc_destination_address,
+            &tx_hash,
+            out_idx,
+        ) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddCert(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _epoch_number: jint,
+        _quality: jlong,
+        _bt_list: jobjectArray,
+        _custom_fields_nullable: jobjectArray, // can be null if there is no constant
+        _end_cumulative_sc_tx_commitment_tree_root: jbyteArray,
+        _btr_fee: jlong,
+        _ft_min_amount: jlong,
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let epoch_number = _epoch_number as u32;
+
+        let quality = _quality as u64;
+
+        //Extract backward transfers
+        let bt_list = read_ct_input!(
+            &_env,
+            ct_read_object_array(&_env, _bt_list, "btList", |bt, field| {
+                let pk_field = format!("{}.publicKeyHash", field);
+                let pk = ct_call_getter(&_env, bt, "getPublicKeyHash", "()[B", &pk_field)?
+                    .l()
+                    .unwrap()
+                    .into_inner();
+                let pk_dest = ct_read_fixed_bytes::<MC_PK_SIZE>(&_env, pk, &pk_field)?;
+
+                let amount = ct_call_getter(&_env, bt, "getAmount", "()J", field)?
+                    .j()
+                    .unwrap() as u64;
+
+                Ok(BackwardTransfer { pk_dest, amount })
+            }),
+            JNI_FALSE
+        );
+        let bt_list_opt = if !bt_list.is_empty() {
+            Some(bt_list.as_slice())
+        } else {
+            None
+        };
+
+        let custom_fields = if _custom_fields_nullable.is_null() {
+            None
+        } else {
+            Some(read_ct_input!(
+                &_env,
+                ct_read_field_element_array(&_env, _custom_fields_nullable, "customFields"),
+                JNI_FALSE
+            ))
+        };
+        let custom_fields_opt = custom_fields
+            .as_ref()
+            .map(|custom_fields| custom_fields.iter().collect());
+
+        let end_cumulative_sc_tx_commitment_tree_root = read_ct_input!(
+            &_env,
+            ct_read_field_element(
+                &_env,
+                _end_cumulative_sc_tx_commitment_tree_root,
+                "endCumulativeScTxCommitmentTreeRoot"
+            ),
+            JNI_FALSE
+        );
+
+        let btr_fee = _btr_fee as u64;
+
+        let ft_min_amount = _ft_min_amount as u64;
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_cert(
+            &sc_id,
+            epoch_number,
+            quality,
+            bt_list_opt,
+            custom_fields_opt,
+            &end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee,
+            ft_min_amount,
+        ) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddCertLeaf(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _leaf: jbyteArray,
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let leaf_fe = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _leaf, "leaf"),
+            JNI_FALSE
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_cert_leaf(&sc_id, &leaf_fe) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCrtLeaves(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        match commitment_tree.get_cert_leaves(&sc_id) {
+            Some(leaves) => {
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let initial_element = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(0)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let leaf_fe_array = _env
+                    .new_object_array(leaves.len() as i32, field_class, initial_element)
+                    .expect("Should be able to create array of FieldElements");
+
+                for (idx, leaf) in leaves.iter().enumerate() {
+                    let leaf_field_ptr: jlong =
+                        (Box::into_raw(Box::new(*leaf)) as i64);
+
+                    let leaf_element = _env
+                        .new_object(field_class, "(J)V", &[JValue::Long(leaf_field_ptr)])
+                        .expect("Should be able to create new long for FieldElement");
+
+                    _env.set_object_array_element(leaf_fe_array, idx as i32, leaf_element)
+                        .expect("Should be able to add FieldElement leaf to an array");
+                }
+
+                let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+                let empty_res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::from(JObject::from(leaf_fe_array))],
+                    )
+                    .expect("Should be able to create new value for Optional");
+
+                *empty_res.l().unwrap()
+            }
+            _ => {
+                let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddCsw(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _amount: jlong,
+        _nullifier: jbyteArray,
+        _mc_pk_hash: jbyteArray,
+    ) -> jboolean {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
+
+        let amount = _amount as u64;
+
+        let nullifier = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _nullifier, "nullifier"),
+            JNI_FALSE
+        );
+
+        let mc_pk_hash = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<MC_PK_SIZE>(&_env, _mc_pk_hash, "mcPkHash"),
+            JNI_FALSE
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        if commitment_tree.add_csw(&sc_id, amount, &nullifier, &mc_pk_hash) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScCrCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_scc(&sc_id) {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetFwtCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_fwt_commitment(&sc_id) {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeBtrCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_bwtr_commitment(&sc_id) {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCertCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_cert_commitment(&sc_id) {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCswCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_csw_commitment(&sc_id) {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_sc_commitment(&sc_id) {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCommitment(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+    ) -> jobject {
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_commitment() {
+            Some(sc_cr_commitment_fe) => {
+                let field_ptr: jlong =
+                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+
+                let field_class = _env
+                    .find_class("com/horizen/librustsidechains/FieldElement")
+                    .expect("Should be able to find FieldElement class");
+
+                let jfe = _env
+                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
+                    .expect("Should be able to create new long for FieldElement");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jfe)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+// Sc Existance proof functions
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScExistenceProof(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_sc_existence_proof(&sc_id) {
+            Some(sc_existence_proof) => {
+                let proof_ptr: jlong =
+                    Box::into_raw(Box::new(sc_existence_proof)) as i64;
+
+                let existence_proof_class = _env
+                    .find_class("com/horizen/commitmenttree/ScExistenceProof")
+                    .expect("Should be able to find ScExistenceProof class");
+
+                let jep = _env
+                    .new_object(existence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
+                    .expect("Should be able to create new long for ScExistenceProof");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jep)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeSerialize(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jbyteArray {
+        serialize_from_jobject::<ScExistenceProof>(&_env, _proof, "existenceProofPointer", None)
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof_bytes: jbyteArray,
+    ) -> jobject {
+        let proof_bytes = _env
+            .convert_byte_array(_proof_bytes)
+            .expect("Should be able to convert to Rust byte array");
+
+        match ScExistenceProof::deserialize(proof_bytes.as_slice()) {
+            Ok(sc_existence_proof) => {
+                let proof_ptr: jlong =
+                    Box::into_raw(Box::new(sc_existence_proof)) as i64;
+
+                let existence_proof_class = _env
+                    .find_class("com/horizen/commitmenttree/ScExistenceProof")
+                    .expect("Should be able to find ScExistenceProof class");
+
+                let jep = _env
+                    .new_object(existence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
+                    .expect("Should be able to create new long for ScExistenceProof");
+
+                *jep
+            }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeToJson(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jstring {
+        json_from_jobject::<ScExistenceProofJson>(&_env, _proof, "existenceProofPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<ScExistenceProofJson>(
+            &_env,
+            _json,
+            "com/horizen/commitmenttree/ScExistenceProof",
+        )
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeFreeScExistenceProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _sc_existence_proof: *mut ScExistenceProof,
+    ) {
+        if _sc_existence_proof.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_sc_existence_proof) });
+    }
+);
+
+// Sc Absence proof functions
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScAbsenceProof(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+    ) -> jobject {
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
+        };
+
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+
+        match commitment_tree.get_sc_absence_proof(&sc_id) {
+            Some(sc_absence_proof) => {
+                let proof_ptr: jlong =
+                    Box::into_raw(Box::new(sc_absence_proof)) as i64;
+
+                let absence_proof_class = _env
+                    .find_class("com/horizen/commitmenttree/ScAbsenceProof")
+                    .expect("Should be able to find ScAbsenceProof class");
+
+                let jep = _env
+                    .new_object(absence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
+                    .expect("Should be able to create new long for ScAbsenceProof");
+
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jep)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            _ => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
+            }
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeSerialize(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jbyteArray {
+        serialize_from_jobject::<ScAbsenceProof>(&_env, _proof, "absenceProofPointer", None)
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof_bytes: jbyteArray,
+    ) -> jobject {
+        let proof_bytes = _env
+            .convert_byte_array(_proof_bytes)
+            .expect("Should be able to convert to Rust byte array");
+
+        match ScAbsenceProof::deserialize(proof_bytes.as_slice()) {
+            Ok(sc_absence_proof) => {
+                let proof_ptr: jlong =
+                    Box::into_raw(Box::new(sc_absence_proof)) as i64;
+
+                let absence_proof_class = _env
+                    .find_class("com/horizen/commitmenttree/ScAbsenceProof")
+                    .expect("Should be able to find ScAbsenceProof class");
+
+                let jep = _env
+                    .new_object(absence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
+                    .expect("Should be able to create new long for ScAbsenceProof");
+
+                *jep
+            }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeToJson(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jstring {
+        json_from_jobject::<ScAbsenceProofJson>(&_env, _proof, "absenceProofPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<ScAbsenceProofJson>(
+            &_env,
+            _json,
+            "com/horizen/commitmenttree/ScAbsenceProof",
+        )
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeFreeScAbsenceProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _sc_absence_proof: *mut ScAbsenceProof,
+    ) {
+        if _sc_absence_proof.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_sc_absence_proof) });
+    }
+);
+
+// Verify existance/absence functions.
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeVerifyScCommitment(
+        _env: JNIEnv,
+        _commitment_tree_class: JObject,
+        _sc_commitment: JObject,
+        _sc_commitment_proof: JObject,
+        _commitment: JObject,
+    ) -> bool {
+        //Read sidechain commitment
+        let sc_commitment_fe = {
+            let i = _env
+                .get_field(_sc_commitment, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer from scCommitment");
+
+            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
+        };
+
+        //Read commitment proof
+        let sc_commitment_proof = {
+            let i = _env
+                .get_field(_sc_commitment_proof, "existenceProofPointer", "J")
+                .expect("Should be able to get field existenceProofPointer from scCommitmentProof");
+
+            read_raw_pointer(&_env, i.j().unwrap() as *const ScExistenceProof)
+        };
 
-                let pk: [u8; 20] = _env
-                    .convert_byte_array(p)
-                    .expect("Should be able to convert to Rust byte array")
-                    .try_into()
-                    .expect("Should be able to write into fixed buffer of size 20");
+        //Read commitment
+        let commitment_fe = {
+            let i = _env
+                .get_field(_commitment, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer from commitment");
 
-                let a = _env
-                    .call_method(o, "getAmount", "()J", &[])
-                    .expect("Should be able to call getAmount method")
-                    .j()
-                    .unwrap() as u64;
+            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
+        };
 
-                bt_list.push((a, pk));
-            }
-        }
+        CommitmentTree::verify_sc_commitment(sc_commitment_fe, sc_commitment_proof, commitment_fe)
+    }
+);
 
-        let bt_list = bt_list
-            .into_iter()
-            .map(|bt_raw| BackwardTransfer {
-                pk_dest: bt_raw.1,
-                amount: bt_raw.0,
-            })
-            .collect::<Vec<_>>();
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeVerifyScAbsence(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _sc_id: jbyteArray,
+        _sc_absence_proof: JObject,
+        _commitment: JObject,
+    ) -> bool {
+        // Read sidechain id
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            false
+        );
 
-        let sc_id = {
-            let f = _env
-                .get_field(_sc_id, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer");
+        //Read commitment proof
+        let sc_absence_proof = {
+            let i = _env
+                .get_field(_sc_absence_proof, "absenceProofPointer", "J")
+                .expect("Should be able to get field absenceProofPointer from scAbsenceProof");
 
-            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, i.j().unwrap() as *const ScAbsenceProof)
         };
 
-        let end_cumulative_sc_tx_comm_tree_root = {
-            let f = _env
-                .get_field(
-                    _end_cumulative_sc_tx_comm_tree_root,
-                    "fieldElementPointer",
-                    "J",
-                )
-                .expect("Should be able to get field fieldElementPointer");
+        //Read commitment
+        let commitment_fe = {
+            let i = _env
+                .get_field(_commitment, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer from commitment");
 
-            read_raw_pointer(&_env, f.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
         };
 
-        //Extract constant
-        let constant = {
-            let c = _env
-                .get_field(_constant, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer");
+        CommitmentTree::verify_sc_absence(&sc_id, sc_absence_proof, commitment_fe)
+    }
+);
 
-            read_raw_pointer(&_env, c.j().unwrap() as *const FieldElement)
+// Tx inclusion proof functions
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetTxInclusionProof(
+        _env: JNIEnv,
+        _commitment_tree: JObject,
+        _tx_type: jbyte,
+        _sc_id: jbyteArray,
+        _index: jint,
+    ) -> jobject {
+        let tx_type = CommitmentTreeTxType::from_u8(_tx_type as u8)
+            .expect("Should be able to parse tx type");
+
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            std::ptr::null::<jobject>() as jobject
+        );
+
+        let commitment_tree = {
+            let t = _env
+                .get_field(_commitment_tree, "commitmentTreePointer", "J")
+                .expect("Should be able to get field commitmentTreePointer");
+
+            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut IncrementalCommitmentTree)
         };
 
-        //Extract proof
-        let proof_bytes = _env
-            .convert_byte_array(_sc_proof_bytes)
-            .expect("Should be able to convert to Rust byte array");
+        let cls_optional = _env.find_class("java/util/Optional").unwrap();
 
-        //Extract vk path
-        let vk_path = _env
-            .get_string(_verification_key_path)
-            .expect("Should be able to read jstring as Rust String");
+        match commitment_tree.get_tx_inclusion_proof(tx_type, &sc_id, _index as usize) {
+            Ok(tx_inclusion_proof) => {
+                let jproof = return_jobject(
+                    &_env,
+                    tx_inclusion_proof,
+                    "com/horizen/commitmenttree/TxInclusionProof",
+                );
 
-        //Verify proof
-        match verify_naive_threshold_sig_proof(
-            constant,
-            sc_id,
-            _epoch_number as u32,
-            end_cumulative_sc_tx_comm_tree_root,
-            _btr_fee as u64,
-            _ft_min_amount as u64,
-            bt_list,
-            _quality as u64,
-            proof_bytes,
-            _check_proof == JNI_TRUE,
-            _compressed_proof == JNI_TRUE,
-            Path::new(vk_path.to_str().unwrap()),
-            _check_vk == JNI_TRUE,
-            _compressed_vk == JNI_TRUE,
-        ) {
-            Ok(result) => {
-                if result {
-                    JNI_TRUE
-                } else {
-                    JNI_FALSE
-                }
+                let res = _env
+                    .call_static_method(
+                        cls_optional,
+                        "of",
+                        "(Ljava/lang/Object;)Ljava/util/Optional;",
+                        &[JValue::Object(jproof)],
+                    )
+                    .unwrap();
+                *res.l().unwrap()
+            }
+            Err(_) => {
+                let empty_res = _env
+                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
+                    .expect("Should be able to create new value for Optional.empty()");
+                *empty_res.l().unwrap()
             }
-            Err(_) => JNI_FALSE, // CRYPTO_ERROR or IO_ERROR
         }
     }
 );
 
-///////// COMMITMENT TREE
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeInit(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeGetLeaf(
         _env: JNIEnv,
-        _class: JClass,
+        _proof: JObject,
     ) -> jobject {
-        // Create new CommitmentTree Rust side
-        let commitment_tree = CommitmentTree::create();
+        let proof = {
+            let p = _env
+                .get_field(_proof, "txInclusionProofPointer", "J")
+                .expect("Should be able to get field txInclusionProofPointer");
 
-        // Create and return new CommitmentTree Java side
-        let commitment_tree_ptr: jlong =
-            Box::into_raw(Box::new(commitment_tree)) as i64;
+            read_raw_pointer(&_env, p.j().unwrap() as *const TxInclusionProof)
+        };
 
-        _env.new_object(_class, "(J)V", &[JValue::Long(commitment_tree_ptr)])
-            .expect("Should be able to create new CommitmentTree object")
-            .into_inner()
+        return_field_element(&_env, *proof.leaf())
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeFreeCommitmentTree(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeGetLeafIndex(
         _env: JNIEnv,
-        _class: JClass,
-        _commitment_tree: *mut CommitmentTree,
-    ) {
-        if _commitment_tree.is_null() {
-            return;
-        }
-        drop(unsafe { Box::from_raw(_commitment_tree) });
+        _proof: JObject,
+    ) -> jlong {
+        let proof = {
+            let p = _env
+                .get_field(_proof, "txInclusionProofPointer", "J")
+                .expect("Should be able to get field txInclusionProofPointer");
+
+            read_raw_pointer(&_env, p.j().unwrap() as *const TxInclusionProof)
+        };
+
+        proof.leaf_index() as jlong
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddScCr(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeVerify(
         _env: JNIEnv,
-        _commitment_tree: JObject,
+        _proof: JObject,
+        _tx_type: jbyte,
         _sc_id: jbyteArray,
-        _amount: jlong,
-        _pub_key: jbyteArray,
-        _tx_hash: jbyteArray,
-        _out_idx: jint,
-        _withdrawal_epoch_length: jint,
-        _mc_btr_request_data_length: jbyte,
-        _custom_field_elements_configs: jobjectArray,
-        _custom_bitvector_elements_configs: jobjectArray,
-        _btr_fee: jlong,
-        _ft_min_amount: jlong,
-        _custom_creation_data: jbyteArray,
-        _constant_nullable: jbyteArray, // can be null if there is no constant
-        _cert_verification_key: jbyteArray,
-        _csw_verification_key_nullable: jbyteArray, // can be null if there is no key for CSWs
+        _leaf: JObject,
+        _commitment: JObject,
     ) -> jboolean {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            match FieldElement::deserialize(sc_id_bytes.as_slice()) {
-                Ok(fe) => fe,
-                Err(_) => return JNI_FALSE,
-            }
+        let tx_type = match CommitmentTreeTxType::from_u8(_tx_type as u8) {
+            Ok(tx_type) => tx_type,
+            Err(_) => return JNI_FALSE,
         };
 
-        let amount = _amount as u64;
+        let sc_id = read_ct_input!(
+            &_env,
+            ct_read_field_element(&_env, _sc_id, "scId"),
+            JNI_FALSE
+        );
 
-        let mut pub_key = [0u8; FIELD_SIZE];
-        get_byte_array(&_env, &_pub_key, &mut pub_key[..]);
+        let leaf = {
+            let fe = _env
+                .get_field(_leaf, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer from leaf");
 
-        let mut tx_hash = [0u8; FIELD_SIZE];
-        get_byte_array(&_env, &_tx_hash, &mut tx_hash[..]);
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
 
-        let out_idx = _out_idx as u32;
+        let proof = {
+            let p = _env
+                .get_field(_proof, "txInclusionProofPointer", "J")
+                .expect("Should be able to get field txInclusionProofPointer");
 
-        let withdrawal_epoch_length = _withdrawal_epoch_length as u32;
+            read_raw_pointer(&_env, p.j().unwrap() as *const TxInclusionProof)
+        };
 
-        let mc_btr_request_data_length = _mc_btr_request_data_length as u8;
+        let commitment = {
+            let fe = _env
+                .get_field(_commitment, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer from commitment");
 
-        let mut custom_field_elements_configs = vec![];
-        let custom_field_size = _env
-            .get_array_length(_custom_field_elements_configs)
-            .expect("Should be able to get _custom_field_elements_configs size");
-        if custom_field_size > 0 {
-            for i in 0..custom_field_size {
-                let custom_field_config = _env
-                    .get_object_array_element(_custom_field_elements_configs, i)
-                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of custom_field_elements_configs array", i));
-
-                let bits = _env
-                    .call_method(custom_field_config, "getBits", "()B", &[])
-                    .expect("Should be able to call getBitVectorSizeBits method")
-                    .b()
-                    .unwrap() as u8;
-
-                custom_field_elements_configs.push(bits);
-            }
+            read_raw_pointer(&_env, fe.j().unwrap() as *const FieldElement)
+        };
+
+        match proof.verify(tx_type, &sc_id, leaf, commitment) {
+            Ok(true) => JNI_TRUE,
+            _ => JNI_FALSE,
         }
-        let custom_field_elements_configs_opt = if !custom_field_elements_configs.is_empty() {
-            Some(custom_field_elements_configs.as_slice())
-        } else {
-            None
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeSerialize(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jbyteArray {
+        let proof = {
+            let p = _env
+                .get_field(_proof, "txInclusionProofPointer", "J")
+                .expect("Should be able to get field txInclusionProofPointer");
+
+            read_raw_pointer(&_env, p.j().unwrap() as *const TxInclusionProof)
         };
 
-        let mut custom_bitvector_elements_configs = vec![];
-        let custom_bitvector_elements_size = _env
-            .get_array_length(_custom_bitvector_elements_configs)
-            .expect("Should be able to get _custom_field_elements_configs size");
-        if custom_bitvector_elements_size > 0 {
-            for i in 0..custom_bitvector_elements_size {
-                let custom_bitvector_element_config = _env.get_object_array_element(_custom_bitvector_elements_configs, i)
-                .unwrap_or_else(|_| panic!("Should be able to get elem {} of custom_bitvector_elements_configs array", i));
-
-                let bit_vector_size_bits = _env
-                    .call_method(
-                        custom_bitvector_element_config,
-                        "getBitVectorSizeBits",
-                        "()I",
-                        &[],
-                    )
-                    .expect("Should be able to call getBitVectorSizeBits method")
-                    .i()
-                    .unwrap() as u32;
+        let proof_bytes = proof
+            .to_bytes()
+            .expect("Should be able to serialize TxInclusionProof");
 
-                let max_compressed_byte_size = _env
-                    .call_method(
-                        custom_bitvector_element_config,
-                        "getMaxCompressedByteSize",
-                        "()I",
-                        &[],
-                    )
-                    .expect("Should be able to call getMaxCompressedByteSize method")
-                    .i()
-                    .unwrap() as u32;
+        _env.byte_array_from_slice(proof_bytes.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _proof_bytes: jbyteArray,
+    ) -> jobject {
+        let proof_bytes = _env
+            .convert_byte_array(_proof_bytes)
+            .expect("Cannot read proof bytes.");
+
+        match TxInclusionProof::from_bytes(proof_bytes.as_slice()) {
+            Ok(proof) => {
+                return_jobject(&_env, proof, "com/horizen/commitmenttree/TxInclusionProof")
+                    .into_inner()
+            }
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeToJson(
+        _env: JNIEnv,
+        _proof: JObject,
+    ) -> jstring {
+        json_from_jobject::<TxInclusionProofJson>(&_env, _proof, "txInclusionProofPointer")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeFromJson(
+        _env: JNIEnv,
+        _class: JClass,
+        _json: JString,
+    ) -> jobject {
+        json_to_jobject::<TxInclusionProofJson>(
+            &_env,
+            _json,
+            "com/horizen/commitmenttree/TxInclusionProof",
+        )
+    }
+);
 
-                custom_bitvector_elements_configs.push(BitVectorElementsConfig {
-                    bit_vector_size_bits,
-                    max_compressed_byte_size,
-                });
-            }
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_TxInclusionProof_nativeFreeTxInclusionProof(
+        _env: JNIEnv,
+        _class: JClass,
+        _tx_inclusion_proof: *mut TxInclusionProof,
+    ) {
+        if _tx_inclusion_proof.is_null() {
+            return;
         }
+        drop(unsafe { Box::from_raw(_tx_inclusion_proof) });
+    }
+);
 
-        let custom_bitvector_elements_configs_opt = if !custom_bitvector_elements_configs.is_empty() {
-            Some(custom_bitvector_elements_configs.as_slice())
-        } else {
-            None
-        };
+fn read_sc_creation_params<'a>(_env: &JNIEnv, _params: JObject) -> &'a SidechainCreationParams {
+    let p = _env
+        .get_field(_params, "sidechainCreationParamsPointer", "J")
+        .expect("Should be able to get field sidechainCreationParamsPointer");
+    read_raw_pointer(&_env, p.j().unwrap() as *const SidechainCreationParams)
+}
 
-        let btr_fee = _btr_fee as u64;
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_SidechainCreationParams_nativeCreate(
+        _env: JNIEnv,
+        _class: JClass,
+        _withdrawal_epoch_length: jint,
+        _amount: jlong,
+        _pub_key: jbyteArray,
+        _custom_creation_data: jbyteArray,
+        _constant_nullable: jbyteArray, // can be null if there is no constant
+        _cert_verification_key: jbyteArray,
+        _csw_verification_key_nullable: jbyteArray, // can be null if there is no key for CSWs
+        _custom_field_elements_configs: jobjectArray,
+        _custom_bitvector_elements_configs: jobjectArray,
+        _ft_min_amount: jlong,
+        _btr_fee: jlong,
+        _mc_btr_request_data_length: jbyte,
+    ) -> jobject {
+        let null = JObject::null().into_inner();
 
-        let ft_min_amount = _ft_min_amount as u64;
+        let pub_key = read_ct_input!(
+            &_env,
+            ct_read_fixed_bytes::<FIELD_SIZE>(&_env, _pub_key, "pubKey"),
+            null
+        );
 
-        let custom_creation_data = _env
-            .convert_byte_array(_custom_creation_data)
-            .expect("Should be able to convert to Rust array");
+        let custom_creation_data = read_ct_input!(
+            &_env,
+            ct_read_bytes(&_env, _custom_creation_data, "customCreationData"),
+            null
+        );
 
-        let custom_creation_data_opt = if !custom_creation_data.is_empty() {
-            Some(custom_creation_data.as_slice())
-        } else {
+        let constant = if _constant_nullable.is_null() {
             None
+        } else {
+            Some(read_ct_input!(
+                &_env,
+                ct_read_field_element(&_env, _constant_nullable, "constant"),
+                null
+            ))
         };
 
-        // let constant_fe;
-        let constant = if _constant_nullable.is_null() {
-            Option::None
+        let cert_verification_key = read_ct_input!(
+            &_env,
+            ct_read_bytes(&_env, _cert_verification_key, "certVerificationKey"),
+            null
+        );
+
+        let csw_verification_key = if _csw_verification_key_nullable.is_null() {
+            None
         } else {
-            let constant_bytes = parse_jbyte_array_to_vec(&_env, &_constant_nullable, FIELD_SIZE);
-            match FieldElement::deserialize(constant_bytes.as_slice()) {
-                Ok(constant_fe) => Option::Some(constant_fe),
-                Err(_) => return JNI_FALSE,
-            }
+            Some(read_ct_input!(
+                &_env,
+                ct_read_bytes(&_env, _csw_verification_key_nullable, "cswVerificationKey"),
+                null
+            ))
         };
 
-        let cert_verification_key = _env
-            .convert_byte_array(_cert_verification_key)
-            .expect("Should be able to convert to Rust byte array");
+        let custom_field_elements_configs = read_ct_input!(
+            &_env,
+            ct_read_field_elements_configs(&_env, _custom_field_elements_configs),
+            null
+        );
 
-        let mut _csw_verification_key_nullable_vec;
-        let csw_verification_key_opt = if _csw_verification_key_nullable.is_null() {
-            Option::None
-        } else {
-            _csw_verification_key_nullable_vec = _env
-                .convert_byte_array(_csw_verification_key_nullable)
-                .expect("Should be able to convert to Rust byte array");
-            Some(_csw_verification_key_nullable_vec.as_slice())
+        let custom_bitvector_elements_configs = read_ct_input!(
+            &_env,
+            ct_read_bitvector_elements_configs(&_env, _custom_bitvector_elements_configs),
+            null
+        );
+
+        let params = SidechainCreationParams {
+            withdrawal_epoch_length: _withdrawal_epoch_length as u32,
+            amount: _amount as u64,
+            pub_key,
+            custom_creation_data,
+            constant,
+            cert_verification_key,
+            csw_verification_key,
+            custom_field_elements_configs,
+            custom_bitvector_elements_configs,
+            ft_min_amount: _ft_min_amount as u64,
+            btr_fee: _btr_fee as u64,
+            mc_btr_request_data_length: _mc_btr_request_data_length as u8,
         };
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        *return_jobject(
+            &_env,
+            params,
+            "com/horizen/commitmenttree/SidechainCreationParams",
+        )
+    }
+);
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_SidechainCreationParams_nativeValidate(
+        _env: JNIEnv,
+        _params: JObject,
+        _cert_circuit_has_constant: jboolean,
+        _supported_segment_size: jint,
+        _zk: jboolean,
+        _max_proof_size: jint,
+        _max_vk_size: jint,
+    ) {
+        let vk_limits = VerificationKeyLimits {
+            supported_segment_size: _supported_segment_size as usize,
+            zk: _zk == JNI_TRUE,
+            max_proof_size: _max_proof_size as usize,
+            max_vk_size: _max_vk_size as usize,
         };
 
-        if commitment_tree.add_scc(
-            &sc_id,
-            amount,
-            &pub_key,
-            &tx_hash,
-            out_idx,
-            withdrawal_epoch_length,
-            mc_btr_request_data_length,
-            custom_field_elements_configs_opt,
-            custom_bitvector_elements_configs_opt,
-            btr_fee,
-            ft_min_amount,
-            custom_creation_data_opt,
-            constant.as_ref(),
-            cert_verification_key.as_slice(),
-            csw_verification_key_opt,
-        ) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
+        if let Err(e) = read_sc_creation_params(&_env, _params)
+            .validate(_cert_circuit_has_constant == JNI_TRUE, &vk_limits)
+        {
+            throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &format!("Invalid sidechain creation: {}", e)
+            );
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddFwt(
+    fn Java_com_horizen_commitmenttree_SidechainCreationParams_nativeSerialize(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-        _amount: jlong,
-        _pub_key: jbyteArray,
-        _mc_return_address: jbyteArray,
-        _tx_hash: jbyteArray,
-        _out_idx: jint,
-    ) -> jboolean {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
+        _params: JObject,
+    ) -> jbyteArray {
+        let params_bytes = read_sc_creation_params(&_env, _params)
+            .to_bytes()
+            .expect("Should be able to serialize SidechainCreationParams");
 
-        let amount = _amount as u64;
+        _env.byte_array_from_slice(params_bytes.as_slice())
+            .expect("Cannot write object.")
+    }
+);
 
-        let mut pub_key = [0u8; FIELD_SIZE];
-        get_byte_array(&_env, &_pub_key, &mut pub_key[..]);
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_SidechainCreationParams_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _params_bytes: jbyteArray,
+    ) -> jobject {
+        let params_bytes = _env
+            .convert_byte_array(_params_bytes)
+            .expect("Cannot read sidechain creation params bytes.");
 
-        let mut mc_return_address = [0u8; MC_PK_SIZE];
-        get_byte_array(&_env, &_mc_return_address, &mut mc_return_address[..]);
+        match SidechainCreationParams::from_bytes(params_bytes.as_slice()) {
+            Ok(params) => *return_jobject(
+                &_env,
+                params,
+                "com/horizen/commitmenttree/SidechainCreationParams",
+            ),
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &e.to_string(),
+                JObject::null().into_inner()
+            ),
+        }
+    }
+);
 
-        let mut tx_hash = [0u8; FIELD_SIZE];
-        get_byte_array(&_env, &_tx_hash, &mut tx_hash[..]);
+ffi_export!(
+    fn Java_com_horizen_commitmenttree_SidechainCreationParams_nativeFreeSidechainCreationParams(
+        _env: JNIEnv,
+        _class: JClass,
+        _params: *mut SidechainCreationParams,
+    ) {
+        if _params.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_params) });
+    }
+);
 
-        let out_idx = _out_idx as u32;
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_Utils_nativeCalculateSidechainId(
+        _env: JNIEnv,
+        _utils: JClass,
+        _tx_hash: jbyteArray,
+        _idx: jint,
+    ) -> jbyteArray {
+        // Parse tx_hash into a [u8; 32]
+        let tx_hash: [u8; 32] = _env
+            .convert_byte_array(_tx_hash)
+            .expect("Should be able to convert to Rust byte array")
+            .try_into()
+            .expect("Should be able to write into fixed buffer of size 32");
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        let idx = _idx as u32;
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
-        };
+        // Compute sc_id
+        let sc_id = compute_sc_id(&tx_hash, idx).expect("Cannot compute sc id.");
 
-        if commitment_tree.add_fwt(
-            &sc_id,
-            amount,
-            &pub_key,
-            &mc_return_address,
-            &tx_hash,
-            out_idx,
-        ) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
-        }
+        // Return sc_id bytes
+        let sc_id_bytes =
+            serialize_to_buffer(&sc_id, None).expect("Should be able to serialize sc_id");
+        _env.byte_array_from_slice(sc_id_bytes.as_slice())
+            .expect("Cannot write jobject.")
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddBtr(
+    fn Java_com_horizen_librustsidechains_Utils_nativeCompressedBitvectorMerkleRoot(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-        _sc_fee: jlong,
-        _mc_destination_address: jbyteArray,
-        _sc_request_data: jobjectArray,
-        _tx_hash: jbyteArray,
-        _out_idx: jint,
-    ) -> jboolean {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
+        _utils: JClass,
+        _compressed_bit_vector: jbyteArray,
+    ) -> jbyteArray {
+        // Parse compressed_bit_vector into a vector
+        let compressed_bit_vector = _env
+            .convert_byte_array(_compressed_bit_vector)
+            .expect("Should be able to convert to Rust byte array");
 
-        let sc_fee = _sc_fee as u64;
+        // Compute merkle_root
+        let merkle_root =
+            merkle_root_from_compressed_bytes_without_checks(compressed_bit_vector.as_slice())
+                .expect("Cannot compute merkle root.");
 
-        let mut mc_destination_address = [0u8; 20];
-        get_byte_array(
-            &_env,
-            &_mc_destination_address,
-            &mut mc_destination_address[..],
-        );
+        // Return merkle_root bytes
+        let merkle_root_bytes = serialize_to_buffer(&merkle_root, None)
+            .expect("Should be able to serialize merkle_root");
+        _env.byte_array_from_slice(merkle_root_bytes.as_slice())
+            .expect("Cannot write jobject.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_Utils_nativeCompressedBitvectorMerkleRootWithSizeCheck(
+        _env: JNIEnv,
+        _utils: JClass,
+        _compressed_bit_vector: jbyteArray,
+        _expected_uncompressed_size: jint,
+    ) -> jbyteArray {
+        // Parse compressed_bit_vector into a vector
+        let compressed_bit_vector = _env
+            .convert_byte_array(_compressed_bit_vector)
+            .expect("Should be able to convert to Rust byte array");
+
+        let expected_uncompressed_size = _expected_uncompressed_size as usize;
 
-        let mut sc_request_data = vec![];
-        let sc_request_data_size = _env
-            .get_array_length(_sc_request_data)
-            .expect("Should be able to get _custom_field_elements_configs size");
-        if sc_request_data_size > 0 {
-            for i in 0..sc_request_data_size {
-                let o = _env.get_object_array_element(_sc_request_data, i).unwrap_or_else(|_| panic!("Should be able to get elem {} of custom_field_elements_configs array", i));
-
-                let data = _env
-                    .convert_byte_array(o.cast())
-                    .expect("Should be able to convert to Rust byte array");
-
-                sc_request_data.push(
-                    FieldElement::deserialize(data.as_slice())
-                        .expect("Can't parse the input sc_request_data into FieldElement"),
+        // Compute merkle_root
+        match merkle_root_from_compressed_bytes(
+            compressed_bit_vector.as_slice(),
+            expected_uncompressed_size,
+        ) {
+            Ok(merkle_root) => {
+                // Return merkle_root bytes
+                let merkle_root_bytes = serialize_to_buffer(&merkle_root, None)
+                    .expect("Should be able to serialize merkle_root");
+                _env.byte_array_from_slice(merkle_root_bytes.as_slice())
+                    .expect("Cannot write jobject.")
+            }
+            Err(_) => {
+                throw!(
+                    &_env,
+                    "java/lang/Exception",
+                    "Cannot compute merkle root with size check.",
+                    JObject::null().into_inner()
                 );
             }
         }
+    }
+);
 
-        let mut tx_hash = [0u8; FIELD_SIZE];
-        get_byte_array(&_env, &_tx_hash, &mut tx_hash[..]);
+///////// ENVELOPE
 
-        let out_idx = _out_idx as u32;
+fn read_artifact_type(_env: &JNIEnv, _artifact_type: jint) -> Option<ArtifactType> {
+    match _artifact_type.try_into().ok().map(ArtifactType::from_tag) {
+        Some(Ok(artifact_type)) => Some(artifact_type),
+        _ => throw!(
+            _env,
+            "java/lang/IllegalArgumentException",
+            &format!("Unknown artifact type {}", _artifact_type),
+            None
+        ),
+    }
+}
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_Envelope_nativeSeal(
+        _env: JNIEnv,
+        _class: JClass,
+        _artifact_type: jint,
+        _payload: jbyteArray,
+    ) -> jbyteArray {
+        let artifact_type = match read_artifact_type(&_env, _artifact_type) {
+            Some(artifact_type) => artifact_type,
+            None => return JObject::null().into_inner(),
+        };
+
+        let payload = _env
+            .convert_byte_array(_payload)
+            .expect("Should be able to convert to Rust byte array");
+
+        _env.byte_array_from_slice(seal_artifact(artifact_type, &payload).as_slice())
+            .expect("Cannot write jobject.")
+    }
+);
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_Envelope_nativeOpen(
+        _env: JNIEnv,
+        _class: JClass,
+        _artifact_type: jint,
+        _data: jbyteArray,
+    ) -> jbyteArray {
+        let artifact_type = match read_artifact_type(&_env, _artifact_type) {
+            Some(artifact_type) => artifact_type,
+            None => return JObject::null().into_inner(),
         };
 
-        if commitment_tree.add_bwtr(
-            &sc_id,
-            sc_fee,
-            sc_request_data.iter().collect(),
-            &mc_destination_address,
-            &tx_hash,
-            out_idx,
-        ) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
+        let data = _env
+            .convert_byte_array(_data)
+            .expect("Should be able to convert to Rust byte array");
+
+        match open_artifact(artifact_type, &data) {
+            Ok(payload) => _env
+                .byte_array_from_slice(payload)
+                .expect("Cannot write jobject."),
+            Err(e) => throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &format!("Invalid envelope: {}", e),
+                JObject::null().into_inner()
+            ),
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddCert(
+    fn Java_com_horizen_librustsidechains_Envelope_nativeIsSealed(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-        _epoch_number: jint,
-        _quality: jlong,
-        _bt_list: jobjectArray,
-        _custom_fields_nullable: jobjectArray, // can be null if there is no constant
-        _end_cumulative_sc_tx_commitment_tree_root: jbyteArray,
-        _btr_fee: jlong,
-        _ft_min_amount: jlong,
+        _class: JClass,
+        _data: jbyteArray,
     ) -> jboolean {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
+        let data = _env
+            .convert_byte_array(_data)
+            .expect("Should be able to convert to Rust byte array");
+
+        is_sealed_artifact(&data) as jboolean
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_librustsidechains_Envelope_nativeSealFile(
+        _env: JNIEnv,
+        _class: JClass,
+        _artifact_type: jint,
+        _path: JString,
+    ) {
+        let artifact_type = match read_artifact_type(&_env, _artifact_type) {
+            Some(artifact_type) => artifact_type,
+            None => return,
         };
 
-        let epoch_number = _epoch_number as u32;
+        let path = _env
+            .get_string(_path)
+            .expect("Should be able to read jstring as Rust String");
+        let path = path.to_str().unwrap();
 
-        let quality = _quality as u64;
+        if let Err(e) = seal_artifact_file(Path::new(path), artifact_type) {
+            throw!(
+                &_env,
+                "java/lang/IllegalArgumentException",
+                &format!("Unable to seal {}: {}", path, e)
+            );
+        }
+    }
+);
 
-        //Extract backward transfers
-        let mut bt_list = vec![];
+///////// SECRET SHARING
 
-        let bt_list_size = _env
-            .get_array_length(_bt_list)
-            .expect("Should be able to get bt_list size");
+// Shares are borrowed from their Java objects, so that no copies of them are made
+fn read_secret_shares<'a>(_env: &JNIEnv, _shares: jobjectArray) -> Vec<&'a SecretShare> {
+    let shares_len = _env
+        .get_array_length(_shares)
+        .expect("Should be able to get shares array size");
 
-        if bt_list_size > 0 {
-            for i in 0..bt_list_size {
-                let o = _env
-                    .get_object_array_element(_bt_list, i)
-                    .unwrap_or_else(|_| panic!("Should be able to get elem {} of bt_list array", i));
+    let mut shares = Vec::with_capacity(shares_len as usize);
+    for i in 0..shares_len {
+        let share_object = _env
+            .get_object_array_element(_shares, i)
+            .unwrap_or_else(|_| panic!("Should be able to get elem {} of shares array", i));
 
-                let p = _env
-                    .call_method(o, "getPublicKeyHash", "()[B", &[])
-                    .expect("Should be able to call getPublicKeyHash method")
-                    .l()
-                    .unwrap()
-                    .cast();
+        let share = {
+            let s = _env
+                .get_field(share_object, "secretSharePointer", "J")
+                .expect("Should be able to get field secretSharePointer");
 
-                let pk: [u8; 20] = _env
-                    .convert_byte_array(p)
-                    .expect("Should be able to convert to Rust byte array")
-                    .try_into()
-                    .expect("Should be able to write into fixed buffer of size 20");
+            read_raw_pointer(&_env, s.j().unwrap() as *const SecretShare)
+        };
 
-                let a = _env
-                    .call_method(o, "getAmount", "()J", &[])
-                    .expect("Should be able to call getAmount method")
-                    .j()
-                    .unwrap() as u64;
+        shares.push(share);
+    }
+    shares
+}
 
-                bt_list.push(BackwardTransfer {
-                    pk_dest: pk,
-                    amount: a,
-                });
-            }
-        }
+fn return_secret_shares(_env: &JNIEnv, shares: Vec<SecretShare>) -> jobjectArray {
+    let share_class = _env
+        .find_class("com/horizen/secretsharing/SecretShare")
+        .expect("Should be able to find SecretShare class");
 
-        let bt_list_opt = if !bt_list.is_empty() {
-            Some(bt_list.as_slice())
-        } else {
-            None
-        };
+    let shares_array = _env
+        .new_object_array(shares.len() as i32, share_class, JObject::null())
+        .expect("Should be able to create array of SecretShare");
 
-        let mut custom_fields = vec![];
-        let custom_fields_opt = if _custom_fields_nullable.is_null() {
-            Option::None
-        } else {
-            let custom_fields_size = _env
-                .get_array_length(_custom_fields_nullable)
-                .expect("Should be able to get custom_fields size");
-
-            if custom_fields_size > 0 {
-                for i in 0..custom_fields_size {
-                    let o = _env
-                        .get_object_array_element(_custom_fields_nullable, i)
-                        .unwrap_or_else(|_| panic!("Should be able to get elem {} of custom_fields array", i));
-
-                    let cf = _env
-                        .convert_byte_array(o.cast())
-                        .expect("Should be able to convert to Rust byte array");
-
-                    custom_fields.push(
-                        FieldElement::deserialize(cf.as_slice())
-                            .expect("Can't parse the input custom_field into FieldElement"),
-                    );
-                }
-            }
-            Some(custom_fields.iter().collect())
-        };
+    for (i, share) in shares.into_iter().enumerate() {
+        let share_object = return_jobject(_env, share, "com/horizen/secretsharing/SecretShare");
+        _env.set_object_array_element(shares_array, i as i32, share_object)
+            .expect("Should be able to add SecretShare to array");
+    }
 
-        let end_cumulative_sc_tx_commitment_tree_root = {
-            let tree_root_bytes = parse_jbyte_array_to_vec(
-                &_env,
-                &_end_cumulative_sc_tx_commitment_tree_root,
-                FIELD_SIZE,
-            );
-            FieldElement::deserialize(tree_root_bytes.as_slice()).expect(
-                "Can't parse the input end_cumulative_sc_tx_commitment_tree_root into FieldElement",
-            )
-        };
+    shares_array
+}
 
-        let btr_fee = _btr_fee as u64;
+ffi_export!(
+    fn Java_com_horizen_secretsharing_SecretShare_nativeGetSecretShareSize(
+        _env: JNIEnv,
+        _class: JClass,
+    ) -> jint {
+        SECRET_SHARE_SIZE as jint
+    }
+);
 
-        let ft_min_amount = _ft_min_amount as u64;
+ffi_export!(
+    fn Java_com_horizen_secretsharing_SecretShare_nativeGetIndex(
+        _env: JNIEnv,
+        _share: JObject,
+    ) -> jint {
+        let share = {
+            let s = _env
+                .get_field(_share, "secretSharePointer", "J")
+                .expect("Should be able to get field secretSharePointer");
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+            read_raw_pointer(&_env, s.j().unwrap() as *const SecretShare)
+        };
+
+        share.index as jint
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_secretsharing_SecretShare_nativeSerialize(
+        _env: JNIEnv,
+        _share: JObject,
+    ) -> jbyteArray {
+        let share = {
+            let s = _env
+                .get_field(_share, "secretSharePointer", "J")
+                .expect("Should be able to get field secretSharePointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, s.j().unwrap() as *const SecretShare)
         };
 
-        if commitment_tree.add_cert(
-            &sc_id,
-            epoch_number,
-            quality,
-            bt_list_opt,
-            custom_fields_opt,
-            &end_cumulative_sc_tx_commitment_tree_root,
-            btr_fee,
-            ft_min_amount,
-        ) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
+        let share_bytes = share
+            .to_bytes()
+            .expect("Should be able to serialize SecretShare");
+
+        _env.byte_array_from_slice(share_bytes.as_slice())
+            .expect("Cannot write object.")
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_secretsharing_SecretShare_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _share_bytes: jbyteArray,
+    ) -> jobject {
+        let share_bytes = Zeroizing::new(
+            _env.convert_byte_array(_share_bytes)
+                .expect("Cannot read bytes."),
+        );
+
+        match SecretShare::from_bytes(share_bytes.as_slice()) {
+            Ok(share) => *return_jobject(&_env, share, "com/horizen/secretsharing/SecretShare"),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddCertLeaf(
+    fn Java_com_horizen_secretsharing_SecretShare_nativeVerify(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-        _leaf: jbyteArray,
+        _share: JObject,
+        _commitment: JObject,
     ) -> jboolean {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
+        let share = {
+            let s = _env
+                .get_field(_share, "secretSharePointer", "J")
+                .expect("Should be able to get field secretSharePointer");
 
-        let leaf_fe = {
-            let leaf_bytes = parse_jbyte_array_to_vec(&_env, &_leaf, FIELD_SIZE);
-            FieldElement::deserialize(leaf_bytes.as_slice())
-                .expect("Can't parse the input leaf_bytes into FieldElement")
+            read_raw_pointer(&_env, s.j().unwrap() as *const SecretShare)
         };
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
         };
 
-        if commitment_tree.add_cert_leaf(&sc_id, &leaf_fe) {
+        if verify_secret_share(share, commitment) {
             JNI_TRUE
         } else {
             JNI_FALSE
@@ -2864,876 +6416,834 @@ ffi_export!(
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCrtLeaves(
+    fn Java_com_horizen_secretsharing_SecretShare_nativeFreeSecretShare(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-    ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
+        _class: JClass,
+        _share: *mut SecretShare,
+    ) {
+        if _share.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_share) });
+    }
+);
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+ffi_export!(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeGetThreshold(
+        _env: JNIEnv,
+        _commitment: JObject,
+    ) -> jint {
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
         };
 
-        match commitment_tree.get_cert_leaves(&sc_id) {
-            Some(leaves) => {
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
-
-                let initial_element = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(0)])
-                    .expect("Should be able to create new long for FieldElement");
-
-                let leaf_fe_array = _env
-                    .new_object_array(leaves.len() as i32, field_class, initial_element)
-                    .expect("Should be able to create array of FieldElements");
-
-                for (idx, leaf) in leaves.iter().enumerate() {
-                    let leaf_field_ptr: jlong =
-                        (Box::into_raw(Box::new(*leaf)) as i64);
-
-                    let leaf_element = _env
-                        .new_object(field_class, "(J)V", &[JValue::Long(leaf_field_ptr)])
-                        .expect("Should be able to create new long for FieldElement");
-
-                    _env.set_object_array_element(leaf_fe_array, idx as i32, leaf_element)
-                        .expect("Should be able to add FieldElement leaf to an array");
-                }
-
-                let cls_optional = _env.find_class("java/util/Optional").unwrap();
-
-                let empty_res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::from(JObject::from(leaf_fe_array))],
-                    )
-                    .expect("Should be able to create new value for Optional");
+        commitment.threshold() as jint
+    }
+);
 
-                *empty_res.l().unwrap()
-            }
-            _ => {
-                let cls_optional = _env.find_class("java/util/Optional").unwrap();
+ffi_export!(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeGetPublicKey(
+        _env: JNIEnv,
+        _commitment: JObject,
+    ) -> jobject {
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
 
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
+        };
 
-                *empty_res.l().unwrap()
-            }
-        }
+        return_jobject(
+            &_env,
+            commitment.public_key(),
+            "com/horizen/schnorrnative/SchnorrPublicKey",
+        )
+        .into_inner()
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeAddCsw(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeGetPublicKeyShare(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-        _amount: jlong,
-        _nullifier: jbyteArray,
-        _mc_pk_hash: jbyteArray,
-    ) -> jboolean {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
+        _commitment: JObject,
+        _index: jint,
+    ) -> jobject {
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
+
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
         };
 
-        let amount = _amount as u64;
+        return_jobject(
+            &_env,
+            commitment.public_key_share(_index as u32),
+            "com/horizen/schnorrnative/SchnorrPublicKey",
+        )
+        .into_inner()
+    }
+);
+
+ffi_export!(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeSerialize(
+        _env: JNIEnv,
+        _commitment: JObject,
+    ) -> jbyteArray {
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
 
-        let nullifier = {
-            let nullifier_bytes = parse_jbyte_array_to_vec(&_env, &_nullifier, FIELD_SIZE);
-            FieldElement::deserialize(nullifier_bytes.as_slice())
-                .expect("Can't parse the input nullifier_bytes into FieldElement")
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
         };
 
-        let mut mc_pk_hash = [0u8; MC_PK_SIZE];
-        get_byte_array(&_env, &_mc_pk_hash, &mut mc_pk_hash[..]);
+        let commitment_bytes = commitment
+            .to_bytes()
+            .expect("Should be able to serialize VssCommitment");
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        _env.byte_array_from_slice(commitment_bytes.as_slice())
+            .expect("Cannot write object.")
+    }
+);
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
-        };
+ffi_export!(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeDeserialize(
+        _env: JNIEnv,
+        _class: JClass,
+        _commitment_bytes: jbyteArray,
+    ) -> jobject {
+        let commitment_bytes = _env
+            .convert_byte_array(_commitment_bytes)
+            .expect("Cannot read bytes.");
 
-        if commitment_tree.add_csw(&sc_id, amount, &nullifier, &mc_pk_hash) {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
+        match VssCommitment::from_bytes(commitment_bytes.as_slice()) {
+            Ok(commitment) => *return_jobject(
+                &_env,
+                commitment,
+                "com/horizen/secretsharing/VssCommitment",
+            ),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScCrCommitment(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeCombine(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
+        _class: JClass,
+        _commitments: jobjectArray,
     ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
-
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
-
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
-        };
-
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
-
-        match commitment_tree.get_scc(&sc_id) {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+        let commitments_len = _env
+            .get_array_length(_commitments)
+            .expect("Should be able to get commitments array size");
+
+        let mut commitments = Vec::with_capacity(commitments_len as usize);
+        for i in 0..commitments_len {
+            let commitment_object = _env
+                .get_object_array_element(_commitments, i)
+                .unwrap_or_else(|_| panic!("Should be able to get elem {} of commitments array", i));
+
+            let commitment = {
+                let c = _env
+                    .get_field(commitment_object, "vssCommitmentPointer", "J")
+                    .expect("Should be able to get field vssCommitmentPointer");
+
+                read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
+            };
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+            commitments.push(commitment.clone());
+        }
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+        match combine_vss_commitments(commitments.as_slice()) {
+            Ok(commitment) => *return_jobject(
+                &_env,
+                commitment,
+                "com/horizen/secretsharing/VssCommitment",
+            ),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
+ffi_export!(
+    fn Java_com_horizen_secretsharing_VssCommitment_nativeFreeVssCommitment(
+        _env: JNIEnv,
+        _class: JClass,
+        _commitment: *mut VssCommitment,
+    ) {
+        if _commitment.is_null() {
+            return;
         }
+        drop(unsafe { Box::from_raw(_commitment) });
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetFwtCommitment(
+    fn Java_com_horizen_secretsharing_ShamirSecretSharing_nativeSplit(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
+        _class: JClass,
+        _secret_key: JObject,
+        _threshold: jint,
+        _num_shares: jint,
     ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
+        let secret_key = {
+            let s = _env
+                .get_field(_secret_key, "secretKeyPointer", "J")
+                .expect("Should be able to get field secretKeyPointer");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const ZeroizingSchnorrSk)
         };
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        let (shares, commitment) =
+            match shamir_split(secret_key, _threshold as usize, _num_shares as usize) {
+                Ok(result) => result,
+                Err(_) => return std::ptr::null::<jobject>() as jobject,
+            };
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
-        };
+        let shares_array = return_secret_shares(&_env, shares);
+        let commitment_object = return_jobject(
+            &_env,
+            commitment,
+            "com/horizen/secretsharing/VssCommitment",
+        );
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+        //Create new ShamirSplitResult object
+        let split_result_class = _env
+            .find_class("com/horizen/secretsharing/ShamirSplitResult")
+            .expect("Should be able to find ShamirSplitResult class");
 
-        match commitment_tree.get_fwt_commitment(&sc_id) {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+        let result = _env
+            .new_object(
+                split_result_class,
+                "([Lcom/horizen/secretsharing/SecretShare;Lcom/horizen/secretsharing/VssCommitment;)V",
+                &[
+                    JValue::Object(JObject::from(shares_array)),
+                    JValue::Object(commitment_object),
+                ],
+            )
+            .expect("Should be able to create new ShamirSplitResult:(SecretShare[], VssCommitment) object");
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+        *result
+    }
+);
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+ffi_export!(
+    fn Java_com_horizen_secretsharing_ShamirSecretSharing_nativeReconstruct(
+        _env: JNIEnv,
+        _class: JClass,
+        _shares: jobjectArray,
+    ) -> jobject {
+        let shares = read_secret_shares(&_env, _shares);
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
+        match shamir_reconstruct(shares.as_slice()) {
+            Ok(secret_key) => *return_jobject(
+                &_env,
+                secret_key,
+                "com/horizen/schnorrnative/SchnorrSecretKey",
+            ),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeBtrCommitment(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeInit(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
+        _class: JClass,
+        _index: jint,
+        _threshold: jint,
+        _num_participants: jint,
     ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
+        match DkgParticipant::new(
+            _index as u32,
+            _threshold as usize,
+            _num_participants as usize,
+        ) {
+            Ok(participant) => *return_jobject(
+                &_env,
+                participant,
+                "com/horizen/secretsharing/DkgParticipant",
+            ),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+ffi_export!(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeGetCommitment(
+        _env: JNIEnv,
+        _participant: JObject,
+    ) -> jobject {
+        let participant = {
+            let p = _env
+                .get_field(_participant, "dkgParticipantPointer", "J")
+                .expect("Should be able to get field dkgParticipantPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, p.j().unwrap() as *const DkgParticipant)
         };
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
-
-        match commitment_tree.get_bwtr_commitment(&sc_id) {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+        return_jobject(
+            &_env,
+            participant.commitment().clone(),
+            "com/horizen/secretsharing/VssCommitment",
+        )
+        .into_inner()
+    }
+);
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+ffi_export!(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeGetShareFor(
+        _env: JNIEnv,
+        _participant: JObject,
+        _index: jint,
+    ) -> jobject {
+        let participant = {
+            let p = _env
+                .get_field(_participant, "dkgParticipantPointer", "J")
+                .expect("Should be able to get field dkgParticipantPointer");
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+            read_raw_pointer(&_env, p.j().unwrap() as *const DkgParticipant)
+        };
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
+        match participant.share_for(_index as u32) {
+            Ok(share) => *return_jobject(&_env, share, "com/horizen/secretsharing/SecretShare"),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCertCommitment(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeGetProofOfKnowledge(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-    ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
-
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        _participant: JObject,
+        _context: JObject,
+    ) -> jbyteArray {
+        let participant = {
+            let p = _env
+                .get_field(_participant, "dkgParticipantPointer", "J")
+                .expect("Should be able to get field dkgParticipantPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, p.j().unwrap() as *const DkgParticipant)
         };
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
-
-        match commitment_tree.get_cert_commitment(&sc_id) {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+        let context = {
+            let c = _env
+                .get_field(_context, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+            read_raw_pointer(&_env, c.j().unwrap() as *const FieldElement)
+        };
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+        let proof_bytes = participant
+            .proof_of_knowledge(context)
+            .and_then(|proof| proof.to_bytes())
+            .expect("Should be able to create proof of knowledge");
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
-        }
+        _env.byte_array_from_slice(proof_bytes.as_slice())
+            .expect("Cannot write object.")
     }
 );
 
-ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCswCommitment(
-        _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-    ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
+ffi_export!(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeVerifyProofOfKnowledge(
+        _env: JNIEnv,
+        _class: JClass,
+        _index: jint,
+        _context: JObject,
+        _commitment: JObject,
+        _proof: jbyteArray,
+    ) -> jboolean {
+        let context = {
+            let c = _env
+                .get_field(_context, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
+
+            read_raw_pointer(&_env, c.j().unwrap() as *const FieldElement)
         };
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
         };
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+        let proof_bytes = _env
+            .convert_byte_array(_proof)
+            .expect("Cannot read proof bytes.");
 
-        match commitment_tree.get_csw_commitment(&sc_id) {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+        let proof = match DkgProofOfKnowledge::from_bytes(proof_bytes.as_slice()) {
+            Ok(proof) => proof,
+            Err(_) => return JNI_FALSE,
+        };
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+        if verify_dkg_proof_of_knowledge(_index as u32, context, commitment, &proof) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+);
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+ffi_export!(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeCombineShares(
+        _env: JNIEnv,
+        _class: JClass,
+        _shares: jobjectArray,
+    ) -> jobject {
+        let shares = read_secret_shares(&_env, _shares);
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
+        match combine_dkg_shares(shares.as_slice()) {
+            Ok(share) => *return_jobject(&_env, share, "com/horizen/secretsharing/SecretShare"),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScCommitment(
+    fn Java_com_horizen_secretsharing_DkgParticipant_nativeFreeDkgParticipant(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-    ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
+        _class: JClass,
+        _participant: *mut DkgParticipant,
+    ) {
+        if _participant.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_participant) });
+    }
+);
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+///////// FROST THRESHOLD SIGNATURES
+
+fn read_frost_signing_commitments(
+    _env: &JNIEnv,
+    _commitments: jobjectArray,
+) -> Vec<FrostSigningCommitment> {
+    let commitments_len = _env
+        .get_array_length(_commitments)
+        .expect("Should be able to get commitments array size");
+
+    let mut commitments = Vec::with_capacity(commitments_len as usize);
+    for i in 0..commitments_len {
+        let commitment_object = _env
+            .get_object_array_element(_commitments, i)
+            .unwrap_or_else(|_| panic!("Should be able to get elem {} of commitments array", i));
+
+        let commitment = {
+            let c = _env
+                .get_field(commitment_object, "signingCommitmentPointer", "J")
+                .expect("Should be able to get field signingCommitmentPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, c.j().unwrap() as *const FrostSigningCommitment)
         };
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+        commitments.push(commitment.clone());
+    }
+    commitments
+}
 
-        match commitment_tree.get_sc_commitment(&sc_id) {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+fn read_frost_signature_shares(_env: &JNIEnv, _shares: jobjectArray) -> Vec<FrostSignatureShare> {
+    let shares_len = _env
+        .get_array_length(_shares)
+        .expect("Should be able to get signature shares array size");
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+    let mut shares = Vec::with_capacity(shares_len as usize);
+    for i in 0..shares_len {
+        let share_object = _env
+            .get_object_array_element(_shares, i)
+            .unwrap_or_else(|_| panic!("Should be able to get elem {} of signature shares array", i));
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+        let share = {
+            let s = _env
+                .get_field(share_object, "signatureSharePointer", "J")
+                .expect("Should be able to get field signatureSharePointer");
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
-        }
+            read_raw_pointer(&_env, s.j().unwrap() as *const FrostSignatureShare)
+        };
+
+        shares.push(share.clone());
+    }
+    shares
+}
+
+ffi_export!(
+    fn Java_com_horizen_frost_FrostSigningCommitment_nativeGetSigningCommitmentSize(
+        _env: JNIEnv,
+        _class: JClass,
+    ) -> jint {
+        FROST_SIGNING_COMMITMENT_SIZE as jint
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetCommitment(
+    fn Java_com_horizen_frost_FrostSigningCommitment_nativeGetIndex(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-    ) -> jobject {
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        _commitment: JObject,
+    ) -> jint {
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "signingCommitmentPointer", "J")
+                .expect("Should be able to get field signingCommitmentPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, c.j().unwrap() as *const FrostSigningCommitment)
         };
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+        commitment.index as jint
+    }
+);
 
-        match commitment_tree.get_commitment() {
-            Some(sc_cr_commitment_fe) => {
-                let field_ptr: jlong =
-                    Box::into_raw(Box::new(sc_cr_commitment_fe)) as i64;
+ffi_export!(
+    fn Java_com_horizen_frost_FrostSigningCommitment_nativeSerialize(
+        _env: JNIEnv,
+        _commitment: JObject,
+    ) -> jbyteArray {
+        let commitment = {
+            let c = _env
+                .get_field(_commitment, "signingCommitmentPointer", "J")
+                .expect("Should be able to get field signingCommitmentPointer");
 
-                let field_class = _env
-                    .find_class("com/horizen/librustsidechains/FieldElement")
-                    .expect("Should be able to find FieldElement class");
+            read_raw_pointer(&_env, c.j().unwrap() as *const FrostSigningCommitment)
+        };
 
-                let jfe = _env
-                    .new_object(field_class, "(J)V", &[JValue::Long(field_ptr)])
-                    .expect("Should be able to create new long for FieldElement");
+        let commitment_bytes = commitment
+            .to_bytes()
+            .expect("Should be able to serialize FrostSigningCommitment");
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jfe)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
-        }
+        _env.byte_array_from_slice(commitment_bytes.as_slice())
+            .expect("Cannot write object.")
     }
 );
 
-// Sc Existance proof functions
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScExistenceProof(
+    fn Java_com_horizen_frost_FrostSigningCommitment_nativeDeserialize(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
+        _class: JClass,
+        _commitment_bytes: jbyteArray,
     ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
-
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        let commitment_bytes = _env
+            .convert_byte_array(_commitment_bytes)
+            .expect("Cannot read bytes.");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
-        };
+        match FrostSigningCommitment::from_bytes(commitment_bytes.as_slice()) {
+            Ok(commitment) => *return_jobject(
+                &_env,
+                commitment,
+                "com/horizen/frost/FrostSigningCommitment",
+            ),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
+    }
+);
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
+ffi_export!(
+    fn Java_com_horizen_frost_FrostSigningCommitment_nativeFreeSigningCommitment(
+        _env: JNIEnv,
+        _class: JClass,
+        _commitment: *mut FrostSigningCommitment,
+    ) {
+        if _commitment.is_null() {
+            return;
+        }
+        drop(unsafe { Box::from_raw(_commitment) });
+    }
+);
 
-        match commitment_tree.get_sc_existence_proof(&sc_id) {
-            Some(sc_existence_proof) => {
-                let proof_ptr: jlong =
-                    Box::into_raw(Box::new(sc_existence_proof)) as i64;
+ffi_export!(
+    fn Java_com_horizen_frost_FrostSignatureShare_nativeGetSignatureShareSize(
+        _env: JNIEnv,
+        _class: JClass,
+    ) -> jint {
+        FROST_SIGNATURE_SHARE_SIZE as jint
+    }
+);
 
-                let existence_proof_class = _env
-                    .find_class("com/horizen/commitmenttree/ScExistenceProof")
-                    .expect("Should be able to find ScExistenceProof class");
+ffi_export!(
+    fn Java_com_horizen_frost_FrostSignatureShare_nativeGetIndex(
+        _env: JNIEnv,
+        _share: JObject,
+    ) -> jint {
+        let share = {
+            let s = _env
+                .get_field(_share, "signatureSharePointer", "J")
+                .expect("Should be able to get field signatureSharePointer");
 
-                let jep = _env
-                    .new_object(existence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
-                    .expect("Should be able to create new long for ScExistenceProof");
+            read_raw_pointer(&_env, s.j().unwrap() as *const FrostSignatureShare)
+        };
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jep)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
-        }
+        share.index as jint
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeSerialize(
+    fn Java_com_horizen_frost_FrostSignatureShare_nativeSerialize(
         _env: JNIEnv,
-        _proof: JObject,
+        _share: JObject,
     ) -> jbyteArray {
-        serialize_from_jobject::<ScExistenceProof>(&_env, _proof, "existenceProofPointer", None)
+        let share = {
+            let s = _env
+                .get_field(_share, "signatureSharePointer", "J")
+                .expect("Should be able to get field signatureSharePointer");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const FrostSignatureShare)
+        };
+
+        let share_bytes = share
+            .to_bytes()
+            .expect("Should be able to serialize FrostSignatureShare");
+
+        _env.byte_array_from_slice(share_bytes.as_slice())
+            .expect("Cannot write object.")
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeDeserialize(
+    fn Java_com_horizen_frost_FrostSignatureShare_nativeDeserialize(
         _env: JNIEnv,
         _class: JClass,
-        _proof_bytes: jbyteArray,
-    ) -> jobject {
-        let proof_bytes = _env
-            .convert_byte_array(_proof_bytes)
-            .expect("Should be able to convert to Rust byte array");
-
-        match ScExistenceProof::deserialize(proof_bytes.as_slice()) {
-            Ok(sc_existence_proof) => {
-                let proof_ptr: jlong =
-                    Box::into_raw(Box::new(sc_existence_proof)) as i64;
-
-                let existence_proof_class = _env
-                    .find_class("com/horizen/commitmenttree/ScExistenceProof")
-                    .expect("Should be able to find ScExistenceProof class");
-
-                let jep = _env
-                    .new_object(existence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
-                    .expect("Should be able to create new long for ScExistenceProof");
+        _share_bytes: jbyteArray,
+    ) -> jobject {
+        let share_bytes = _env
+            .convert_byte_array(_share_bytes)
+            .expect("Cannot read bytes.");
 
-                *jep
-            }
+        match FrostSignatureShare::from_bytes(share_bytes.as_slice()) {
+            Ok(share) => *return_jobject(&_env, share, "com/horizen/frost/FrostSignatureShare"),
             Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_ScExistenceProof_nativeFreeScExistenceProof(
+    fn Java_com_horizen_frost_FrostSignatureShare_nativeFreeSignatureShare(
         _env: JNIEnv,
         _class: JClass,
-        _sc_existence_proof: *mut ScExistenceProof,
+        _share: *mut FrostSignatureShare,
     ) {
-        if _sc_existence_proof.is_null() {
+        if _share.is_null() {
             return;
         }
-        drop(unsafe { Box::from_raw(_sc_existence_proof) });
+        drop(unsafe { Box::from_raw(_share) });
     }
 );
 
-// Sc Absence proof functions
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeGetScAbsenceProof(
+    fn Java_com_horizen_frost_FrostSigner_nativeInit(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
+        _class: JClass,
+        _secret_share: JObject,
+        _group_public_key: JObject,
     ) -> jobject {
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
+        let secret_share = {
+            let s = _env
+                .get_field(_secret_share, "secretSharePointer", "J")
+                .expect("Should be able to get field secretSharePointer");
+
+            read_raw_pointer(&_env, s.j().unwrap() as *const SecretShare)
         };
 
-        let commitment_tree = {
-            let t = _env
-                .get_field(_commitment_tree, "commitmentTreePointer", "J")
-                .expect("Should be able to get field commitmentTreePointer");
+        let group_public_key = {
+            let p = _env
+                .get_field(_group_public_key, "publicKeyPointer", "J")
+                .expect("Should be able to get field publicKeyPointer");
 
-            read_mut_raw_pointer(&_env, t.j().unwrap() as *mut CommitmentTree)
+            read_raw_pointer(&_env, p.j().unwrap() as *const SchnorrPk)
         };
 
-        let cls_optional = _env.find_class("java/util/Optional").unwrap();
-
-        match commitment_tree.get_sc_absence_proof(&sc_id) {
-            Some(sc_absence_proof) => {
-                let proof_ptr: jlong =
-                    Box::into_raw(Box::new(sc_absence_proof)) as i64;
+        let signer = FrostSigner::new(secret_share.clone(), *group_public_key);
+        *return_jobject(&_env, signer, "com/horizen/frost/FrostSigner")
+    }
+);
 
-                let absence_proof_class = _env
-                    .find_class("com/horizen/commitmenttree/ScAbsenceProof")
-                    .expect("Should be able to find ScAbsenceProof class");
+ffi_export!(
+    fn Java_com_horizen_frost_FrostSigner_nativeGetIndex(
+        _env: JNIEnv,
+        _signer: JObject,
+    ) -> jint {
+        let signer = {
+            let s = _env
+                .get_field(_signer, "signerPointer", "J")
+                .expect("Should be able to get field signerPointer");
 
-                let jep = _env
-                    .new_object(absence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
-                    .expect("Should be able to create new long for ScAbsenceProof");
+            read_raw_pointer(&_env, s.j().unwrap() as *const FrostSigner)
+        };
 
-                let res = _env
-                    .call_static_method(
-                        cls_optional,
-                        "of",
-                        "(Ljava/lang/Object;)Ljava/util/Optional;",
-                        &[JValue::Object(jep)],
-                    )
-                    .unwrap();
-                *res.l().unwrap()
-            }
-            _ => {
-                let empty_res = _env
-                    .call_static_method(cls_optional, "empty", "()Ljava/util/Optional;", &[])
-                    .expect("Should be able to create new value for Optional.empty()");
-                *empty_res.l().unwrap()
-            }
-        }
+        signer.index() as jint
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeSerialize(
+    fn Java_com_horizen_frost_FrostSigner_nativeCommit(
         _env: JNIEnv,
-        _proof: JObject,
-    ) -> jbyteArray {
-        serialize_from_jobject::<ScAbsenceProof>(&_env, _proof, "absenceProofPointer", None)
+        _signer: JObject,
+    ) -> jobject {
+        let signer = {
+            let s = _env
+                .get_field(_signer, "signerPointer", "J")
+                .expect("Should be able to get field signerPointer");
+
+            read_mut_raw_pointer(&_env, s.j().unwrap() as *mut FrostSigner)
+        };
+
+        return_jobject(
+            &_env,
+            signer.commit(),
+            "com/horizen/frost/FrostSigningCommitment",
+        )
+        .into_inner()
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeDeserialize(
+    fn Java_com_horizen_frost_FrostSigner_nativeSign(
         _env: JNIEnv,
-        _class: JClass,
-        _proof_bytes: jbyteArray,
+        _signer: JObject,
+        _message: JObject,
+        _commitments: jobjectArray,
     ) -> jobject {
-        let proof_bytes = _env
-            .convert_byte_array(_proof_bytes)
-            .expect("Should be able to convert to Rust byte array");
+        let signer = {
+            let s = _env
+                .get_field(_signer, "signerPointer", "J")
+                .expect("Should be able to get field signerPointer");
 
-        match ScAbsenceProof::deserialize(proof_bytes.as_slice()) {
-            Ok(sc_absence_proof) => {
-                let proof_ptr: jlong =
-                    Box::into_raw(Box::new(sc_absence_proof)) as i64;
+            read_mut_raw_pointer(&_env, s.j().unwrap() as *mut FrostSigner)
+        };
 
-                let absence_proof_class = _env
-                    .find_class("com/horizen/commitmenttree/ScAbsenceProof")
-                    .expect("Should be able to find ScAbsenceProof class");
+        let message = {
+            let m = _env
+                .get_field(_message, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-                let jep = _env
-                    .new_object(absence_proof_class, "(J)V", &[JValue::Long(proof_ptr)])
-                    .expect("Should be able to create new long for ScAbsenceProof");
+            read_raw_pointer(&_env, m.j().unwrap() as *const FieldElement)
+        };
 
-                *jep
-            }
+        let commitments = read_frost_signing_commitments(&_env, _commitments);
+
+        match signer.sign(message, commitments.as_slice()) {
+            Ok(share) => *return_jobject(&_env, share, "com/horizen/frost/FrostSignatureShare"),
             Err(_) => std::ptr::null::<jobject>() as jobject,
         }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_ScAbsenceProof_nativeFreeScAbsenceProof(
+    fn Java_com_horizen_frost_FrostSigner_nativeFreeSigner(
         _env: JNIEnv,
         _class: JClass,
-        _sc_absence_proof: *mut ScAbsenceProof,
+        _signer: *mut FrostSigner,
     ) {
-        if _sc_absence_proof.is_null() {
+        if _signer.is_null() {
             return;
         }
-        drop(unsafe { Box::from_raw(_sc_absence_proof) });
+        drop(unsafe { Box::from_raw(_signer) });
     }
 );
 
-// Verify existance/absence functions.
-
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeVerifyScCommitment(
+    fn Java_com_horizen_frost_FrostAggregator_nativeInit(
         _env: JNIEnv,
-        _commitment_tree_class: JObject,
-        _sc_commitment: JObject,
-        _sc_commitment_proof: JObject,
-        _commitment: JObject,
-    ) -> bool {
-        //Read sidechain commitment
-        let sc_commitment_fe = {
-            let i = _env
-                .get_field(_sc_commitment, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer from scCommitment");
+        _class: JClass,
+        _group_commitment: JObject,
+        _message: JObject,
+        _commitments: jobjectArray,
+    ) -> jobject {
+        let group_commitment = {
+            let c = _env
+                .get_field(_group_commitment, "vssCommitmentPointer", "J")
+                .expect("Should be able to get field vssCommitmentPointer");
 
-            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, c.j().unwrap() as *const VssCommitment)
         };
 
-        //Read commitment proof
-        let sc_commitment_proof = {
-            let i = _env
-                .get_field(_sc_commitment_proof, "existenceProofPointer", "J")
-                .expect("Should be able to get field existenceProofPointer from scCommitmentProof");
+        let message = {
+            let m = _env
+                .get_field(_message, "fieldElementPointer", "J")
+                .expect("Should be able to get field fieldElementPointer");
 
-            read_raw_pointer(&_env, i.j().unwrap() as *const ScExistenceProof)
+            read_raw_pointer(&_env, m.j().unwrap() as *const FieldElement)
         };
 
-        //Read commitment
-        let commitment_fe = {
-            let i = _env
-                .get_field(_commitment, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer from commitment");
-
-            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
-        };
+        let commitments = read_frost_signing_commitments(&_env, _commitments);
 
-        CommitmentTree::verify_sc_commitment(sc_commitment_fe, sc_commitment_proof, commitment_fe)
+        match FrostAggregator::new(group_commitment.clone(), *message, commitments.as_slice()) {
+            Ok(aggregator) => *return_jobject(&_env, aggregator, "com/horizen/frost/FrostAggregator"),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_commitmenttree_CommitmentTree_nativeVerifyScAbsence(
+    fn Java_com_horizen_frost_FrostAggregator_nativeVerifyShare(
         _env: JNIEnv,
-        _commitment_tree: JObject,
-        _sc_id: jbyteArray,
-        _sc_absence_proof: JObject,
-        _commitment: JObject,
-    ) -> bool {
-        // Read sidechain id
-        let sc_id = {
-            let sc_id_bytes = parse_jbyte_array_to_vec(&_env, &_sc_id, FIELD_SIZE);
-            FieldElement::deserialize(sc_id_bytes.as_slice())
-                .expect("Can't parse the input sc_id_bytes into FieldElement")
-        };
-
-        //Read commitment proof
-        let sc_absence_proof = {
-            let i = _env
-                .get_field(_sc_absence_proof, "absenceProofPointer", "J")
-                .expect("Should be able to get field absenceProofPointer from scAbsenceProof");
+        _aggregator: JObject,
+        _share: JObject,
+    ) -> jboolean {
+        let aggregator = {
+            let a = _env
+                .get_field(_aggregator, "aggregatorPointer", "J")
+                .expect("Should be able to get field aggregatorPointer");
 
-            read_raw_pointer(&_env, i.j().unwrap() as *const ScAbsenceProof)
+            read_raw_pointer(&_env, a.j().unwrap() as *const FrostAggregator)
         };
 
-        //Read commitment
-        let commitment_fe = {
-            let i = _env
-                .get_field(_commitment, "fieldElementPointer", "J")
-                .expect("Should be able to get field fieldElementPointer from commitment");
+        let share = {
+            let s = _env
+                .get_field(_share, "signatureSharePointer", "J")
+                .expect("Should be able to get field signatureSharePointer");
 
-            read_raw_pointer(&_env, i.j().unwrap() as *const FieldElement)
+            read_raw_pointer(&_env, s.j().unwrap() as *const FrostSignatureShare)
         };
 
-        CommitmentTree::verify_sc_absence(&sc_id, sc_absence_proof, commitment_fe)
+        if aggregator.verify_share(share) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_librustsidechains_Utils_nativeCalculateSidechainId(
+    fn Java_com_horizen_frost_FrostAggregator_nativeAggregate(
         _env: JNIEnv,
-        _utils: JClass,
-        _tx_hash: jbyteArray,
-        _idx: jint,
-    ) -> jbyteArray {
-        // Parse tx_hash into a [u8; 32]
-        let tx_hash: [u8; 32] = _env
-            .convert_byte_array(_tx_hash)
-            .expect("Should be able to convert to Rust byte array")
-            .try_into()
-            .expect("Should be able to write into fixed buffer of size 32");
-
-        let idx = _idx as u32;
-
-        // Compute sc_id
-        let sc_id = compute_sc_id(&tx_hash, idx).expect("Cannot compute sc id.");
-
-        // Return sc_id bytes
-        let sc_id_bytes =
-            serialize_to_buffer(&sc_id, None).expect("Should be able to serialize sc_id");
-        _env.byte_array_from_slice(sc_id_bytes.as_slice())
-            .expect("Cannot write jobject.")
-    }
-);
+        _aggregator: JObject,
+        _shares: jobjectArray,
+    ) -> jobject {
+        let aggregator = {
+            let a = _env
+                .get_field(_aggregator, "aggregatorPointer", "J")
+                .expect("Should be able to get field aggregatorPointer");
 
-ffi_export!(
-    fn Java_com_horizen_librustsidechains_Utils_nativeCompressedBitvectorMerkleRoot(
-        _env: JNIEnv,
-        _utils: JClass,
-        _compressed_bit_vector: jbyteArray,
-    ) -> jbyteArray {
-        // Parse compressed_bit_vector into a vector
-        let compressed_bit_vector = _env
-            .convert_byte_array(_compressed_bit_vector)
-            .expect("Should be able to convert to Rust byte array");
+            read_raw_pointer(&_env, a.j().unwrap() as *const FrostAggregator)
+        };
 
-        // Compute merkle_root
-        let merkle_root =
-            merkle_root_from_compressed_bytes_without_checks(compressed_bit_vector.as_slice())
-                .expect("Cannot compute merkle root.");
+        let shares = read_frost_signature_shares(&_env, _shares);
 
-        // Return merkle_root bytes
-        let merkle_root_bytes = serialize_to_buffer(&merkle_root, None)
-            .expect("Should be able to serialize merkle_root");
-        _env.byte_array_from_slice(merkle_root_bytes.as_slice())
-            .expect("Cannot write jobject.")
+        match aggregator.aggregate(shares.as_slice()) {
+            Ok(signature) => *return_jobject(
+                &_env,
+                signature,
+                "com/horizen/schnorrnative/SchnorrSignature",
+            ),
+            Err(_) => std::ptr::null::<jobject>() as jobject,
+        }
     }
 );
 
 ffi_export!(
-    fn Java_com_horizen_librustsidechains_Utils_nativeCompressedBitvectorMerkleRootWithSizeCheck(
+    fn Java_com_horizen_frost_FrostAggregator_nativeFreeAggregator(
         _env: JNIEnv,
-        _utils: JClass,
-        _compressed_bit_vector: jbyteArray,
-        _expected_uncompressed_size: jint,
-    ) -> jbyteArray {
-        // Parse compressed_bit_vector into a vector
-        let compressed_bit_vector = _env
-            .convert_byte_array(_compressed_bit_vector)
-            .expect("Should be able to convert to Rust byte array");
-
-        let expected_uncompressed_size = _expected_uncompressed_size as usize;
-
-        // Compute merkle_root
-        match merkle_root_from_compressed_bytes(
-            compressed_bit_vector.as_slice(),
-            expected_uncompressed_size,
-        ) {
-            Ok(merkle_root) => {
-                // Return merkle_root bytes
-                let merkle_root_bytes = serialize_to_buffer(&merkle_root, None)
-                    .expect("Should be able to serialize merkle_root");
-                _env.byte_array_from_slice(merkle_root_bytes.as_slice())
-                    .expect("Cannot write jobject.")
-            }
-            Err(_) => {
-                throw!(
-                    &_env,
-                    "java/lang/Exception",
-                    "Cannot compute merkle root with size check.",
-                    JObject::null().into_inner()
-                );
-            }
+        _class: JClass,
+        _aggregator: *mut FrostAggregator,
+    ) {
+        if _aggregator.is_null() {
+            return;
         }
+        drop(unsafe { Box::from_raw(_aggregator) });
     }
 );
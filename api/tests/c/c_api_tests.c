@@ -0,0 +1,406 @@
+/*
+ * Tests of the C API, linked into the test executable of the api crate and run by the c_api
+ * test of src/c_api.rs. zendoo_c_api_tests() returns the number of failed checks.
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "zendoo_sc.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                            \
+    do {                                                                       \
+        if (!(cond)) {                                                         \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+                    #cond);                                                    \
+            failures++;                                                        \
+        }                                                                      \
+    } while (0)
+
+#define CHECK_OK(call) CHECK((call) == ZENDOO_ERROR_OK)
+
+static bool field_elements_equal(const ZendooFieldElement *a, const ZendooFieldElement *b)
+{
+    bool equal = false;
+    CHECK_OK(zendoo_field_element_equals(a, b, &equal));
+    return equal;
+}
+
+static void test_field_element(void)
+{
+    ZendooFieldElement *fe = NULL, *deserialized = NULL, *other = NULL;
+    ZendooBuffer bytes;
+
+    CHECK_OK(zendoo_field_element_from_u64(42, &fe));
+    CHECK_OK(zendoo_field_element_to_bytes(fe, &bytes));
+    CHECK(bytes.len == zendoo_field_element_size());
+
+    CHECK_OK(zendoo_field_element_from_bytes(bytes.data, bytes.len, &deserialized));
+    CHECK(field_elements_equal(fe, deserialized));
+
+    CHECK_OK(zendoo_field_element_from_u64(43, &other));
+    CHECK(!field_elements_equal(fe, other));
+
+    /* Wrong length, NULL pointers */
+    CHECK(zendoo_field_element_from_bytes(bytes.data, bytes.len - 1, &other) ==
+          ZENDOO_ERROR_INVALID_INPUT);
+    CHECK(zendoo_field_element_from_u64(42, NULL) == ZENDOO_ERROR_NULL_POINTER);
+    CHECK(zendoo_field_element_to_bytes(NULL, &bytes) == ZENDOO_ERROR_NULL_POINTER);
+
+    zendoo_buffer_free(bytes);
+    zendoo_field_element_free(fe);
+    zendoo_field_element_free(deserialized);
+    zendoo_field_element_free(other);
+    zendoo_field_element_free(NULL);
+}
+
+static void test_poseidon_hash(void)
+{
+    ZendooFieldElement *inputs[2] = {NULL, NULL};
+    ZendooFieldElement *digest = NULL, *other_digest = NULL;
+    ZendooPoseidonHash *h = NULL;
+    uint8_t bytes[3] = {1, 2, 3};
+
+    CHECK_OK(zendoo_field_element_from_u64(1, &inputs[0]));
+    CHECK_OK(zendoo_field_element_from_u64(2, &inputs[1]));
+
+    CHECK_OK(zendoo_poseidon_hash_new_constant_length(2, NULL, 0, &h));
+    CHECK_OK(zendoo_poseidon_hash_update(h, inputs[0]));
+
+    /* Not enough inputs */
+    CHECK(zendoo_poseidon_hash_finalize(h, &digest) == ZENDOO_ERROR_CRYPTO_ERROR);
+
+    CHECK_OK(zendoo_poseidon_hash_update(h, inputs[1]));
+    CHECK_OK(zendoo_poseidon_hash_finalize(h, &digest));
+    zendoo_poseidon_hash_free(h);
+
+    /* Personalization changes the digest */
+    CHECK_OK(zendoo_poseidon_hash_new_constant_length(
+        2, (const ZendooFieldElement *const *)inputs, 1, &h));
+    CHECK_OK(zendoo_poseidon_hash_update(h, inputs[0]));
+    CHECK_OK(zendoo_poseidon_hash_update(h, inputs[1]));
+    CHECK_OK(zendoo_poseidon_hash_finalize(h, &other_digest));
+    CHECK(!field_elements_equal(digest, other_digest));
+    zendoo_field_element_free(other_digest);
+
+    /* After a reset without personalization, same digest of the first instance */
+    CHECK_OK(zendoo_poseidon_hash_reset(h, NULL, 0));
+    CHECK_OK(zendoo_poseidon_hash_update(h, inputs[0]));
+    CHECK_OK(zendoo_poseidon_hash_update(h, inputs[1]));
+    CHECK_OK(zendoo_poseidon_hash_finalize(h, &other_digest));
+    CHECK(field_elements_equal(digest, other_digest));
+    zendoo_field_element_free(other_digest);
+    zendoo_poseidon_hash_free(h);
+
+    /* Bytes */
+    CHECK_OK(zendoo_poseidon_hash_new_variable_length(false, NULL, 0, &h));
+    CHECK_OK(zendoo_poseidon_hash_update_bytes(h, bytes, sizeof(bytes)));
+    CHECK_OK(zendoo_poseidon_hash_finalize(h, &other_digest));
+    CHECK(!field_elements_equal(digest, other_digest));
+    zendoo_field_element_free(other_digest);
+    zendoo_poseidon_hash_free(h);
+
+    zendoo_field_element_free(digest);
+    zendoo_field_element_free(inputs[0]);
+    zendoo_field_element_free(inputs[1]);
+}
+
+static void test_merkle_tree(void)
+{
+    const uintptr_t height = 4;
+    ZendooMerkleTree *tree = NULL;
+    ZendooMerklePath *path = NULL, *deserialized_path = NULL;
+    ZendooFieldElement *leaves[5];
+    ZendooFieldElement *root = NULL;
+    ZendooBuffer path_bytes;
+    bool verified = false;
+    uint64_t i;
+
+    CHECK_OK(zendoo_merkle_tree_new(height, 1 << height, &tree));
+    for (i = 0; i < 5; i++) {
+        CHECK_OK(zendoo_field_element_from_u64(i, &leaves[i]));
+        CHECK_OK(zendoo_merkle_tree_append(tree, leaves[i]));
+    }
+
+    /* Root and paths are available only after finalization */
+    CHECK(zendoo_merkle_tree_root(tree, &root) == ZENDOO_ERROR_INVALID_INPUT);
+    CHECK_OK(zendoo_merkle_tree_finalize(tree));
+    CHECK_OK(zendoo_merkle_tree_root(tree, &root));
+    CHECK(zendoo_merkle_tree_append(tree, leaves[0]) == ZENDOO_ERROR_INVALID_INPUT);
+
+    for (i = 0; i < 5; i++) {
+        CHECK_OK(zendoo_merkle_tree_get_path(tree, i, &path));
+        CHECK_OK(zendoo_merkle_path_verify(path, height, leaves[i], root, &verified));
+        CHECK(verified);
+        CHECK_OK(zendoo_merkle_path_verify(path, height, leaves[(i + 1) % 5], root, &verified));
+        CHECK(!verified);
+
+        CHECK_OK(zendoo_merkle_path_to_bytes(path, &path_bytes));
+        CHECK_OK(zendoo_merkle_path_from_bytes(path_bytes.data, path_bytes.len, &deserialized_path));
+        CHECK_OK(zendoo_merkle_path_verify(deserialized_path, height, leaves[i], root, &verified));
+        CHECK(verified);
+
+        zendoo_buffer_free(path_bytes);
+        zendoo_merkle_path_free(deserialized_path);
+        zendoo_merkle_path_free(path);
+    }
+    CHECK(zendoo_merkle_tree_get_path(tree, 1 << height, &path) == ZENDOO_ERROR_INVALID_INPUT);
+
+    zendoo_field_element_free(root);
+    for (i = 0; i < 5; i++)
+        zendoo_field_element_free(leaves[i]);
+    zendoo_merkle_tree_free(tree);
+}
+
+static void test_schnorr(void)
+{
+    ZendooSchnorrPublicKey *pk = NULL, *derived_pk = NULL, *deserialized_pk = NULL;
+    ZendooSchnorrSecretKey *sk = NULL;
+    ZendooSchnorrSignature *sig = NULL, *deserialized_sig = NULL;
+    ZendooFieldElement *msg = NULL, *other_msg = NULL;
+    ZendooBuffer pk_bytes, sig_bytes;
+    bool verified = false;
+
+    CHECK_OK(zendoo_schnorr_generate_key(&pk, &sk));
+    CHECK_OK(zendoo_schnorr_verify_public_key(pk, &verified));
+    CHECK(verified);
+    CHECK_OK(zendoo_schnorr_get_public_key(sk, &derived_pk));
+
+    CHECK_OK(zendoo_field_element_from_u64(1234, &msg));
+    CHECK_OK(zendoo_field_element_from_u64(4321, &other_msg));
+    CHECK_OK(zendoo_schnorr_sign(msg, sk, pk, &sig));
+    CHECK_OK(zendoo_schnorr_verify(msg, derived_pk, sig, &verified));
+    CHECK(verified);
+    CHECK_OK(zendoo_schnorr_verify(other_msg, pk, sig, &verified));
+    CHECK(!verified);
+
+    CHECK_OK(zendoo_schnorr_public_key_to_bytes(pk, &pk_bytes));
+    CHECK_OK(zendoo_schnorr_public_key_from_bytes(pk_bytes.data, pk_bytes.len, &deserialized_pk));
+    CHECK_OK(zendoo_schnorr_signature_to_bytes(sig, &sig_bytes));
+    CHECK_OK(zendoo_schnorr_signature_from_bytes(sig_bytes.data, sig_bytes.len, &deserialized_sig));
+    CHECK_OK(zendoo_schnorr_verify(msg, deserialized_pk, deserialized_sig, &verified));
+    CHECK(verified);
+
+    /* Truncated public key */
+    CHECK(zendoo_schnorr_public_key_from_bytes(pk_bytes.data, pk_bytes.len - 1, &deserialized_pk) ==
+          ZENDOO_ERROR_INVALID_INPUT);
+
+    zendoo_buffer_free(pk_bytes);
+    zendoo_buffer_free(sig_bytes);
+    zendoo_field_element_free(msg);
+    zendoo_field_element_free(other_msg);
+    zendoo_schnorr_signature_free(sig);
+    zendoo_schnorr_signature_free(deserialized_sig);
+    zendoo_schnorr_public_key_free(pk);
+    zendoo_schnorr_public_key_free(derived_pk);
+    zendoo_schnorr_public_key_free(deserialized_pk);
+    zendoo_schnorr_secret_key_free(sk);
+}
+
+static void test_vrf(void)
+{
+    ZendooVrfPublicKey *pk = NULL, *deserialized_pk = NULL;
+    ZendooVrfSecretKey *sk = NULL;
+    ZendooVrfProof *proof = NULL, *deserialized_proof = NULL;
+    ZendooFieldElement *msg = NULL, *other_msg = NULL, *output = NULL, *verified_output = NULL;
+    ZendooBuffer pk_bytes, proof_bytes;
+    bool valid = false;
+
+    CHECK_OK(zendoo_vrf_generate_key(&pk, &sk));
+    CHECK_OK(zendoo_vrf_verify_public_key(pk, &valid));
+    CHECK(valid);
+
+    CHECK_OK(zendoo_field_element_from_u64(1234, &msg));
+    CHECK_OK(zendoo_field_element_from_u64(4321, &other_msg));
+    CHECK_OK(zendoo_vrf_prove(msg, sk, pk, &proof, &output));
+
+    CHECK_OK(zendoo_vrf_public_key_to_bytes(pk, &pk_bytes));
+    CHECK_OK(zendoo_vrf_public_key_from_bytes(pk_bytes.data, pk_bytes.len, &deserialized_pk));
+    CHECK_OK(zendoo_vrf_proof_to_bytes(proof, &proof_bytes));
+    CHECK_OK(zendoo_vrf_proof_from_bytes(proof_bytes.data, proof_bytes.len, &deserialized_proof));
+
+    CHECK_OK(zendoo_vrf_proof_to_hash(msg, deserialized_pk, deserialized_proof, &verified_output));
+    CHECK(field_elements_equal(output, verified_output));
+    zendoo_field_element_free(verified_output);
+    verified_output = NULL;
+
+    /* Proof for another message */
+    CHECK(zendoo_vrf_proof_to_hash(other_msg, pk, proof, &verified_output) ==
+          ZENDOO_ERROR_CRYPTO_ERROR);
+    CHECK(verified_output == NULL);
+
+    zendoo_buffer_free(pk_bytes);
+    zendoo_buffer_free(proof_bytes);
+    zendoo_field_element_free(msg);
+    zendoo_field_element_free(other_msg);
+    zendoo_field_element_free(output);
+    zendoo_vrf_proof_free(proof);
+    zendoo_vrf_proof_free(deserialized_proof);
+    zendoo_vrf_public_key_free(pk);
+    zendoo_vrf_public_key_free(deserialized_pk);
+    zendoo_vrf_secret_key_free(sk);
+}
+
+static void test_commitment_tree(void)
+{
+    ZendooCommitmentTree *tree = NULL, *restored_tree = NULL;
+    ZendooFieldElement *sc_id = NULL, *other_sc_id = NULL, *nullifier = NULL;
+    ZendooFieldElement *commitment = NULL, *restored_commitment = NULL, *sc_commitment = NULL;
+    uint8_t pub_key[32], tx_hash[32], mc_address[ZENDOO_MC_PK_SIZE];
+    ZendooBuffer state;
+
+    memset(pub_key, 1, sizeof(pub_key));
+    memset(tx_hash, 2, sizeof(tx_hash));
+    memset(mc_address, 3, sizeof(mc_address));
+
+    CHECK_OK(zendoo_field_element_from_u64(1, &sc_id));
+    CHECK_OK(zendoo_field_element_from_u64(2, &other_sc_id));
+    CHECK_OK(zendoo_field_element_from_u64(3, &nullifier));
+
+    CHECK_OK(zendoo_commitment_tree_new(&tree));
+    CHECK(zendoo_commitment_tree_get_sc_commitment(tree, sc_id, &sc_commitment) ==
+          ZENDOO_ERROR_INVALID_INPUT);
+
+    CHECK_OK(zendoo_commitment_tree_add_fwt(tree, sc_id, 100, pub_key, mc_address, tx_hash, 0));
+    CHECK_OK(zendoo_commitment_tree_add_bwtr(tree, sc_id, 10, NULL, 0, mc_address, tx_hash, 1));
+    CHECK_OK(zendoo_commitment_tree_add_csw(tree, other_sc_id, 50, nullifier, mc_address));
+
+    /* A sidechain can't be both alive and ceased */
+    CHECK(zendoo_commitment_tree_add_csw(tree, sc_id, 50, nullifier, mc_address) ==
+          ZENDOO_ERROR_INVALID_INPUT);
+
+    CHECK_OK(zendoo_commitment_tree_get_sc_commitment(tree, sc_id, &sc_commitment));
+    CHECK_OK(zendoo_commitment_tree_get_commitment(tree, &commitment));
+
+    CHECK_OK(zendoo_commitment_tree_serialize_state(tree, &state));
+    CHECK_OK(zendoo_commitment_tree_deserialize_state(state.data, state.len, &restored_tree));
+    CHECK_OK(zendoo_commitment_tree_get_commitment(restored_tree, &restored_commitment));
+    CHECK(field_elements_equal(commitment, restored_commitment));
+
+    /* Corrupted state */
+    state.data[state.len / 2] ^= 1;
+    zendoo_commitment_tree_free(restored_tree);
+    restored_tree = NULL;
+    CHECK(zendoo_commitment_tree_deserialize_state(state.data, state.len, &restored_tree) ==
+          ZENDOO_ERROR_INVALID_INPUT);
+    CHECK(restored_tree == NULL);
+
+    zendoo_buffer_free(state);
+    zendoo_field_element_free(sc_id);
+    zendoo_field_element_free(other_sc_id);
+    zendoo_field_element_free(nullifier);
+    zendoo_field_element_free(commitment);
+    zendoo_field_element_free(restored_commitment);
+    zendoo_field_element_free(sc_commitment);
+    zendoo_commitment_tree_free(tree);
+}
+
+static void temp_path(char *path, size_t len, const char *name)
+{
+    const char *dir = getenv("TMPDIR");
+    snprintf(path, len, "%s/%s", dir != NULL ? dir : "/tmp", name);
+}
+
+static void test_naive_threshold_sig(void)
+{
+    ZendooSchnorrPublicKey *pks[2] = {NULL, NULL};
+    ZendooSchnorrSecretKey *sks[2] = {NULL, NULL};
+    ZendooSchnorrSignature *sigs[2] = {NULL, NULL};
+    ZendooFieldElement *sc_id = NULL, *root = NULL, *msg = NULL, *constant = NULL;
+    ZendooBackwardTransfer bt_list[1];
+    ZendooBuffer proof = {NULL, 0};
+    uint64_t quality = 0;
+    bool verified = false;
+    char pk_path[512], vk_path[512];
+    int i;
+
+    temp_path(pk_path, sizeof(pk_path), "zendoo_c_api_tests_pk");
+    temp_path(vk_path, sizeof(vk_path), "zendoo_c_api_tests_vk");
+
+    memset(bt_list[0].pk_dest, 4, sizeof(bt_list[0].pk_dest));
+    bt_list[0].amount = 10;
+
+    CHECK_OK(zendoo_field_element_from_u64(1, &sc_id));
+    CHECK_OK(zendoo_field_element_from_u64(2, &root));
+    CHECK_OK(zendoo_compute_msg_to_sign(sc_id, 5, root, 0, 0, bt_list, 1, &msg));
+
+    for (i = 0; i < 2; i++)
+        CHECK_OK(zendoo_schnorr_generate_key(&pks[i], &sks[i]));
+    CHECK_OK(zendoo_compute_pks_threshold_hash(
+        (const ZendooSchnorrPublicKey *const *)pks, 2, 1, &constant));
+
+    /* Unknown proving system */
+    CHECK(zendoo_init_dlog_keys(7, 1 << 17, 1 << 14) == ZENDOO_ERROR_INVALID_INPUT);
+    CHECK(zendoo_naive_threshold_sig_setup(7, 2, pk_path, vk_path, false, 7000, 4000, true,
+                                           true) == ZENDOO_ERROR_INVALID_INPUT);
+
+    CHECK_OK(zendoo_init_dlog_keys(ZENDOO_PROVING_SYSTEM_COBOUNDARY_MARLIN, 1 << 17, 1 << 14));
+    CHECK_OK(zendoo_naive_threshold_sig_setup(ZENDOO_PROVING_SYSTEM_COBOUNDARY_MARLIN, 2, pk_path,
+                                              vk_path, false, 7000, 4000, true, true));
+
+    /* Not enough signatures: rejected before reading the proving key */
+    CHECK(zendoo_naive_threshold_sig_create_proof(
+              (const ZendooSchnorrPublicKey *const *)pks,
+              (const ZendooSchnorrSignature *const *)sigs, 2, sc_id, 5, root, 0, 0, bt_list, 1,
+              1, "missing_key", false, false, true, true, &proof,
+              &quality) == ZENDOO_ERROR_INVALID_INPUT);
+
+    /* Missing proving key */
+    CHECK_OK(zendoo_schnorr_sign(msg, sks[0], pks[0], &sigs[0]));
+    CHECK(zendoo_naive_threshold_sig_create_proof(
+              (const ZendooSchnorrPublicKey *const *)pks,
+              (const ZendooSchnorrSignature *const *)sigs, 2, sc_id, 5, root, 0, 0, bt_list, 1,
+              1, "missing_key", false, false, true, true, &proof,
+              &quality) == ZENDOO_ERROR_CRYPTO_ERROR);
+    CHECK(proof.data == NULL);
+
+    /* Proof with one signature out of two */
+    CHECK_OK(zendoo_naive_threshold_sig_create_proof(
+        (const ZendooSchnorrPublicKey *const *)pks, (const ZendooSchnorrSignature *const *)sigs,
+        2, sc_id, 5, root, 0, 0, bt_list, 1, 1, pk_path, false, false, true, true, &proof,
+        &quality));
+    CHECK(proof.data != NULL && proof.len > 0);
+    CHECK(quality == 1);
+
+    CHECK_OK(zendoo_naive_threshold_sig_verify_proof(constant, sc_id, 5, root, 0, 0, bt_list, 1,
+                                                     quality, proof.data, proof.len, true, true,
+                                                     vk_path, true, true, &verified));
+    CHECK(verified);
+
+    /* Wrong public inputs */
+    CHECK_OK(zendoo_naive_threshold_sig_verify_proof(constant, sc_id, 5, root, 0, 0, bt_list, 1,
+                                                     quality + 1, proof.data, proof.len, true,
+                                                     true, vk_path, true, true, &verified));
+    CHECK(!verified);
+    CHECK_OK(zendoo_naive_threshold_sig_verify_proof(constant, sc_id, 6, root, 0, 0, bt_list, 1,
+                                                     quality, proof.data, proof.len, true, true,
+                                                     vk_path, true, true, &verified));
+    CHECK(!verified);
+
+    zendoo_buffer_free(proof);
+    remove(pk_path);
+    remove(vk_path);
+    zendoo_field_element_free(sc_id);
+    zendoo_field_element_free(root);
+    zendoo_field_element_free(msg);
+    zendoo_field_element_free(constant);
+    for (i = 0; i < 2; i++) {
+        zendoo_schnorr_public_key_free(pks[i]);
+        zendoo_schnorr_secret_key_free(sks[i]);
+        zendoo_schnorr_signature_free(sigs[i]);
+    }
+}
+
+int zendoo_c_api_tests(void)
+{
+    test_field_element();
+    test_poseidon_hash();
+    test_merkle_tree();
+    test_schnorr();
+    test_vrf();
+    test_commitment_tree();
+    test_naive_threshold_sig();
+    return failures;
+}
@@ -0,0 +1,311 @@
+use cctp_primitives::{
+    proving_system::ProvingSystem,
+    utils::{data_structures::*, serialization::*},
+};
+use demo_circuit::type_mapping::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use zendoo_sc_lib::{
+    deserialize_secret, BackwardTransferJson, FieldElementJson, JsonFormat, SchnorrPublicKeyJson,
+    SchnorrSignatureJson, VecJson, Zeroize, Zeroizing, ZeroizingSchnorrSk,
+};
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+// Files read and written by the CLI are JSON. Public objects inside them have the versioned
+// JSON representation of zendoo_sc_lib::json, e.g. {"type": "FieldElement", "version": 1, ...};
+// secret keys, which have none, and proofs are hex encoded with the same serialization of the
+// JNI API. Field elements given on the command line are hex encoded.
+
+pub fn field_element_from_hex(hex_str: &str) -> Result<FieldElement, Error> {
+    let bytes = hex::decode(hex_str.trim())?;
+    if bytes.len() != FIELD_SIZE {
+        Err(format!(
+            "Field elements are {} bytes long, found {}",
+            FIELD_SIZE,
+            bytes.len()
+        ))?
+    }
+    deserialize_from_buffer(&bytes, None, None)
+}
+
+pub fn proving_system_to_str(proving_system: &ProvingSystem) -> &'static str {
+    match proving_system {
+        ProvingSystem::Undefined => "undefined",
+        ProvingSystem::Darlin => "darlin",
+        ProvingSystem::CoboundaryMarlin => "coboundary_marlin",
+    }
+}
+
+pub fn proving_system_from_str(proving_system: &str) -> Result<ProvingSystem, Error> {
+    match proving_system {
+        "darlin" => Ok(ProvingSystem::Darlin),
+        "coboundary_marlin" => Ok(ProvingSystem::CoboundaryMarlin),
+        _ => Err(format!(
+            "Unknown proving system '{}', expected 'darlin' or 'coboundary_marlin'",
+            proving_system
+        )
+        .into()),
+    }
+}
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    serde_json::from_reader(File::open(path)?)
+        .map_err(|e| format!("Unable to parse {}: {}", path.display(), e).into())
+}
+
+// Write `value` to `path` or, if absent, to stdout. Files holding secrets are made
+// readable by the owner only, even if they already existed with wider permissions.
+pub fn write_json<T: Serialize>(value: &T, path: Option<&Path>, secret: bool) -> Result<(), Error> {
+    let json = serde_json::to_string_pretty(value)?;
+    match path {
+        None => println!("{}", json),
+        Some(path) => {
+            let mut options = OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                if secret {
+                    options.mode(0o600);
+                }
+            }
+            #[cfg(not(unix))]
+            let _ = secret;
+            let mut file = options.open(path)?;
+            // The mode above applies only to newly created files
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if secret {
+                    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+                }
+            }
+            writeln!(file, "{}", json)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parameters of the DLOG keys. The keys are derived deterministically from them and are
+/// kept in memory, so each command regenerates them from this file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DlogParams {
+    pub proving_system: String,
+    pub max_segment_size: usize,
+    pub supported_segment_size: usize,
+}
+
+/// Schnorr key pair, or public key only if `secret_key` is absent.
+/// The secret key is wiped when dropped, and it is not Debug nor Clone to avoid leaking copies.
+#[derive(Serialize, Deserialize)]
+pub struct SchnorrKeyFile {
+    #[serde(with = "SchnorrPublicKeyJson")]
+    pub public_key: SchnorrPk,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_key: Option<String>,
+}
+
+impl SchnorrKeyFile {
+    pub fn secret_key(&self) -> Result<ZeroizingSchnorrSk, Error> {
+        let secret_key = self.secret_key.as_ref().ok_or("Missing secret key")?;
+        let bytes = Zeroizing::new(hex::decode(secret_key.trim())?);
+        deserialize_secret(&bytes)
+    }
+}
+
+impl Drop for SchnorrKeyFile {
+    fn drop(&mut self) {
+        if let Some(secret_key) = self.secret_key.as_mut() {
+            // Zero bytes are valid UTF-8
+            unsafe { secret_key.as_bytes_mut() }.zeroize();
+        }
+    }
+}
+
+/// Certificate fields entering the message signed by the certificate signers.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CertificateData {
+    #[serde(with = "FieldElementJson")]
+    pub sc_id: FieldElement,
+    pub epoch_number: u32,
+    #[serde(with = "FieldElementJson")]
+    pub end_cumulative_sc_tx_commitment_tree_root: FieldElement,
+    pub btr_fee: u64,
+    pub ft_min_amount: u64,
+    #[serde(default, with = "VecJson::<BackwardTransferJson>")]
+    pub bt_list: Vec<BackwardTransfer>,
+}
+
+// Signatures of the signers, in the same order as their keys, as a JSON array of signatures
+// or nulls for the missing ones
+pub fn read_signatures(path: &Path) -> Result<Vec<Option<SchnorrSig>>, Error> {
+    read_json::<Vec<Option<JsonValue>>>(path)?
+        .iter()
+        .map(|sig| {
+            sig.as_ref()
+                .map(SchnorrSignatureJson::from_json_value)
+                .transpose()
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofFile {
+    pub proof: String,
+    pub quality: u64,
+}
+
+impl ProofFile {
+    pub fn proof_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(hex::decode(self.proof.trim())?)
+    }
+}
+
+pub fn file_size(path: &Path) -> Result<u64, Error> {
+    Ok(fs::metadata(path)?.len())
+}
+
+// First `len` bytes of the file at `path`, or all of them if the file is shorter.
+pub fn read_file_prefix(path: &Path, len: u64) -> Result<Vec<u8>, Error> {
+    let mut prefix = Vec::new();
+    File::open(path)?.take(len).read_to_end(&mut prefix)?;
+    Ok(prefix)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use zendoo_sc_lib::{
+        get_random_field_element, schnorr_generate_key, schnorr_sign, serialize_secret,
+    };
+
+    #[test]
+    fn key_file() {
+        let fe = get_random_field_element(1);
+        let fe_hex = hex::encode(serialize_to_buffer(&fe, None).unwrap());
+        assert_eq!(field_element_from_hex(&fe_hex).unwrap(), fe);
+        assert!(field_element_from_hex(&fe_hex[2..]).is_err());
+        assert!(field_element_from_hex("not hex").is_err());
+
+        let (pk, sk) = schnorr_generate_key();
+        let key_file = SchnorrKeyFile {
+            public_key: pk,
+            secret_key: Some(hex::encode(serialize_secret(&*sk).unwrap().as_slice())),
+        };
+        assert_eq!(key_file.secret_key().unwrap(), sk);
+        let value = serde_json::to_value(&key_file).unwrap();
+        assert_eq!(value["public_key"]["type"], "SchnorrPublicKey");
+
+        // A public key file has no secret key
+        let pk_file: SchnorrKeyFile = serde_json::from_value(serde_json::json!({
+            "public_key": SchnorrPublicKeyJson::to_json_value(&pk).unwrap()
+        }))
+        .unwrap();
+        assert_eq!(pk_file.public_key, pk);
+        assert!(pk_file.secret_key().is_err());
+        assert!(!serde_json::to_string(&pk_file)
+            .unwrap()
+            .contains("secret_key"));
+
+        // Not the JSON representation of a public key
+        assert!(serde_json::from_value::<SchnorrKeyFile>(serde_json::json!({
+            "public_key": value["public_key"]["publicKey"]
+        }))
+        .is_err());
+
+        for ps in [ProvingSystem::Darlin, ProvingSystem::CoboundaryMarlin].iter() {
+            assert!(proving_system_from_str(proving_system_to_str(ps)).unwrap() == *ps);
+        }
+        assert!(proving_system_from_str("undefined").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn secret_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join("cli_secret_file_permissions.json");
+        let _ = fs::remove_file(&path);
+
+        // Overwriting an existing world readable file restricts its permissions
+        write_json(&"public", Some(&path), false).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        write_json(&"secret", Some(&path), true).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(read_json::<String>(&path).unwrap(), "secret");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn certificate_data() {
+        let sc_id = FieldElementJson::to_json(&get_random_field_element(1)).unwrap();
+        let root = FieldElementJson::to_json(&get_random_field_element(2)).unwrap();
+        let bt = BackwardTransferJson::to_json(&BackwardTransfer {
+            pk_dest: [1u8; MC_PK_SIZE],
+            amount: 5,
+        })
+        .unwrap();
+        let json = format!(
+            r#"{{
+                "sc_id": {},
+                "epoch_number": 10,
+                "end_cumulative_sc_tx_commitment_tree_root": {},
+                "btr_fee": 100,
+                "ft_min_amount": 200,
+                "bt_list": [{}]
+            }}"#,
+            sc_id, root, bt
+        );
+        let cert: CertificateData = serde_json::from_str(&json).unwrap();
+        assert_eq!(cert.sc_id, get_random_field_element(1));
+        assert_eq!(
+            cert.end_cumulative_sc_tx_commitment_tree_root,
+            get_random_field_element(2)
+        );
+        assert_eq!(cert.bt_list.len(), 1);
+        assert_eq!(cert.bt_list[0].pk_dest, [1u8; MC_PK_SIZE]);
+        assert_eq!(cert.bt_list[0].amount, 5);
+
+        // Wrong pk_dest length
+        let wrong_bt = bt.replace(&"01".repeat(MC_PK_SIZE), &"01".repeat(MC_PK_SIZE - 1));
+        assert!(serde_json::from_str::<CertificateData>(&json.replace(&bt, &wrong_bt)).is_err());
+
+        // The bt list can be omitted
+        let cert: CertificateData = serde_json::from_str(&format!(
+            r#"{{"sc_id": {}, "epoch_number": 10,
+                "end_cumulative_sc_tx_commitment_tree_root": {},
+                "btr_fee": 100, "ft_min_amount": 200}}"#,
+            sc_id, root
+        ))
+        .unwrap();
+        assert!(cert.bt_list.is_empty());
+    }
+
+    #[test]
+    fn signatures_file() {
+        let (pk, sk) = schnorr_generate_key();
+        let sig = schnorr_sign(&get_random_field_element(1), &sk, &pk).unwrap();
+        let path = std::env::temp_dir().join("cli_signatures_file.json");
+
+        let sig_json = SchnorrSignatureJson::to_json_value(&sig).unwrap();
+        write_json(&serde_json::json!([sig_json, null]), Some(&path), false).unwrap();
+        let sigs = read_signatures(&path).unwrap();
+        assert_eq!(sigs, vec![Some(sig), None]);
+
+        // Hex encoded signatures are not accepted
+        write_json(
+            &serde_json::json!([sig_json["signature"]]),
+            Some(&path),
+            false,
+        )
+        .unwrap();
+        assert!(read_signatures(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
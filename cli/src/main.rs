@@ -0,0 +1,560 @@
+#![allow(clippy::upper_case_acronyms, clippy::try_err)]
+
+use cctp_primitives::{
+    proving_system::{init_dlog_keys, ProvingSystem},
+    utils::serialization::*,
+};
+use demo_circuit::{generate_circuit_keypair, get_instance_for_setup, type_mapping::*};
+use serde_json::json;
+use structopt::StructOpt;
+use zendoo_sc_lib::*;
+
+use std::path::{Path, PathBuf};
+use std::process;
+
+mod formats;
+use formats::*;
+
+//*****************************Command line interface***********************************************
+//
+// Setup, proving and verification of the naive threshold signature circuit without writing
+// any Java code. Inputs and outputs are JSON (see formats.rs); results are written to stdout
+// unless an output file is given.
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "zendoo-sc-cli",
+    about = "Setup, proving and verification of the naive threshold signature circuit"
+)]
+enum Command {
+    /// Generate the DLOG keys and write their parameters, needed by the other commands
+    GenerateDlogKeys {
+        /// "darlin" or "coboundary_marlin"
+        #[structopt(long, default_value = "coboundary_marlin")]
+        proving_system: String,
+        #[structopt(long, default_value = "131072")]
+        max_segment_size: usize,
+        #[structopt(long, default_value = "16384")]
+        supported_segment_size: usize,
+        #[structopt(long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// Generate the proving and verification keys of the circuit supporting `max_pks` keys
+    Setup {
+        #[structopt(long, parse(from_os_str))]
+        dlog: PathBuf,
+        #[structopt(long)]
+        max_pks: usize,
+        #[structopt(long, parse(from_os_str))]
+        proving_key: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        verification_key: PathBuf,
+        #[structopt(long)]
+        zk: bool,
+        #[structopt(long, default_value = "7000")]
+        max_proof_size: usize,
+        #[structopt(long, default_value = "4000")]
+        max_vk_size: usize,
+    },
+    /// Generate a Schnorr key pair
+    SchnorrKeygen {
+        /// Created readable by the owner only
+        #[structopt(long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// Compute the constant of the circuit, H(H(pks), threshold)
+    ComputeConstant {
+        /// Key files, in the order of the signers
+        #[structopt(long, parse(from_os_str), required = true)]
+        pks: Vec<PathBuf>,
+        #[structopt(long)]
+        threshold: u64,
+    },
+    /// Compute the message to sign for a certificate
+    ComputeMsgToSign {
+        #[structopt(long, parse(from_os_str))]
+        cert: PathBuf,
+    },
+    /// Sign a certificate, or a hex encoded field element such as the "value" written by
+    /// `compute-msg-to-sign`
+    Sign {
+        #[structopt(long, parse(from_os_str))]
+        key: PathBuf,
+        #[structopt(long, parse(from_os_str), required_unless = "message")]
+        cert: Option<PathBuf>,
+        #[structopt(long, conflicts_with = "cert")]
+        message: Option<String>,
+    },
+    /// Create a proof for a certificate
+    CreateProof {
+        #[structopt(long, parse(from_os_str))]
+        dlog: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        proving_key: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        cert: PathBuf,
+        /// Key files, in the order of the signers
+        #[structopt(long, parse(from_os_str), required = true)]
+        pks: Vec<PathBuf>,
+        /// JSON array with the signature of each signer, as written by `sign`, or null if
+        /// missing
+        #[structopt(long, parse(from_os_str))]
+        signatures: PathBuf,
+        #[structopt(long)]
+        threshold: u64,
+        #[structopt(long)]
+        zk: bool,
+        #[structopt(long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// Verify the proof of a certificate. Exits with status 1 if the proof is not valid.
+    VerifyProof {
+        #[structopt(long, parse(from_os_str))]
+        dlog: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        verification_key: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        cert: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        proof: PathBuf,
+        /// Hex encoded constant of the circuit, the "value" written by `compute-constant`
+        #[structopt(long)]
+        constant: String,
+    },
+    /// Print proving system and size of a proving key, verification key or proof file
+    Inspect {
+        /// "proving_key", "verification_key" or "proof"
+        #[structopt(long)]
+        kind: String,
+        #[structopt(parse(from_os_str))]
+        path: PathBuf,
+    },
+    /// Wrap a proving or verification key file, in place, in an envelope with type, version
+    /// and checksum
+    Seal {
+        /// "proving_key" or "verification_key"
+        #[structopt(long)]
+        kind: String,
+        #[structopt(parse(from_os_str))]
+        path: PathBuf,
+    },
+}
+
+fn init_dlog_keys_from_file(path: &Path) -> Result<ProvingSystem, Error> {
+    let params: DlogParams = read_json(path)?;
+    let proving_system = proving_system_from_str(&params.proving_system)?;
+    init_dlog_keys(
+        proving_system.clone(),
+        params.max_segment_size,
+        params.supported_segment_size,
+    )?;
+    Ok(proving_system)
+}
+
+fn read_pks(paths: &[PathBuf]) -> Result<Vec<SchnorrPk>, Error> {
+    paths
+        .iter()
+        .map(|path| Ok(read_json::<SchnorrKeyFile>(path)?.public_key))
+        .collect()
+}
+
+fn compute_cert_msg(cert: &CertificateData) -> Result<FieldElement, Error> {
+    let (_, msg) = compute_msg_to_sign(
+        &cert.sc_id,
+        cert.epoch_number,
+        &cert.end_cumulative_sc_tx_commitment_tree_root,
+        cert.btr_fee,
+        cert.ft_min_amount,
+        cert.bt_list.clone(),
+    )?;
+    Ok(msg)
+}
+
+fn generate_dlog_keys(
+    proving_system: String,
+    max_segment_size: usize,
+    supported_segment_size: usize,
+) -> Result<DlogParams, Error> {
+    init_dlog_keys(
+        proving_system_from_str(&proving_system)?,
+        max_segment_size,
+        supported_segment_size,
+    )?;
+    Ok(DlogParams {
+        proving_system,
+        max_segment_size,
+        supported_segment_size,
+    })
+}
+
+fn schnorr_keygen() -> Result<SchnorrKeyFile, Error> {
+    let (pk, sk) = schnorr_generate_key();
+    Ok(SchnorrKeyFile {
+        public_key: pk,
+        secret_key: Some(hex::encode(serialize_secret(&*sk)?.as_slice())),
+    })
+}
+
+fn sign(
+    key: &SchnorrKeyFile,
+    cert: Option<&CertificateData>,
+    message: Option<&str>,
+) -> Result<SchnorrSig, Error> {
+    let msg = match (cert, message) {
+        (Some(cert), None) => compute_cert_msg(cert)?,
+        (None, Some(message)) => field_element_from_hex(message)?,
+        _ => Err("Exactly one of certificate and message must be given")?,
+    };
+    schnorr_sign(&msg, &key.secret_key()?, &key.public_key)
+}
+
+fn create_proof(
+    proving_key: &Path,
+    cert: &CertificateData,
+    pks: &[SchnorrPk],
+    sigs: &[Option<SchnorrSig>],
+    threshold: u64,
+    zk: bool,
+) -> Result<ProofFile, Error> {
+    if sigs.len() != pks.len() {
+        Err(format!(
+            "Expected {} signatures (or nulls), one for each public key, found {}",
+            pks.len(),
+            sigs.len()
+        ))?
+    }
+
+    // Check the signatures here, to give a meaningful error if they are not enough
+    let msg = compute_cert_msg(cert)?;
+    let mut valid_sigs = 0;
+    for (pk, sig) in pks.iter().zip(sigs.iter()) {
+        if let Some(sig) = sig {
+            if schnorr_verify_signature(&msg, pk, sig)? {
+                valid_sigs += 1;
+            }
+        }
+    }
+    if valid_sigs < threshold {
+        Err(format!(
+            "Only {} valid signatures, {} are needed",
+            valid_sigs, threshold
+        ))?
+    }
+
+    let (proof, quality) = create_naive_threshold_sig_proof(
+        pks,
+        sigs.to_vec(),
+        &cert.sc_id,
+        cert.epoch_number,
+        &cert.end_cumulative_sc_tx_commitment_tree_root,
+        cert.btr_fee,
+        cert.ft_min_amount,
+        cert.bt_list.clone(),
+        threshold,
+        proving_key,
+        false,
+        zk,
+        true,
+        true,
+    )?;
+
+    Ok(ProofFile {
+        proof: hex::encode(proof),
+        quality,
+    })
+}
+
+fn verify_proof(
+    verification_key: &Path,
+    cert: &CertificateData,
+    proof: &ProofFile,
+    constant: &str,
+) -> Result<bool, Error> {
+    verify_naive_threshold_sig_proof(
+        &field_element_from_hex(constant)?,
+        &cert.sc_id,
+        cert.epoch_number,
+        &cert.end_cumulative_sc_tx_commitment_tree_root,
+        cert.btr_fee,
+        cert.ft_min_amount,
+        cert.bt_list.clone(),
+        proof.quality,
+        proof.proof_bytes()?,
+        true,
+        true,
+        verification_key,
+        true,
+        true,
+    )
+}
+
+fn key_artifact_type(kind: &str) -> Result<ArtifactType, Error> {
+    match kind {
+        "proving_key" => Ok(ArtifactType::ProverKey),
+        "verification_key" => Ok(ArtifactType::VerifierKey),
+        _ => Err(format!(
+            "Unknown key kind '{}', expected 'proving_key' or 'verification_key'",
+            kind
+        )
+        .into()),
+    }
+}
+
+fn inspect(kind: &str, path: &Path) -> Result<serde_json::Value, Error> {
+    let (proving_system, size, sealed) = match kind {
+        "proving_key" | "verification_key" => (
+            read_artifact_proving_system(path, key_artifact_type(kind)?)?,
+            file_size(path)?,
+            is_sealed_artifact(&read_file_prefix(path, ENVELOPE_PREFIX_SIZE as u64)?),
+        ),
+        "proof" => {
+            let proof = read_json::<ProofFile>(path)?.proof_bytes()?;
+            let payload = open_artifact(ArtifactType::Proof, &proof)?;
+            if payload.is_empty() {
+                Err("Empty proof")?
+            }
+            (
+                deserialize_from_buffer::<ProvingSystem>(&payload[..1], None, None)?,
+                proof.len() as u64,
+                is_sealed_artifact(&proof),
+            )
+        }
+        _ => Err(format!(
+            "Unknown kind '{}', expected 'proving_key', 'verification_key' or 'proof'",
+            kind
+        ))?,
+    };
+    Ok(json!({
+        "kind": kind,
+        "proving_system": proving_system_to_str(&proving_system),
+        "size": size,
+        "sealed": sealed,
+    }))
+}
+
+fn run(command: Command) -> Result<bool, Error> {
+    match command {
+        Command::GenerateDlogKeys {
+            proving_system,
+            max_segment_size,
+            supported_segment_size,
+            out,
+        } => {
+            let params =
+                generate_dlog_keys(proving_system, max_segment_size, supported_segment_size)?;
+            write_json(&params, out.as_deref(), false)?;
+        }
+        Command::Setup {
+            dlog,
+            max_pks,
+            proving_key,
+            verification_key,
+            zk,
+            max_proof_size,
+            max_vk_size,
+        } => {
+            let proving_system = init_dlog_keys_from_file(&dlog)?;
+            generate_circuit_keypair(
+                get_instance_for_setup(max_pks),
+                proving_system,
+                &proving_key,
+                &verification_key,
+                max_proof_size,
+                max_vk_size,
+                zk,
+                Some(true),
+                Some(true),
+            )?;
+        }
+        Command::SchnorrKeygen { out } => {
+            write_json(&schnorr_keygen()?, out.as_deref(), true)?;
+        }
+        Command::ComputeConstant { pks, threshold } => {
+            let constant = compute_pks_threshold_hash(&read_pks(&pks)?, threshold)?;
+            write_json(&FieldElementJson::to_json_value(&constant)?, None, false)?;
+        }
+        Command::ComputeMsgToSign { cert } => {
+            let msg = compute_cert_msg(&read_json(&cert)?)?;
+            write_json(&FieldElementJson::to_json_value(&msg)?, None, false)?;
+        }
+        Command::Sign { key, cert, message } => {
+            let cert = cert.map(|cert| read_json(&cert)).transpose()?;
+            let sig = sign(&read_json(&key)?, cert.as_ref(), message.as_deref())?;
+            write_json(&SchnorrSignatureJson::to_json_value(&sig)?, None, false)?;
+        }
+        Command::CreateProof {
+            dlog,
+            proving_key,
+            cert,
+            pks,
+            signatures,
+            threshold,
+            zk,
+            out,
+        } => {
+            init_dlog_keys_from_file(&dlog)?;
+            let proof = create_proof(
+                &proving_key,
+                &read_json(&cert)?,
+                &read_pks(&pks)?,
+                &read_signatures(&signatures)?,
+                threshold,
+                zk,
+            )?;
+            write_json(&proof, out.as_deref(), false)?;
+        }
+        Command::VerifyProof {
+            dlog,
+            verification_key,
+            cert,
+            proof,
+            constant,
+        } => {
+            init_dlog_keys_from_file(&dlog)?;
+            let verified = verify_proof(
+                &verification_key,
+                &read_json(&cert)?,
+                &read_json(&proof)?,
+                &constant,
+            )?;
+            write_json(&json!({ "verified": verified }), None, false)?;
+            return Ok(verified);
+        }
+        Command::Inspect { kind, path } => {
+            write_json(&inspect(&kind, &path)?, None, false)?;
+        }
+        Command::Seal { kind, path } => {
+            seal_artifact_file(&path, key_artifact_type(&kind)?)?;
+        }
+    }
+    Ok(true)
+}
+
+fn main() {
+    match run(Command::from_args()) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cctp_primitives::utils::data_structures::BackwardTransfer;
+
+    fn to_hex(fe: &FieldElement) -> String {
+        hex::encode(serialize_to_buffer(fe, None).unwrap())
+    }
+
+    #[test]
+    fn naive_threshold_sig_workflow() {
+        let tmp_dir = std::env::temp_dir();
+        let pk_path = tmp_dir.join("cli_sample_pk");
+        let vk_path = tmp_dir.join("cli_sample_vk");
+        let threshold = 2;
+
+        let params = generate_dlog_keys("coboundary_marlin".to_owned(), 1 << 17, 1 << 14).unwrap();
+        assert!(generate_dlog_keys("undefined".to_owned(), 1 << 17, 1 << 14).is_err());
+        generate_circuit_keypair(
+            get_instance_for_setup(3),
+            proving_system_from_str(&params.proving_system).unwrap(),
+            &pk_path,
+            &vk_path,
+            7000,
+            4000,
+            false,
+            Some(true),
+            Some(true),
+        )
+        .unwrap();
+
+        let cert = CertificateData {
+            sc_id: get_random_field_element(1),
+            epoch_number: 10,
+            end_cumulative_sc_tx_commitment_tree_root: get_random_field_element(2),
+            btr_fee: 100,
+            ft_min_amount: 200,
+            bt_list: vec![BackwardTransfer {
+                pk_dest: [1u8; MC_PK_SIZE],
+                amount: 10,
+            }],
+        };
+
+        let keys = (0..3)
+            .map(|_| schnorr_keygen().unwrap())
+            .collect::<Vec<_>>();
+        let pks = keys.iter().map(|key| key.public_key).collect::<Vec<_>>();
+
+        // Signing the certificate or its message is the same
+        let msg = to_hex(&compute_cert_msg(&cert).unwrap());
+        let sig = sign(&keys[0], Some(&cert), None).unwrap();
+        assert!(
+            schnorr_verify_signature(&field_element_from_hex(&msg).unwrap(), &pks[0], &sig)
+                .unwrap()
+        );
+        assert!(sign(&keys[0], None, Some(&msg)).is_ok());
+        assert!(sign(&keys[0], None, None).is_err());
+
+        let mut signatures = keys
+            .iter()
+            .map(|key| Some(sign(key, Some(&cert), None).unwrap()))
+            .collect::<Vec<_>>();
+        signatures[2] = None;
+
+        // Not enough signatures
+        assert!(create_proof(&pk_path, &cert, &pks, &signatures[..1], threshold, false).is_err());
+        let mut few_signatures = signatures.clone();
+        few_signatures[1] = None;
+        assert!(create_proof(&pk_path, &cert, &pks, &few_signatures, threshold, false).is_err());
+
+        let proof = create_proof(&pk_path, &cert, &pks, &signatures, threshold, false).unwrap();
+        assert_eq!(proof.quality, 2);
+
+        let constant = to_hex(&compute_pks_threshold_hash(&pks, threshold).unwrap());
+        assert!(verify_proof(&vk_path, &cert, &proof, &constant).unwrap());
+
+        let wrong_proof = ProofFile {
+            quality: 1,
+            ..proof.clone()
+        };
+        assert!(!verify_proof(&vk_path, &cert, &wrong_proof, &constant).unwrap());
+
+        // Inspect
+        let proof_path = tmp_dir.join("cli_sample_proof.json");
+        write_json(&proof, Some(&proof_path), false).unwrap();
+        let info = inspect("proof", &proof_path).unwrap();
+        assert_eq!(info["proving_system"], "coboundary_marlin");
+        assert_eq!(info["size"], proof.proof_bytes().unwrap().len() as u64);
+        assert_eq!(info["sealed"], false);
+        let info = inspect("verification_key", &vk_path).unwrap();
+        assert_eq!(info["proving_system"], "coboundary_marlin");
+        assert_eq!(info["sealed"], false);
+        assert!(inspect("certificate", &vk_path).is_err());
+
+        // Sealed verification key and proof
+        run(Command::Seal {
+            kind: "verification_key".to_owned(),
+            path: vk_path.clone(),
+        })
+        .unwrap();
+        let info = inspect("verification_key", &vk_path).unwrap();
+        assert_eq!(info["proving_system"], "coboundary_marlin");
+        assert_eq!(info["sealed"], true);
+        assert!(seal_artifact_file(&vk_path, ArtifactType::VerifierKey).is_err());
+
+        let sealed_proof = ProofFile {
+            proof: hex::encode(seal_artifact(
+                ArtifactType::Proof,
+                &proof.proof_bytes().unwrap(),
+            )),
+            ..proof.clone()
+        };
+        assert!(verify_proof(&vk_path, &cert, &sealed_proof, &constant).unwrap());
+        write_json(&sealed_proof, Some(&proof_path), false).unwrap();
+        assert_eq!(inspect("proof", &proof_path).unwrap()["sealed"], true);
+    }
+}
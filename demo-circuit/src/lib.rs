@@ -50,6 +50,12 @@ pub use self::naive_threshold_sig::*;
 pub mod constants;
 pub use self::constants::*;
 
+pub mod merkle_path;
+pub use self::merkle_path::*;
+
+pub mod poseidon_primitives;
+pub use self::poseidon_primitives::*;
+
 pub mod type_mapping;
 pub use self::type_mapping::*;
 
@@ -63,6 +69,53 @@ use cctp_primitives::{
 use r1cs_core::ConstraintSynthesizer;
 use std::path::Path;
 
+/// Size figures of a circuit, as they would result from a setup with a given
+/// proving system and segment size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitSizeInfo {
+    pub num_constraints: usize,
+    pub proof_size: usize,
+    pub vk_size: usize,
+}
+
+impl CircuitSizeInfo {
+    /// Return true if proof and vk sizes don't exceed `max_proof_size` and `max_vk_size`.
+    pub fn fits(&self, max_proof_size: usize, max_vk_size: usize) -> bool {
+        self.proof_size <= max_proof_size && self.vk_size <= max_vk_size
+    }
+}
+
+/// Utility function: compute the number of constraints of circuit `circ` and the
+/// sizes of the proof and verification key that a setup with `proving_system`,
+/// on a committer key supporting `segment_size`, would produce. No DLOG keys are
+/// required to be loaded.
+pub fn get_circuit_size_info<C: ConstraintSynthesizer<FieldElement>>(
+    circ: C,
+    proving_system: ProvingSystem,
+    segment_size: usize,
+    zk: bool,
+) -> Result<CircuitSizeInfo, Error> {
+    match proving_system {
+        ProvingSystem::Undefined => Err(ProvingSystemError::UndefinedProvingSystem)?,
+        ProvingSystem::CoboundaryMarlin => {
+            let index = CoboundaryMarlin::get_index_info(circ)?;
+            let num_constraints = index.index_info.num_constraints;
+            let (proof_size, vk_size) = compute_proof_vk_size(
+                segment_size.next_power_of_two(),
+                index.index_info,
+                zk,
+                proving_system,
+            );
+            Ok(CircuitSizeInfo {
+                num_constraints,
+                proof_size,
+                vk_size,
+            })
+        }
+        ProvingSystem::Darlin => Err("Circuit size estimation is not supported for Darlin")?,
+    }
+}
+
 /// Utility function: generate and save to specified paths the SNARK proving and
 /// verification key associated to circuit `circ`. Check that their sizes are
 /// compatible with `max_proof_size` and `max_vk_size`.
@@ -0,0 +1,228 @@
+//! Gadget to verify, inside a circuit, Poseidon Merkle paths of fixed height of the trees
+//! built by `InMemoryOptimizedMerkleTree` (i.e. `GingerMHT`).
+//! It wraps the field based binary Merkle path gadget of r1cs_crypto, instantiated with the
+//! same parameters of `GingerMHT`, so that the hashing is the one of the native tree.
+
+use algebra::Field;
+use cctp_primitives::utils::mht::{GingerMHTParams, GingerMHTPath};
+use primitives::merkle_tree::field_based_mht::FieldBasedMHTPath;
+use r1cs_crypto::{
+    crh::TweedleFrPoseidonHashGadget as PoseidonHashGadget,
+    merkle_tree::field_based_mht::{
+        FieldBasedBinaryMerkleTreePathGadget, FieldBasedMerkleTreePathGadget,
+    },
+};
+use r1cs_std::{
+    alloc::AllocGadget,
+    bits::{boolean::Boolean, FromBitsGadget},
+    eq::EqGadget,
+    fields::fp::FpGadget,
+};
+
+use r1cs_core::{ConstraintSystem, SynthesisError};
+
+use crate::type_mapping::*;
+
+/// The r1cs_crypto gadget for the paths of `GingerMHT`.
+pub type GingerMHTPathGadget =
+    FieldBasedBinaryMerkleTreePathGadget<GingerMHTParams, PoseidonHashGadget, FieldElement>;
+
+/// A Merkle path of fixed height allocated in the circuit.
+#[derive(Clone)]
+pub struct MerklePathGadget {
+    path: GingerMHTPathGadget,
+}
+
+impl MerklePathGadget {
+    /// Allocate as witness a Merkle path for a tree of height `height`.
+    /// `path` can be None when generating the constraints for the setup.
+    pub fn alloc<CS: ConstraintSystem<FieldElement>>(
+        mut cs: CS,
+        path: Option<&GingerMHTPath>,
+        height: usize,
+    ) -> Result<Self, SynthesisError> {
+        let path = match path {
+            Some(path) => {
+                if path.get_length() != height {
+                    return Err(SynthesisError::Other(format!(
+                        "Invalid Merkle path length. Expected: {}, found: {}",
+                        height,
+                        path.get_length()
+                    )));
+                }
+                path.clone()
+            }
+            // The values are not used during the setup, only the height matters
+            None => GingerMHTPath::new(vec![(FieldElement::zero(), false); height]),
+        };
+
+        let path_g = GingerMHTPathGadget::alloc(cs.ns(|| "alloc path"), || Ok(path))?;
+        Ok(Self { path: path_g })
+    }
+
+    pub fn height(&self) -> usize {
+        self.path.length()
+    }
+
+    /// Enforce and return the root of the tree, given the `leaf` this path refers to.
+    pub fn enforce_root_from_leaf<CS: ConstraintSystem<FieldElement>>(
+        &self,
+        cs: CS,
+        leaf: &FpGadget<FieldElement>,
+    ) -> Result<FpGadget<FieldElement>, SynthesisError> {
+        self.path.enforce_root_from_leaf(cs, leaf)
+    }
+
+    /// Enforce that `leaf` belongs to the tree with root `root`.
+    pub fn enforce_membership<CS: ConstraintSystem<FieldElement>>(
+        &self,
+        cs: CS,
+        leaf: &FpGadget<FieldElement>,
+        root: &FpGadget<FieldElement>,
+    ) -> Result<(), SynthesisError> {
+        self.conditionally_enforce_membership(cs, leaf, root, &Boolean::constant(true))
+    }
+
+    /// Enforce that `leaf` belongs to the tree with root `root` only if `should_enforce` is true.
+    pub fn conditionally_enforce_membership<CS: ConstraintSystem<FieldElement>>(
+        &self,
+        mut cs: CS,
+        leaf: &FpGadget<FieldElement>,
+        root: &FpGadget<FieldElement>,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let computed_root = self.enforce_root_from_leaf(cs.ns(|| "compute root"), leaf)?;
+        computed_root.conditional_enforce_equal(
+            cs.ns(|| "computed root == root"),
+            root,
+            should_enforce,
+        )
+    }
+
+    /// Enforce that this path refers to the leaf at index `leaf_index`.
+    pub fn enforce_leaf_index<CS: ConstraintSystem<FieldElement>>(
+        &self,
+        mut cs: CS,
+        leaf_index: &FpGadget<FieldElement>,
+    ) -> Result<(), SynthesisError> {
+        // from_bits() wants the most significant bit first
+        let mut bits = self
+            .path
+            .enforce_leaf_index_bits(cs.ns(|| "get leaf index bits"))?;
+        bits.reverse();
+        let index_g = FpGadget::<FieldElement>::from_bits(cs.ns(|| "pack index bits"), &bits)?;
+        index_g.enforce_equal(cs.ns(|| "leaf index == expected"), leaf_index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use cctp_primitives::utils::mht::*;
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+    use rand::{rngs::OsRng, Rng};
+
+    type FrGadget = FpGadget<FieldElement>;
+
+    const HEIGHT: usize = 5;
+
+    fn build_tree(num_leaves: usize) -> (GingerMHT, Vec<FieldElement>) {
+        let mut rng = OsRng::default();
+        let mut tree = new_ginger_mht(HEIGHT, 1 << HEIGHT).unwrap();
+        let leaves = (0..num_leaves)
+            .map(|_| FieldElement::rand(&mut rng))
+            .collect::<Vec<_>>();
+        for leaf in leaves.iter() {
+            append_leaf_to_ginger_mht(&mut tree, leaf).unwrap();
+        }
+        finalize_ginger_mht_in_place(&mut tree).unwrap();
+        (tree, leaves)
+    }
+
+    fn check_path(
+        path: &GingerMHTPath,
+        leaf: FieldElement,
+        root: FieldElement,
+        leaf_index: u64,
+    ) -> bool {
+        let mut cs = TestConstraintSystem::<FieldElement>::new();
+
+        let path_g = MerklePathGadget::alloc(cs.ns(|| "alloc path"), Some(path), HEIGHT).unwrap();
+        let leaf_g = FrGadget::alloc(cs.ns(|| "alloc leaf"), || Ok(leaf)).unwrap();
+        let root_g = FrGadget::alloc_input(cs.ns(|| "alloc root"), || Ok(root)).unwrap();
+        let index_g = FrGadget::alloc(cs.ns(|| "alloc index"), || {
+            Ok(FieldElement::from(leaf_index))
+        })
+        .unwrap();
+
+        path_g
+            .enforce_membership(cs.ns(|| "enforce membership"), &leaf_g, &root_g)
+            .unwrap();
+        path_g
+            .enforce_leaf_index(cs.ns(|| "enforce leaf index"), &index_g)
+            .unwrap();
+
+        if !cs.is_satisfied() {
+            println!("{:?}", cs.which_is_unsatisfied());
+        }
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn merkle_path_gadget() {
+        let mut rng = OsRng::default();
+        let (tree, leaves) = build_tree(20);
+        let root = get_ginger_mht_root(&tree).unwrap();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = get_ginger_mht_path(&tree, i as u64).unwrap();
+            assert!(check_path(&path, *leaf, root, i as u64));
+        }
+
+        // Empty leaves are zero
+        let path = get_ginger_mht_path(&tree, 31).unwrap();
+        assert!(check_path(&path, FieldElement::zero(), root, 31));
+
+        // Negative cases
+        let i: usize = rng.gen_range(0..leaves.len());
+        let path = get_ginger_mht_path(&tree, i as u64).unwrap();
+        assert!(!check_path(&path, rng.gen(), root, i as u64));
+        assert!(!check_path(&path, leaves[i], rng.gen(), i as u64));
+        assert!(!check_path(&path, leaves[i], root, (i as u64) ^ 1));
+
+        // Path of the wrong height
+        let mut cs = TestConstraintSystem::<FieldElement>::new();
+        assert!(MerklePathGadget::alloc(cs.ns(|| "alloc path"), Some(&path), HEIGHT + 1).is_err());
+    }
+
+    #[test]
+    fn merkle_path_gadget_conditional() {
+        let (tree, leaves) = build_tree(4);
+        let root = get_ginger_mht_root(&tree).unwrap();
+        let path = get_ginger_mht_path(&tree, 0).unwrap();
+
+        for &should_enforce in [true, false].iter() {
+            let mut cs = TestConstraintSystem::<FieldElement>::new();
+
+            let path_g =
+                MerklePathGadget::alloc(cs.ns(|| "alloc path"), Some(&path), HEIGHT).unwrap();
+            // Wrong leaf
+            let leaf_g = FrGadget::alloc(cs.ns(|| "alloc leaf"), || Ok(leaves[1])).unwrap();
+            let root_g = FrGadget::alloc_input(cs.ns(|| "alloc root"), || Ok(root)).unwrap();
+            let should_enforce_g =
+                Boolean::alloc(cs.ns(|| "alloc should enforce"), || Ok(should_enforce)).unwrap();
+
+            path_g
+                .conditionally_enforce_membership(
+                    cs.ns(|| "enforce membership"),
+                    &leaf_g,
+                    &root_g,
+                    &should_enforce_g,
+                )
+                .unwrap();
+
+            assert_eq!(cs.is_satisfied(), !should_enforce);
+        }
+    }
+}
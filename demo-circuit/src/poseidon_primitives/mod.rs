@@ -0,0 +1,256 @@
+//! Gadgets for the Poseidon based commitment, PRF and KDF exposed by the api crate.
+//! Each primitive is a constant length Poseidon hash personalized with its own domain tag
+//! (see `poseidon_domain_hash`), whose inputs are:
+//! - commitment: the randomness, then the message;
+//! - PRF: the key, then the input;
+//! - KDF: the key, the domain of the derived key and its index.
+//!
+//! The personalization of `FieldHash` absorbs the personalization, padded with zeros to a
+//! multiple of the rate, before the inputs. The hash gadget doesn't take a personalization,
+//! so the gadgets below absorb the same constants in front of the inputs: the native and
+//! in-circuit computations are exactly the same hash.
+
+use algebra::Field;
+use primitives::crh::{FieldBasedHash, FieldBasedHashParameters};
+use r1cs_crypto::crh::{FieldBasedHashGadget, TweedleFrPoseidonHashGadget as PoseidonHashGadget};
+use r1cs_std::{
+    bits::boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpGadget, FieldGadget},
+};
+
+use r1cs_core::{ConstraintSystem, SynthesisError};
+
+use crate::type_mapping::*;
+
+pub const POSEIDON_COMMITMENT_DOMAIN: u64 = u64::from_le_bytes(*b"zen_comm");
+pub const POSEIDON_PRF_DOMAIN: u64 = u64::from_le_bytes(*b"zen_prf_");
+pub const POSEIDON_KDF_DOMAIN: u64 = u64::from_le_bytes(*b"zen_kdf_");
+
+const FIELD_HASH_RATE: usize =
+    <<FieldHash as FieldBasedHash>::Parameters as FieldBasedHashParameters>::R;
+
+/// Poseidon hash of `inputs`, personalized with the `domain` tag.
+pub fn poseidon_domain_hash(domain: u64, inputs: &[FieldElement]) -> Result<FieldElement, Error> {
+    let personalization = [FieldElement::from(domain)];
+    let mut h = FieldHash::init_constant_length(inputs.len(), Some(&personalization));
+    for input in inputs.iter() {
+        h.update(*input);
+    }
+    h.finalize()
+}
+
+/// Enforce and return the Poseidon hash of `inputs`, personalized with the constant `domain`
+/// tag, i.e. the in-circuit counterpart of `poseidon_domain_hash`.
+fn enforce_hash_with_domain<CS: ConstraintSystem<FieldElement>>(
+    mut cs: CS,
+    domain: u64,
+    inputs: &[FpGadget<FieldElement>],
+) -> Result<FpGadget<FieldElement>, SynthesisError> {
+    // Personalization: the domain tag, padded with zeros to a multiple of the rate
+    let mut personalization = vec![FieldElement::zero(); FIELD_HASH_RATE];
+    personalization[0] = FieldElement::from(domain);
+
+    let mut hash_inputs = Vec::with_capacity(FIELD_HASH_RATE + inputs.len());
+    for (i, constant) in personalization.iter().enumerate() {
+        hash_inputs.push(
+            FpGadget::<FieldElement>::zero(cs.ns(|| format!("zero {}", i)))?
+                .add_constant(cs.ns(|| format!("personalization {}", i)), constant)?,
+        );
+    }
+    hash_inputs.extend_from_slice(inputs);
+
+    PoseidonHashGadget::enforce_hash_constant_length(cs.ns(|| "hash inputs"), &hash_inputs)
+}
+
+/// Hiding commitment to a message made of one or more field elements.
+pub struct PoseidonCommitmentGadget;
+
+impl PoseidonCommitmentGadget {
+    /// Enforce and return the commitment to `message` with randomness `randomness`.
+    pub fn enforce_commitment<CS: ConstraintSystem<FieldElement>>(
+        cs: CS,
+        message: &[FpGadget<FieldElement>],
+        randomness: &FpGadget<FieldElement>,
+    ) -> Result<FpGadget<FieldElement>, SynthesisError> {
+        let mut inputs = Vec::with_capacity(message.len() + 1);
+        inputs.push(randomness.clone());
+        inputs.extend_from_slice(message);
+        enforce_hash_with_domain(cs, POSEIDON_COMMITMENT_DOMAIN, &inputs)
+    }
+
+    /// Enforce that (`message`, `randomness`) is an opening of `commitment`.
+    pub fn enforce_opening<CS: ConstraintSystem<FieldElement>>(
+        cs: CS,
+        commitment: &FpGadget<FieldElement>,
+        message: &[FpGadget<FieldElement>],
+        randomness: &FpGadget<FieldElement>,
+    ) -> Result<(), SynthesisError> {
+        Self::conditionally_enforce_opening(
+            cs,
+            commitment,
+            message,
+            randomness,
+            &Boolean::constant(true),
+        )
+    }
+
+    /// Enforce that (`message`, `randomness`) is an opening of `commitment` only if
+    /// `should_enforce` is true.
+    pub fn conditionally_enforce_opening<CS: ConstraintSystem<FieldElement>>(
+        mut cs: CS,
+        commitment: &FpGadget<FieldElement>,
+        message: &[FpGadget<FieldElement>],
+        randomness: &FpGadget<FieldElement>,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let computed_commitment =
+            Self::enforce_commitment(cs.ns(|| "compute commitment"), message, randomness)?;
+        computed_commitment.conditional_enforce_equal(
+            cs.ns(|| "computed commitment == commitment"),
+            commitment,
+            should_enforce,
+        )
+    }
+}
+
+/// Pseudo random function keyed by a field element.
+pub struct PoseidonPrfGadget;
+
+impl PoseidonPrfGadget {
+    /// Enforce and return the PRF of `input` under `key`.
+    pub fn enforce_prf<CS: ConstraintSystem<FieldElement>>(
+        cs: CS,
+        key: &FpGadget<FieldElement>,
+        input: &FpGadget<FieldElement>,
+    ) -> Result<FpGadget<FieldElement>, SynthesisError> {
+        enforce_hash_with_domain(cs, POSEIDON_PRF_DOMAIN, &[key.clone(), input.clone()])
+    }
+}
+
+/// Derivation of keys, separated by domain and index, from a master key.
+pub struct PoseidonKdfGadget;
+
+impl PoseidonKdfGadget {
+    /// Enforce and return the key of index `index` in the domain `domain` derived from `key`.
+    pub fn enforce_kdf<CS: ConstraintSystem<FieldElement>>(
+        cs: CS,
+        key: &FpGadget<FieldElement>,
+        domain: &FpGadget<FieldElement>,
+        index: &FpGadget<FieldElement>,
+    ) -> Result<FpGadget<FieldElement>, SynthesisError> {
+        enforce_hash_with_domain(
+            cs,
+            POSEIDON_KDF_DOMAIN,
+            &[key.clone(), domain.clone(), index.clone()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use r1cs_std::{alloc::AllocGadget, test_constraint_system::TestConstraintSystem};
+    use rand::rngs::OsRng;
+
+    type FrGadget = FpGadget<FieldElement>;
+
+    fn native_hash_with_domain(domain: u64, inputs: &[FieldElement]) -> FieldElement {
+        poseidon_domain_hash(domain, inputs).unwrap()
+    }
+
+    fn alloc_all<CS: ConstraintSystem<FieldElement>>(
+        mut cs: CS,
+        values: &[FieldElement],
+    ) -> Vec<FrGadget> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                FrGadget::alloc(cs.ns(|| format!("alloc value {}", i)), || Ok(*v)).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn poseidon_commitment_gadget() {
+        let mut rng = OsRng::default();
+        let message = (0..3)
+            .map(|_| FieldElement::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let randomness = FieldElement::rand(&mut rng);
+        let mut inputs = vec![randomness];
+        inputs.extend_from_slice(&message);
+        let commitment = native_hash_with_domain(POSEIDON_COMMITMENT_DOMAIN, &inputs);
+
+        for &(wrong_randomness, should_enforce) in
+            [(false, true), (true, true), (true, false)].iter()
+        {
+            let mut cs = TestConstraintSystem::<FieldElement>::new();
+
+            let message_g = alloc_all(cs.ns(|| "alloc message"), &message);
+            let randomness_g = FrGadget::alloc(cs.ns(|| "alloc randomness"), || {
+                Ok(if wrong_randomness {
+                    FieldElement::rand(&mut rng)
+                } else {
+                    randomness
+                })
+            })
+            .unwrap();
+            let commitment_g =
+                FrGadget::alloc_input(cs.ns(|| "alloc commitment"), || Ok(commitment)).unwrap();
+            let should_enforce_g =
+                Boolean::alloc(cs.ns(|| "alloc should enforce"), || Ok(should_enforce)).unwrap();
+
+            PoseidonCommitmentGadget::conditionally_enforce_opening(
+                cs.ns(|| "enforce opening"),
+                &commitment_g,
+                &message_g,
+                &randomness_g,
+                &should_enforce_g,
+            )
+            .unwrap();
+
+            assert_eq!(cs.is_satisfied(), !(wrong_randomness && should_enforce));
+        }
+    }
+
+    #[test]
+    fn poseidon_prf_and_kdf_gadgets() {
+        let mut rng = OsRng::default();
+        let key = FieldElement::rand(&mut rng);
+        let input = FieldElement::rand(&mut rng);
+        let domain = FieldElement::rand(&mut rng);
+        let index = FieldElement::from(7u64);
+
+        let mut cs = TestConstraintSystem::<FieldElement>::new();
+        let values_g = alloc_all(cs.ns(|| "alloc values"), &[key, input, domain, index]);
+
+        let prf_g = PoseidonPrfGadget::enforce_prf(cs.ns(|| "prf"), &values_g[0], &values_g[1])
+            .unwrap();
+        let kdf_g = PoseidonKdfGadget::enforce_kdf(
+            cs.ns(|| "kdf"),
+            &values_g[0],
+            &values_g[2],
+            &values_g[3],
+        )
+        .unwrap();
+        assert!(cs.is_satisfied());
+
+        assert_eq!(
+            prf_g.get_value().unwrap(),
+            native_hash_with_domain(POSEIDON_PRF_DOMAIN, &[key, input])
+        );
+        assert_eq!(
+            kdf_g.get_value().unwrap(),
+            native_hash_with_domain(POSEIDON_KDF_DOMAIN, &[key, domain, index])
+        );
+
+        // Different domains give unrelated outputs even on the same inputs
+        assert_ne!(
+            prf_g.get_value().unwrap(),
+            native_hash_with_domain(POSEIDON_COMMITMENT_DOMAIN, &[key, input])
+        );
+    }
+}
@@ -42,18 +42,42 @@ public class CommitmentTree implements AutoCloseable {
         freeCommitmentTree();
     }
 
+    private native byte[] nativeSerialize();
+
+    /*
+     * Export the state of this tree, i.e. the transactions added so far.
+     * The tree can be restored by calling deserialize().
+     */
+    public byte[] serialize() {
+        if (commitmentTreePointer == 0)
+            throw new IllegalStateException("CommitmentTree instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native CommitmentTree nativeDeserialize(byte[] state) throws CommitmentTreeInputException;
+
+    /*
+     * Restore a tree from the bytes returned by serialize(), by adding again the transactions
+     * in the same order. Return NULL if it was not possible to restore the tree (e.g. data
+     * is corrupted or has an unsupported version).
+     */
+    public static CommitmentTree deserialize(byte[] state) throws CommitmentTreeInputException {
+        return nativeDeserialize(state);
+    }
+
     private native boolean nativeAddScCr(byte[] scId, long amount, byte[] pubKey, byte[] txHash, int outIdx,
                                          int withdrawalEpochLength, byte mcBtrRequestDataLength,
                                          CustomFieldElementsConfig[] customFieldElementsConfigs,
                                          CustomBitvectorElementsConfig[] customBitvectorElementsConfigs,
                                          long btrFee, long ftMinAmount, byte[] customCreationData,
-                                         byte[] constantNullable, byte[] certVerificationKey, byte[] cswVerificationKeyNullable);
+                                         byte[] constantNullable, byte[] certVerificationKey, byte[] cswVerificationKeyNullable)
+            throws CommitmentTreeInputException;
     
     public boolean addScCr(byte[] scId, long amount, byte[] pubKey, byte[] txHash, int outIdx, int withdrawalEpochLength,
                            byte mcBtrRequestDataLength, CustomFieldElementsConfig[] customFieldElementsConfigs,
                            CustomBitvectorElementsConfig[] customBitvectorElementsConfigs, long btrFee,
                            long ftMinAmount, byte[] customCreationData, Optional<byte[]> constantOpt,
-                           byte[] certVerificationKey, Optional<byte[]> cswVerificationKeyOpt) {
+                           byte[] certVerificationKey, Optional<byte[]> cswVerificationKeyOpt) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeAddScCr(scId, amount, pubKey, txHash, outIdx, withdrawalEpochLength,
@@ -61,19 +85,30 @@ public class CommitmentTree implements AutoCloseable {
                 btrFee, ftMinAmount, customCreationData, constantOpt.orElse(null), certVerificationKey,
                 cswVerificationKeyOpt.orElse(null));
     }
+
+    private native boolean nativeAddScCrWithParams(byte[] scId, byte[] txHash, int outIdx,
+                                                   SidechainCreationParams params) throws CommitmentTreeInputException;
+
+    public boolean addScCr(byte[] scId, byte[] txHash, int outIdx, SidechainCreationParams params)
+            throws CommitmentTreeInputException {
+        if (commitmentTreePointer == 0)
+            throw new IllegalStateException("CommitmentTree instance was freed.");
+        params.checkNotFreed();
+        return nativeAddScCrWithParams(scId, txHash, outIdx, params);
+    }
     
-    private native boolean nativeAddFwt(byte[] scId, long amount, byte[] pubKey, byte[] mcReturnAddress, byte[] txHash, int outIdx);
+    private native boolean nativeAddFwt(byte[] scId, long amount, byte[] pubKey, byte[] mcReturnAddress, byte[] txHash, int outIdx) throws CommitmentTreeInputException;
     
-    public boolean addFwt(byte[] scId, long amount, byte[] pubKey, byte[] mcReturnAddress, byte[] txHash, int outIdx) {
+    public boolean addFwt(byte[] scId, long amount, byte[] pubKey, byte[] mcReturnAddress, byte[] txHash, int outIdx) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeAddFwt(scId, amount, pubKey, mcReturnAddress, txHash, outIdx);
     }
 
     private native boolean nativeAddBtr(byte[] scId, long scFee, byte[] mcDestinationAddress,
-                                               byte[][] scRequestData, byte[] txHash, int outIdx);
+                                               byte[][] scRequestData, byte[] txHash, int outIdx) throws CommitmentTreeInputException;
     
-    public boolean addBtr(byte[] scId, long scFee, byte[] mcDestinationAddress, byte[][] scRequestData, byte[] txHash, int outIdx) {
+    public boolean addBtr(byte[] scId, long scFee, byte[] mcDestinationAddress, byte[][] scRequestData, byte[] txHash, int outIdx) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeAddBtr(scId, scFee, mcDestinationAddress, scRequestData, txHash, outIdx);
@@ -81,84 +116,84 @@ public class CommitmentTree implements AutoCloseable {
     
     private native boolean nativeAddCert(byte[] scId, int epochNumber, long quality,
                                          BackwardTransfer[] btList, byte[][] customFieldsNullable,
-                                         byte[] endCumulativeScTxCommitmentTreeRoot, long btrFee, long ftMinAmount);
+                                         byte[] endCumulativeScTxCommitmentTreeRoot, long btrFee, long ftMinAmount) throws CommitmentTreeInputException;
 
     public boolean addCert(byte[] scId, int epochNumber, long quality,
                            BackwardTransfer[] btList, Optional<byte[][]> customFieldsOpt,
-                           byte[] endCumulativeScTxCommitmentTreeRoot, long btrFee, long ftMinAmount) {
+                           byte[] endCumulativeScTxCommitmentTreeRoot, long btrFee, long ftMinAmount) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeAddCert(scId, epochNumber, quality, btList,
                 customFieldsOpt.orElse(null), endCumulativeScTxCommitmentTreeRoot, btrFee, ftMinAmount);
     }
 
-    public native boolean nativeAddCertLeaf(byte[] scId, byte[] leaf);
+    public native boolean nativeAddCertLeaf(byte[] scId, byte[] leaf) throws CommitmentTreeInputException;
 
-    public boolean addCertLeaf(byte[] scId, byte[] leaf) {
+    public boolean addCertLeaf(byte[] scId, byte[] leaf) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeAddCertLeaf(scId, leaf);
     }
     
-    private native boolean nativeAddCsw(byte[] scId, long amount, byte[] nullifier, byte[] mcPubKeyHash);
+    private native boolean nativeAddCsw(byte[] scId, long amount, byte[] nullifier, byte[] mcPubKeyHash) throws CommitmentTreeInputException;
     
-    public boolean addCsw(byte[] scId, long amount, byte[] nullifier, byte[] mcPubKeyHash) {
+    public boolean addCsw(byte[] scId, long amount, byte[] nullifier, byte[] mcPubKeyHash) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeAddCsw(scId, amount, nullifier, mcPubKeyHash);
     }
 
 
-    private native Optional<FieldElement[]>  nativeGetCrtLeaves(byte[] scId);
-    public Optional<List<FieldElement>> getCrtLeaves(byte[] scId) {
+    private native Optional<FieldElement[]>  nativeGetCrtLeaves(byte[] scId) throws CommitmentTreeInputException;
+    public Optional<List<FieldElement>> getCrtLeaves(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetCrtLeaves(scId).map(array -> new ArrayList<FieldElement>(Arrays.asList(array)));
     }
     
-    private native Optional<FieldElement> nativeGetScCrCommitment(byte[] scId);
+    private native Optional<FieldElement> nativeGetScCrCommitment(byte[] scId) throws CommitmentTreeInputException;
     
-    public Optional<FieldElement> getScCrCommitment(byte[] scId) {
+    public Optional<FieldElement> getScCrCommitment(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetScCrCommitment(scId);
     }
 
-    private native Optional<FieldElement> nativeGetFwtCommitment(byte[] scId);
+    private native Optional<FieldElement> nativeGetFwtCommitment(byte[] scId) throws CommitmentTreeInputException;
     
-    public Optional<FieldElement> getFwtCommitment(byte[] scId) {
+    public Optional<FieldElement> getFwtCommitment(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetFwtCommitment(scId);
     }
 
-    private native Optional<FieldElement> nativeBtrCommitment(byte[] scId);
+    private native Optional<FieldElement> nativeBtrCommitment(byte[] scId) throws CommitmentTreeInputException;
     
-    public Optional<FieldElement> getBtrCommitment(byte[] scId) {
+    public Optional<FieldElement> getBtrCommitment(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeBtrCommitment(scId);
     }
 
-    private native Optional<FieldElement> nativeGetCertCommitment(byte[] scId);
+    private native Optional<FieldElement> nativeGetCertCommitment(byte[] scId) throws CommitmentTreeInputException;
     
-    public Optional<FieldElement> getCertCommitment(byte[] scId) {
+    public Optional<FieldElement> getCertCommitment(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetCertCommitment(scId);
     }
 
-    private native Optional<FieldElement> nativeGetCswCommitment(byte[] scId);
+    private native Optional<FieldElement> nativeGetCswCommitment(byte[] scId) throws CommitmentTreeInputException;
     
-    public Optional<FieldElement> getCswCommitment(byte[] scId) {
+    public Optional<FieldElement> getCswCommitment(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetCswCommitment(scId);
     }
 
-    private native Optional<FieldElement> nativeGetScCommitment(byte[] scId);
+    private native Optional<FieldElement> nativeGetScCommitment(byte[] scId) throws CommitmentTreeInputException;
     
-    public Optional<FieldElement> getScCommitment(byte[] scId) {
+    public Optional<FieldElement> getScCommitment(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetScCommitment(scId);
@@ -172,9 +207,9 @@ public class CommitmentTree implements AutoCloseable {
         return nativeGetCommitment();
     }
 
-    private native Optional<ScExistenceProof> nativeGetScExistenceProof(byte[] scId);
+    private native Optional<ScExistenceProof> nativeGetScExistenceProof(byte[] scId) throws CommitmentTreeInputException;
 
-    public Optional<ScExistenceProof> getScExistenceProof(byte[] scId) {
+    public Optional<ScExistenceProof> getScExistenceProof(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetScExistenceProof(scId);
@@ -186,17 +221,45 @@ public class CommitmentTree implements AutoCloseable {
         return nativeVerifyScCommitment(scCommitment, existenceProof, commitment);
     }
 
-    private native Optional<ScAbsenceProof> nativeGetScAbsenceProof(byte[] scId);
+    private native Optional<ScAbsenceProof> nativeGetScAbsenceProof(byte[] scId) throws CommitmentTreeInputException;
 
-    public Optional<ScAbsenceProof> getScAbsenceProof(byte[] scId) {
+    public Optional<ScAbsenceProof> getScAbsenceProof(byte[] scId) throws CommitmentTreeInputException {
         if (commitmentTreePointer == 0)
             throw new IllegalStateException("CommitmentTree instance was freed.");
         return nativeGetScAbsenceProof(scId);
     }
 
-    private static native boolean nativeVerifyScAbsence(byte[] scId, ScAbsenceProof absenceProof, FieldElement commitment);
+    private static native boolean nativeVerifyScAbsence(byte[] scId, ScAbsenceProof absenceProof, FieldElement commitment) throws CommitmentTreeInputException;
 
-    public static boolean verifyScAbsence(byte[] scId, ScAbsenceProof absenceProof, FieldElement commitment) {
+    public static boolean verifyScAbsence(byte[] scId, ScAbsenceProof absenceProof, FieldElement commitment) throws CommitmentTreeInputException {
         return nativeVerifyScAbsence(scId, absenceProof, commitment);
     }
+
+    private native Optional<TxInclusionProof> nativeGetTxInclusionProof(byte txType, byte[] scId, int index) throws CommitmentTreeInputException;
+
+    private Optional<TxInclusionProof> getTxInclusionProof(byte txType, byte[] scId, int index) throws CommitmentTreeInputException {
+        if (commitmentTreePointer == 0)
+            throw new IllegalStateException("CommitmentTree instance was freed.");
+        return nativeGetTxInclusionProof(txType, scId, index);
+    }
+
+    // Inclusion proof of the index-th forward transfer added for the sidechain scId
+    public Optional<TxInclusionProof> getFwtInclusionProof(byte[] scId, int index) throws CommitmentTreeInputException {
+        return getTxInclusionProof(TxInclusionProof.FWT_TX_TYPE, scId, index);
+    }
+
+    // Inclusion proof of the index-th backward transfer request added for the sidechain scId
+    public Optional<TxInclusionProof> getBtrInclusionProof(byte[] scId, int index) throws CommitmentTreeInputException {
+        return getTxInclusionProof(TxInclusionProof.BTR_TX_TYPE, scId, index);
+    }
+
+    // Inclusion proof of the index-th certificate (or certificate leaf) added for the sidechain scId
+    public Optional<TxInclusionProof> getCertInclusionProof(byte[] scId, int index) throws CommitmentTreeInputException {
+        return getTxInclusionProof(TxInclusionProof.CERT_TX_TYPE, scId, index);
+    }
+
+    // Inclusion proof of the index-th ceased sidechain withdrawal added for the sidechain scId
+    public Optional<TxInclusionProof> getCswInclusionProof(byte[] scId, int index) throws CommitmentTreeInputException {
+        return getTxInclusionProof(TxInclusionProof.CSW_TX_TYPE, scId, index);
+    }
 }
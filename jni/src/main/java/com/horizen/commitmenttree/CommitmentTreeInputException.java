@@ -0,0 +1,19 @@
+package com.horizen.commitmenttree;
+
+/*
+ * Thrown by CommitmentTree functions when one of their inputs is malformed (e.g. a byte array
+ * of the wrong length, or bytes not representing a valid field element).
+ */
+public class CommitmentTreeInputException extends Exception {
+    private final String fieldName;
+
+    public CommitmentTreeInputException(String fieldName, String message) {
+        super(message);
+        this.fieldName = fieldName;
+    }
+
+    // Name of the malformed parameter, e.g. "scId" or "scRequestData[1]"
+    public String getFieldName() {
+        return fieldName;
+    }
+}
@@ -41,6 +41,28 @@ public class ScAbsenceProof implements AutoCloseable {
         return nativeDeserialize(absenceProofBytes);
     }
 
+    private native String nativeToJson();
+
+    /*
+    * Versioned JSON representation, with binary data hex encoded:
+    * {"type": "ScAbsenceProof", "version": 1, ...}
+    */
+    public String toJson() {
+        if (absenceProofPointer == 0)
+            throw new IllegalStateException("Absence proof was freed.");
+        return nativeToJson();
+    }
+
+    private static native ScAbsenceProof nativeFromJson(String json);
+
+    /*
+    * Read the JSON representation returned by toJson().
+    * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+    */
+    public static ScAbsenceProof fromJson(String json) {
+        return nativeFromJson(json);
+    }
+
     @Override
     public void close() throws Exception {
         freeScAbsenceProof();
@@ -41,6 +41,28 @@ public class ScExistenceProof implements AutoCloseable {
         return nativeDeserialize(existanceProofBytes);
     }
 
+    private native String nativeToJson();
+
+    /*
+    * Versioned JSON representation, with binary data hex encoded:
+    * {"type": "ScExistenceProof", "version": 1, ...}
+    */
+    public String toJson() {
+        if (existenceProofPointer == 0)
+            throw new IllegalStateException("Existence proof was freed.");
+        return nativeToJson();
+    }
+
+    private static native ScExistenceProof nativeFromJson(String json);
+
+    /*
+    * Read the JSON representation returned by toJson().
+    * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+    */
+    public static ScExistenceProof fromJson(String json) {
+        return nativeFromJson(json);
+    }
+
     @Override
     public void close() throws Exception {
         freeScExistenceProof();
@@ -0,0 +1,88 @@
+package com.horizen.commitmenttree;
+
+import com.horizen.librustsidechains.Library;
+
+import java.util.Optional;
+
+/*
+ * Parameters of a sidechain declared in the sidechain creation output of a mainchain
+ * transaction. They can be validated with the same checks the mainchain performs, serialized
+ * in the format of the mainchain output and added to a CommitmentTree.
+ */
+public class SidechainCreationParams implements AutoCloseable {
+    private long sidechainCreationParamsPointer;
+
+    static {
+        Library.load();
+    }
+
+    private SidechainCreationParams(long sidechainCreationParamsPointer) {
+        if (sidechainCreationParamsPointer == 0)
+            throw new IllegalArgumentException("sidechainCreationParamsPointer must be not null.");
+        this.sidechainCreationParamsPointer = sidechainCreationParamsPointer;
+    }
+
+    private static native SidechainCreationParams nativeCreate(int withdrawalEpochLength, long amount, byte[] pubKey,
+                                                               byte[] customCreationData, byte[] constantNullable,
+                                                               byte[] certVerificationKey, byte[] cswVerificationKeyNullable,
+                                                               CustomFieldElementsConfig[] customFieldElementsConfigs,
+                                                               CustomBitvectorElementsConfig[] customBitvectorElementsConfigs,
+                                                               long ftMinAmount, long btrFee, byte mcBtrRequestDataLength)
+            throws CommitmentTreeInputException;
+
+    public static SidechainCreationParams create(int withdrawalEpochLength, long amount, byte[] pubKey,
+                                                 byte[] customCreationData, Optional<byte[]> constantOpt,
+                                                 byte[] certVerificationKey, Optional<byte[]> cswVerificationKeyOpt,
+                                                 CustomFieldElementsConfig[] customFieldElementsConfigs,
+                                                 CustomBitvectorElementsConfig[] customBitvectorElementsConfigs,
+                                                 long ftMinAmount, long btrFee, byte mcBtrRequestDataLength)
+            throws CommitmentTreeInputException {
+        return nativeCreate(withdrawalEpochLength, amount, pubKey, customCreationData, constantOpt.orElse(null),
+                certVerificationKey, cswVerificationKeyOpt.orElse(null), customFieldElementsConfigs,
+                customBitvectorElementsConfigs, ftMinAmount, btrFee, mcBtrRequestDataLength);
+    }
+
+    private static native void nativeFreeSidechainCreationParams(long sidechainCreationParamsPointer);
+
+    // Free Rust memory
+    public void freeSidechainCreationParams() {
+        if (sidechainCreationParamsPointer != 0) {
+            nativeFreeSidechainCreationParams(this.sidechainCreationParamsPointer);
+            sidechainCreationParamsPointer = 0;
+        }
+    }
+
+    void checkNotFreed() {
+        if (sidechainCreationParamsPointer == 0)
+            throw new IllegalStateException("Sidechain creation params were freed.");
+    }
+
+    private native void nativeValidate(boolean certCircuitHasConstant, int supportedSegmentSize, boolean zk,
+                                       int maxProofSize, int maxVkSize);
+
+    // Check the parameters as the mainchain does, throwing an IllegalArgumentException with the
+    // reason if they are not valid. The verification keys are checked against the given limits.
+    public void validate(boolean certCircuitHasConstant, int supportedSegmentSize, boolean zk,
+                         int maxProofSize, int maxVkSize) {
+        checkNotFreed();
+        nativeValidate(certCircuitHasConstant, supportedSegmentSize, zk, maxProofSize, maxVkSize);
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        checkNotFreed();
+        return nativeSerialize();
+    }
+
+    private static native SidechainCreationParams nativeDeserialize(byte[] sidechainCreationParamsBytes);
+
+    public static SidechainCreationParams deserialize(byte[] sidechainCreationParamsBytes) {
+        return nativeDeserialize(sidechainCreationParamsBytes);
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeSidechainCreationParams();
+    }
+}
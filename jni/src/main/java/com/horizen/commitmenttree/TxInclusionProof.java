@@ -0,0 +1,113 @@
+package com.horizen.commitmenttree;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+
+/*
+ * Proof that a single transaction (forward transfer, backward transfer request, certificate or
+ * ceased sidechain withdrawal) is included in the sub-tree of its sidechain and, through the
+ * sidechain commitment, in the overall commitment of a CommitmentTree.
+ */
+public class TxInclusionProof implements AutoCloseable {
+    public static final byte FWT_TX_TYPE = 0;
+    public static final byte BTR_TX_TYPE = 1;
+    public static final byte CERT_TX_TYPE = 2;
+    public static final byte CSW_TX_TYPE = 3;
+
+    private long txInclusionProofPointer;
+
+    static {
+        Library.load();
+    }
+
+    private TxInclusionProof(long txInclusionProofPointer) {
+        if (txInclusionProofPointer == 0)
+            throw new IllegalArgumentException("txInclusionProofPointer must be not null.");
+        this.txInclusionProofPointer = txInclusionProofPointer;
+    }
+
+    private static native void nativeFreeTxInclusionProof(long txInclusionProofPointer);
+
+    // Free Rust memory
+    public void freeTxInclusionProof() {
+        if (txInclusionProofPointer != 0) {
+            nativeFreeTxInclusionProof(this.txInclusionProofPointer);
+            txInclusionProofPointer = 0;
+        }
+    }
+
+    private native FieldElement nativeGetLeaf();
+
+    // Leaf of the transaction in the sub-tree of its type
+    public FieldElement getLeaf() {
+        if (txInclusionProofPointer == 0)
+            throw new IllegalStateException("Tx inclusion proof was freed.");
+        return nativeGetLeaf();
+    }
+
+    private native long nativeGetLeafIndex();
+
+    // Position of the transaction in the sub-tree of its type
+    public long getLeafIndex() {
+        if (txInclusionProofPointer == 0)
+            throw new IllegalStateException("Tx inclusion proof was freed.");
+        return nativeGetLeafIndex();
+    }
+
+    private native boolean nativeVerify(byte txType, byte[] scId, FieldElement leaf, FieldElement commitment)
+            throws CommitmentTreeInputException;
+
+    /*
+     * Verify that the transaction of type txType (one of the *_TX_TYPE constants) of the sidechain
+     * scId, with the given leaf in the sub-tree of its type, is included in the CommitmentTree with
+     * the given commitment. Returns false if the proof is for another transaction.
+     * Throws CommitmentTreeInputException if scId is malformed.
+     */
+    public boolean verify(byte txType, byte[] scId, FieldElement leaf, FieldElement commitment)
+            throws CommitmentTreeInputException {
+        if (txInclusionProofPointer == 0)
+            throw new IllegalStateException("Tx inclusion proof was freed.");
+        return nativeVerify(txType, scId, leaf, commitment);
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (txInclusionProofPointer == 0)
+            throw new IllegalStateException("Tx inclusion proof was freed.");
+        return nativeSerialize();
+    }
+
+    private static native TxInclusionProof nativeDeserialize(byte[] txInclusionProofBytes);
+
+    public static TxInclusionProof deserialize(byte[] txInclusionProofBytes) {
+        return nativeDeserialize(txInclusionProofBytes);
+    }
+
+    private native String nativeToJson();
+
+    /*
+    * Versioned JSON representation, with binary data hex encoded:
+    * {"type": "TxInclusionProof", "version": 1, ...}
+    */
+    public String toJson() {
+        if (txInclusionProofPointer == 0)
+            throw new IllegalStateException("Tx inclusion proof was freed.");
+        return nativeToJson();
+    }
+
+    private static native TxInclusionProof nativeFromJson(String json);
+
+    /*
+    * Read the JSON representation returned by toJson().
+    * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+    */
+    public static TxInclusionProof fromJson(String json) {
+        return nativeFromJson(json);
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeTxInclusionProof();
+    }
+}
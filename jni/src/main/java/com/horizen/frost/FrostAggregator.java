@@ -0,0 +1,76 @@
+package com.horizen.frost;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+import com.horizen.schnorrnative.SchnorrSignature;
+import com.horizen.secretsharing.VssCommitment;
+
+import java.util.List;
+
+/*
+ * Coordinator of a FROST signing session on a given message: verifies the signature shares of
+ * the signers and aggregates them into an ordinary SchnorrSignature, verifiable against the
+ * group public key.
+ */
+public class FrostAggregator implements AutoCloseable {
+
+    private long aggregatorPointer;
+
+    static {
+        Library.load();
+    }
+
+    private FrostAggregator(long aggregatorPointer) {
+        if (aggregatorPointer == 0)
+            throw new IllegalArgumentException("aggregatorPointer must be not null.");
+        this.aggregatorPointer = aggregatorPointer;
+    }
+
+    private static native FrostAggregator nativeInit(VssCommitment groupCommitment, FieldElement message, FrostSigningCommitment[] commitments);
+
+    /*
+     * `groupCommitment` is the joint VssCommitment of the key generation, `commitments` the ones
+     * received in round 1 by the signers of the session.
+     * Return NULL if signers are less than the threshold, or if `commitments` are not valid.
+     */
+    public static FrostAggregator init(VssCommitment groupCommitment, FieldElement message, List<FrostSigningCommitment> commitments) {
+        return nativeInit(groupCommitment, message, commitments.toArray(new FrostSigningCommitment[0]));
+    }
+
+    private native boolean nativeVerifyShare(FrostSignatureShare share);
+
+    /*
+     * Verify the signature share of a single signer, allowing to identify misbehaving ones.
+     */
+    public boolean verifyShare(FrostSignatureShare share) {
+        if (aggregatorPointer == 0)
+            throw new IllegalStateException("FrostAggregator instance was freed.");
+        return nativeVerifyShare(share);
+    }
+
+    private native SchnorrSignature nativeAggregate(FrostSignatureShare[] shares);
+
+    /*
+     * Aggregate the shares of all the signers of the session into a SchnorrSignature.
+     * Return NULL if any share is missing or invalid.
+     */
+    public SchnorrSignature aggregate(List<FrostSignatureShare> shares) {
+        if (aggregatorPointer == 0)
+            throw new IllegalStateException("FrostAggregator instance was freed.");
+        return nativeAggregate(shares.toArray(new FrostSignatureShare[0]));
+    }
+
+    private native void nativeFreeAggregator(long aggregatorPointer);
+
+    public void freeAggregator() {
+        if (aggregatorPointer != 0) {
+            nativeFreeAggregator(this.aggregatorPointer);
+            aggregatorPointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeAggregator();
+    }
+}
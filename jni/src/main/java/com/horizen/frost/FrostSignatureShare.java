@@ -0,0 +1,71 @@
+package com.horizen.frost;
+
+import com.horizen.librustsidechains.Library;
+
+/*
+ * Share of a signature, output by the second round of a FROST signing session.
+ */
+public class FrostSignatureShare implements AutoCloseable {
+
+    public static final int SIGNATURE_SHARE_LENGTH;
+
+    private long signatureSharePointer;
+
+    private static native int nativeGetSignatureShareSize();
+
+    static {
+        Library.load();
+        SIGNATURE_SHARE_LENGTH = nativeGetSignatureShareSize();
+    }
+
+    private FrostSignatureShare(long signatureSharePointer) {
+        if (signatureSharePointer == 0)
+            throw new IllegalArgumentException("signatureSharePointer must be not null.");
+        this.signatureSharePointer = signatureSharePointer;
+    }
+
+    private native int nativeGetIndex();
+
+    /*
+     * Return the index of the signer that produced this share.
+     */
+    public int getIndex() {
+        if (signatureSharePointer == 0)
+            throw new IllegalStateException("FrostSignatureShare instance was freed.");
+        return nativeGetIndex();
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (signatureSharePointer == 0)
+            throw new IllegalStateException("FrostSignatureShare instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native FrostSignatureShare nativeDeserialize(byte[] signatureShareBytes);
+
+    /*
+     * Return NULL if `signatureShareBytes` don't represent a valid FrostSignatureShare.
+     */
+    public static FrostSignatureShare deserialize(byte[] signatureShareBytes) {
+        if (signatureShareBytes.length != SIGNATURE_SHARE_LENGTH)
+            throw new IllegalArgumentException(String.format("Incorrect signature share length, %d expected, %d found", SIGNATURE_SHARE_LENGTH, signatureShareBytes.length));
+
+        return nativeDeserialize(signatureShareBytes);
+    }
+
+    private native void nativeFreeSignatureShare(long signatureSharePointer);
+
+    public void freeSignatureShare() {
+        if (signatureSharePointer != 0) {
+            nativeFreeSignatureShare(this.signatureSharePointer);
+            signatureSharePointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeSignatureShare();
+    }
+}
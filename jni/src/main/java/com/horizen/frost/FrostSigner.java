@@ -0,0 +1,84 @@
+package com.horizen.frost;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+import com.horizen.schnorrnative.SchnorrPublicKey;
+import com.horizen.secretsharing.SecretShare;
+
+import java.util.List;
+
+/*
+ * Holder of a share of a group Schnorr secret key (e.g. obtained via DkgParticipant or
+ * ShamirSecretSharing) taking part to FROST signing sessions:
+ * 1) Call commit() and send the resulting commitment to the coordinator;
+ * 2) Once received the commitments of all the signers of the session, call sign()
+ *    and send the resulting share to the coordinator (see FrostAggregator).
+ */
+public class FrostSigner implements AutoCloseable {
+
+    private long signerPointer;
+
+    static {
+        Library.load();
+    }
+
+    private FrostSigner(long signerPointer) {
+        if (signerPointer == 0)
+            throw new IllegalArgumentException("signerPointer must be not null.");
+        this.signerPointer = signerPointer;
+    }
+
+    private static native FrostSigner nativeInit(SecretShare secretShare, SchnorrPublicKey groupPublicKey);
+
+    public static FrostSigner init(SecretShare secretShare, SchnorrPublicKey groupPublicKey) {
+        return nativeInit(secretShare, groupPublicKey);
+    }
+
+    private native int nativeGetIndex();
+
+    public int getIndex() {
+        if (signerPointer == 0)
+            throw new IllegalStateException("FrostSigner instance was freed.");
+        return nativeGetIndex();
+    }
+
+    private native FrostSigningCommitment nativeCommit();
+
+    /*
+     * Round 1: sample fresh nonces for a new signing session and return the commitment to them.
+     * Nonces of a previous, not completed, session are discarded.
+     */
+    public FrostSigningCommitment commit() {
+        if (signerPointer == 0)
+            throw new IllegalStateException("FrostSigner instance was freed.");
+        return nativeCommit();
+    }
+
+    private native FrostSignatureShare nativeSign(FieldElement message, FrostSigningCommitment[] commitments);
+
+    /*
+     * Round 2: sign `message` given the commitments of all the signers of the session, including
+     * the one of this signer. Nonces are consumed in any case, so commit() must be called again
+     * before the next signing session.
+     * Return NULL if commit() was not called before, or if `commitments` are not valid.
+     */
+    public FrostSignatureShare sign(FieldElement message, List<FrostSigningCommitment> commitments) {
+        if (signerPointer == 0)
+            throw new IllegalStateException("FrostSigner instance was freed.");
+        return nativeSign(message, commitments.toArray(new FrostSigningCommitment[0]));
+    }
+
+    private native void nativeFreeSigner(long signerPointer);
+
+    public void freeSigner() {
+        if (signerPointer != 0) {
+            nativeFreeSigner(this.signerPointer);
+            signerPointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeSigner();
+    }
+}
@@ -0,0 +1,71 @@
+package com.horizen.frost;
+
+import com.horizen.librustsidechains.Library;
+
+/*
+ * Commitment to the nonces of a signer, output by the first round of a FROST signing session.
+ */
+public class FrostSigningCommitment implements AutoCloseable {
+
+    public static final int SIGNING_COMMITMENT_LENGTH;
+
+    private long signingCommitmentPointer;
+
+    private static native int nativeGetSigningCommitmentSize();
+
+    static {
+        Library.load();
+        SIGNING_COMMITMENT_LENGTH = nativeGetSigningCommitmentSize();
+    }
+
+    private FrostSigningCommitment(long signingCommitmentPointer) {
+        if (signingCommitmentPointer == 0)
+            throw new IllegalArgumentException("signingCommitmentPointer must be not null.");
+        this.signingCommitmentPointer = signingCommitmentPointer;
+    }
+
+    private native int nativeGetIndex();
+
+    /*
+     * Return the index of the signer that produced this commitment.
+     */
+    public int getIndex() {
+        if (signingCommitmentPointer == 0)
+            throw new IllegalStateException("FrostSigningCommitment instance was freed.");
+        return nativeGetIndex();
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (signingCommitmentPointer == 0)
+            throw new IllegalStateException("FrostSigningCommitment instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native FrostSigningCommitment nativeDeserialize(byte[] signingCommitmentBytes);
+
+    /*
+     * Return NULL if `signingCommitmentBytes` don't represent a valid FrostSigningCommitment.
+     */
+    public static FrostSigningCommitment deserialize(byte[] signingCommitmentBytes) {
+        if (signingCommitmentBytes.length != SIGNING_COMMITMENT_LENGTH)
+            throw new IllegalArgumentException(String.format("Incorrect signing commitment length, %d expected, %d found", SIGNING_COMMITMENT_LENGTH, signingCommitmentBytes.length));
+
+        return nativeDeserialize(signingCommitmentBytes);
+    }
+
+    private native void nativeFreeSigningCommitment(long signingCommitmentPointer);
+
+    public void freeSigningCommitment() {
+        if (signingCommitmentPointer != 0) {
+            nativeFreeSigningCommitment(this.signingCommitmentPointer);
+            signingCommitmentPointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeSigningCommitment();
+    }
+}
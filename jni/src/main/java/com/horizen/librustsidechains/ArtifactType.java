@@ -0,0 +1,26 @@
+package com.horizen.librustsidechains;
+
+public enum ArtifactType {
+    FIELD_ELEMENT(1),
+    SCHNORR_PUBLIC_KEY(2),
+    SCHNORR_SECRET_KEY(3),
+    SCHNORR_SIGNATURE(4),
+    VRF_PUBLIC_KEY(5),
+    VRF_SECRET_KEY(6),
+    VRF_PROOF(7),
+    PROVER_KEY(8),
+    VERIFIER_KEY(9),
+    PROOF(10)
+    ;
+
+    // Tag written in the envelope header, must match the Rust ArtifactType
+    private final int tag;
+
+    ArtifactType(int tag) {
+        this.tag = tag;
+    }
+
+    public int tag() {
+        return tag;
+    }
+}
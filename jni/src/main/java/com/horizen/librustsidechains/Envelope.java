@@ -0,0 +1,46 @@
+package com.horizen.librustsidechains;
+
+/*  Optional envelope around serialized artifacts:
+ *  | magic "ZENV" | artifact type | version | payload length | checksum | payload |
+ *  Readers of keys and proofs accept both sealed and bare data: data starting with the magic, a
+ *  known artifact type and the current version is an envelope, anything else is bare data.
+ */
+public class Envelope {
+    static {
+        Library.load();
+    }
+
+    private Envelope() {}
+
+    private static native byte[] nativeSeal(int artifactType, byte[] payload);
+
+    public static byte[] seal(ArtifactType artifactType, byte[] payload) {
+        return nativeSeal(artifactType.tag(), payload);
+    }
+
+    private static native byte[] nativeOpen(int artifactType, byte[] data);
+
+    /*  Return the payload of sealed data, or data itself if not sealed.
+     *  Throws IllegalArgumentException if the envelope is of another type or length, or its
+     *  checksum doesn't match.
+     */
+    public static byte[] open(ArtifactType artifactType, byte[] data) {
+        return nativeOpen(artifactType.tag(), data);
+    }
+
+    private static native boolean nativeIsSealed(byte[] data);
+
+    // True if data starts as an envelope: magic, known artifact type and current version
+    public static boolean isSealed(byte[] data) {
+        return nativeIsSealed(data);
+    }
+
+    private static native void nativeSealFile(int artifactType, String path);
+
+    /*  Seal, in place, the bare artifact file at path, keeping its permissions.
+     *  Throws IllegalArgumentException if the file can't be read or written, or is already sealed.
+     */
+    public static void sealFile(ArtifactType artifactType, String path) {
+        nativeSealFile(artifactType.tag(), path);
+    }
+}
@@ -93,6 +93,28 @@ public class FieldElement implements AutoCloseable {
         return nativeEquals((FieldElement) o);
     }
 
+    private native String nativeToJson();
+
+    /*
+    * Versioned JSON representation, with binary data hex encoded:
+    * {"type": "FieldElement", "version": 1, ...}
+    */
+    public String toJson() {
+        if (fieldElementPointer == 0)
+            throw new IllegalStateException("Field element was freed.");
+        return nativeToJson();
+    }
+
+    private static native FieldElement nativeFromJson(String json);
+
+    /*
+    * Read the JSON representation returned by toJson().
+    * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+    */
+    public static FieldElement fromJson(String json) {
+        return nativeFromJson(json);
+    }
+
     @Override
     public void close() {
         freeFieldElement();
@@ -92,6 +92,18 @@ public class InMemoryOptimizedMerkleTree implements AutoCloseable {
         return nativeGetMerklePath(leafIndex);
     }
 
+    private native MerkleMultiProof nativeGetMerkleMultiProof(long[] leafIndices);
+
+    /*
+    * Compute and return the MerkleMultiProof for the leaves at `leafIndices` (in any order).
+    * Return NULL if it was not possible to get the MerkleMultiProof.
+    */
+    public MerkleMultiProof getMerkleMultiProof(long[] leafIndices) {
+        if (inMemoryOptimizedMerkleTreePointer == 0)
+            throw new IllegalStateException("InMemoryOptimizedMerkleTree instance was freed.");
+        return nativeGetMerkleMultiProof(leafIndices);
+    }
+
     private native void nativeReset();
 
     /*
@@ -103,6 +115,30 @@ public class InMemoryOptimizedMerkleTree implements AutoCloseable {
         nativeReset();
     }
 
+    private native byte[] nativeSerialize();
+
+    /*
+     * Serialize the whole state of this instance (leaves, parameters and whether it's finalized
+     * or not), with a versioned format and an integrity check, so that it can be restored later
+     * by calling deserialize().
+     */
+    public byte[] serialize() {
+        if (inMemoryOptimizedMerkleTreePointer == 0)
+            throw new IllegalStateException("InMemoryOptimizedMerkleTree instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native InMemoryOptimizedMerkleTree nativeDeserialize(byte[] snapshot);
+
+    /*
+     * Restore a tree from the bytes returned by serialize(). The internal nodes are recomputed.
+     * Return NULL if it was not possible to restore the tree (e.g. data is corrupted or has
+     * an unsupported version).
+     */
+    public static InMemoryOptimizedMerkleTree deserialize(byte[] snapshot) {
+        return nativeDeserialize(snapshot);
+    }
+
     private native void nativeFreeInMemoryOptimizedMerkleTree(long inMemoryOptimizedMerkleTreePointer);
 
     public void freeInMemoryOptimizedMerkleTree(){
@@ -0,0 +1,110 @@
+package com.horizen.merkletreenative;
+
+import com.horizen.librustsidechains.Library;
+import com.horizen.librustsidechains.FieldElement;
+
+/*
+ * Proof of membership of several leaves in the same Merkle Tree. It's more compact than one
+ * MerklePath per leaf, as the nodes shared among the paths are stored only once.
+ */
+public class MerkleMultiProof implements AutoCloseable {
+
+    private long merkleMultiProofPointer;
+
+    static {
+        Library.load();
+    }
+
+    private MerkleMultiProof(long merkleMultiProofPointer) {
+        if (merkleMultiProofPointer == 0)
+            throw new IllegalArgumentException("merkleMultiProofPointer must be not null.");
+        this.merkleMultiProofPointer = merkleMultiProofPointer;
+    }
+
+    private native int nativeGetHeight();
+
+    /*
+    * Returns the height of the Merkle Tree this proof refers to.
+    */
+    public int getHeight() {
+        if (merkleMultiProofPointer == 0)
+            throw new IllegalStateException("MerkleMultiProof instance was freed.");
+        return nativeGetHeight();
+    }
+
+    private native long[] nativeGetLeafIndices();
+
+    /*
+    * Returns the indices of the leaves proven, in increasing order.
+    */
+    public long[] getLeafIndices() {
+        if (merkleMultiProofPointer == 0)
+            throw new IllegalStateException("MerkleMultiProof instance was freed.");
+        return nativeGetLeafIndices();
+    }
+
+    private native boolean nativeVerify(FieldElement[] leaves, FieldElement root);
+
+    /*
+    * Verify that `leaves` are at the indices returned by getLeafIndices(), in the same order,
+    * in the Merkle Tree with root `root`.
+    */
+    public boolean verify(FieldElement[] leaves, FieldElement root) {
+        if (merkleMultiProofPointer == 0)
+            throw new IllegalStateException("MerkleMultiProof instance was freed.");
+        return nativeVerify(leaves, root);
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (merkleMultiProofPointer == 0)
+            throw new IllegalStateException("MerkleMultiProof instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native MerkleMultiProof nativeDeserialize(byte[] proofBytes);
+
+    /*
+    * Return NULL if `proofBytes` is not a valid MerkleMultiProof.
+    */
+    public static MerkleMultiProof deserialize(byte[] proofBytes) {
+        return nativeDeserialize(proofBytes);
+    }
+
+    private native String nativeToJson();
+
+    /*
+    * Versioned JSON representation, with binary data hex encoded:
+    * {"type": "MerkleMultiProof", "version": 1, ...}
+    */
+    public String toJson() {
+        if (merkleMultiProofPointer == 0)
+            throw new IllegalStateException("MerkleMultiProof instance was freed.");
+        return nativeToJson();
+    }
+
+    private static native MerkleMultiProof nativeFromJson(String json);
+
+    /*
+    * Read the JSON representation returned by toJson().
+    * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+    */
+    public static MerkleMultiProof fromJson(String json) {
+        return nativeFromJson(json);
+    }
+
+    private native void nativeFreeMerkleMultiProof(long merkleMultiProofPointer);
+
+    public void freeMerkleMultiProof(){
+        if (merkleMultiProofPointer != 0) {
+            nativeFreeMerkleMultiProof(this.merkleMultiProofPointer);
+            merkleMultiProofPointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeMerkleMultiProof();
+    }
+}
@@ -128,6 +128,28 @@ public class MerklePath implements AutoCloseable {
         }
     }
 
+    private native String nativeToJson();
+
+    /*
+    * Versioned JSON representation, with binary data hex encoded:
+    * {"type": "MerklePath", "version": 1, ...}
+    */
+    public String toJson() {
+        if (merklePathPointer == 0)
+            throw new IllegalStateException("MerklePath instance was freed.");
+        return nativeToJson();
+    }
+
+    private static native MerklePath nativeFromJson(String json);
+
+    /*
+    * Read the JSON representation returned by toJson().
+    * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+    */
+    public static MerklePath fromJson(String json) {
+        return nativeFromJson(json);
+    }
+
     @Override
     public void close() throws Exception {
         freeMerklePath();
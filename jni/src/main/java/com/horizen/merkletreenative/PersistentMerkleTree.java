@@ -0,0 +1,146 @@
+package com.horizen.merkletreenative;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+
+/*
+ * Poseidon Merkle tree whose nodes are stored on disk, so that it's not bounded by the available
+ * RAM and survives restarts. Roots and paths are the same of an InMemoryOptimizedMerkleTree with
+ * the same height and leaves.
+ * Changes are kept in memory until commit() is called: commits are crash safe, i.e. after a crash
+ * the tree is reopened in the state of the last successful commit.
+ * Only one instance at a time must be opened on the same directory.
+ */
+public class PersistentMerkleTree implements AutoCloseable {
+
+    private long persistentMerkleTreePointer;
+
+    static {
+        Library.load();
+    }
+
+    private PersistentMerkleTree(long persistentMerkleTreePointer) {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalArgumentException("persistentMerkleTreePointer must be not null.");
+        this.persistentMerkleTreePointer = persistentMerkleTreePointer;
+    }
+
+    private static native PersistentMerkleTree nativeInit(String path, int height);
+
+    /* Opens the tree of the given `height` stored in the directory `path`, or creates
+     * a new empty one if the directory doesn't contain any.
+     * Return NULL if it was not possible to open the tree (e.g. I/O error, data is corrupted,
+     * or the stored tree has a different height).
+     */
+    public static PersistentMerkleTree init(String path, int height) {
+        return nativeInit(path, height);
+    }
+
+    private native long nativeAppend(FieldElement leaf);
+
+    /*
+     * Append a new leaf `leaf` to this instance, returning its index.
+     * Return -1 if the operation was not successful (e.g. the tree is full).
+     */
+    public long append(FieldElement leaf) {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeAppend(leaf);
+    }
+
+    private native boolean nativeUpdate(long leafIndex, FieldElement leaf);
+
+    /*
+     * Replace the leaf at `leafIndex`, that must have been already appended, with `leaf`.
+     * Return false if the operation was not successful.
+     */
+    public boolean update(long leafIndex, FieldElement leaf) {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeUpdate(leafIndex, leaf);
+    }
+
+    private native FieldElement nativeGetLeaf(long leafIndex);
+
+    /*
+     * Return the leaf at `leafIndex`, or NULL if it doesn't exist.
+     */
+    public FieldElement getLeaf(long leafIndex) {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeGetLeaf(leafIndex);
+    }
+
+    private native long nativeGetNumLeaves();
+
+    public long getNumLeaves() {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeGetNumLeaves();
+    }
+
+    private native FieldElement nativeRoot();
+
+    /*
+     * Returns the root of the tree, including uncommitted changes.
+     * Return NULL if it was not possible to get the root.
+     */
+    public FieldElement root() {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeRoot();
+    }
+
+    private native MerklePath nativeGetMerklePath(long leafIndex);
+
+    /*
+     * Compute and return the MerklePath from the leaf at `leafIndex` to the root of the tree.
+     * Return NULL if it was not possible to get the MerklePath.
+     */
+    public MerklePath getMerklePath(long leafIndex) {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeGetMerklePath(leafIndex);
+    }
+
+    private native boolean nativeCommit();
+
+    /*
+     * Durably store all the changes since the last commit.
+     * Return false if the operation was not successful: in this case the changes are still
+     * pending, and the tree on disk is in the state of the last successful commit.
+     */
+    public boolean commit() {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        return nativeCommit();
+    }
+
+    private native void nativeRollback();
+
+    /*
+     * Discard all the changes since the last commit.
+     */
+    public void rollback() {
+        if (persistentMerkleTreePointer == 0)
+            throw new IllegalStateException("PersistentMerkleTree instance was freed.");
+        nativeRollback();
+    }
+
+    private native void nativeFreePersistentMerkleTree(long persistentMerkleTreePointer);
+
+    /*
+     * Free the instance: uncommitted changes are lost.
+     */
+    public void freePersistentMerkleTree() {
+        if (persistentMerkleTreePointer != 0) {
+            nativeFreePersistentMerkleTree(this.persistentMerkleTreePointer);
+            persistentMerkleTreePointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freePersistentMerkleTree();
+    }
+}
@@ -0,0 +1,174 @@
+package com.horizen.merkletreenative;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+
+/*
+ * Poseidon Merkle tree of fixed height in which leaves can be inserted, updated and removed
+ * at any position. Empty positions hold the zero leaf, so roots and paths are the same of an
+ * InMemoryOptimizedMerkleTree of the same height with the same leaves at the same positions.
+ * Proofs are MerklePath instances: a membership proof proves that a position holds a given
+ * leaf, a non-membership proof proves that a position is empty.
+ * Changes are batched: the affected nodes are recomputed only when the root or a proof is requested.
+ */
+public class SparseMerkleTree implements AutoCloseable {
+
+    private long sparseMerkleTreePointer;
+
+    public static final int MAX_HEIGHT = 63;
+
+    static {
+        Library.load();
+    }
+
+    private SparseMerkleTree(long sparseMerkleTreePointer) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalArgumentException("sparseMerkleTreePointer must be not null.");
+        this.sparseMerkleTreePointer = sparseMerkleTreePointer;
+    }
+
+    private static native SparseMerkleTree nativeInit(int height);
+
+    /* Creates a new empty tree of the given `height`, in [1, MAX_HEIGHT].
+     * Return NULL if it was not possible to create the tree.
+     */
+    public static SparseMerkleTree init(int height) {
+        return nativeInit(height);
+    }
+
+    private native boolean nativeInsert(long position, FieldElement leaf);
+
+    /*
+     * Insert `leaf` at the empty `position`.
+     * Return false if the operation was not successful (e.g. position is not empty
+     * or out of range, or leaf is zero).
+     */
+    public boolean insert(long position, FieldElement leaf) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeInsert(position, leaf);
+    }
+
+    private native boolean nativeUpdate(long position, FieldElement leaf);
+
+    /*
+     * Replace the leaf at the non empty `position` with `leaf`.
+     * Return false if the operation was not successful.
+     */
+    public boolean update(long position, FieldElement leaf) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeUpdate(position, leaf);
+    }
+
+    private native boolean nativeRemove(long position);
+
+    /*
+     * Remove the leaf at the non empty `position`.
+     * Return false if the operation was not successful.
+     */
+    public boolean remove(long position) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeRemove(position);
+    }
+
+    private native FieldElement nativeGetLeaf(long position);
+
+    /*
+     * Return the leaf at `position`, or NULL if the position is empty.
+     */
+    public FieldElement getLeaf(long position) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeGetLeaf(position);
+    }
+
+    private native long nativeGetNumLeaves();
+
+    /*
+     * Return the number of non empty positions.
+     */
+    public long getNumLeaves() {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeGetNumLeaves();
+    }
+
+    private native FieldElement nativeRoot();
+
+    /*
+     * Returns the root of the tree, or NULL if it was not possible to compute it.
+     */
+    public FieldElement root() {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeRoot();
+    }
+
+    private native MerklePath nativeGetMembershipProof(long position);
+
+    /*
+     * Return the proof that the non empty `position` holds its current leaf,
+     * or NULL if it was not possible to compute it (e.g. position is empty).
+     */
+    public MerklePath getMembershipProof(long position) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeGetMembershipProof(position);
+    }
+
+    private native MerklePath nativeGetNonMembershipProof(long position);
+
+    /*
+     * Return the proof that `position` is empty, or NULL if it was not possible
+     * to compute it (e.g. position is not empty).
+     */
+    public MerklePath getNonMembershipProof(long position) {
+        if (sparseMerkleTreePointer == 0)
+            throw new IllegalStateException("SparseMerkleTree instance was freed.");
+        return nativeGetNonMembershipProof(position);
+    }
+
+    private static native boolean nativeVerifyMembershipProof(MerklePath proof, int height, long position, FieldElement leaf, FieldElement root);
+
+    /*
+     * Verify that `proof` proves that `leaf` is at `position` in the tree of height `height` and root `root`.
+     */
+    public static boolean verifyMembershipProof(MerklePath proof, int height, long position, FieldElement leaf, FieldElement root) {
+        return nativeVerifyMembershipProof(proof, height, position, leaf, root);
+    }
+
+    private static native boolean nativeVerifyNonMembershipProof(MerklePath proof, int height, long position, FieldElement root);
+
+    /*
+     * Verify that `proof` proves that `position` is empty in the tree of height `height` and root `root`.
+     */
+    public static boolean verifyNonMembershipProof(MerklePath proof, int height, long position, FieldElement root) {
+        return nativeVerifyNonMembershipProof(proof, height, position, root);
+    }
+
+    private static native long nativeGetPositionFromKey(FieldElement key, int height);
+
+    /*
+     * Return the position corresponding to `key` in a tree of height `height`, i.e. `key`
+     * itself, or -1 if height is invalid or `key` is not smaller than 2^height.
+     */
+    public static long getPositionFromKey(FieldElement key, int height) {
+        return nativeGetPositionFromKey(key, height);
+    }
+
+    private native void nativeFreeSparseMerkleTree(long sparseMerkleTreePointer);
+
+    public void freeSparseMerkleTree() {
+        if (sparseMerkleTreePointer != 0) {
+            nativeFreeSparseMerkleTree(this.sparseMerkleTreePointer);
+            sparseMerkleTreePointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeSparseMerkleTree();
+    }
+}
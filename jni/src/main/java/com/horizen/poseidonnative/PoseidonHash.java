@@ -22,20 +22,20 @@ public class PoseidonHash implements AutoCloseable {
         this.poseidonHashPointer = poseidonHashPointer;
     }
 
-    private static native PoseidonHash nativeGetConstantLengthPoseidonHash(int inputSize, FieldElement[] personalization);
+    private static native PoseidonHash nativeGetConstantLengthPoseidonHash(int inputSize, FieldElement[] personalization, boolean resumable);
 
-    private static native PoseidonHash nativeGetVariableLengthPoseidonHash(boolean modRate, FieldElement[] personalization);
+    private static native PoseidonHash nativeGetVariableLengthPoseidonHash(boolean modRate, FieldElement[] personalization, boolean resumable);
 
     /*
     * Default that returns a variable input length, non modRate, new PoseidonHash instance.
     */
     public static PoseidonHash getInstance(){
-        return nativeGetVariableLengthPoseidonHash(false, new FieldElement[0]);
+        return nativeGetVariableLengthPoseidonHash(false, new FieldElement[0], false);
     }
 
     public static PoseidonHash getInstance(FieldElement[] personalization)
     {
-       return nativeGetVariableLengthPoseidonHash(false, personalization);
+       return nativeGetVariableLengthPoseidonHash(false, personalization, false);
     }
 
     /*
@@ -44,12 +44,22 @@ public class PoseidonHash implements AutoCloseable {
      * inputSize elements, an exception will be raised Rust-side.
      */
     public static PoseidonHash getInstanceConstantLength(int inputSize){
-        return nativeGetConstantLengthPoseidonHash(inputSize, new FieldElement[0]);
+        return nativeGetConstantLengthPoseidonHash(inputSize, new FieldElement[0], false);
     }
 
     public static PoseidonHash getInstanceConstantLength(int inputSize, FieldElement[] personalization)
     {
-        return nativeGetConstantLengthPoseidonHash(inputSize, personalization);
+        return nativeGetConstantLengthPoseidonHash(inputSize, personalization, false);
+    }
+
+    /*
+     * Same as getInstanceConstantLength(inputSize, personalization), but if resumable is True
+     * the instance keeps all the field elements it absorbs, so that it can be cloned and its
+     * state serialized. Instances that don't need it should not be resumable.
+     */
+    public static PoseidonHash getInstanceConstantLength(int inputSize, FieldElement[] personalization, boolean resumable)
+    {
+        return nativeGetConstantLengthPoseidonHash(inputSize, personalization, resumable);
     }
 
     /*
@@ -59,12 +69,22 @@ public class PoseidonHash implements AutoCloseable {
      * not multiple of the rate, and modRate is True, an exception will be raised Rust-side.
      */
     public static PoseidonHash getInstanceVariableLength(boolean modRate){
-        return nativeGetVariableLengthPoseidonHash(modRate, new FieldElement[0]);
+        return nativeGetVariableLengthPoseidonHash(modRate, new FieldElement[0], false);
     }
 
     public static PoseidonHash getInstanceVariableLength(boolean modRate, FieldElement[] personalization)
     {
-        return nativeGetVariableLengthPoseidonHash(modRate, personalization);
+        return nativeGetVariableLengthPoseidonHash(modRate, personalization, false);
+    }
+
+    /*
+     * Same as getInstanceVariableLength(modRate, personalization), but if resumable is True
+     * the instance keeps all the field elements it absorbs, so that it can be cloned and its
+     * state serialized. Instances that don't need it should not be resumable.
+     */
+    public static PoseidonHash getInstanceVariableLength(boolean modRate, FieldElement[] personalization, boolean resumable)
+    {
+        return nativeGetVariableLengthPoseidonHash(modRate, personalization, resumable);
     }
 
     private native void nativeUpdate(FieldElement input);
@@ -78,6 +98,19 @@ public class PoseidonHash implements AutoCloseable {
         nativeUpdate(input);
     }
 
+    private native void nativeUpdateBytes(byte[] input);
+
+    /*
+     * Update this instance with the specified bytes. The bytes are prefixed by their length
+     * and packed into field elements, so more than one field element is absorbed: instances
+     * constructed by calling getInstanceConstantLength(inputSize, ...) must take it into account.
+     */
+    public void updateBytes(byte[] input) {
+        if (poseidonHashPointer == 0)
+            throw new IllegalStateException("PoseidonHash instance was freed.");
+        nativeUpdateBytes(input);
+    }
+
     private native FieldElement nativeFinalize();
 
     /*
@@ -111,6 +144,58 @@ public class PoseidonHash implements AutoCloseable {
         return nativeReset(new FieldElement[0]);
     }
 
+    private native PoseidonHash nativeClone();
+
+    /*
+     * Return a new, independent, PoseidonHash instance in the same state as this one:
+     * updating one of them doesn't affect the other.
+     * Throws IllegalStateException if this instance was not created as resumable.
+     */
+    @Override
+    public PoseidonHash clone() {
+        if (poseidonHashPointer == 0)
+            throw new IllegalStateException("PoseidonHash instance was freed.");
+        return nativeClone();
+    }
+
+    private native byte[] nativeSerializeState();
+
+    /*
+     * Serialize the state of this instance, so that the hash can be resumed later (e.g. after
+     * a restart) by calling deserializeState(). The size of the state grows with the number
+     * of field elements absorbed since the instance was created or last reset.
+     * Throws IllegalStateException if this instance was not created as resumable.
+     */
+    public byte[] serializeState() {
+        if (poseidonHashPointer == 0)
+            throw new IllegalStateException("PoseidonHash instance was freed.");
+        return nativeSerializeState();
+    }
+
+    private static native PoseidonHash nativeDeserializeState(byte[] state);
+
+    /*
+     * Restore a resumable PoseidonHash instance from a state obtained by calling serializeState().
+     * Return null if the state is malformed or corrupted.
+     */
+    public static PoseidonHash deserializeState(byte[] state) {
+        return nativeDeserializeState(state);
+    }
+
+    private static native FieldElement nativeHashBytes(byte[] domain, byte[] data);
+
+    /*
+     * Compute and return the digest of data, under the domain separation tag domain:
+     * digests computed with different domains are unrelated, even on the same data.
+     */
+    public static FieldElement hashBytes(byte[] domain, byte[] data) {
+        return nativeHashBytes(domain, data);
+    }
+
+    public static FieldElement hashBytes(byte[] data) {
+        return nativeHashBytes(new byte[0], data);
+    }
+
     /**
      * @deprecated
      * Kept for backward compatibility if needed. Use init -> update -> finalize
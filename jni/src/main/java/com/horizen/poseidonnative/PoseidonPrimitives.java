@@ -0,0 +1,57 @@
+package com.horizen.poseidonnative;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+
+/*
+ * Commitment, PRF and KDF built on the Poseidon hash, each one with its own domain separation:
+ * the same computations can be enforced inside a circuit, e.g. to prove ownership of a note
+ * given its commitment, or the correct derivation of its nullifier.
+ */
+public class PoseidonPrimitives {
+
+    static {
+        Library.load();
+    }
+
+    private static native FieldElement nativeCommit(FieldElement[] message, FieldElement randomness);
+
+    /*
+     * Return the commitment to `message` with randomness `randomness`.
+     * NOTE: The commitment is hiding only if `randomness` is uniformly random (e.g. generated
+     *       by calling FieldElement.createRandom()) and never reused.
+     */
+    public static FieldElement commit(FieldElement[] message, FieldElement randomness) {
+        return nativeCommit(message, randomness);
+    }
+
+    private static native boolean nativeVerifyCommitment(FieldElement commitment, FieldElement[] message, FieldElement randomness);
+
+    /*
+     * Return true if (`message`, `randomness`) is an opening of `commitment`, false otherwise.
+     */
+    public static boolean verifyCommitment(FieldElement commitment, FieldElement[] message, FieldElement randomness) {
+        return nativeVerifyCommitment(commitment, message, randomness);
+    }
+
+    private static native FieldElement nativePrf(FieldElement key, FieldElement input);
+
+    /*
+     * Return the output of the PRF keyed by `key` on `input`.
+     */
+    public static FieldElement prf(FieldElement key, FieldElement input) {
+        return nativePrf(key, input);
+    }
+
+    private static native FieldElement nativeKdf(FieldElement key, FieldElement domain, long index);
+
+    /*
+     * Return the key of index `index` in the domain `domain` derived from the master key `key`.
+     * Keys derived for different domains or indices are independent.
+     */
+    public static FieldElement kdf(FieldElement key, FieldElement domain, long index) {
+        if (index < 0)
+            throw new IllegalArgumentException("index must be not negative.");
+        return nativeKdf(key, domain, index);
+    }
+}
@@ -80,6 +80,28 @@ public class SchnorrPublicKey implements AutoCloseable
     return nativeVerifyKey();
   }
 
+  private native String nativeToJson();
+
+  /*
+  * Versioned JSON representation, with binary data hex encoded:
+  * {"type": "SchnorrPublicKey", "version": 1, ...}
+  */
+  public String toJson() {
+    if (publicKeyPointer == 0)
+      throw new IllegalStateException("Public key was freed.");
+    return nativeToJson();
+  }
+
+  private static native SchnorrPublicKey nativeFromJson(String json);
+
+  /*
+  * Read the JSON representation returned by toJson().
+  * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+  */
+  public static SchnorrPublicKey fromJson(String json) {
+    return nativeFromJson(json);
+  }
+
   @Override
   public void close() throws Exception {
     freePublicKey();
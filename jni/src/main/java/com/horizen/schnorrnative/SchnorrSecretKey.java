@@ -6,12 +6,17 @@ public class SchnorrSecretKey implements AutoCloseable
 {
     public static final int SECRET_KEY_LENGTH;
 
+    public static final int KEYSTORE_LENGTH;
+
     private long secretKeyPointer;
 
     private static native int nativeGetSecretKeySize();
+
+    private static native int nativeGetKeystoreSize();
     static {
         Library.load();
         SECRET_KEY_LENGTH = nativeGetSecretKeySize();
+        KEYSTORE_LENGTH = nativeGetKeystoreSize();
     }
 
     private SchnorrSecretKey(long secretKeyPointer) {
@@ -39,6 +44,53 @@ public class SchnorrSecretKey implements AutoCloseable
         return nativeSerializeSecretKey();
     }
 
+    private native boolean nativeEquals(SchnorrSecretKey sk);
+
+    /*
+     * Comparison is performed in constant time.
+     */
+    @Override
+    public boolean equals(Object o) {
+
+        if (o == this) {
+            return true;
+        }
+
+        if (!(o instanceof SchnorrSecretKey)) {
+            return false;
+        }
+
+        if (secretKeyPointer == 0 || ((SchnorrSecretKey) o).secretKeyPointer == 0)
+            throw new IllegalStateException("Secret key was freed.");
+
+        return nativeEquals((SchnorrSecretKey) o);
+    }
+
+    private native byte[] nativeEncrypt(byte[] password);
+
+    /*
+     * Encrypt this secret key with `password` into a versioned and authenticated keystore.
+     */
+    public byte[] encrypt(byte[] password) {
+        if (secretKeyPointer == 0)
+            throw new IllegalStateException("Secret key was freed.");
+
+        return nativeEncrypt(password);
+    }
+
+    private static native SchnorrSecretKey nativeDecrypt(byte[] keystoreBytes, byte[] password);
+
+    /*
+     * Decrypt a keystore produced by encrypt().
+     * Return NULL if the keystore is corrupted, is not a SchnorrSecretKey keystore, or `password` is wrong.
+     */
+    public static SchnorrSecretKey decrypt(byte[] keystoreBytes, byte[] password) {
+        if (keystoreBytes.length != KEYSTORE_LENGTH)
+            throw new IllegalArgumentException(String.format("Incorrect keystore length, %d expected, %d found", KEYSTORE_LENGTH, keystoreBytes.length));
+
+        return nativeDecrypt(keystoreBytes, password);
+    }
+
     private native void nativeFreeSecretKey();
 
     public void freeSecretKey() {
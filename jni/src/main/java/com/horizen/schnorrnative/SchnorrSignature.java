@@ -64,6 +64,28 @@ public class SchnorrSignature implements AutoCloseable
     }
   }
 
+  private native String nativeToJson();
+
+  /*
+  * Versioned JSON representation, with binary data hex encoded:
+  * {"type": "SchnorrSignature", "version": 1, ...}
+  */
+  public String toJson() {
+    if (signaturePointer == 0)
+      throw new IllegalStateException("Schnorr signature was freed.");
+    return nativeToJson();
+  }
+
+  private static native SchnorrSignature nativeFromJson(String json);
+
+  /*
+  * Read the JSON representation returned by toJson().
+  * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+  */
+  public static SchnorrSignature fromJson(String json) {
+    return nativeFromJson(json);
+  }
+
   @Override
   public void close() throws Exception {
     freeSignature();
@@ -0,0 +1,111 @@
+package com.horizen.secretsharing;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+
+import java.util.List;
+
+/*
+ * Participant to a distributed key generation among `numParticipants` parties, in which each party
+ * deals a Feldman VSS of a random secret. The resulting secret key is never known to anyone, and any
+ * `threshold` participants can use their shares of it.
+ * Protocol:
+ * 1) Each participant broadcasts getCommitment() and getProofOfKnowledge(context), with `context`
+ *    identifying the DKG session;
+ * 2) Each participant checks the proofs of all the others with verifyProofOfKnowledge(), aborting
+ *    if any is invalid, and privately sends getShareFor(j) to participant j;
+ * 3) Each participant verifies the received shares against the senders' commitments, and combines
+ *    them by calling combineShares(); the joint public key is VssCommitment.combine(commitments).getPublicKey().
+ */
+public class DkgParticipant implements AutoCloseable {
+
+    private long dkgParticipantPointer;
+
+    static {
+        Library.load();
+    }
+
+    private DkgParticipant(long dkgParticipantPointer) {
+        if (dkgParticipantPointer == 0)
+            throw new IllegalArgumentException("dkgParticipantPointer must be not null.");
+        this.dkgParticipantPointer = dkgParticipantPointer;
+    }
+
+    private static native DkgParticipant nativeInit(int index, int threshold, int numParticipants);
+
+    /*
+     * Return NULL if `index` is not in 1..numParticipants or threshold is not in 1..numParticipants.
+     */
+    public static DkgParticipant init(int index, int threshold, int numParticipants) {
+        return nativeInit(index, threshold, numParticipants);
+    }
+
+    private native VssCommitment nativeGetCommitment();
+
+    public VssCommitment getCommitment() {
+        if (dkgParticipantPointer == 0)
+            throw new IllegalStateException("DkgParticipant instance was freed.");
+        return nativeGetCommitment();
+    }
+
+    private native byte[] nativeGetProofOfKnowledge(FieldElement context);
+
+    /*
+     * Return the proof of knowledge of the secret dealt by this participant, for the DKG session
+     * identified by `context`. It prevents participants from choosing their commitment as a function
+     * of the others' ones to control the joint public key.
+     */
+    public byte[] getProofOfKnowledge(FieldElement context) {
+        if (dkgParticipantPointer == 0)
+            throw new IllegalStateException("DkgParticipant instance was freed.");
+        return nativeGetProofOfKnowledge(context);
+    }
+
+    private static native boolean nativeVerifyProofOfKnowledge(int index, FieldElement context,
+                                                               VssCommitment commitment, byte[] proof);
+
+    /*
+     * Verify the proof of knowledge of participant `index`, in the DKG session identified by `context`,
+     * of the secret committed in `commitment`.
+     */
+    public static boolean verifyProofOfKnowledge(int index, FieldElement context, VssCommitment commitment,
+                                                 byte[] proof) {
+        return nativeVerifyProofOfKnowledge(index, context, commitment, proof);
+    }
+
+    private native SecretShare nativeGetShareFor(int index);
+
+    /*
+     * Return the share to be privately sent to participant `index`, or NULL if `index` is not valid.
+     */
+    public SecretShare getShareFor(int index) {
+        if (dkgParticipantPointer == 0)
+            throw new IllegalStateException("DkgParticipant instance was freed.");
+        return nativeGetShareFor(index);
+    }
+
+    private static native SecretShare nativeCombineShares(SecretShare[] shares);
+
+    /*
+     * Combine the shares received from all the participants (including the own one)
+     * into the share of the joint secret key.
+     * Return NULL if shares are empty or don't have all the same index.
+     */
+    public static SecretShare combineShares(List<SecretShare> shares) {
+        return nativeCombineShares(shares.toArray(new SecretShare[0]));
+    }
+
+    private native void nativeFreeDkgParticipant(long dkgParticipantPointer);
+
+    public void freeDkgParticipant() {
+        if (dkgParticipantPointer != 0) {
+            nativeFreeDkgParticipant(this.dkgParticipantPointer);
+            dkgParticipantPointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeDkgParticipant();
+    }
+}
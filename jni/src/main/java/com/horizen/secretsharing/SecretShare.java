@@ -0,0 +1,80 @@
+package com.horizen.secretsharing;
+
+import com.horizen.librustsidechains.Library;
+
+public class SecretShare implements AutoCloseable {
+
+    public static final int SECRET_SHARE_LENGTH;
+
+    private long secretSharePointer;
+
+    private static native int nativeGetSecretShareSize();
+
+    static {
+        Library.load();
+        SECRET_SHARE_LENGTH = nativeGetSecretShareSize();
+    }
+
+    private SecretShare(long secretSharePointer) {
+        if (secretSharePointer == 0)
+            throw new IllegalArgumentException("secretSharePointer must be not null.");
+        this.secretSharePointer = secretSharePointer;
+    }
+
+    private native int nativeGetIndex();
+
+    /*
+     * Return the index of this share, i.e. the point (always greater than 0)
+     * at which the sharing polynomial has been evaluated.
+     */
+    public int getIndex() {
+        if (secretSharePointer == 0)
+            throw new IllegalStateException("SecretShare instance was freed.");
+        return nativeGetIndex();
+    }
+
+    private native boolean nativeVerify(VssCommitment commitment);
+
+    /*
+     * Verify that this share is consistent with the sharing polynomial committed in `commitment`.
+     */
+    public boolean verify(VssCommitment commitment) {
+        if (secretSharePointer == 0)
+            throw new IllegalStateException("SecretShare instance was freed.");
+        return nativeVerify(commitment);
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (secretSharePointer == 0)
+            throw new IllegalStateException("SecretShare instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native SecretShare nativeDeserialize(byte[] secretShareBytes);
+
+    /*
+     * Return NULL if `secretShareBytes` don't represent a valid SecretShare.
+     */
+    public static SecretShare deserialize(byte[] secretShareBytes) {
+        if (secretShareBytes.length != SECRET_SHARE_LENGTH)
+            throw new IllegalArgumentException(String.format("Incorrect secret share length, %d expected, %d found", SECRET_SHARE_LENGTH, secretShareBytes.length));
+
+        return nativeDeserialize(secretShareBytes);
+    }
+
+    private native void nativeFreeSecretShare(long secretSharePointer);
+
+    public void freeSecretShare() {
+        if (secretSharePointer != 0) {
+            nativeFreeSecretShare(this.secretSharePointer);
+            secretSharePointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeSecretShare();
+    }
+}
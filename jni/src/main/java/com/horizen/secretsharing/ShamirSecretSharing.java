@@ -0,0 +1,36 @@
+package com.horizen.secretsharing;
+
+import com.horizen.librustsidechains.Library;
+import com.horizen.schnorrnative.SchnorrSecretKey;
+
+import java.util.List;
+
+public class ShamirSecretSharing {
+
+    static {
+        Library.load();
+    }
+
+    private static native ShamirSplitResult nativeSplit(SchnorrSecretKey secretKey, int threshold, int numShares);
+
+    /*
+     * Split `secretKey` into `numShares` shares, of indices 1..numShares, any `threshold` of which
+     * are enough to reconstruct it. The returned VssCommitment allows each holder to verify its share.
+     * Return NULL if threshold is 0 or greater than numShares.
+     */
+    public static ShamirSplitResult split(SchnorrSecretKey secretKey, int threshold, int numShares) {
+        return nativeSplit(secretKey, threshold, numShares);
+    }
+
+    private static native SchnorrSecretKey nativeReconstruct(SecretShare[] shares);
+
+    /*
+     * Reconstruct the secret key from `shares`.
+     * NOTE: Passing less shares than the threshold results in a wrong key: if possible,
+     *       check the result against VssCommitment.getPublicKey().
+     * Return NULL if shares are empty or have duplicated indices.
+     */
+    public static SchnorrSecretKey reconstruct(List<SecretShare> shares) {
+        return nativeReconstruct(shares.toArray(new SecretShare[0]));
+    }
+}
@@ -0,0 +1,25 @@
+package com.horizen.secretsharing;
+
+import com.horizen.librustsidechains.Library;
+
+public class ShamirSplitResult {
+    private SecretShare[] shares;
+    private VssCommitment commitment;
+
+    static {
+        Library.load();
+    }
+
+    public ShamirSplitResult(SecretShare[] shares, VssCommitment commitment) {
+        this.shares = shares;
+        this.commitment = commitment;
+    }
+
+    public SecretShare[] getShares() {
+        return this.shares;
+    }
+
+    public VssCommitment getCommitment() {
+        return this.commitment;
+    }
+}
@@ -0,0 +1,99 @@
+package com.horizen.secretsharing;
+
+import com.horizen.librustsidechains.Library;
+import com.horizen.schnorrnative.SchnorrPublicKey;
+
+import java.util.List;
+
+/*
+ * Feldman commitment to the coefficients of a sharing polynomial.
+ */
+public class VssCommitment implements AutoCloseable {
+
+    private long vssCommitmentPointer;
+
+    static {
+        Library.load();
+    }
+
+    private VssCommitment(long vssCommitmentPointer) {
+        if (vssCommitmentPointer == 0)
+            throw new IllegalArgumentException("vssCommitmentPointer must be not null.");
+        this.vssCommitmentPointer = vssCommitmentPointer;
+    }
+
+    private native int nativeGetThreshold();
+
+    /*
+     * Return the number of shares needed to reconstruct the committed secret.
+     */
+    public int getThreshold() {
+        if (vssCommitmentPointer == 0)
+            throw new IllegalStateException("VssCommitment instance was freed.");
+        return nativeGetThreshold();
+    }
+
+    private native SchnorrPublicKey nativeGetPublicKey();
+
+    /*
+     * Return the public key corresponding to the shared secret key.
+     */
+    public SchnorrPublicKey getPublicKey() {
+        if (vssCommitmentPointer == 0)
+            throw new IllegalStateException("VssCommitment instance was freed.");
+        return nativeGetPublicKey();
+    }
+
+    private native SchnorrPublicKey nativeGetPublicKeyShare(int index);
+
+    /*
+     * Return the public key corresponding to the secret share of index `index`.
+     */
+    public SchnorrPublicKey getPublicKeyShare(int index) {
+        if (vssCommitmentPointer == 0)
+            throw new IllegalStateException("VssCommitment instance was freed.");
+        return nativeGetPublicKeyShare(index);
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (vssCommitmentPointer == 0)
+            throw new IllegalStateException("VssCommitment instance was freed.");
+        return nativeSerialize();
+    }
+
+    private static native VssCommitment nativeDeserialize(byte[] vssCommitmentBytes);
+
+    /*
+     * Return NULL if `vssCommitmentBytes` don't represent a valid VssCommitment.
+     */
+    public static VssCommitment deserialize(byte[] vssCommitmentBytes) {
+        return nativeDeserialize(vssCommitmentBytes);
+    }
+
+    private static native VssCommitment nativeCombine(VssCommitment[] commitments);
+
+    /*
+     * Combine the commitments broadcast by all the participants to a DKG into the commitment
+     * to the joint sharing polynomial, whose public key is the joint public key.
+     * Return NULL if the commitments have different thresholds.
+     */
+    public static VssCommitment combine(List<VssCommitment> commitments) {
+        return nativeCombine(commitments.toArray(new VssCommitment[0]));
+    }
+
+    private native void nativeFreeVssCommitment(long vssCommitmentPointer);
+
+    public void freeVssCommitment() {
+        if (vssCommitmentPointer != 0) {
+            nativeFreeVssCommitment(this.vssCommitmentPointer);
+            vssCommitmentPointer = 0;
+        }
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeVssCommitment();
+    }
+}
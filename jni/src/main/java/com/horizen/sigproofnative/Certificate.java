@@ -0,0 +1,158 @@
+package com.horizen.sigproofnative;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.librustsidechains.Library;
+
+import java.util.List;
+
+/*
+ * Certificate of a sidechain epoch, carrying its proof, in the mainchain wire format.
+ * It's built without inputs and change outputs, which are added by the mainchain wallet
+ * when funding it.
+ */
+public class Certificate implements AutoCloseable {
+
+    private long certificatePointer;
+
+    static {
+        Library.load();
+    }
+
+    private Certificate(long certificatePointer) {
+        if (certificatePointer == 0)
+            throw new IllegalArgumentException("certificatePointer must be not null.");
+        this.certificatePointer = certificatePointer;
+    }
+
+    private static native Certificate nativeCreate(
+            FieldElement scId,
+            int epochNumber,
+            long quality,
+            FieldElement endCumulativeScTxCommTreeRoot,
+            BackwardTransfer[] btList,
+            FieldElement[] customFields,
+            long btrFee,
+            long ftMinAmount,
+            byte[] proof
+    );
+
+    /*
+     * Throws IllegalArgumentException if the fields are out of the mainchain limits
+     * or the proof is malformed. The proof may be sealed in an envelope.
+     */
+    public static Certificate create(
+            FieldElement scId,
+            int epochNumber,
+            long quality,
+            FieldElement endCumulativeScTxCommTreeRoot,
+            List<BackwardTransfer> btList,
+            List<FieldElement> customFields,
+            long btrFee,
+            long ftMinAmount,
+            byte[] proof
+    )
+    {
+        return nativeCreate(
+                scId, epochNumber, quality, endCumulativeScTxCommTreeRoot,
+                btList.toArray(new BackwardTransfer[0]),
+                customFields.toArray(new FieldElement[0]),
+                btrFee, ftMinAmount, proof
+        );
+    }
+
+    // Certificate carrying the proof and quality of the naive threshold signature circuit
+    public static Certificate create(
+            FieldElement scId,
+            int epochNumber,
+            FieldElement endCumulativeScTxCommTreeRoot,
+            List<BackwardTransfer> btList,
+            long btrFee,
+            long ftMinAmount,
+            CreateProofResult proofResult
+    )
+    {
+        return nativeCreate(
+                scId, epochNumber, proofResult.getQuality(), endCumulativeScTxCommTreeRoot,
+                btList.toArray(new BackwardTransfer[0]), new FieldElement[0],
+                btrFee, ftMinAmount, proofResult.getProof()
+        );
+    }
+
+    private static native void nativeFreeCertificate(long certificatePointer);
+
+    // Free Rust memory
+    public void freeCertificate() {
+        if (certificatePointer != 0) {
+            nativeFreeCertificate(this.certificatePointer);
+            certificatePointer = 0;
+        }
+    }
+
+    private native long nativeGetQuality();
+
+    public long getQuality() {
+        if (certificatePointer == 0)
+            throw new IllegalStateException("Certificate was freed.");
+        return nativeGetQuality();
+    }
+
+    private native byte[] nativeGetProof();
+
+    // Bare proof, without envelope
+    public byte[] getProof() {
+        if (certificatePointer == 0)
+            throw new IllegalStateException("Certificate was freed.");
+        return nativeGetProof();
+    }
+
+    private native boolean nativeVerify(
+            FieldElement constant,
+            boolean checkProof,
+            boolean compressedProof,
+            String verificationKeyPath,
+            boolean checkVerificationKey,
+            boolean compressedVk
+    );
+
+    /*  Verify the proof of the certificate. `constant` can be null if the sidechain has none.
+     *  Throws IllegalArgumentException if the proof or the verification key can't be read.
+     */
+    public boolean verify(
+            FieldElement constant,
+            boolean checkProof,
+            boolean compressedProof,
+            String verificationKeyPath,
+            boolean checkVerificationKey,
+            boolean compressedVk
+    )
+    {
+        if (certificatePointer == 0)
+            throw new IllegalStateException("Certificate was freed.");
+        return nativeVerify(constant, checkProof, compressedProof, verificationKeyPath,
+                checkVerificationKey, compressedVk);
+    }
+
+    public boolean verify(FieldElement constant, String verificationKeyPath) {
+        return verify(constant, true, true, verificationKeyPath, false, true);
+    }
+
+    private native byte[] nativeSerialize();
+
+    public byte[] serialize() {
+        if (certificatePointer == 0)
+            throw new IllegalStateException("Certificate was freed.");
+        return nativeSerialize();
+    }
+
+    private static native Certificate nativeDeserialize(byte[] certificateBytes);
+
+    // Throws IllegalArgumentException if the bytes are not a valid certificate
+    public static Certificate deserialize(byte[] certificateBytes) {
+        return nativeDeserialize(certificateBytes);
+    }
+
+    @Override
+    public void close() throws Exception {
+        freeCertificate();
+    }
+}
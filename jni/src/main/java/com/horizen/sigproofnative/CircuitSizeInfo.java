@@ -0,0 +1,41 @@
+package com.horizen.sigproofnative;
+
+import com.horizen.librustsidechains.Library;
+
+public class CircuitSizeInfo {
+    private long numConstraints;
+    private int proofSize;
+    private int vkSize;
+    private boolean fits;
+
+    static {
+        Library.load();
+    }
+
+    public CircuitSizeInfo(long numConstraints, int proofSize, int vkSize, boolean fits) {
+        this.numConstraints = numConstraints;
+        this.proofSize = proofSize;
+        this.vkSize = vkSize;
+        this.fits = fits;
+    }
+
+    public long getNumConstraints() {
+        return this.numConstraints;
+    }
+
+    public int getProofSize() {
+        return this.proofSize;
+    }
+
+    public int getVkSize() {
+        return this.vkSize;
+    }
+
+    /*
+     * Return True if proof size and vk size don't exceed the limits
+     * the CircuitSizeInfo was computed for.
+     */
+    public boolean fits() {
+        return this.fits;
+    }
+}
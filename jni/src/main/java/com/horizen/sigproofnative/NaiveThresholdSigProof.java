@@ -110,6 +110,60 @@ public class NaiveThresholdSigProof {
         );
     }
 
+    private static native CircuitSizeInfo nativeGetCircuitSizeInfo(
+        ProvingSystemType psType,
+        long maxPks,
+        boolean zk,
+        int supportedSegmentSize,
+        int maxProofSize,
+        int maxVkSize
+    );
+
+    /*
+     * Compute number of constraints, proof size and vk size of the circuit supporting
+     * `maxPks` public keys, and whether they fit `maxProofSize` and `maxVkSize`,
+     * without performing the setup. DLOG keys are not required to be loaded.
+     * Return NULL if it was not possible to compute them, or if any of the sizes is negative.
+     */
+    public static CircuitSizeInfo getCircuitSizeInfo(
+        ProvingSystemType psType,
+        long maxPks,
+        boolean zk,
+        int supportedSegmentSize,
+        int maxProofSize,
+        int maxVkSize
+    )
+    {
+        return nativeGetCircuitSizeInfo(psType, maxPks, zk, supportedSegmentSize, maxProofSize, maxVkSize);
+    }
+
+    private static native long nativeGetMaxSupportedPks(
+        ProvingSystemType psType,
+        boolean zk,
+        int supportedSegmentSize,
+        int maxProofSize,
+        int maxVkSize,
+        long maxPksBound
+    );
+
+    /*
+     * Return the largest maxPks, not greater than `maxPksBound`, for which proof and vk
+     * sizes of the circuit don't exceed `maxProofSize` and `maxVkSize`.
+     * Return 0 if no circuit fits the limits, -1 if an error occurred or if any of the sizes
+     * is negative.
+     */
+    public static long getMaxSupportedPks(
+        ProvingSystemType psType,
+        boolean zk,
+        int supportedSegmentSize,
+        int maxProofSize,
+        int maxVkSize,
+        long maxPksBound
+    )
+    {
+        return nativeGetMaxSupportedPks(psType, zk, supportedSegmentSize, maxProofSize, maxVkSize, maxPksBound);
+    }
+
     private static native CreateProofResult nativeCreateProof(
             BackwardTransfer[] bt,
             FieldElement scId,
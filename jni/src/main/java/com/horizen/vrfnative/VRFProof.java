@@ -70,6 +70,28 @@ public class VRFProof implements AutoCloseable
     }
   }
 
+  private native String nativeToJson();
+
+  /*
+  * Versioned JSON representation, with binary data hex encoded:
+  * {"type": "VRFProof", "version": 1, ...}
+  */
+  public String toJson() {
+    if (proofPointer == 0)
+      throw new IllegalStateException("Proof was freed.");
+    return nativeToJson();
+  }
+
+  private static native VRFProof nativeFromJson(String json);
+
+  /*
+  * Read the JSON representation returned by toJson().
+  * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+  */
+  public static VRFProof fromJson(String json) {
+    return nativeFromJson(json);
+  }
+
   @Override
   public void close() throws Exception {
     freeProof();
@@ -81,6 +81,28 @@ public class VRFPublicKey implements AutoCloseable
     return nativeProofToHash(proof, message);
   }
 
+  private native String nativeToJson();
+
+  /*
+  * Versioned JSON representation, with binary data hex encoded:
+  * {"type": "VRFPublicKey", "version": 1, ...}
+  */
+  public String toJson() {
+    if (publicKeyPointer == 0)
+      throw new IllegalStateException("Public key was freed.");
+    return nativeToJson();
+  }
+
+  private static native VRFPublicKey nativeFromJson(String json);
+
+  /*
+  * Read the JSON representation returned by toJson().
+  * Throws IllegalArgumentException if `json` is malformed, or of another type or version.
+  */
+  public static VRFPublicKey fromJson(String json) {
+    return nativeFromJson(json);
+  }
+
   @Override
   public void close() throws Exception {
     freePublicKey();
@@ -6,13 +6,18 @@ public class VRFSecretKey implements AutoCloseable
 {
     public static final int SECRET_KEY_LENGTH;
 
+    public static final int KEYSTORE_LENGTH;
+
     private long secretKeyPointer;
 
     private static native int nativeGetSecretKeySize();
 
+    private static native int nativeGetKeystoreSize();
+
     static {
         Library.load();
         SECRET_KEY_LENGTH = nativeGetSecretKeySize();
+        KEYSTORE_LENGTH = nativeGetKeystoreSize();
     }
 
     private VRFSecretKey(long secretKeyPointer) {
@@ -40,6 +45,53 @@ public class VRFSecretKey implements AutoCloseable
         return nativeSerializeSecretKey();
     }
 
+    private native boolean nativeEquals(VRFSecretKey sk);
+
+    /*
+     * Comparison is performed in constant time.
+     */
+    @Override
+    public boolean equals(Object o) {
+
+        if (o == this) {
+            return true;
+        }
+
+        if (!(o instanceof VRFSecretKey)) {
+            return false;
+        }
+
+        if (secretKeyPointer == 0 || ((VRFSecretKey) o).secretKeyPointer == 0)
+            throw new IllegalStateException("Secret key was freed.");
+
+        return nativeEquals((VRFSecretKey) o);
+    }
+
+    private native byte[] nativeEncrypt(byte[] password);
+
+    /*
+     * Encrypt this secret key with `password` into a versioned and authenticated keystore.
+     */
+    public byte[] encrypt(byte[] password) {
+        if (secretKeyPointer == 0)
+            throw new IllegalStateException("Secret key was freed.");
+
+        return nativeEncrypt(password);
+    }
+
+    private static native VRFSecretKey nativeDecrypt(byte[] keystoreBytes, byte[] password);
+
+    /*
+     * Decrypt a keystore produced by encrypt().
+     * Return NULL if the keystore is corrupted, is not a VRFSecretKey keystore, or `password` is wrong.
+     */
+    public static VRFSecretKey decrypt(byte[] keystoreBytes, byte[] password) {
+        if (keystoreBytes.length != KEYSTORE_LENGTH)
+            throw new IllegalArgumentException(String.format("Incorrect keystore length, %d expected, %d found", KEYSTORE_LENGTH, keystoreBytes.length));
+
+        return nativeDecrypt(keystoreBytes, password);
+    }
+
     private native void nativeFreeSecretKey();
 
     public void freeSecretKey() {
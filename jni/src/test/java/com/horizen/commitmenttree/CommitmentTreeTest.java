@@ -2,6 +2,7 @@ package com.horizen.commitmenttree;
 
 import org.junit.Ignore;
 import org.junit.Test;
+import org.junit.function.ThrowingRunnable;
 import static org.junit.Assert.*;
 import com.horizen.commitmenttree.CommitmentTree;
 import com.horizen.sigproofnative.BackwardTransfer;
@@ -32,7 +33,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addScCreation() {
+    public void addScCreation() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -111,7 +112,65 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addForwardTransfer() {
+    public void addScCreationWithParams() throws Exception {
+        byte[] txHash = generateRandomBytes(32);
+        int outIdx = 1;
+        byte[] scId = generateFieldElementBytes();
+        byte[] pubKey = generateRandomBytes(32);
+        byte[] customCreationData = generateRandomBytes(100);
+        byte[] constant = generateFieldElementBytes();
+        byte[] certVerificationKey = generateRandomBytes(2000); // random bytes simulating snark Vk
+        CustomFieldElementsConfig[] customFieldElementsConfigs = new CustomFieldElementsConfig[]{
+                new CustomFieldElementsConfig((byte) 8)
+        };
+        CustomBitvectorElementsConfig[] customBitvectorElementsConfigs = new CustomBitvectorElementsConfig[] {
+                new CustomBitvectorElementsConfig(254 * 8, 300)
+        };
+
+        try (SidechainCreationParams params = SidechainCreationParams.create(10, 1000, pubKey, customCreationData,
+                Optional.of(constant), certVerificationKey, Optional.empty(), customFieldElementsConfigs,
+                customBitvectorElementsConfigs, 20, 10, (byte) 1)) {
+
+            // Same commitment of the sidechain creation output added field by field
+            CommitmentTree commTree = CommitmentTree.init();
+            assertTrue("Sidechain creation output expected to be added.",
+                    commTree.addScCr(scId, txHash, outIdx, params));
+            CommitmentTree referenceTree = CommitmentTree.init();
+            assertTrue("Sidechain creation output expected to be added.",
+                    referenceTree.addScCr(scId, 1000, pubKey, txHash, outIdx, 10, (byte) 1,
+                            customFieldElementsConfigs, customBitvectorElementsConfigs, 10, 20,
+                            customCreationData, Optional.of(constant), certVerificationKey, Optional.empty()));
+
+            FieldElement commitment = commTree.getCommitment().get();
+            FieldElement referenceCommitment = referenceTree.getCommitment().get();
+            assertEquals("Commitments expected to be the same.", referenceCommitment, commitment);
+            commitment.freeFieldElement();
+            referenceCommitment.freeFieldElement();
+            commTree.freeCommitmentTree();
+            referenceTree.freeCommitmentTree();
+
+            byte[] paramsBytes = params.serialize();
+            try (SidechainCreationParams deserializedParams = SidechainCreationParams.deserialize(paramsBytes)) {
+                assertArrayEquals("Deserialized params should be serialized to same bytes",
+                        paramsBytes, deserializedParams.serialize());
+            }
+            assertThrows(IllegalArgumentException.class,
+                    () -> SidechainCreationParams.deserialize(Arrays.copyOf(paramsBytes, paramsBytes.length - 1)));
+
+            // Random bytes are not a valid verification key
+            assertThrows(IllegalArgumentException.class, () -> params.validate(true, 1 << 14, false, 7000, 4000));
+        }
+
+        // Malformed inputs are reported on the Java side parameter
+        CommitmentTreeInputException e = assertThrows(CommitmentTreeInputException.class,
+                () -> SidechainCreationParams.create(10, 1000, generateRandomBytes(31), customCreationData,
+                        Optional.empty(), certVerificationKey, Optional.empty(), customFieldElementsConfigs,
+                        customBitvectorElementsConfigs, 20, 10, (byte) 1));
+        assertEquals("pubKey", e.getFieldName());
+    }
+
+    @Test
+    public void addForwardTransfer() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -131,7 +190,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addBackwardTransfer() {
+    public void addBackwardTransfer() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -151,7 +210,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addBackwardTransferWithEmptyRequestData() {
+    public void addBackwardTransferWithEmptyRequestData() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -170,7 +229,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addCeasedSidechainWithdrawal() {
+    public void addCeasedSidechainWithdrawal() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -188,7 +247,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addCertificate() {
+    public void addCertificate() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -231,7 +290,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void addCertificateLeaf() {
+    public void addCertificateLeaf() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -259,7 +318,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void existenceProofTest() {
+    public void existenceProofTest() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -294,7 +353,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void existenceProofSerializationTest() {
+    public void existenceProofSerializationTest() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[] scId = generateFieldElementBytes();
 
@@ -319,6 +378,11 @@ public class CommitmentTreeTest {
         ScExistenceProof deserializedExistanceProof = ScExistenceProof.deserialize(existenceProofBytes);
 
         assertArrayEquals("Deserialized existence proof should be serialized to same bytes", deserializedExistanceProof.serialize(), existenceProofBytes);
+
+        ScExistenceProof existenceProofFromJson = ScExistenceProof.fromJson(existenceOpt.get().toJson());
+        assertArrayEquals("Existence proof read from JSON should be serialized to same bytes", existenceProofFromJson.serialize(), existenceProofBytes);
+        assertThrows(IllegalArgumentException.class, () -> ScAbsenceProof.fromJson(existenceOpt.get().toJson()));
+        existenceProofFromJson.freeScExistenceProof();
         assertTrue("Commitment verification of original proof expected to be successful", CommitmentTree.verifyScCommitment(scCommitmentOpt.get(), existenceOpt.get(), commitmentOpt.get()));
         assertTrue("Commitment verification of deserialized proof expected to be successful", CommitmentTree.verifyScCommitment(scCommitmentOpt.get(), deserializedExistanceProof, commitmentOpt.get()));
 
@@ -330,7 +394,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void absenceProofTest() {
+    public void absenceProofTest() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[][] scId = new byte[5][];
 
@@ -399,7 +463,7 @@ public class CommitmentTreeTest {
     }
 
     @Test
-    public void absenceProofSerializationTest() {
+    public void absenceProofSerializationTest() throws Exception {
         CommitmentTree commTree = CommitmentTree.init();
         byte[][] scId = new byte[3][];
 
@@ -464,4 +528,237 @@ public class CommitmentTreeTest {
         commitmentOpt.get().freeFieldElement();
         commTree.freeCommitmentTree();
     }
-}
\ No newline at end of file
+
+    @Test
+    public void serializationTest() throws Exception {
+        CommitmentTree commTree = CommitmentTree.init();
+        byte[] scId = generateFieldElementBytes();
+        byte[] otherScId = generateFieldElementBytes();
+
+        for (byte[] id: new byte[][] { scId, otherScId }) {
+            assertTrue("Forward transfer output expected to be added.",
+                    commTree.addFwt(id, 100, generateFieldElementBytes(), generateRandomBytes(20),
+                            generateFieldElementBytes(), 200));
+            assertTrue("Backward transfer output expected to be added.",
+                    commTree.addBtr(id, 120, generateRandomBytes(20),
+                            new byte[][] { generateFieldElementBytes() }, generateFieldElementBytes(), 220));
+        }
+        assertTrue("Ceased Sidechain Withdrawal output expected to be added.",
+                commTree.addCsw(generateFieldElementBytes(), 140, generateFieldElementBytes(), generateRandomBytes(20)));
+
+        FieldElement commitment = commTree.getCommitment().get();
+        FieldElement scCommitment = commTree.getScCommitment(scId).get();
+
+        // Restored tree has the same commitments
+        byte[] state = commTree.serialize();
+        CommitmentTree restoredTree = CommitmentTree.deserialize(state);
+        assertNotNull("Commitment tree expected to be restored.", restoredTree);
+
+        FieldElement restoredCommitment = restoredTree.getCommitment().get();
+        FieldElement restoredScCommitment = restoredTree.getScCommitment(scId).get();
+        assertEquals("Different commitment found after deserialization.", commitment, restoredCommitment);
+        assertEquals("Different sidechain commitment found after deserialization.", scCommitment, restoredScCommitment);
+
+        // Adding a transaction to another sidechain changes the overall commitment only
+        assertTrue("Forward transfer output expected to be added.",
+                commTree.addFwt(otherScId, 10, generateFieldElementBytes(), generateRandomBytes(20),
+                        generateFieldElementBytes(), 0));
+        FieldElement updatedCommitment = commTree.getCommitment().get();
+        FieldElement updatedScCommitment = commTree.getScCommitment(scId).get();
+        assertNotEquals("Commitment expected to change.", commitment, updatedCommitment);
+        assertEquals("Sidechain commitment expected not to change.", scCommitment, updatedScCommitment);
+
+        // Corrupted state
+        state[state.length / 2] ^= 1;
+        assertNull("Corrupted state expected to be rejected.", CommitmentTree.deserialize(state));
+
+        commitment.freeFieldElement();
+        scCommitment.freeFieldElement();
+        restoredCommitment.freeFieldElement();
+        restoredScCommitment.freeFieldElement();
+        updatedCommitment.freeFieldElement();
+        updatedScCommitment.freeFieldElement();
+        restoredTree.freeCommitmentTree();
+        commTree.freeCommitmentTree();
+    }
+
+    @Test
+    public void txInclusionProofTest() throws Exception {
+        CommitmentTree commTree = CommitmentTree.init();
+        byte[] scId = generateFieldElementBytes();
+        byte[] ceasedScId = generateFieldElementBytes();
+
+        for (int i = 0; i < 3; i++) {
+            assertTrue("Forward transfer output expected to be added.",
+                    commTree.addFwt(scId, 100, generateFieldElementBytes(), generateRandomBytes(20),
+                            generateFieldElementBytes(), i));
+        }
+        assertTrue("Backward transfer output expected to be added.",
+                commTree.addBtr(scId, 120, generateRandomBytes(20),
+                        new byte[][] { generateFieldElementBytes() }, generateFieldElementBytes(), 0));
+        assertTrue("Certificate leaf expected to be added.",
+                commTree.addCertLeaf(scId, generateFieldElementBytes()));
+        assertTrue("Ceased Sidechain Withdrawal output expected to be added.",
+                commTree.addCsw(ceasedScId, 140, generateFieldElementBytes(), generateRandomBytes(20)));
+
+        FieldElement commitment = commTree.getCommitment().get();
+
+        TxInclusionProof[] proofs = new TxInclusionProof[] {
+            commTree.getFwtInclusionProof(scId, 2).get(),
+            commTree.getBtrInclusionProof(scId, 0).get(),
+            commTree.getCertInclusionProof(scId, 0).get(),
+            commTree.getCswInclusionProof(ceasedScId, 0).get()
+        };
+        byte[] txTypes = new byte[] {
+            TxInclusionProof.FWT_TX_TYPE,
+            TxInclusionProof.BTR_TX_TYPE,
+            TxInclusionProof.CERT_TX_TYPE,
+            TxInclusionProof.CSW_TX_TYPE
+        };
+        byte[][] scIds = new byte[][] { scId, scId, scId, ceasedScId };
+        assertEquals("Different leaf index found.", 2, proofs[0].getLeafIndex());
+
+        try (FieldElement wrongCommitment = FieldElement.createRandom();
+             FieldElement wrongLeaf = FieldElement.createRandom()) {
+            for (int i = 0; i < proofs.length; i++) {
+                TxInclusionProof proof = proofs[i];
+                byte txType = txTypes[i];
+                FieldElement leaf = proof.getLeaf();
+
+                assertTrue("Transaction expected to be included.", proof.verify(txType, scIds[i], leaf, commitment));
+                assertFalse("Transaction expected not to be included.",
+                        proof.verify(txType, scIds[i], leaf, wrongCommitment));
+
+                // Proof of another transaction
+                assertFalse("Proof expected to be for another transaction type.",
+                        proof.verify(txTypes[(i + 1) % txTypes.length], scIds[i], leaf, commitment));
+                assertFalse("Proof expected to be for another sidechain.",
+                        proof.verify(txType, generateFieldElementBytes(), leaf, commitment));
+                assertFalse("Proof expected to be for another leaf.",
+                        proof.verify(txType, scIds[i], wrongLeaf, commitment));
+                assertMalformed("scId", () -> proof.verify(txType, new byte[1], leaf, commitment));
+
+                TxInclusionProof deserializedProof = TxInclusionProof.deserialize(proof.serialize());
+                assertNotNull("Proof expected to be deserialized.", deserializedProof);
+                assertTrue("Transaction expected to be included.",
+                        deserializedProof.verify(txType, scIds[i], leaf, commitment));
+
+                TxInclusionProof proofFromJson = TxInclusionProof.fromJson(proof.toJson());
+                assertArrayEquals("Proof read from JSON should be serialized to same bytes", proof.serialize(), proofFromJson.serialize());
+                assertThrows(IllegalArgumentException.class, () -> ScExistenceProof.fromJson(proofFromJson.toJson()));
+                proofFromJson.freeTxInclusionProof();
+
+                FieldElement deserializedLeaf = deserializedProof.getLeaf();
+                assertEquals("Different leaf found after deserialization.", leaf, deserializedLeaf);
+
+                leaf.freeFieldElement();
+                deserializedLeaf.freeFieldElement();
+                deserializedProof.freeTxInclusionProof();
+                proof.freeTxInclusionProof();
+            }
+        }
+
+        // Missing transactions
+        assertFalse("Proof expected to be missed.", commTree.getFwtInclusionProof(scId, 3).isPresent());
+        assertFalse("Proof expected to be missed.", commTree.getCswInclusionProof(scId, 0).isPresent());
+        assertNull("Invalid proof expected to be rejected.", TxInclusionProof.deserialize(new byte[] { 0 }));
+
+        commitment.freeFieldElement();
+        commTree.freeCommitmentTree();
+    }
+
+    private void assertMalformed(String fieldName, ThrowingRunnable call) {
+        CommitmentTreeInputException e = assertThrows(CommitmentTreeInputException.class, call);
+        assertEquals("Different malformed field reported.", fieldName, e.getFieldName());
+    }
+
+    // Byte array of random length different from the expected one
+    private byte[] generateWrongLengthBytes(Random rnd, int expectedLength) {
+        int len = rnd.nextInt(2 * expectedLength);
+        return generateRandomBytes(len >= expectedLength ? len + 1 : len);
+    }
+
+    @Test
+    public void malformedInputsTest() throws Exception {
+        CommitmentTree commTree = CommitmentTree.init();
+        Random rnd = new Random();
+
+        int fieldSize = FieldElement.FIELD_ELEMENT_LENGTH;
+        byte[] scId = generateFieldElementBytes();
+        byte[] invalidFieldElement = new byte[fieldSize];
+        Arrays.fill(invalidFieldElement, (byte) 0xFF);
+
+        for (int i = 0; i < 20; i++) {
+            byte[] wrongFieldElement = rnd.nextBoolean() ? invalidFieldElement : generateWrongLengthBytes(rnd, fieldSize);
+            byte[] wrongAddress = generateWrongLengthBytes(rnd, 20);
+
+            // Forward transfers
+            assertMalformed("scId", () -> commTree.addFwt(wrongFieldElement, 100, generateFieldElementBytes(),
+                    generateRandomBytes(20), generateFieldElementBytes(), 0));
+            assertMalformed("pubKey", () -> commTree.addFwt(scId, 100, generateWrongLengthBytes(rnd, 32),
+                    generateRandomBytes(20), generateFieldElementBytes(), 0));
+            assertMalformed("mcReturnAddress", () -> commTree.addFwt(scId, 100, generateFieldElementBytes(),
+                    wrongAddress, generateFieldElementBytes(), 0));
+            assertMalformed("txHash", () -> commTree.addFwt(scId, 100, generateFieldElementBytes(),
+                    generateRandomBytes(20), generateWrongLengthBytes(rnd, 32), 0));
+
+            // Backward transfer requests
+            assertMalformed("mcDestinationAddress", () -> commTree.addBtr(scId, 120, wrongAddress,
+                    new byte[][] { generateFieldElementBytes() }, generateFieldElementBytes(), 0));
+            assertMalformed("scRequestData[1]", () -> commTree.addBtr(scId, 120, generateRandomBytes(20),
+                    new byte[][] { generateFieldElementBytes(), wrongFieldElement }, generateFieldElementBytes(), 0));
+            assertMalformed("scRequestData[0]", () -> commTree.addBtr(scId, 120, generateRandomBytes(20),
+                    new byte[][] { null }, generateFieldElementBytes(), 0));
+
+            // Certificates
+            BackwardTransfer[] btList = new BackwardTransfer[] {
+                    new BackwardTransfer(generateRandomBytes(20), 100L),
+                    new BackwardTransfer(wrongAddress, 200L)
+            };
+            assertMalformed("btList[1].publicKeyHash", () -> commTree.addCert(scId, 0, 0, btList,
+                    Optional.empty(), generateFieldElementBytes(), 0, 0));
+            assertMalformed("customFields[0]", () -> commTree.addCert(scId, 0, 0, new BackwardTransfer[0],
+                    Optional.of(new byte[][] { wrongFieldElement }), generateFieldElementBytes(), 0, 0));
+            assertMalformed("endCumulativeScTxCommitmentTreeRoot", () -> commTree.addCert(scId, 0, 0,
+                    new BackwardTransfer[0], Optional.empty(), wrongFieldElement, 0, 0));
+            assertMalformed("leaf", () -> commTree.addCertLeaf(scId, wrongFieldElement));
+
+            // Ceased sidechain withdrawals
+            assertMalformed("nullifier", () -> commTree.addCsw(scId, 140, wrongFieldElement, generateRandomBytes(20)));
+            assertMalformed("mcPkHash", () -> commTree.addCsw(scId, 140, generateFieldElementBytes(), wrongAddress));
+
+            // Getters
+            assertMalformed("scId", () -> commTree.getScCommitment(wrongFieldElement));
+            assertMalformed("scId", () -> commTree.getFwtCommitment(wrongFieldElement));
+            assertMalformed("scId", () -> commTree.getScExistenceProof(wrongFieldElement));
+            assertMalformed("scId", () -> commTree.getScAbsenceProof(wrongFieldElement));
+            assertMalformed("scId", () -> commTree.getFwtInclusionProof(wrongFieldElement, 0));
+        }
+
+        // Sidechain creation
+        assertMalformed("constant", () -> commTree.addScCr(scId, 100, generateFieldElementBytes(),
+                generateFieldElementBytes(), 0, 1000, (byte) 1, new CustomFieldElementsConfig[0],
+                new CustomBitvectorElementsConfig[0], 0, 0, new byte[0], Optional.of(invalidFieldElement),
+                generateRandomBytes(2000), Optional.empty()));
+        assertMalformed("customBitvectorElementsConfigs[0]", () -> commTree.addScCr(scId, 100,
+                generateFieldElementBytes(), generateFieldElementBytes(), 0, 1000, (byte) 1,
+                new CustomFieldElementsConfig[0], new CustomBitvectorElementsConfig[] { null }, 0, 0, new byte[0],
+                Optional.empty(), generateRandomBytes(2000), Optional.empty()));
+        assertMalformed("certVerificationKey", () -> commTree.addScCr(scId, 100, generateFieldElementBytes(),
+                generateFieldElementBytes(), 0, 1000, (byte) 1, new CustomFieldElementsConfig[0],
+                new CustomBitvectorElementsConfig[0], 0, 0, new byte[0], Optional.empty(), null, Optional.empty()));
+
+        assertMalformed("state", () -> CommitmentTree.deserialize(null));
+
+        // Nothing has been added
+        CommitmentTree emptyTree = CommitmentTree.init();
+        FieldElement commitment = commTree.getCommitment().get();
+        FieldElement emptyCommitment = emptyTree.getCommitment().get();
+        assertEquals("Malformed inputs expected not to change the tree.", emptyCommitment, commitment);
+
+        commitment.freeFieldElement();
+        emptyCommitment.freeFieldElement();
+        emptyTree.freeCommitmentTree();
+        commTree.freeCommitmentTree();
+    }
+}
@@ -0,0 +1,103 @@
+package com.horizen.frost;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.schnorrnative.SchnorrKeyPair;
+import com.horizen.schnorrnative.SchnorrSignature;
+import com.horizen.secretsharing.SecretShare;
+import com.horizen.secretsharing.ShamirSecretSharing;
+import com.horizen.secretsharing.ShamirSplitResult;
+import com.horizen.secretsharing.VssCommitment;
+import org.junit.Test;
+
+import java.util.ArrayList;
+import java.util.List;
+
+import static org.junit.Assert.*;
+
+public class FrostTest {
+
+    static int threshold = 3;
+    static int numShares = 5;
+
+    @Test
+    public void testFrostSignAggregate() throws Exception {
+        try(SchnorrKeyPair keyPair = SchnorrKeyPair.generate(); FieldElement message = FieldElement.createRandom())
+        {
+            ShamirSplitResult splitResult = ShamirSecretSharing.split(keyPair.getSecretKey(), threshold, numShares);
+            assertNotNull("Secret key split must succeed", splitResult);
+            SecretShare[] shares = splitResult.getShares();
+            VssCommitment vssCommitment = splitResult.getCommitment();
+
+            // Signers 1, 3 and 5 take part to the session
+            List<FrostSigner> signers = new ArrayList<>();
+            for (int i: new int[]{0, 2, 4})
+                signers.add(FrostSigner.init(shares[i], keyPair.getPublicKey()));
+
+            // Round 1
+            List<FrostSigningCommitment> commitments = new ArrayList<>();
+            for (FrostSigner signer: signers) {
+                FrostSigningCommitment commitment = signer.commit();
+                assertEquals(signer.getIndex(), commitment.getIndex());
+
+                // Serialization/deserialization
+                byte[] commitmentBytes = commitment.serialize();
+                assertEquals(FrostSigningCommitment.SIGNING_COMMITMENT_LENGTH, commitmentBytes.length);
+                FrostSigningCommitment commitmentDeserialized = FrostSigningCommitment.deserialize(commitmentBytes);
+                assertNotNull("Signing commitment deserialization must succeed", commitmentDeserialized);
+                assertArrayEquals(commitmentBytes, commitmentDeserialized.serialize());
+                commitment.freeSigningCommitment();
+
+                commitments.add(commitmentDeserialized);
+            }
+
+            // Round 2
+            List<FrostSignatureShare> signatureShares = new ArrayList<>();
+            for (FrostSigner signer: signers) {
+                FrostSignatureShare share = signer.sign(message, commitments);
+                assertNotNull("Signing must succeed", share);
+                assertEquals(signer.getIndex(), share.getIndex());
+
+                byte[] shareBytes = share.serialize();
+                assertEquals(FrostSignatureShare.SIGNATURE_SHARE_LENGTH, shareBytes.length);
+                FrostSignatureShare shareDeserialized = FrostSignatureShare.deserialize(shareBytes);
+                assertNotNull("Signature share deserialization must succeed", shareDeserialized);
+                assertArrayEquals(shareBytes, shareDeserialized.serialize());
+                share.freeSignatureShare();
+
+                signatureShares.add(shareDeserialized);
+            }
+
+            // Nonces can't be reused
+            assertNull(signers.get(0).sign(message, commitments));
+
+            FrostAggregator aggregator = FrostAggregator.init(vssCommitment, message, commitments);
+            assertNotNull("Aggregator creation must succeed", aggregator);
+            for (FrostSignatureShare share: signatureShares)
+                assertTrue("Signature share must be verified", aggregator.verifyShare(share));
+
+            SchnorrSignature signature = aggregator.aggregate(signatureShares);
+            assertNotNull("Aggregation must succeed", signature);
+            assertTrue("Aggregated signature must be verified", keyPair.getPublicKey().verifySignature(signature, message));
+
+            // Negative cases
+            assertNull(aggregator.aggregate(signatureShares.subList(0, 2)));
+            assertNull(FrostAggregator.init(vssCommitment, message, commitments.subList(0, 2)));
+
+            try(FieldElement wrongMessage = FieldElement.createRandom()) {
+                assertFalse(keyPair.getPublicKey().verifySignature(signature, wrongMessage));
+            }
+
+            signature.freeSignature();
+            aggregator.freeAggregator();
+            for (FrostSignatureShare share: signatureShares)
+                share.freeSignatureShare();
+            for (FrostSigningCommitment commitment: commitments)
+                commitment.freeSigningCommitment();
+            for (FrostSigner signer: signers)
+                signer.freeSigner();
+            for (SecretShare share: shares)
+                share.freeSecretShare();
+            vssCommitment.freeVssCommitment();
+        }
+    }
+}
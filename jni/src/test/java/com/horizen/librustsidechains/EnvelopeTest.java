@@ -0,0 +1,58 @@
+package com.horizen.librustsidechains;
+
+import org.junit.Test;
+
+import java.io.File;
+import java.nio.file.Files;
+import java.util.Arrays;
+
+import static org.junit.Assert.*;
+
+public class EnvelopeTest {
+
+    @Test
+    public void testSealOpen() throws Exception {
+        try (FieldElement fe = FieldElement.createRandom(42)) {
+            byte[] feBytes = fe.serializeFieldElement();
+
+            byte[] sealed = Envelope.seal(ArtifactType.FIELD_ELEMENT, feBytes);
+            assertTrue("Data must be sealed", Envelope.isSealed(sealed));
+            assertFalse("Data must not be sealed", Envelope.isSealed(feBytes));
+            assertArrayEquals(feBytes, Envelope.open(ArtifactType.FIELD_ELEMENT, sealed));
+
+            // Bare data is returned unchanged
+            assertArrayEquals(feBytes, Envelope.open(ArtifactType.FIELD_ELEMENT, feBytes));
+
+            // Bare data starting with the magic too, if followed by an unknown artifact type
+            byte[] bare = Arrays.copyOf(sealed, feBytes.length);
+            bare[4] = 0;
+            assertFalse("Data must not be sealed", Envelope.isSealed(bare));
+            assertArrayEquals(bare, Envelope.open(ArtifactType.FIELD_ELEMENT, bare));
+
+            // Invalid envelopes are rejected: wrong type, truncated, corrupted
+            assertThrows(IllegalArgumentException.class, () -> Envelope.open(ArtifactType.SCHNORR_SIGNATURE, sealed));
+            byte[] truncated = Arrays.copyOf(sealed, sealed.length - 1);
+            assertThrows(IllegalArgumentException.class, () -> Envelope.open(ArtifactType.FIELD_ELEMENT, truncated));
+            byte[] corrupted = sealed.clone();
+            corrupted[corrupted.length - 1] ^= 1;
+            assertThrows(IllegalArgumentException.class, () -> Envelope.open(ArtifactType.FIELD_ELEMENT, corrupted));
+        }
+    }
+
+    @Test
+    public void testSealFile() throws Exception {
+        File file = File.createTempFile("envelope_test", null);
+        file.deleteOnExit();
+        byte[] payload = {2, 1, 2, 3};
+        Files.write(file.toPath(), payload);
+
+        Envelope.sealFile(ArtifactType.VERIFIER_KEY, file.getAbsolutePath());
+        byte[] sealed = Files.readAllBytes(file.toPath());
+        assertTrue("File must be sealed", Envelope.isSealed(sealed));
+        assertArrayEquals(payload, Envelope.open(ArtifactType.VERIFIER_KEY, sealed));
+
+        // Already sealed
+        assertThrows(IllegalArgumentException.class,
+                () -> Envelope.sealFile(ArtifactType.VERIFIER_KEY, file.getAbsolutePath()));
+    }
+}
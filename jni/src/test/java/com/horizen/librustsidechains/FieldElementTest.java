@@ -45,4 +45,26 @@ public class FieldElementTest {
             }
         }
     }
-}
\ No newline at end of file
+
+    @Test
+    public void testJson() throws Exception {
+        try
+        (
+            FieldElement fieldElement = FieldElement.createRandom();
+            FieldElement fieldElementFromJson = FieldElement.fromJson(fieldElement.toJson())
+        )
+        {
+            String json = fieldElement.toJson();
+            assertTrue("JSON must contain the type", json.contains("\"type\":\"FieldElement\""));
+            assertTrue("JSON must contain the version", json.contains("\"version\":1"));
+            assertEquals("Field element must be the same.", fieldElement, fieldElementFromJson);
+
+            // Wrong type, wrong version, malformed
+            assertThrows(IllegalArgumentException.class,
+                    () -> FieldElement.fromJson(json.replace("\"FieldElement\"", "\"SchnorrSignature\"")));
+            assertThrows(IllegalArgumentException.class,
+                    () -> FieldElement.fromJson(json.replace("\"version\":1", "\"version\":2")));
+            assertThrows(IllegalArgumentException.class, () -> FieldElement.fromJson("{\"value\""));
+        }
+    }
+}
@@ -12,6 +12,7 @@ import java.util.ArrayList;
 
 import java.io.File;
 import java.io.FileInputStream;
+import java.nio.file.Files;
 
 import static org.junit.Assert.*;
 
@@ -129,6 +130,11 @@ public class MerkleTreeTest {
             MerklePath pathDeserialized = MerklePath.deserialize(merklePathBytes);
             assertTrue("Deserialized Merkle Path must be verified", pathDeserialized.verify(testLeaves.get(i), mhtRoot));
 
+            // JSON test
+            MerklePath pathFromJson = MerklePath.fromJson(path.toJson());
+            assertArrayEquals("Merkle Path read from JSON must be the same", merklePathBytes, pathFromJson.serialize());
+            pathFromJson.freeMerklePath();
+
             if (i == 0) { // leftmost check
                 assertTrue("Path must be the leftmost", path.isLeftmost());
             }
@@ -191,6 +197,300 @@ public class MerkleTreeTest {
         }
     }
 
+    @Test
+    public void testPersistentMerkleTree() throws Exception {
+        int height = 6;
+        int numLeaves = 40;
+        String treePath = Files.createTempDirectory("persistent_mht").toString();
+
+        InMemoryOptimizedMerkleTree mht = InMemoryOptimizedMerkleTree.init(height, 1 << height);
+        assertNotNull("Merkle Tree initialization must succeed", mht);
+
+        PersistentMerkleTree pmt = PersistentMerkleTree.init(treePath, height);
+        assertNotNull("Persistent Merkle Tree initialization must succeed", pmt);
+
+        List<FieldElement> testLeaves = new ArrayList<>();
+        for (int i = 0; i < numLeaves; i++) {
+            FieldElement leaf = FieldElement.createRandom(i);
+            testLeaves.add(leaf);
+            assertTrue("Leaf append must be successfull", mht.append(leaf));
+            assertEquals("Leaf index must be correct", i, pmt.append(leaf));
+        }
+        assertTrue("Merkle Tree finalization must succeed", mht.finalizeTreeInPlace());
+
+        // Same root and paths of InMemoryOptimizedMerkleTree
+        FieldElement mhtRoot = mht.root();
+        FieldElement pmtRoot = pmt.root();
+        assertEquals("PersistentMerkleTree root must be the same of InMemoryOptimizedMerkleTree", mhtRoot, pmtRoot);
+
+        for (int i = 0; i < numLeaves; i++) {
+            MerklePath path = pmt.getMerklePath(i);
+            assertNotNull("Path must not be NULL", path);
+            assertTrue("Merkle Path must be verified", path.verify(height, testLeaves.get(i), mhtRoot));
+            assertEquals("Leaf index computed from path must be correct", i, path.leafIndex());
+            path.freeMerklePath();
+        }
+
+        // Uncommitted changes are lost when reopening
+        assertTrue("Commit must succeed", pmt.commit());
+        FieldElement newLeaf = FieldElement.createRandom(numLeaves);
+        assertTrue("Leaf update must succeed", pmt.update(0, newLeaf));
+        assertEquals(numLeaves, pmt.append(newLeaf));
+        pmt.freePersistentMerkleTree();
+
+        pmt = PersistentMerkleTree.init(treePath, height);
+        assertNotNull("Persistent Merkle Tree reopening must succeed", pmt);
+        assertEquals(numLeaves, pmt.getNumLeaves());
+        FieldElement reopenedRoot = pmt.root();
+        assertEquals("Root must be the one of the last commit", mhtRoot, reopenedRoot);
+
+        // Rollback
+        assertTrue("Leaf update must succeed", pmt.update(0, newLeaf));
+        FieldElement updatedLeaf = pmt.getLeaf(0);
+        assertEquals(newLeaf, updatedLeaf);
+        pmt.rollback();
+        FieldElement restoredLeaf = pmt.getLeaf(0);
+        assertEquals(testLeaves.get(0), restoredLeaf);
+
+        // Negative cases
+        assertFalse("Update of not existing leaf must fail", pmt.update(numLeaves, newLeaf));
+        assertNull("Not existing leaf must be NULL", pmt.getLeaf(numLeaves));
+        assertNull("Opening with different height must fail", PersistentMerkleTree.init(treePath, height + 1));
+
+        // Free memory
+        pmt.freePersistentMerkleTree();
+        mht.freeInMemoryOptimizedMerkleTree();
+        mhtRoot.freeFieldElement();
+        pmtRoot.freeFieldElement();
+        reopenedRoot.freeFieldElement();
+        updatedLeaf.freeFieldElement();
+        restoredLeaf.freeFieldElement();
+        newLeaf.freeFieldElement();
+        for (FieldElement leaf: testLeaves)
+            leaf.freeFieldElement();
+        for (File f: new File(treePath).listFiles())
+            f.delete();
+        new File(treePath).delete();
+    }
+
+    @Test
+    public void testMerkleMultiProof() throws Exception {
+        int height = 6;
+        int numLeaves = 40;
+
+        InMemoryOptimizedMerkleTree mht = InMemoryOptimizedMerkleTree.init(height, 1 << height);
+        assertNotNull("Merkle Tree initialization must succeed", mht);
+
+        List<FieldElement> testLeaves = new ArrayList<>();
+        for (int i = 0; i < numLeaves; i++) {
+            FieldElement leaf = FieldElement.createRandom(i);
+            testLeaves.add(leaf);
+            assertTrue("Leaf append must be successfull", mht.append(leaf));
+        }
+        assertTrue("Merkle Tree finalization must succeed", mht.finalizeTreeInPlace());
+        FieldElement root = mht.root();
+
+        long[] leafIndices = { 31L, 2L, 3L, 17L, 2L };
+        MerkleMultiProof proof = mht.getMerkleMultiProof(leafIndices);
+        assertNotNull("Multi proof must not be NULL", proof);
+        assertEquals(height, proof.getHeight());
+        assertArrayEquals("Leaf indices must be sorted and deduplicated", new long[]{ 2L, 3L, 17L, 31L }, proof.getLeafIndices());
+
+        FieldElement[] provenLeaves = new FieldElement[] { testLeaves.get(2), testLeaves.get(3), testLeaves.get(17), testLeaves.get(31) };
+        assertTrue("Multi proof must be verified", proof.verify(provenLeaves, root));
+
+        // Serialization round trip
+        byte[] proofBytes = proof.serialize();
+        MerkleMultiProof deserializedProof = MerkleMultiProof.deserialize(proofBytes);
+        assertNotNull("Multi proof deserialization must succeed", deserializedProof);
+        assertTrue("Deserialized multi proof must be verified", deserializedProof.verify(provenLeaves, root));
+        assertNull("Deserialization of truncated multi proof must fail",
+            MerkleMultiProof.deserialize(java.util.Arrays.copyOf(proofBytes, proofBytes.length - 1)));
+
+        // JSON round trip
+        MerkleMultiProof proofFromJson = MerkleMultiProof.fromJson(proof.toJson());
+        assertArrayEquals("Multi proof read from JSON must be serialized to same bytes", proofBytes, proofFromJson.serialize());
+        assertThrows(IllegalArgumentException.class, () -> MerklePath.fromJson(proofFromJson.toJson()));
+        proofFromJson.freeMerkleMultiProof();
+
+        // Negative cases
+        FieldElement[] wrongLeaves = new FieldElement[] { testLeaves.get(3), testLeaves.get(2), testLeaves.get(17), testLeaves.get(31) };
+        assertFalse("Multi proof with wrong leaves must not be verified", proof.verify(wrongLeaves, root));
+        assertFalse("Multi proof with wrong number of leaves must not be verified",
+            proof.verify(new FieldElement[] { testLeaves.get(2) }, root));
+        assertNull("Multi proof for no leaves must be NULL", mht.getMerkleMultiProof(new long[0]));
+        assertNull("Multi proof for out of range leaf must be NULL", mht.getMerkleMultiProof(new long[]{ 1L << height }));
+
+        // Free memory
+        proof.freeMerkleMultiProof();
+        deserializedProof.freeMerkleMultiProof();
+        mht.freeInMemoryOptimizedMerkleTree();
+        root.freeFieldElement();
+        for (FieldElement leaf: testLeaves)
+            leaf.freeFieldElement();
+    }
+
+    @Test
+    public void testMerkleTreeSnapshot() throws Exception {
+        int height = 6;
+        int numLeaves = 20;
+
+        InMemoryOptimizedMerkleTree mht = InMemoryOptimizedMerkleTree.init(height, 8);
+        assertNotNull("Merkle Tree initialization must succeed", mht);
+
+        List<FieldElement> testLeaves = new ArrayList<>();
+        for (int i = 0; i < numLeaves; i++) {
+            FieldElement leaf = FieldElement.createRandom(i);
+            testLeaves.add(leaf);
+            assertTrue("Leaf append must be successfull", mht.append(leaf));
+        }
+
+        // Restore a non finalized tree and keep on appending to both
+        InMemoryOptimizedMerkleTree restoredMht = InMemoryOptimizedMerkleTree.deserialize(mht.serialize());
+        assertNotNull("Merkle Tree deserialization must succeed", restoredMht);
+        FieldElement newLeaf = FieldElement.createRandom(numLeaves);
+        assertTrue(mht.append(newLeaf));
+        assertTrue(restoredMht.append(newLeaf));
+
+        // Restore a finalized tree
+        assertTrue("Merkle Tree finalization must succeed", mht.finalizeTreeInPlace());
+        assertTrue("Merkle Tree finalization must succeed", restoredMht.finalizeTreeInPlace());
+        byte[] snapshot = mht.serialize();
+        InMemoryOptimizedMerkleTree restoredFinalizedMht = InMemoryOptimizedMerkleTree.deserialize(snapshot);
+        assertNotNull("Merkle Tree deserialization must succeed", restoredFinalizedMht);
+
+        FieldElement root = mht.root();
+        FieldElement restoredRoot = restoredMht.root();
+        FieldElement restoredFinalizedRoot = restoredFinalizedMht.root();
+        assertEquals("Restored tree root must be the same of the original one", root, restoredRoot);
+        assertEquals("Restored tree root must be the same of the original one", root, restoredFinalizedRoot);
+
+        MerklePath path = restoredFinalizedMht.getMerklePath(numLeaves);
+        assertTrue("Merkle Path of restored tree must be verified", path.verify(height, newLeaf, root));
+        path.freeMerklePath();
+
+        // Corrupted snapshot
+        snapshot[snapshot.length / 2] ^= 1;
+        assertNull("Deserialization of corrupted snapshot must fail", InMemoryOptimizedMerkleTree.deserialize(snapshot));
+        assertNull("Deserialization of empty snapshot must fail", InMemoryOptimizedMerkleTree.deserialize(new byte[0]));
+
+        // Free memory
+        mht.freeInMemoryOptimizedMerkleTree();
+        restoredMht.freeInMemoryOptimizedMerkleTree();
+        restoredFinalizedMht.freeInMemoryOptimizedMerkleTree();
+        root.freeFieldElement();
+        restoredRoot.freeFieldElement();
+        restoredFinalizedRoot.freeFieldElement();
+        newLeaf.freeFieldElement();
+        for (FieldElement leaf: testLeaves)
+            leaf.freeFieldElement();
+    }
+
+    @Test
+    public void testSparseMerkleTree() throws Exception {
+        int height = 6;
+        long[] positions = { 0L, 5L, 17L, 33L, 63L };
+
+        InMemoryOptimizedMerkleTree mht = InMemoryOptimizedMerkleTree.init(height, 1 << height);
+        assertNotNull("Merkle Tree initialization must succeed", mht);
+
+        SparseMerkleTree smt = SparseMerkleTree.init(height);
+        assertNotNull("Sparse Merkle Tree initialization must succeed", smt);
+
+        // Insert leaves and set the same ones in the in-memory tree, zero elsewhere
+        List<FieldElement> testLeaves = new ArrayList<>();
+        for (int i = 0; i < positions.length; i++) {
+            FieldElement leaf = FieldElement.createRandom(i);
+            testLeaves.add(leaf);
+            assertTrue("Leaf insertion must succeed", smt.insert(positions[i], leaf));
+        }
+        assertEquals(positions.length, smt.getNumLeaves());
+
+        FieldElement zero = FieldElement.createFromLong(0);
+        int next = 0;
+        for (long pos = 0; pos < (1 << height); pos++) {
+            if (next < positions.length && positions[next] == pos) {
+                assertTrue(mht.append(testLeaves.get(next)));
+                next++;
+            } else {
+                assertTrue(mht.append(zero));
+            }
+        }
+        assertTrue("Merkle Tree finalization must succeed", mht.finalizeTreeInPlace());
+
+        FieldElement mhtRoot = mht.root();
+        FieldElement smtRoot = smt.root();
+        assertEquals("SparseMerkleTree root must be the same of InMemoryOptimizedMerkleTree", mhtRoot, smtRoot);
+
+        // Membership proofs
+        for (int i = 0; i < positions.length; i++) {
+            assertNull("Non membership proof of non empty position must be NULL", smt.getNonMembershipProof(positions[i]));
+            MerklePath proof = smt.getMembershipProof(positions[i]);
+            assertNotNull("Membership proof must not be NULL", proof);
+            assertTrue("Membership proof must be verified",
+                SparseMerkleTree.verifyMembershipProof(proof, height, positions[i], testLeaves.get(i), smtRoot));
+            assertFalse("Membership proof must not be verified for a different position",
+                SparseMerkleTree.verifyMembershipProof(proof, height, positions[i] ^ 1, testLeaves.get(i), smtRoot));
+            assertFalse("Membership proof must not be a non membership proof",
+                SparseMerkleTree.verifyNonMembershipProof(proof, height, positions[i], smtRoot));
+            proof.freeMerklePath();
+        }
+
+        // Non membership proofs
+        long emptyPosition = 6L;
+        assertNull("Leaf of empty position must be NULL", smt.getLeaf(emptyPosition));
+        assertNull("Membership proof of empty position must be NULL", smt.getMembershipProof(emptyPosition));
+        MerklePath nonMembershipProof = smt.getNonMembershipProof(emptyPosition);
+        assertNotNull("Non membership proof must not be NULL", nonMembershipProof);
+        assertTrue("Non membership proof must be verified",
+            SparseMerkleTree.verifyNonMembershipProof(nonMembershipProof, height, emptyPosition, smtRoot));
+        nonMembershipProof.freeMerklePath();
+
+        // Update and removal
+        FieldElement newLeaf = FieldElement.createRandom(positions.length);
+        assertTrue("Leaf update must succeed", smt.update(positions[1], newLeaf));
+        FieldElement updatedLeaf = smt.getLeaf(positions[1]);
+        assertEquals(newLeaf, updatedLeaf);
+        assertTrue("Leaf removal must succeed", smt.remove(positions[1]));
+        assertEquals(positions.length - 1, smt.getNumLeaves());
+
+        FieldElement newRoot = smt.root();
+        assertNotEquals("Root must change after removal", smtRoot, newRoot);
+        nonMembershipProof = smt.getNonMembershipProof(positions[1]);
+        assertTrue("Non membership proof of removed leaf must be verified",
+            SparseMerkleTree.verifyNonMembershipProof(nonMembershipProof, height, positions[1], newRoot));
+        assertFalse("Non membership proof must not be verified against old root",
+            SparseMerkleTree.verifyNonMembershipProof(nonMembershipProof, height, positions[1], smtRoot));
+        nonMembershipProof.freeMerklePath();
+
+        // Negative cases
+        assertFalse("Insertion in non empty position must fail", smt.insert(positions[0], newLeaf));
+        assertFalse("Insertion of zero leaf must fail", smt.insert(emptyPosition, zero));
+        assertFalse("Insertion out of range must fail", smt.insert(1L << height, newLeaf));
+        assertFalse("Update of empty position must fail", smt.update(emptyPosition, newLeaf));
+        assertFalse("Removal of empty position must fail", smt.remove(emptyPosition));
+        assertNull("Init with invalid height must fail", SparseMerkleTree.init(SparseMerkleTree.MAX_HEIGHT + 1));
+
+        // Position from key
+        FieldElement key = FieldElement.createFromLong(0x1234L);
+        assertEquals(0x1234L, SparseMerkleTree.getPositionFromKey(key, 16));
+        assertEquals(-1L, SparseMerkleTree.getPositionFromKey(key, 8));
+        assertEquals(-1L, SparseMerkleTree.getPositionFromKey(key, SparseMerkleTree.MAX_HEIGHT + 1));
+
+        // Free memory
+        smt.freeSparseMerkleTree();
+        mht.freeInMemoryOptimizedMerkleTree();
+        mhtRoot.freeFieldElement();
+        smtRoot.freeFieldElement();
+        newRoot.freeFieldElement();
+        updatedLeaf.freeFieldElement();
+        newLeaf.freeFieldElement();
+        zero.freeFieldElement();
+        key.freeFieldElement();
+        for (FieldElement leaf: testLeaves)
+            leaf.freeFieldElement();
+    }
+
     @After
     public void freeTestParams(){
         for (FieldElement leaf: leaves)
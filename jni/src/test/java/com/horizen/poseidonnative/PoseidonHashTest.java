@@ -2,8 +2,10 @@ package com.horizen.poseidonnative;
 
 import com.horizen.librustsidechains.FieldElement;
 
+import java.util.Arrays;
 import java.util.List;
 import java.util.ArrayList;
+import java.util.Random;
 
 import org.junit.Test;
 import org.junit.BeforeClass;
@@ -129,6 +131,129 @@ public class PoseidonHashTest {
         }
     }
 
+    @Test
+    public void testHashBytes() throws Exception {
+        byte[] data = new byte[100];
+        new Random(42).nextBytes(data);
+        byte[] domain = "domain".getBytes();
+
+        try
+        (
+            FieldElement hash = PoseidonHash.hashBytes(domain, data);
+            FieldElement otherDomainHash = PoseidonHash.hashBytes("other domain".getBytes(), data);
+            FieldElement paddedHash = PoseidonHash.hashBytes(domain, Arrays.copyOf(data, data.length + 1));
+            PoseidonHash digest = PoseidonHash.getInstanceVariableLength(false)
+        )
+        {
+            assertNotNull("Hashing bytes must not fail", hash);
+            assertNotEquals("Different domains must give different hashes", hash, otherDomainHash);
+            assertNotEquals("Trailing zero bytes must not be ignored", hash, paddedHash);
+
+            // One-shot hash is equal to updating with domain and data
+            digest.updateBytes(domain);
+            digest.updateBytes(data);
+            try (FieldElement streamedHash = digest.finalizeHash()) {
+                assertEquals("One-shot and streamed hashes must be equal", hash, streamedHash);
+            }
+        }
+
+        // Same bytes split differently among updates
+        try
+        (
+            PoseidonHash digest1 = PoseidonHash.getInstanceVariableLength(false);
+            PoseidonHash digest2 = PoseidonHash.getInstanceVariableLength(false)
+        )
+        {
+            digest1.updateBytes(Arrays.copyOfRange(data, 0, 10));
+            digest1.updateBytes(Arrays.copyOfRange(data, 10, data.length));
+            digest2.updateBytes(Arrays.copyOfRange(data, 0, 11));
+            digest2.updateBytes(Arrays.copyOfRange(data, 11, data.length));
+            try
+            (
+                FieldElement hash1 = digest1.finalizeHash();
+                FieldElement hash2 = digest2.finalizeHash()
+            )
+            {
+                assertNotEquals("Differently split bytes must give different hashes", hash1, hash2);
+            }
+        }
+    }
+
+    @Test
+    public void testCloneAndResume() throws Exception {
+        int checkpoint = hashInput.size() / 2;
+
+        try
+        (
+            PoseidonHash digest = PoseidonHash.getInstanceConstantLength(hashInput.size(), new FieldElement[0], true);
+            PoseidonHash expectedDigest = PoseidonHash.getInstanceConstantLength(hashInput.size())
+        )
+        {
+            for (FieldElement fe: hashInput)
+                expectedDigest.update(fe);
+
+            // Non resumable instances can't be cloned or serialized
+            try {
+                expectedDigest.serializeState();
+                fail("Non resumable instance must not be serialized");
+            } catch (IllegalStateException e) {
+                // expected
+            }
+            try {
+                expectedDigest.clone();
+                fail("Non resumable instance must not be cloned");
+            } catch (IllegalStateException e) {
+                // expected
+            }
+
+            for (FieldElement fe: hashInput.subList(0, checkpoint))
+                digest.update(fe);
+
+            // Checkpoint in the middle of the hash
+            byte[] state = digest.serializeState();
+
+            try
+            (
+                PoseidonHash clonedDigest = digest.clone();
+                PoseidonHash restoredDigest = PoseidonHash.deserializeState(state)
+            )
+            {
+                assertNotNull("State must be restored", restoredDigest);
+
+                for (FieldElement fe: hashInput.subList(checkpoint, hashInput.size())) {
+                    digest.update(fe);
+                    clonedDigest.update(fe);
+                    restoredDigest.update(fe);
+                }
+
+                try
+                (
+                    FieldElement expectedHash = expectedDigest.finalizeHash();
+                    FieldElement hash = digest.finalizeHash();
+                    FieldElement clonedHash = clonedDigest.finalizeHash();
+                    FieldElement restoredHash = restoredDigest.finalizeHash()
+                )
+                {
+                    assertEquals("Hash must be equal to the expected one", expectedHash, hash);
+                    assertEquals("Hash of the cloned instance must be equal to the expected one", expectedHash, clonedHash);
+                    assertEquals("Hash of the restored instance must be equal to the expected one", expectedHash, restoredHash);
+
+                    // Updating the clone must not affect the original instance
+                    clonedDigest.update(hashInput.get(0));
+                    try (FieldElement hashAfterCloneUpdate = digest.finalizeHash()) {
+                        assertEquals("Clone must be independent from the original instance", hash, hashAfterCloneUpdate);
+                    }
+                }
+            }
+
+            // Corrupted state
+            byte[] corruptedState = Arrays.copyOf(state, state.length);
+            corruptedState[corruptedState.length / 2] ^= 1;
+            assertNull("Corrupted state must not be restored", PoseidonHash.deserializeState(corruptedState));
+            assertNull("Truncated state must not be restored", PoseidonHash.deserializeState(Arrays.copyOf(state, state.length - 1)));
+        }
+    }
+
     @AfterClass
     public static void freeHashInput() {
         for (FieldElement fe: hashInput)
@@ -0,0 +1,68 @@
+package com.horizen.poseidonnative;
+
+import com.horizen.librustsidechains.FieldElement;
+import org.junit.Test;
+
+import static org.junit.Assert.*;
+
+public class PoseidonPrimitivesTest {
+
+    @Test
+    public void testCommitment() throws Exception {
+        try
+        (
+            FieldElement m0 = FieldElement.createRandom();
+            FieldElement m1 = FieldElement.createRandom();
+            FieldElement randomness = FieldElement.createRandom();
+            FieldElement wrongRandomness = FieldElement.createRandom()
+        )
+        {
+            FieldElement[] message = {m0, m1};
+            try
+            (
+                FieldElement commitment = PoseidonPrimitives.commit(message, randomness);
+                FieldElement otherCommitment = PoseidonPrimitives.commit(message, wrongRandomness)
+            )
+            {
+                assertNotNull("Commitment must be computed", commitment);
+                assertNotEquals("Commitments with different randomness must be different", commitment, otherCommitment);
+
+                assertTrue("Opening must be valid", PoseidonPrimitives.verifyCommitment(commitment, message, randomness));
+                assertFalse("Opening with wrong randomness must be invalid",
+                        PoseidonPrimitives.verifyCommitment(commitment, message, wrongRandomness));
+                assertFalse("Opening with wrong message must be invalid",
+                        PoseidonPrimitives.verifyCommitment(commitment, new FieldElement[]{m1, m0}, randomness));
+                assertFalse("Opening with truncated message must be invalid",
+                        PoseidonPrimitives.verifyCommitment(commitment, new FieldElement[]{m0}, randomness));
+            }
+        }
+    }
+
+    @Test
+    public void testPrfAndKdf() throws Exception {
+        try
+        (
+            FieldElement key = FieldElement.createRandom();
+            FieldElement otherKey = FieldElement.createRandom();
+            FieldElement input = FieldElement.createRandom();
+            FieldElement domain = FieldElement.createRandom();
+            FieldElement prf = PoseidonPrimitives.prf(key, input);
+            FieldElement samePrf = PoseidonPrimitives.prf(key, input);
+            FieldElement otherKeyPrf = PoseidonPrimitives.prf(otherKey, input);
+            FieldElement derivedKey = PoseidonPrimitives.kdf(key, domain, 0);
+            FieldElement otherIndexKey = PoseidonPrimitives.kdf(key, domain, 1);
+            FieldElement otherDomainKey = PoseidonPrimitives.kdf(key, input, 0)
+        )
+        {
+            assertEquals("PRF must be deterministic", prf, samePrf);
+            assertNotEquals("PRF outputs under different keys must be different", prf, otherKeyPrf);
+
+            assertNotEquals("Keys of different indices must be different", derivedKey, otherIndexKey);
+            assertNotEquals("Keys of different domains must be different", derivedKey, otherDomainKey);
+        }
+
+        try (FieldElement key = FieldElement.createRandom()) {
+            assertThrows(IllegalArgumentException.class, () -> PoseidonPrimitives.kdf(key, key, -1));
+        }
+    }
+}
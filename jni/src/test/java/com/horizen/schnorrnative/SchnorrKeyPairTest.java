@@ -78,4 +78,24 @@ public class SchnorrKeyPairTest {
             }
         }
     }
+
+    @Test
+    public void testJson() throws Exception {
+        try
+        (
+            SchnorrKeyPair keyPair = SchnorrKeyPair.generate();
+            FieldElement message = FieldElement.createRandom();
+            SchnorrSignature signature = keyPair.signMessage(message);
+            SchnorrPublicKey publicKey = SchnorrPublicKey.fromJson(keyPair.getPublicKey().toJson());
+            SchnorrSignature signatureFromJson = SchnorrSignature.fromJson(signature.toJson())
+        )
+        {
+            assertArrayEquals("Public key must be the same",
+                    keyPair.getPublicKey().serializePublicKey(), publicKey.serializePublicKey());
+            assertTrue("Signature read from JSON must be verified", publicKey.verifySignature(signatureFromJson, message));
+
+            // A signature is not a public key
+            assertThrows(IllegalArgumentException.class, () -> SchnorrPublicKey.fromJson(signature.toJson()));
+        }
+    }
 }
@@ -42,4 +42,37 @@ public class SchnorrSecretKeyTest {
             }
         }
     }
+
+    @Test
+    public void testKeystore() throws Exception {
+        byte[] password = "correct horse battery staple".getBytes();
+
+        try(SchnorrKeyPair keyPair = SchnorrKeyPair.generate())
+        {
+            byte[] secretKeyBytes = keyPair.getSecretKey().serializeSecretKey();
+
+            byte[] keystore = keyPair.getSecretKey().encrypt(password);
+            assertEquals("Keystore size must be - " + SchnorrSecretKey.KEYSTORE_LENGTH,
+                    SchnorrSecretKey.KEYSTORE_LENGTH,
+                    keystore.length);
+
+            try(SchnorrSecretKey decryptedSecretKey = SchnorrSecretKey.decrypt(keystore, password))
+            {
+                assertNotNull("Keystore decryption must succeed.", decryptedSecretKey);
+                assertArrayEquals("Decrypted secret key must be the same.", secretKeyBytes, decryptedSecretKey.serializeSecretKey());
+                assertEquals("Decrypted secret key must be equal.", keyPair.getSecretKey(), decryptedSecretKey);
+            }
+
+            try(SchnorrKeyPair otherKeyPair = SchnorrKeyPair.generate())
+            {
+                assertNotEquals("Different secret keys must not be equal.", keyPair.getSecretKey(), otherKeyPair.getSecretKey());
+            }
+
+            assertNull("Decryption with wrong password must fail.", SchnorrSecretKey.decrypt(keystore, "wrong password".getBytes()));
+
+            byte[] corruptedKeystore = keystore.clone();
+            corruptedKeystore[corruptedKeystore.length / 2] ^= 1;
+            assertNull("Decryption of corrupted keystore must fail.", SchnorrSecretKey.decrypt(corruptedKeystore, password));
+        }
+    }
 }
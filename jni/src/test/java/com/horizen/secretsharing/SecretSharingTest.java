@@ -0,0 +1,130 @@
+package com.horizen.secretsharing;
+
+import com.horizen.librustsidechains.FieldElement;
+import com.horizen.schnorrnative.SchnorrKeyPair;
+import com.horizen.schnorrnative.SchnorrPublicKey;
+import com.horizen.schnorrnative.SchnorrSecretKey;
+import org.junit.Test;
+
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+import static org.junit.Assert.*;
+
+public class SecretSharingTest {
+
+    static int threshold = 3;
+    static int numShares = 5;
+
+    @Test
+    public void testSplitReconstruct() throws Exception {
+        try(SchnorrKeyPair keyPair = SchnorrKeyPair.generate())
+        {
+            ShamirSplitResult splitResult = ShamirSecretSharing.split(keyPair.getSecretKey(), threshold, numShares);
+            assertNotNull("Secret key split must succeed", splitResult);
+
+            SecretShare[] shares = splitResult.getShares();
+            VssCommitment commitment = splitResult.getCommitment();
+            assertEquals(numShares, shares.length);
+            assertEquals(threshold, commitment.getThreshold());
+            assertArrayEquals(
+                keyPair.getPublicKey().serializePublicKey(),
+                commitment.getPublicKey().serializePublicKey()
+            );
+
+            for (SecretShare share: shares) {
+                assertTrue("Share must be verified", share.verify(commitment));
+
+                // Serialization/deserialization
+                SecretShare shareDeserialized = SecretShare.deserialize(share.serialize());
+                assertNotNull("Share deserialization must succeed", shareDeserialized);
+                assertEquals(share.getIndex(), shareDeserialized.getIndex());
+                assertTrue(shareDeserialized.verify(commitment));
+                shareDeserialized.freeSecretShare();
+            }
+
+            // Any threshold shares reconstruct the secret key
+            SchnorrSecretKey sk = ShamirSecretSharing.reconstruct(Arrays.asList(shares[4], shares[1], shares[2]));
+            assertNotNull("Secret key reconstruction must succeed", sk);
+            assertArrayEquals(keyPair.getSecretKey().serializeSecretKey(), sk.serializeSecretKey());
+            sk.freeSecretKey();
+
+            // Less shares don't
+            sk = ShamirSecretSharing.reconstruct(Arrays.asList(shares[0], shares[1]));
+            assertFalse(Arrays.equals(keyPair.getSecretKey().serializeSecretKey(), sk.serializeSecretKey()));
+            sk.freeSecretKey();
+
+            // Negative cases
+            assertNull(ShamirSecretSharing.split(keyPair.getSecretKey(), numShares + 1, numShares));
+            assertNull(ShamirSecretSharing.reconstruct(Arrays.asList(shares[0], shares[0])));
+
+            for (SecretShare share: shares)
+                share.freeSecretShare();
+            commitment.freeVssCommitment();
+        }
+    }
+
+    @Test
+    public void testDkg() throws Exception {
+        List<DkgParticipant> participants = new ArrayList<>();
+        List<VssCommitment> commitments = new ArrayList<>();
+        for (int i = 1; i <= numShares; i++) {
+            DkgParticipant participant = DkgParticipant.init(i, threshold, numShares);
+            assertNotNull("DKG participant initialization must succeed", participant);
+            participants.add(participant);
+            commitments.add(participant.getCommitment());
+        }
+
+        // Each participant proves the knowledge of its secret
+        try (FieldElement context = FieldElement.createRandom()) {
+            for (int i = 0; i < numShares; i++) {
+                byte[] proof = participants.get(i).getProofOfKnowledge(context);
+                assertTrue("Proof of knowledge must be verified",
+                        DkgParticipant.verifyProofOfKnowledge(i + 1, context, commitments.get(i), proof));
+                assertFalse("Proof of knowledge of another participant must not be verified",
+                        DkgParticipant.verifyProofOfKnowledge(i % numShares + 2, context, commitments.get(i), proof));
+            }
+        }
+
+        VssCommitment jointCommitment = VssCommitment.combine(commitments);
+        assertNotNull("Commitments combination must succeed", jointCommitment);
+
+        // Each participant verifies and combines the shares it received
+        List<SecretShare> jointShares = new ArrayList<>();
+        for (int j = 1; j <= numShares; j++) {
+            List<SecretShare> received = new ArrayList<>();
+            for (int i = 0; i < numShares; i++) {
+                SecretShare share = participants.get(i).getShareFor(j);
+                assertTrue("Received share must be verified", share.verify(commitments.get(i)));
+                received.add(share);
+            }
+            SecretShare jointShare = DkgParticipant.combineShares(received);
+            assertNotNull("Shares combination must succeed", jointShare);
+            assertTrue("Joint share must be verified", jointShare.verify(jointCommitment));
+            jointShares.add(jointShare);
+
+            for (SecretShare share: received)
+                share.freeSecretShare();
+        }
+
+        // Any threshold shares give the secret key of the joint public key
+        try
+        (
+            SchnorrSecretKey sk = ShamirSecretSharing.reconstruct(jointShares.subList(1, 1 + threshold));
+            SchnorrPublicKey pk = sk.getPublicKey();
+            SchnorrPublicKey jointPk = jointCommitment.getPublicKey()
+        )
+        {
+            assertArrayEquals(jointPk.serializePublicKey(), pk.serializePublicKey());
+        }
+
+        for (SecretShare share: jointShares)
+            share.freeSecretShare();
+        for (VssCommitment commitment: commitments)
+            commitment.freeVssCommitment();
+        for (DkgParticipant participant: participants)
+            participant.freeDkgParticipant();
+        jointCommitment.freeVssCommitment();
+    }
+}
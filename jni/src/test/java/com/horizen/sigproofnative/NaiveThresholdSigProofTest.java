@@ -16,14 +16,18 @@ import org.junit.Test;
 import java.io.File;
 import java.io.FileReader;
 import java.util.ArrayList;
+import java.util.Arrays;
 import java.util.List;
 import java.util.Random;
 import java.util.Optional;
 
+import static org.junit.Assert.assertNull;
 import static org.junit.Assert.assertNotNull;
 import static org.junit.Assert.assertTrue;
 import static org.junit.Assert.assertFalse;
 import static org.junit.Assert.assertEquals;
+import static org.junit.Assert.assertArrayEquals;
+import static org.junit.Assert.assertThrows;
 
 public class NaiveThresholdSigProofTest {
 
@@ -55,6 +59,22 @@ public class NaiveThresholdSigProofTest {
     @BeforeClass
     public static void initKeys() {
         assertTrue(ProvingSystem.generateDLogKeys(psType, maxSegmentSize, supportedSegmentSize));
+
+        // Circuit size estimation must agree with the setup
+        CircuitSizeInfo info = NaiveThresholdSigProof.getCircuitSizeInfo(psType, keyCount, zk, supportedSegmentSize, maxProofSize, maxVkSize);
+        assertNotNull("Circuit size info computation must be successful", info);
+        assertTrue(info.fits());
+        assertTrue(info.getNumConstraints() > 0);
+        assertFalse(NaiveThresholdSigProof.getCircuitSizeInfo(psType, keyCount, zk, supportedSegmentSize, 1, maxVkSize).fits());
+        assertTrue(NaiveThresholdSigProof.getMaxSupportedPks(psType, zk, supportedSegmentSize, info.getProofSize(), info.getVkSize(), 2 * keyCount) >= keyCount);
+        assertEquals(0, NaiveThresholdSigProof.getMaxSupportedPks(psType, zk, supportedSegmentSize, 1, 1, 2 * keyCount));
+
+        // Negative sizes are rejected
+        assertNull(NaiveThresholdSigProof.getCircuitSizeInfo(psType, -1, zk, supportedSegmentSize, maxProofSize, maxVkSize));
+        assertNull(NaiveThresholdSigProof.getCircuitSizeInfo(psType, keyCount, zk, -1, maxProofSize, maxVkSize));
+        assertEquals(-1, NaiveThresholdSigProof.getMaxSupportedPks(psType, zk, supportedSegmentSize, -1, maxVkSize, 2 * keyCount));
+        assertEquals(-1, NaiveThresholdSigProof.getMaxSupportedPks(psType, zk, supportedSegmentSize, maxProofSize, maxVkSize, -1));
+
         assertTrue(NaiveThresholdSigProof.setup(psType, keyCount, snarkPkPath, snarkVkPath, zk, maxProofSize, maxVkSize));
         assertFalse(NaiveThresholdSigProof.setup(psType, keyCount, snarkPkPath, snarkVkPath, zk, 1, maxVkSize));
         assertFalse(NaiveThresholdSigProof.setup(psType, keyCount, snarkPkPath, snarkVkPath, zk, maxProofSize, 1));
@@ -155,6 +175,83 @@ public class NaiveThresholdSigProofTest {
         assertFalse("Proof must not be verified", isProofVerified);
     }
 
+    @Test
+    public void testCreateCertificate() throws Exception {
+        scId = FieldElement.createRandom();
+        endCumulativeScTxCommTreeRoot = FieldElement.createRandom();
+
+        // The mainchain accepts only positive backward transfer amounts
+        for (int i = 0; i < 3; i++) {
+            byte[] publicKeyHash = new byte[BackwardTransfer.MC_PK_HASH_SIZE];
+            publicKeyHash[0] = (byte) i;
+            btList.add(new BackwardTransfer(publicKeyHash, 1000L * (i + 1)));
+        }
+
+        FieldElement msgToSign = NaiveThresholdSigProof.createMsgToSign(
+            btList.toArray(new BackwardTransfer[0]),
+            scId,
+            epochNumber,
+            endCumulativeScTxCommTreeRoot,
+            btrFee,
+            ftMinAmount
+        );
+        for (int i = 0; i < keyCount; i++) {
+            SchnorrKeyPair keyPair = SchnorrKeyPair.generate();
+            publicKeyList.add(keyPair.getPublicKey());
+            signatureList.add(keyPair.signMessage(msgToSign));
+            keyPair.getSecretKey().freeSecretKey();
+        }
+        msgToSign.freeFieldElement();
+
+        CreateProofResult proofResult = NaiveThresholdSigProof.createProof(
+            btList, scId, epochNumber, endCumulativeScTxCommTreeRoot,
+            btrFee, ftMinAmount, signatureList, publicKeyList, threshold,
+            snarkPkPath, false, zk
+        );
+        assertNotNull("Proof creation must be successful", proofResult);
+
+        try (
+            FieldElement constant = NaiveThresholdSigProof.getConstant(publicKeyList, threshold);
+            FieldElement wrongConstant = NaiveThresholdSigProof.getConstant(publicKeyList, threshold + 1);
+            Certificate cert = Certificate.create(
+                scId, epochNumber, endCumulativeScTxCommTreeRoot, btList, btrFee, ftMinAmount, proofResult
+            )
+        )
+        {
+            assertEquals(keyCount, cert.getQuality());
+            assertArrayEquals(proofResult.getProof(), cert.getProof());
+            assertTrue("Certificate must be verified", cert.verify(constant, snarkVkPath));
+            assertFalse("Certificate must not be verified", cert.verify(wrongConstant, snarkVkPath));
+            assertThrows(IllegalArgumentException.class,
+                    () -> cert.verify(constant, snarkVkPath + ".missing"));
+
+            // Serialization round trip
+            byte[] certBytes = cert.serialize();
+            try (Certificate deserializedCert = Certificate.deserialize(certBytes)) {
+                assertArrayEquals(certBytes, deserializedCert.serialize());
+                assertTrue("Certificate must be verified", deserializedCert.verify(constant, snarkVkPath));
+            }
+            assertThrows(IllegalArgumentException.class,
+                    () -> Certificate.deserialize(Arrays.copyOf(certBytes, certBytes.length - 1)));
+
+            // Certificate with a quality different from the proven one
+            try (Certificate wrongCert = Certificate.create(
+                    scId, epochNumber, threshold, endCumulativeScTxCommTreeRoot, btList,
+                    new ArrayList<>(), btrFee, ftMinAmount, proofResult.getProof()
+            ))
+            {
+                assertFalse("Certificate must not be verified", wrongCert.verify(constant, snarkVkPath));
+            }
+
+            // Invalid backward transfer amount
+            List<BackwardTransfer> wrongBtList = new ArrayList<>(btList);
+            wrongBtList.add(new BackwardTransfer(new byte[BackwardTransfer.MC_PK_HASH_SIZE], -1L));
+            assertThrows(IllegalArgumentException.class, () -> Certificate.create(
+                    scId, epochNumber, endCumulativeScTxCommTreeRoot, wrongBtList, btrFee, ftMinAmount, proofResult
+            ));
+        }
+    }
+
     @After
     public void freeData() {
         for (SchnorrPublicKey pk: publicKeyList)
@@ -2,6 +2,7 @@ package com.horizen.vrfnative;
 
 import com.horizen.librustsidechains.FieldElement;
 import com.horizen.schnorrnative.SchnorrKeyPair;
+import com.horizen.schnorrnative.SchnorrPublicKey;
 import com.horizen.schnorrnative.SchnorrSignature;
 import org.junit.Test;
 
@@ -9,6 +10,7 @@ import static org.junit.Assert.assertNull;
 import static org.junit.Assert.assertNotNull;
 import static org.junit.Assert.assertTrue;
 import static org.junit.Assert.assertEquals;
+import static org.junit.Assert.assertThrows;
 
 public class VRFKeyPairTest {
 
@@ -101,4 +103,24 @@ public class VRFKeyPairTest {
             }
         }
     }
+
+    @Test
+    public void testJson() throws Exception {
+        try
+        (
+            VRFKeyPair keyPair = VRFKeyPair.generate();
+            FieldElement message = FieldElement.createRandom();
+            VRFProveResult proofVRFOutputPair = keyPair.prove(message);
+            VRFPublicKey publicKey = VRFPublicKey.fromJson(keyPair.getPublicKey().toJson());
+            VRFProof proof = VRFProof.fromJson(proofVRFOutputPair.getVRFProof().toJson());
+            FieldElement vrfOutput = publicKey.proofToHash(proof, message)
+        )
+        {
+            assertEquals("VRF outputs must be equal", proofVRFOutputPair.getVRFOutput(), vrfOutput);
+
+            // Schnorr and VRF public keys have different JSON types
+            String publicKeyJson = keyPair.getPublicKey().toJson();
+            assertThrows(IllegalArgumentException.class, () -> SchnorrPublicKey.fromJson(publicKeyJson));
+        }
+    }
 }
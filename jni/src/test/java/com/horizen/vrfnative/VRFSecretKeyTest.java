@@ -46,4 +46,37 @@ public class VRFSecretKeyTest {
             }
         }
     }
+
+    @Test
+    public void testKeystore() throws Exception {
+        byte[] password = "correct horse battery staple".getBytes();
+
+        try(VRFKeyPair keyPair = VRFKeyPair.generate())
+        {
+            byte[] secretKeyBytes = keyPair.getSecretKey().serializeSecretKey();
+
+            byte[] keystore = keyPair.getSecretKey().encrypt(password);
+            assertEquals("Keystore size must be - " + VRFSecretKey.KEYSTORE_LENGTH,
+                    VRFSecretKey.KEYSTORE_LENGTH,
+                    keystore.length);
+
+            try(VRFSecretKey decryptedSecretKey = VRFSecretKey.decrypt(keystore, password))
+            {
+                assertNotNull("Keystore decryption must succeed.", decryptedSecretKey);
+                assertArrayEquals("Decrypted secret key must be the same.", secretKeyBytes, decryptedSecretKey.serializeSecretKey());
+                assertEquals("Decrypted secret key must be equal.", keyPair.getSecretKey(), decryptedSecretKey);
+            }
+
+            try(VRFKeyPair otherKeyPair = VRFKeyPair.generate())
+            {
+                assertNotEquals("Different secret keys must not be equal.", keyPair.getSecretKey(), otherKeyPair.getSecretKey());
+            }
+
+            assertNull("Decryption with wrong password must fail.", VRFSecretKey.decrypt(keystore, "wrong password".getBytes()));
+
+            byte[] corruptedKeystore = keystore.clone();
+            corruptedKeystore[corruptedKeystore.length / 2] ^= 1;
+            assertNull("Decryption of corrupted keystore must fail.", VRFSecretKey.decrypt(corruptedKeystore, password));
+        }
+    }
 }
@@ -0,0 +1,686 @@
+#![allow(
+    clippy::upper_case_acronyms,
+    clippy::too_many_arguments,
+    clippy::type_complexity
+)]
+
+use algebra::{serialize::*, SemanticallyValid};
+use cctp_primitives::{
+    proving_system::{init_dlog_keys, ProvingSystem},
+    utils::{data_structures::*, mht::*, serialization::*},
+};
+use demo_circuit::{generate_circuit_keypair, get_instance_for_setup, type_mapping::*};
+use pyo3::{
+    class::basic::CompareOp,
+    create_exception,
+    exceptions::{PyException, PyNotImplementedError, PyValueError},
+    prelude::*,
+    types::PyBytes,
+    wrap_pyfunction, PyObjectProtocol,
+};
+use zendoo_sc_lib::*;
+
+use std::path::Path;
+
+//*****************************Python bindings******************************************************
+//
+// Extension module `zendoo_sc`, built with maturin (see pyproject.toml). Objects are Python
+// classes wrapping the Rust types; failures are reported as exceptions:
+// - ValueError for inputs that are malformed or not valid for the operation;
+// - zendoo_sc.CryptoError for failures of the cryptographic operations.
+// The long running calls (setup, proof creation and verification) release the GIL.
+
+create_exception!(zendoo_sc, CryptoError, PyException);
+
+fn crypto<T, E: ToString>(result: Result<T, E>) -> PyResult<T> {
+    result.map_err(|e| CryptoError::new_err(e.to_string()))
+}
+
+fn invalid<T, E: ToString>(result: Result<T, E>) -> PyResult<T> {
+    result.map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn check(condition: bool, msg: &str) -> PyResult<()> {
+    if condition {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(msg.to_owned()))
+    }
+}
+
+fn to_bytes<'py, T: CanonicalSerialize>(
+    py: Python<'py>,
+    obj: &T,
+    compressed: Option<bool>,
+) -> PyResult<&'py PyBytes> {
+    Ok(PyBytes::new(
+        py,
+        &crypto(serialize_to_buffer(obj, compressed))?,
+    ))
+}
+
+fn from_bytes<T: CanonicalDeserialize + SemanticallyValid>(
+    bytes: &[u8],
+    compressed: Option<bool>,
+) -> PyResult<T> {
+    invalid(deserialize_from_buffer(bytes, Some(true), compressed))
+}
+
+fn read_proving_system(proving_system: &str) -> PyResult<ProvingSystem> {
+    match proving_system {
+        "darlin" => Ok(ProvingSystem::Darlin),
+        "coboundary_marlin" => Ok(ProvingSystem::CoboundaryMarlin),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown proving system '{}', expected 'darlin' or 'coboundary_marlin'",
+            proving_system
+        ))),
+    }
+}
+
+fn personalization_to_vec(
+    personalization: &Option<Vec<PyFieldElement>>,
+) -> Option<Vec<&FieldElement>> {
+    personalization
+        .as_ref()
+        .map(|p| p.iter().map(|fe| &fe.0).collect())
+}
+
+fn read_bt_list(bt_list: &[PyBackwardTransfer]) -> Vec<BackwardTransfer> {
+    bt_list
+        .iter()
+        .map(|bt| BackwardTransfer {
+            pk_dest: bt.pk_dest,
+            amount: bt.amount,
+        })
+        .collect()
+}
+
+////////////FIELD ELEMENT
+
+/// Element of the field over which all the hashes and circuits are defined.
+#[pyclass(name = "FieldElement")]
+#[derive(Clone)]
+struct PyFieldElement(FieldElement);
+
+#[pymethods]
+impl PyFieldElement {
+    #[new]
+    fn new(value: u64) -> Self {
+        Self(FieldElement::from(value))
+    }
+
+    /// Random field element, deterministically derived from `seed`. For tests only.
+    #[staticmethod]
+    fn random(seed: u64) -> Self {
+        Self(get_random_field_element(seed))
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        check(bytes.len() == FIELD_SIZE, "wrong field element size")?;
+        from_bytes(bytes, None).map(Self)
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        to_bytes(py, &self.0, None)
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for PyFieldElement {
+    fn __richcmp__(&self, other: PyRef<PyFieldElement>, op: CompareOp) -> PyResult<bool> {
+        match op {
+            CompareOp::Eq => Ok(self.0 == other.0),
+            CompareOp::Ne => Ok(self.0 != other.0),
+            _ => Err(PyNotImplementedError::new_err(
+                "field elements are not ordered",
+            )),
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let bytes = crypto(serialize_to_buffer(&self.0, None))?;
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(format!("FieldElement({})", hex))
+    }
+}
+
+////////////POSEIDON HASH
+
+/// Poseidon hash, either of a fixed number of inputs or of any number of them.
+#[pyclass(name = "PoseidonHash")]
+struct PyPoseidonHash(ResumablePoseidonHash);
+
+#[pymethods]
+impl PyPoseidonHash {
+    #[staticmethod]
+    fn constant_length(input_size: usize, personalization: Option<Vec<PyFieldElement>>) -> Self {
+        Self(ResumablePoseidonHash::constant_length(
+            input_size,
+            personalization_to_vec(&personalization),
+        ))
+    }
+
+    /// If `mod_rate` is true, the number of inputs must be a multiple of the rate.
+    #[staticmethod]
+    fn variable_length(mod_rate: bool, personalization: Option<Vec<PyFieldElement>>) -> Self {
+        Self(ResumablePoseidonHash::variable_length(
+            mod_rate,
+            personalization_to_vec(&personalization),
+        ))
+    }
+
+    fn update(&mut self, input: PyRef<PyFieldElement>) {
+        self.0.update(&input.0);
+    }
+
+    /// Absorb `bytes`, length prefixed and packed into field elements.
+    fn update_bytes(&mut self, bytes: &[u8]) -> PyResult<()> {
+        crypto(self.0.update_bytes(bytes))
+    }
+
+    /// Raises CryptoError if the number of inputs is not the expected one.
+    fn finalize(&self) -> PyResult<PyFieldElement> {
+        crypto(self.0.finalize()).map(PyFieldElement)
+    }
+
+    fn reset(&mut self, personalization: Option<Vec<PyFieldElement>>) {
+        self.0.reset(personalization_to_vec(&personalization));
+    }
+}
+
+////////////MERKLE TREE
+
+/// In memory Poseidon Merkle tree, optimized for appending leaves in batches of
+/// `processing_step`.
+#[pyclass(name = "MerkleTree")]
+struct PyMerkleTree(InMemoryOptimizedMerkleTree);
+
+#[pymethods]
+impl PyMerkleTree {
+    #[new]
+    fn new(height: usize, processing_step: usize) -> PyResult<Self> {
+        invalid(InMemoryOptimizedMerkleTree::new(height, processing_step)).map(Self)
+    }
+
+    /// Raises ValueError if the tree is full or finalized.
+    fn append(&mut self, leaf: PyRef<PyFieldElement>) -> PyResult<()> {
+        invalid(self.0.append(&leaf.0))
+    }
+
+    /// No more leaves can be appended afterwards, root and paths become available.
+    fn finalize(&mut self) -> PyResult<()> {
+        crypto(self.0.finalize_in_place())
+    }
+
+    fn root(&self) -> PyResult<PyFieldElement> {
+        get_ginger_mht_root(&self.0)
+            .map(PyFieldElement)
+            .ok_or_else(|| PyValueError::new_err("tree not finalized"))
+    }
+
+    fn get_path(&self, leaf_index: u64) -> PyResult<PyMerklePath> {
+        get_ginger_mht_path(&self.0, leaf_index)
+            .map(PyMerklePath)
+            .ok_or_else(|| PyValueError::new_err("tree not finalized or leaf index out of range"))
+    }
+}
+
+#[pyclass(name = "MerklePath")]
+struct PyMerklePath(GingerMHTPath);
+
+#[pymethods]
+impl PyMerklePath {
+    /// Check that this is the path of `leaf` in the tree of height `height` with root `root`.
+    fn verify(
+        &self,
+        height: usize,
+        leaf: PyRef<PyFieldElement>,
+        root: PyRef<PyFieldElement>,
+    ) -> PyResult<bool> {
+        Ok(self.0.is_valid()
+            && crypto(verify_ginger_merkle_path(&self.0, height, &leaf.0, &root.0))?)
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        from_bytes(bytes, None).map(Self)
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        to_bytes(py, &self.0, None)
+    }
+}
+
+////////////SCHNORR
+
+/// Schnorr secret key. Its memory is wiped when it's garbage collected.
+#[pyclass(name = "SchnorrSecretKey")]
+struct PySchnorrSecretKey(ZeroizingSchnorrSk);
+
+#[pymethods]
+impl PySchnorrSecretKey {
+    #[staticmethod]
+    fn generate() -> Self {
+        let (_, sk) = schnorr_generate_key();
+        Self(sk)
+    }
+
+    fn public_key(&self) -> PySchnorrPublicKey {
+        PySchnorrPublicKey(schnorr_get_public_key(&self.0))
+    }
+
+    fn sign(&self, message: PyRef<PyFieldElement>) -> PyResult<PySchnorrSignature> {
+        let pk = schnorr_get_public_key(&self.0);
+        crypto(schnorr_sign(&message.0, &self.0, &pk)).map(PySchnorrSignature)
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        invalid(deserialize_secret::<SchnorrSk>(bytes)).map(Self)
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &crypto(serialize_secret(&*self.0))?))
+    }
+}
+
+#[pyclass(name = "SchnorrPublicKey")]
+#[derive(Clone)]
+struct PySchnorrPublicKey(SchnorrPk);
+
+#[pymethods]
+impl PySchnorrPublicKey {
+    fn verify_key(&self) -> bool {
+        schnorr_verify_public_key(&self.0)
+    }
+
+    fn verify_signature(
+        &self,
+        message: PyRef<PyFieldElement>,
+        signature: PyRef<PySchnorrSignature>,
+    ) -> PyResult<bool> {
+        crypto(schnorr_verify_signature(&message.0, &self.0, &signature.0))
+    }
+
+    /// Deserialize a compressed public key. Raises ValueError if the key is not valid.
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        from_bytes(bytes, Some(true)).map(Self)
+    }
+
+    /// Compressed serialization of the public key.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        to_bytes(py, &self.0, Some(true))
+    }
+}
+
+#[pyclass(name = "SchnorrSignature")]
+#[derive(Clone)]
+struct PySchnorrSignature(SchnorrSig);
+
+#[pymethods]
+impl PySchnorrSignature {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        from_bytes(bytes, None).map(Self)
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        to_bytes(py, &self.0, None)
+    }
+}
+
+////////////VRF
+
+/// VRF secret key. Its memory is wiped when it's garbage collected.
+#[pyclass(name = "VrfSecretKey")]
+struct PyVrfSecretKey(ZeroizingVRFSk);
+
+#[pymethods]
+impl PyVrfSecretKey {
+    #[staticmethod]
+    fn generate() -> Self {
+        let (_, sk) = vrf_generate_key();
+        Self(sk)
+    }
+
+    fn public_key(&self) -> PyVrfPublicKey {
+        PyVrfPublicKey(vrf_get_public_key(&self.0))
+    }
+
+    /// Returns the proof and the VRF output for `message`.
+    fn prove(&self, message: PyRef<PyFieldElement>) -> PyResult<(PyVrfProof, PyFieldElement)> {
+        let pk = vrf_get_public_key(&self.0);
+        let (proof, output) = crypto(vrf_prove(&message.0, &self.0, &pk))?;
+        Ok((PyVrfProof(proof), PyFieldElement(output)))
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        invalid(deserialize_secret::<VRFSk>(bytes)).map(Self)
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &crypto(serialize_secret(&*self.0))?))
+    }
+}
+
+#[pyclass(name = "VrfPublicKey")]
+#[derive(Clone)]
+struct PyVrfPublicKey(VRFPk);
+
+#[pymethods]
+impl PyVrfPublicKey {
+    fn verify_key(&self) -> bool {
+        vrf_verify_public_key(&self.0)
+    }
+
+    /// VRF output for `message`. Raises CryptoError if `proof` is not valid.
+    fn proof_to_hash(
+        &self,
+        message: PyRef<PyFieldElement>,
+        proof: PyRef<PyVrfProof>,
+    ) -> PyResult<PyFieldElement> {
+        crypto(vrf_proof_to_hash(&message.0, &self.0, &proof.0)).map(PyFieldElement)
+    }
+
+    /// Deserialize a compressed public key. Raises ValueError if the key is not valid.
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        from_bytes(bytes, Some(true)).map(Self)
+    }
+
+    /// Compressed serialization of the public key.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        to_bytes(py, &self.0, Some(true))
+    }
+}
+
+#[pyclass(name = "VrfProof")]
+struct PyVrfProof(VRFProof);
+
+#[pymethods]
+impl PyVrfProof {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        from_bytes(bytes, Some(true)).map(Self)
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        to_bytes(py, &self.0, Some(true))
+    }
+}
+
+////////////NAIVE THRESHOLD SIGNATURE CIRCUIT
+
+/// Backward transfer of a certificate: `amount` paid to the mainchain public key hash `pk_dest`.
+#[pyclass(name = "BackwardTransfer")]
+#[derive(Clone)]
+struct PyBackwardTransfer {
+    pk_dest: [u8; MC_PK_SIZE],
+    #[pyo3(get)]
+    amount: u64,
+}
+
+#[pymethods]
+impl PyBackwardTransfer {
+    #[new]
+    fn new(pk_dest: &[u8], amount: u64) -> PyResult<Self> {
+        check(pk_dest.len() == MC_PK_SIZE, "wrong pk_dest size")?;
+        let mut bt = Self {
+            pk_dest: [0u8; MC_PK_SIZE],
+            amount,
+        };
+        bt.pk_dest.copy_from_slice(pk_dest);
+        Ok(bt)
+    }
+
+    #[getter]
+    fn pk_dest<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.pk_dest)
+    }
+}
+
+/// Generate the DLOG keys, required before any setup, proof creation or verification.
+/// `proving_system` is either "darlin" or "coboundary_marlin".
+#[pyfunction]
+fn generate_dlog_keys(
+    py: Python,
+    proving_system: &str,
+    max_segment_size: usize,
+    supported_segment_size: usize,
+) -> PyResult<()> {
+    let proving_system = read_proving_system(proving_system)?;
+    crypto(py.allow_threads(|| {
+        init_dlog_keys(proving_system, max_segment_size, supported_segment_size)
+            .map_err(|e| e.to_string())
+    }))
+}
+
+/// Generate and write to the given paths the proving and verification keys of the naive
+/// threshold signature circuit supporting `max_pks` public keys.
+#[pyfunction(zk = "false", compress_pk = "true", compress_vk = "true")]
+fn naive_threshold_sig_setup(
+    py: Python,
+    proving_system: &str,
+    max_pks: usize,
+    proving_key_path: &str,
+    verification_key_path: &str,
+    max_proof_size: usize,
+    max_vk_size: usize,
+    zk: bool,
+    compress_pk: bool,
+    compress_vk: bool,
+) -> PyResult<()> {
+    let proving_system = read_proving_system(proving_system)?;
+    crypto(py.allow_threads(|| {
+        generate_circuit_keypair(
+            get_instance_for_setup(max_pks),
+            proving_system,
+            Path::new(proving_key_path),
+            Path::new(verification_key_path),
+            max_proof_size,
+            max_vk_size,
+            zk,
+            Some(compress_pk),
+            Some(compress_vk),
+        )
+        .map_err(|e| e.to_string())
+    }))
+}
+
+/// H(H(pks), threshold), the constant of the naive threshold signature circuit.
+#[pyfunction]
+fn compute_pks_threshold_hash(
+    pks: Vec<PySchnorrPublicKey>,
+    threshold: u64,
+) -> PyResult<PyFieldElement> {
+    let pks = pks.into_iter().map(|pk| pk.0).collect::<Vec<_>>();
+    crypto(zendoo_sc_lib::compute_pks_threshold_hash(&pks, threshold)).map(PyFieldElement)
+}
+
+/// Message signed by the signers of a certificate.
+#[pyfunction]
+fn compute_msg_to_sign(
+    sc_id: PyRef<PyFieldElement>,
+    epoch_number: u32,
+    end_cumulative_sc_tx_commitment_tree_root: PyRef<PyFieldElement>,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: Vec<PyBackwardTransfer>,
+) -> PyResult<PyFieldElement> {
+    let (_, msg) = crypto(zendoo_sc_lib::compute_msg_to_sign(
+        &sc_id.0,
+        epoch_number,
+        &end_cumulative_sc_tx_commitment_tree_root.0,
+        btr_fee,
+        ft_min_amount,
+        read_bt_list(&bt_list),
+    ))?;
+    Ok(PyFieldElement(msg))
+}
+
+/// Create a naive threshold signature proof. `sigs` has one entry for each public key, None
+/// if the corresponding signature is missing. Returns the proof and the number of valid
+/// signatures (the quality of the certificate). Raises ValueError if the valid signatures
+/// are less than `threshold`.
+#[pyfunction(
+    enforce_membership = "false",
+    zk = "false",
+    compressed_pk = "true",
+    compress_proof = "true"
+)]
+fn create_naive_threshold_sig_proof<'py>(
+    py: Python<'py>,
+    pks: Vec<PySchnorrPublicKey>,
+    sigs: Vec<Option<PySchnorrSignature>>,
+    sc_id: PyRef<PyFieldElement>,
+    epoch_number: u32,
+    end_cumulative_sc_tx_commitment_tree_root: PyRef<PyFieldElement>,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: Vec<PyBackwardTransfer>,
+    threshold: u64,
+    proving_key_path: &str,
+    enforce_membership: bool,
+    zk: bool,
+    compressed_pk: bool,
+    compress_proof: bool,
+) -> PyResult<(&'py PyBytes, u64)> {
+    check(
+        pks.len() == sigs.len(),
+        "pks and sigs must have the same length",
+    )?;
+    let pks = pks.into_iter().map(|pk| pk.0).collect::<Vec<_>>();
+    let sigs = sigs
+        .into_iter()
+        .map(|sig| sig.map(|sig| sig.0))
+        .collect::<Vec<_>>();
+    let sc_id = sc_id.0;
+    let end_cumulative_sc_tx_commitment_tree_root = end_cumulative_sc_tx_commitment_tree_root.0;
+
+    // Check the signatures here, so that not reaching the threshold is reported as an
+    // invalid input rather than as a failure of the prover
+    let (_, msg) = crypto(zendoo_sc_lib::compute_msg_to_sign(
+        &sc_id,
+        epoch_number,
+        &end_cumulative_sc_tx_commitment_tree_root,
+        btr_fee,
+        ft_min_amount,
+        read_bt_list(&bt_list),
+    ))?;
+    let mut valid_sigs = 0;
+    for (pk, sig) in pks.iter().zip(sigs.iter()) {
+        if let Some(sig) = sig {
+            if crypto(schnorr_verify_signature(&msg, pk, sig))? {
+                valid_sigs += 1;
+            }
+        }
+    }
+    check(valid_sigs >= threshold, "not enough valid signatures")?;
+
+    let bt_list = read_bt_list(&bt_list);
+    let (proof, quality) = crypto(py.allow_threads(|| {
+        zendoo_sc_lib::create_naive_threshold_sig_proof(
+            &pks,
+            sigs,
+            &sc_id,
+            epoch_number,
+            &end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee,
+            ft_min_amount,
+            bt_list,
+            threshold,
+            Path::new(proving_key_path),
+            enforce_membership,
+            zk,
+            compressed_pk,
+            compress_proof,
+        )
+        .map_err(|e| e.to_string())
+    }))?;
+
+    Ok((PyBytes::new(py, &proof), quality))
+}
+
+/// Verify a naive threshold signature proof of quality `valid_sigs`, where `constant` is the
+/// one returned by compute_pks_threshold_hash().
+#[pyfunction(
+    check_proof = "true",
+    compressed_proof = "true",
+    check_vk = "true",
+    compressed_vk = "true"
+)]
+fn verify_naive_threshold_sig_proof(
+    py: Python,
+    constant: PyRef<PyFieldElement>,
+    sc_id: PyRef<PyFieldElement>,
+    epoch_number: u32,
+    end_cumulative_sc_tx_commitment_tree_root: PyRef<PyFieldElement>,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: Vec<PyBackwardTransfer>,
+    valid_sigs: u64,
+    proof: &[u8],
+    verification_key_path: &str,
+    check_proof: bool,
+    compressed_proof: bool,
+    check_vk: bool,
+    compressed_vk: bool,
+) -> PyResult<bool> {
+    check(!proof.is_empty(), "empty proof")?;
+    let constant = constant.0;
+    let sc_id = sc_id.0;
+    let end_cumulative_sc_tx_commitment_tree_root = end_cumulative_sc_tx_commitment_tree_root.0;
+    let bt_list = read_bt_list(&bt_list);
+    let proof = proof.to_vec();
+
+    crypto(py.allow_threads(|| {
+        zendoo_sc_lib::verify_naive_threshold_sig_proof(
+            &constant,
+            &sc_id,
+            epoch_number,
+            &end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee,
+            ft_min_amount,
+            bt_list,
+            valid_sigs,
+            proof,
+            check_proof,
+            compressed_proof,
+            Path::new(verification_key_path),
+            check_vk,
+            compressed_vk,
+        )
+        .map_err(|e| e.to_string())
+    }))
+}
+
+#[pymodule]
+fn zendoo_sc(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("CryptoError", py.get_type::<CryptoError>())?;
+    m.add("FIELD_SIZE", FIELD_SIZE)?;
+    m.add("MC_PK_SIZE", MC_PK_SIZE)?;
+
+    m.add_class::<PyFieldElement>()?;
+    m.add_class::<PyPoseidonHash>()?;
+    m.add_class::<PyMerkleTree>()?;
+    m.add_class::<PyMerklePath>()?;
+    m.add_class::<PySchnorrSecretKey>()?;
+    m.add_class::<PySchnorrPublicKey>()?;
+    m.add_class::<PySchnorrSignature>()?;
+    m.add_class::<PyVrfSecretKey>()?;
+    m.add_class::<PyVrfPublicKey>()?;
+    m.add_class::<PyVrfProof>()?;
+    m.add_class::<PyBackwardTransfer>()?;
+
+    m.add_function(wrap_pyfunction!(generate_dlog_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(naive_threshold_sig_setup, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_pks_threshold_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_msg_to_sign, m)?)?;
+    m.add_function(wrap_pyfunction!(create_naive_threshold_sig_proof, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_naive_threshold_sig_proof, m)?)?;
+
+    Ok(())
+}
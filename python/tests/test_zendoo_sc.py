@@ -0,0 +1,190 @@
+import os
+
+import pytest
+
+import zendoo_sc
+from zendoo_sc import (
+    BackwardTransfer,
+    FieldElement,
+    MerklePath,
+    MerkleTree,
+    PoseidonHash,
+    SchnorrPublicKey,
+    SchnorrSecretKey,
+    SchnorrSignature,
+    VrfProof,
+    VrfPublicKey,
+    VrfSecretKey,
+)
+
+
+def test_field_element():
+    fe = FieldElement(42)
+    fe_bytes = fe.to_bytes()
+    assert len(fe_bytes) == zendoo_sc.FIELD_SIZE
+    assert FieldElement.from_bytes(fe_bytes) == fe
+    assert FieldElement(43) != fe
+    assert FieldElement.random(1) == FieldElement.random(1)
+
+    with pytest.raises(ValueError):
+        FieldElement.from_bytes(fe_bytes[:-1])
+    with pytest.raises(ValueError):
+        FieldElement.from_bytes(b"\xff" * zendoo_sc.FIELD_SIZE)
+
+
+def test_poseidon_hash():
+    inputs = [FieldElement(1), FieldElement(2)]
+
+    h = PoseidonHash.constant_length(2)
+    h.update(inputs[0])
+
+    # Not enough inputs
+    with pytest.raises(zendoo_sc.CryptoError):
+        h.finalize()
+
+    h.update(inputs[1])
+    digest = h.finalize()
+
+    # Personalization changes the digest
+    h = PoseidonHash.constant_length(2, [FieldElement(3)])
+    for fe in inputs:
+        h.update(fe)
+    assert h.finalize() != digest
+
+    # After a reset without personalization, same digest of the first instance
+    h.reset()
+    for fe in inputs:
+        h.update(fe)
+    assert h.finalize() == digest
+
+    h = PoseidonHash.variable_length(False)
+    h.update_bytes(b"\x01\x02\x03")
+    bytes_digest = h.finalize()
+    assert bytes_digest != digest
+
+
+def test_merkle_tree():
+    height = 4
+    leaves = [FieldElement.random(i) for i in range(5)]
+
+    tree = MerkleTree(height, 1 << height)
+    for leaf in leaves:
+        tree.append(leaf)
+
+    # Root and paths are available only after finalization
+    with pytest.raises(ValueError):
+        tree.root()
+    tree.finalize()
+    root = tree.root()
+    with pytest.raises(ValueError):
+        tree.append(leaves[0])
+
+    for i, leaf in enumerate(leaves):
+        path = tree.get_path(i)
+        assert path.verify(height, leaf, root)
+        assert not path.verify(height, leaves[(i + 1) % len(leaves)], root)
+        assert MerklePath.from_bytes(path.to_bytes()).verify(height, leaf, root)
+
+    with pytest.raises(ValueError):
+        tree.get_path(1 << height)
+
+
+def test_schnorr():
+    sk = SchnorrSecretKey.generate()
+    pk = sk.public_key()
+    assert pk.verify_key()
+
+    msg = FieldElement.random(1)
+    sig = sk.sign(msg)
+    assert pk.verify_signature(msg, sig)
+    assert not pk.verify_signature(FieldElement.random(2), sig)
+
+    # Serialization round trips
+    pk_bytes = pk.to_bytes()
+    deserialized_pk = SchnorrPublicKey.from_bytes(pk_bytes)
+    deserialized_sig = SchnorrSignature.from_bytes(sig.to_bytes())
+    assert deserialized_pk.verify_signature(msg, deserialized_sig)
+    assert SchnorrSecretKey.from_bytes(sk.to_bytes()).public_key().to_bytes() == pk_bytes
+
+    with pytest.raises(ValueError):
+        SchnorrPublicKey.from_bytes(pk_bytes[:-1])
+
+
+def test_vrf():
+    sk = VrfSecretKey.generate()
+    pk = sk.public_key()
+    assert pk.verify_key()
+
+    msg = FieldElement.random(1)
+    proof, output = sk.prove(msg)
+
+    deserialized_pk = VrfPublicKey.from_bytes(pk.to_bytes())
+    deserialized_proof = VrfProof.from_bytes(proof.to_bytes())
+    assert deserialized_pk.proof_to_hash(msg, deserialized_proof) == output
+
+    # Proof for another message
+    with pytest.raises(zendoo_sc.CryptoError):
+        pk.proof_to_hash(FieldElement.random(2), proof)
+
+
+def test_backward_transfer():
+    bt = BackwardTransfer(b"\x01" * zendoo_sc.MC_PK_SIZE, 10)
+    assert bt.pk_dest == b"\x01" * zendoo_sc.MC_PK_SIZE
+    assert bt.amount == 10
+
+    with pytest.raises(ValueError):
+        BackwardTransfer(b"\x01" * (zendoo_sc.MC_PK_SIZE - 1), 10)
+
+
+def test_naive_threshold_sig_proof(tmp_path):
+    key_count = 7
+    threshold = 5
+    epoch_number = 10
+    btr_fee = 100
+    ft_min_amount = 200
+    pk_path = str(tmp_path / "test_snark_pk")
+    vk_path = str(tmp_path / "test_snark_vk")
+
+    zendoo_sc.generate_dlog_keys("coboundary_marlin", 1 << 17, 1 << 15)
+    zendoo_sc.naive_threshold_sig_setup(
+        "coboundary_marlin", key_count, pk_path, vk_path, 7000, 4000
+    )
+
+    sc_id = FieldElement.random(1)
+    end_cumulative_sc_tx_commitment_tree_root = FieldElement.random(2)
+    bt_list = [BackwardTransfer(os.urandom(zendoo_sc.MC_PK_SIZE), i) for i in range(3)]
+
+    sks = [SchnorrSecretKey.generate() for _ in range(key_count)]
+    pks = [sk.public_key() for sk in sks]
+
+    msg = zendoo_sc.compute_msg_to_sign(
+        sc_id, epoch_number, end_cumulative_sc_tx_commitment_tree_root,
+        btr_fee, ft_min_amount, bt_list
+    )
+    sigs = [sk.sign(msg) if i < threshold else None for i, sk in enumerate(sks)]
+    constant = zendoo_sc.compute_pks_threshold_hash(pks, threshold)
+
+    # Not enough signatures
+    with pytest.raises(ValueError):
+        zendoo_sc.create_naive_threshold_sig_proof(
+            pks, sigs[:threshold - 1] + [None] * (key_count - threshold + 1), sc_id,
+            epoch_number, end_cumulative_sc_tx_commitment_tree_root, btr_fee,
+            ft_min_amount, bt_list, threshold, pk_path
+        )
+
+    proof, quality = zendoo_sc.create_naive_threshold_sig_proof(
+        pks, sigs, sc_id, epoch_number, end_cumulative_sc_tx_commitment_tree_root,
+        btr_fee, ft_min_amount, bt_list, threshold, pk_path
+    )
+    assert quality == threshold
+
+    assert zendoo_sc.verify_naive_threshold_sig_proof(
+        constant, sc_id, epoch_number, end_cumulative_sc_tx_commitment_tree_root,
+        btr_fee, ft_min_amount, bt_list, quality, proof, vk_path
+    )
+
+    # Wrong quality
+    assert not zendoo_sc.verify_naive_threshold_sig_proof(
+        constant, sc_id, epoch_number, end_cumulative_sc_tx_commitment_tree_root,
+        btr_fee, ft_min_amount, bt_list, quality - 1, proof, vk_path
+    )
@@ -0,0 +1,309 @@
+#![allow(clippy::upper_case_acronyms, clippy::new_without_default)]
+
+use algebra::{serialize::*, SemanticallyValid};
+use cctp_primitives::utils::{mht::*, serialization::*};
+use demo_circuit::type_mapping::*;
+use wasm_bindgen::prelude::*;
+use zendoo_sc_lib::*;
+
+//*****************************WebAssembly bindings*************************************************
+//
+// wasm-bindgen API of the primitives needed by light clients (e.g. browser wallets): field
+// elements, Poseidon hash, Merkle path verification, Schnorr and VRF keys. Proof creation and
+// verification are not included.
+// Objects are JS classes wrapping the Rust types, serialized as in the JNI API; failures are
+// thrown as JS Errors. u64 values are BigInts on the JS side.
+
+fn js_error<E: ToString>(e: E) -> JsValue {
+    js_sys::Error::new(&e.to_string()).into()
+}
+
+fn to_bytes<T: CanonicalSerialize>(obj: &T, compressed: Option<bool>) -> Result<Vec<u8>, JsValue> {
+    serialize_to_buffer(obj, compressed).map_err(js_error)
+}
+
+fn from_bytes<T: CanonicalDeserialize + SemanticallyValid>(
+    bytes: &[u8],
+    compressed: Option<bool>,
+) -> Result<T, JsValue> {
+    deserialize_from_buffer(bytes, Some(true), compressed).map_err(js_error)
+}
+
+////////////FIELD ELEMENT
+
+#[wasm_bindgen(js_name = FieldElement)]
+pub struct WasmFieldElement(FieldElement);
+
+#[wasm_bindgen(js_class = FieldElement)]
+impl WasmFieldElement {
+    #[wasm_bindgen(constructor)]
+    pub fn new(value: u64) -> Self {
+        Self(FieldElement::from(value))
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmFieldElement, JsValue> {
+        if bytes.len() != FIELD_SIZE {
+            return Err(js_error("wrong field element size"));
+        }
+        from_bytes(bytes, None).map(Self)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        to_bytes(&self.0, None)
+    }
+
+    pub fn equals(&self, other: &WasmFieldElement) -> bool {
+        self.0 == other.0
+    }
+}
+
+////////////POSEIDON HASH
+
+/// Poseidon hash without personalization.
+#[wasm_bindgen(js_name = PoseidonHash)]
+pub struct WasmPoseidonHash(ResumablePoseidonHash);
+
+#[wasm_bindgen(js_class = PoseidonHash)]
+impl WasmPoseidonHash {
+    /// Hash of exactly `input_size` inputs.
+    #[wasm_bindgen(js_name = constantLength)]
+    pub fn constant_length(input_size: usize) -> Self {
+        Self(ResumablePoseidonHash::constant_length(input_size, None))
+    }
+
+    /// Hash of any number of inputs, which must be a multiple of the rate if `mod_rate` is true.
+    #[wasm_bindgen(js_name = variableLength)]
+    pub fn variable_length(mod_rate: bool) -> Self {
+        Self(ResumablePoseidonHash::variable_length(mod_rate, None))
+    }
+
+    pub fn update(&mut self, input: &WasmFieldElement) {
+        self.0.update(&input.0);
+    }
+
+    /// Absorb `bytes`, length prefixed and packed into field elements.
+    #[wasm_bindgen(js_name = updateBytes)]
+    pub fn update_bytes(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        self.0.update_bytes(bytes).map_err(js_error)
+    }
+
+    /// Throws if the number of inputs is not the expected one.
+    pub fn finalize(&self) -> Result<WasmFieldElement, JsValue> {
+        self.0.finalize().map(WasmFieldElement).map_err(js_error)
+    }
+
+    pub fn reset(&mut self) {
+        self.0.reset(None);
+    }
+}
+
+////////////MERKLE PATH
+
+#[wasm_bindgen(js_name = MerklePath)]
+pub struct WasmMerklePath(GingerMHTPath);
+
+#[wasm_bindgen(js_class = MerklePath)]
+impl WasmMerklePath {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmMerklePath, JsValue> {
+        from_bytes(bytes, None).map(Self)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        to_bytes(&self.0, None)
+    }
+
+    /// Check that this is the path of `leaf` in the tree of height `height` with root `root`.
+    pub fn verify(
+        &self,
+        height: usize,
+        leaf: &WasmFieldElement,
+        root: &WasmFieldElement,
+    ) -> Result<bool, JsValue> {
+        Ok(self.0.is_valid()
+            && verify_ginger_merkle_path(&self.0, height, &leaf.0, &root.0).map_err(js_error)?)
+    }
+}
+
+////////////SCHNORR
+
+/// Schnorr secret key. Its memory is wiped when it's freed.
+#[wasm_bindgen(js_name = SchnorrSecretKey)]
+pub struct WasmSchnorrSecretKey(ZeroizingSchnorrSk);
+
+#[wasm_bindgen(js_class = SchnorrSecretKey)]
+impl WasmSchnorrSecretKey {
+    pub fn generate() -> Self {
+        let (_, sk) = schnorr_generate_key();
+        Self(sk)
+    }
+
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self) -> WasmSchnorrPublicKey {
+        WasmSchnorrPublicKey(schnorr_get_public_key(&self.0))
+    }
+
+    pub fn sign(&self, message: &WasmFieldElement) -> Result<WasmSchnorrSignature, JsValue> {
+        let pk = schnorr_get_public_key(&self.0);
+        schnorr_sign(&message.0, &self.0, &pk)
+            .map(WasmSchnorrSignature)
+            .map_err(js_error)
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSchnorrSecretKey, JsValue> {
+        deserialize_secret::<SchnorrSk>(bytes)
+            .map(Self)
+            .map_err(js_error)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        serialize_secret(&*self.0)
+            .map(|bytes| bytes.to_vec())
+            .map_err(js_error)
+    }
+}
+
+#[wasm_bindgen(js_name = SchnorrPublicKey)]
+pub struct WasmSchnorrPublicKey(SchnorrPk);
+
+#[wasm_bindgen(js_class = SchnorrPublicKey)]
+impl WasmSchnorrPublicKey {
+    #[wasm_bindgen(js_name = verifyKey)]
+    pub fn verify_key(&self) -> bool {
+        schnorr_verify_public_key(&self.0)
+    }
+
+    #[wasm_bindgen(js_name = verifySignature)]
+    pub fn verify_signature(
+        &self,
+        message: &WasmFieldElement,
+        signature: &WasmSchnorrSignature,
+    ) -> Result<bool, JsValue> {
+        schnorr_verify_signature(&message.0, &self.0, &signature.0).map_err(js_error)
+    }
+
+    /// Deserialize a compressed public key. Throws if the key is not valid.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSchnorrPublicKey, JsValue> {
+        from_bytes(bytes, Some(true)).map(Self)
+    }
+
+    /// Compressed serialization of the public key.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        to_bytes(&self.0, Some(true))
+    }
+}
+
+#[wasm_bindgen(js_name = SchnorrSignature)]
+pub struct WasmSchnorrSignature(SchnorrSig);
+
+#[wasm_bindgen(js_class = SchnorrSignature)]
+impl WasmSchnorrSignature {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSchnorrSignature, JsValue> {
+        from_bytes(bytes, None).map(Self)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        to_bytes(&self.0, None)
+    }
+}
+
+////////////VRF
+
+/// VRF secret key. Its memory is wiped when it's freed.
+#[wasm_bindgen(js_name = VrfSecretKey)]
+pub struct WasmVrfSecretKey(ZeroizingVRFSk);
+
+#[wasm_bindgen(js_class = VrfSecretKey)]
+impl WasmVrfSecretKey {
+    pub fn generate() -> Self {
+        let (_, sk) = vrf_generate_key();
+        Self(sk)
+    }
+
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self) -> WasmVrfPublicKey {
+        WasmVrfPublicKey(vrf_get_public_key(&self.0))
+    }
+
+    /// Proof for `message`. The VRF output can be obtained with VrfPublicKey.proofToHash().
+    pub fn prove(&self, message: &WasmFieldElement) -> Result<WasmVrfProof, JsValue> {
+        let pk = vrf_get_public_key(&self.0);
+        vrf_prove(&message.0, &self.0, &pk)
+            .map(|(proof, _)| WasmVrfProof(proof))
+            .map_err(js_error)
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmVrfSecretKey, JsValue> {
+        deserialize_secret::<VRFSk>(bytes)
+            .map(Self)
+            .map_err(js_error)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        serialize_secret(&*self.0)
+            .map(|bytes| bytes.to_vec())
+            .map_err(js_error)
+    }
+}
+
+#[wasm_bindgen(js_name = VrfPublicKey)]
+pub struct WasmVrfPublicKey(VRFPk);
+
+#[wasm_bindgen(js_class = VrfPublicKey)]
+impl WasmVrfPublicKey {
+    #[wasm_bindgen(js_name = verifyKey)]
+    pub fn verify_key(&self) -> bool {
+        vrf_verify_public_key(&self.0)
+    }
+
+    /// VRF output for `message`. Throws if `proof` is not valid.
+    #[wasm_bindgen(js_name = proofToHash)]
+    pub fn proof_to_hash(
+        &self,
+        message: &WasmFieldElement,
+        proof: &WasmVrfProof,
+    ) -> Result<WasmFieldElement, JsValue> {
+        vrf_proof_to_hash(&message.0, &self.0, &proof.0)
+            .map(WasmFieldElement)
+            .map_err(js_error)
+    }
+
+    /// Deserialize a compressed public key. Throws if the key is not valid.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmVrfPublicKey, JsValue> {
+        from_bytes(bytes, Some(true)).map(Self)
+    }
+
+    /// Compressed serialization of the public key.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        to_bytes(&self.0, Some(true))
+    }
+}
+
+#[wasm_bindgen(js_name = VrfProof)]
+pub struct WasmVrfProof(VRFProof);
+
+#[wasm_bindgen(js_class = VrfProof)]
+impl WasmVrfProof {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmVrfProof, JsValue> {
+        from_bytes(bytes, Some(true)).map(Self)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        to_bytes(&self.0, Some(true))
+    }
+}
@@ -0,0 +1,126 @@
+//! Tests of the wasm bindings, run in a headless browser with
+//! `wasm-pack test --headless --firefox` (or `--chrome`).
+
+use cctp_primitives::utils::{mht::*, serialization::*};
+use demo_circuit::type_mapping::*;
+use wasm_bindgen_test::*;
+use zendoo_sc_lib::{
+    get_random_field_element, schnorr_verify_signature, InMemoryOptimizedMerkleTree,
+};
+use zendoo_sc_wasm::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn to_wasm(fe: &FieldElement) -> WasmFieldElement {
+    WasmFieldElement::from_bytes(&serialize_to_buffer(fe, None).unwrap()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn field_element() {
+    let fe = WasmFieldElement::new(42);
+    let fe_bytes = fe.to_bytes().unwrap();
+    assert_eq!(fe_bytes.len(), FIELD_SIZE);
+    assert!(WasmFieldElement::from_bytes(&fe_bytes).unwrap().equals(&fe));
+    assert!(!WasmFieldElement::new(43).equals(&fe));
+    assert!(WasmFieldElement::from_bytes(&fe_bytes[1..]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn poseidon_hash() {
+    let inputs = [get_random_field_element(1), get_random_field_element(2)];
+
+    // Same digest of the native implementation
+    let expected = {
+        let mut h = zendoo_sc_lib::ResumablePoseidonHash::constant_length(2, None);
+        inputs.iter().for_each(|fe| h.update(fe));
+        h.finalize().unwrap()
+    };
+
+    let mut h = WasmPoseidonHash::constant_length(2);
+    h.update(&to_wasm(&inputs[0]));
+
+    // Not enough inputs
+    assert!(h.finalize().is_err());
+
+    h.update(&to_wasm(&inputs[1]));
+    assert!(h.finalize().unwrap().equals(&to_wasm(&expected)));
+
+    h.reset();
+    h.update_bytes(&[1, 2, 3]).unwrap();
+    assert!(!h.finalize().unwrap().equals(&to_wasm(&expected)));
+}
+
+#[wasm_bindgen_test]
+fn merkle_path() {
+    let height = 4;
+    let leaves = (0..5).map(get_random_field_element).collect::<Vec<_>>();
+
+    let mut tree = InMemoryOptimizedMerkleTree::new(height, 1 << height).unwrap();
+    leaves.iter().for_each(|leaf| tree.append(leaf).unwrap());
+    tree.finalize_in_place().unwrap();
+    let root = to_wasm(&get_ginger_mht_root(&tree).unwrap());
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let path = get_ginger_mht_path(&tree, i as u64).unwrap();
+        let path = WasmMerklePath::from_bytes(&serialize_to_buffer(&path, None).unwrap()).unwrap();
+        assert!(path.verify(height, &to_wasm(leaf), &root).unwrap());
+        assert!(!path
+            .verify(height, &to_wasm(&leaves[(i + 1) % leaves.len()]), &root)
+            .unwrap());
+    }
+}
+
+#[wasm_bindgen_test]
+fn schnorr() {
+    let sk = WasmSchnorrSecretKey::generate();
+    let pk = sk.public_key();
+    assert!(pk.verify_key());
+
+    let msg = to_wasm(&get_random_field_element(1));
+    let sig = sk.sign(&msg).unwrap();
+    assert!(pk.verify_signature(&msg, &sig).unwrap());
+    assert!(!pk
+        .verify_signature(&to_wasm(&get_random_field_element(2)), &sig)
+        .unwrap());
+
+    // Serialization round trips
+    let pk_bytes = pk.to_bytes().unwrap();
+    let sig_bytes = sig.to_bytes().unwrap();
+    let deserialized_pk = WasmSchnorrPublicKey::from_bytes(&pk_bytes).unwrap();
+    let deserialized_sig = WasmSchnorrSignature::from_bytes(&sig_bytes).unwrap();
+    assert!(deserialized_pk
+        .verify_signature(&msg, &deserialized_sig)
+        .unwrap());
+    let deserialized_sk = WasmSchnorrSecretKey::from_bytes(&sk.to_bytes().unwrap()).unwrap();
+    assert_eq!(deserialized_sk.public_key().to_bytes().unwrap(), pk_bytes);
+    assert!(WasmSchnorrPublicKey::from_bytes(&pk_bytes[1..]).is_err());
+
+    // The signature verifies with the native implementation
+    let native_pk: SchnorrPk = deserialize_from_buffer(&pk_bytes, Some(true), Some(true)).unwrap();
+    let native_sig: SchnorrSig = deserialize_from_buffer(&sig_bytes, Some(true), None).unwrap();
+    let native_msg: FieldElement =
+        deserialize_from_buffer(&msg.to_bytes().unwrap(), None, None).unwrap();
+    assert!(schnorr_verify_signature(&native_msg, &native_pk, &native_sig).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn vrf() {
+    let sk = WasmVrfSecretKey::generate();
+    let pk = sk.public_key();
+    assert!(pk.verify_key());
+
+    let msg = to_wasm(&get_random_field_element(1));
+    let proof = sk.prove(&msg).unwrap();
+
+    let deserialized_pk = WasmVrfPublicKey::from_bytes(&pk.to_bytes().unwrap()).unwrap();
+    let deserialized_proof = WasmVrfProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+    let output = deserialized_pk
+        .proof_to_hash(&msg, &deserialized_proof)
+        .unwrap();
+    assert!(pk.proof_to_hash(&msg, &proof).unwrap().equals(&output));
+
+    // Proof for another message
+    assert!(pk
+        .proof_to_hash(&to_wasm(&get_random_field_element(2)), &proof)
+        .is_err());
+}
@@ -1,3 +1,5 @@
+#![warn(missing_docs)]
+
 use algebra::{AffineCurve, ProjectiveCurve, ToConstraintField, UniformRand};
 use primitives::{
     crh::{bowe_hopwood::BoweHopwoodPedersenParameters, FieldBasedHash},
@@ -5,7 +7,10 @@ use primitives::{
     vrf::{ecvrf::FieldBasedEcVrfPk, FieldBasedVrf},
 };
 
-use demo_circuit::{constants::VRFParams, naive_threshold_sig::*, type_mapping::*};
+use demo_circuit::{
+    constants::VRFParams, get_circuit_size_info, naive_threshold_sig::*, type_mapping::*,
+    CircuitSizeInfo,
+};
 use lazy_static::*;
 use rand::{rngs::OsRng, SeedableRng};
 use rand_xorshift::XorShiftRng;
@@ -23,12 +28,16 @@ use cctp_primitives::{
     },
 };
 
+use crate::{envelope::*, secret::*};
+
 use std::path::Path;
 
 //*******************************Generic functions**********************************************
 
-// NOTE: This function relies on a non-cryptographically safe RNG, therefore it
-// must be used ONLY for testing purposes
+/// Samples a field element from a RNG seeded with `seed`.
+///
+/// NOTE: This function relies on a non-cryptographically safe RNG, therefore it
+/// must be used ONLY for testing purposes
 pub fn get_random_field_element(seed: u64) -> FieldElement {
     let mut rng = XorShiftRng::seed_from_u64(seed);
     FieldElement::rand(&mut rng)
@@ -36,20 +45,27 @@ pub fn get_random_field_element(seed: u64) -> FieldElement {
 
 //***************************Schnorr types and functions********************************************
 
-pub fn schnorr_generate_key() -> (SchnorrPk, SchnorrSk) {
+/// Generates a new Schnorr key pair.
+/// The secret key is wiped when dropped, together with the copy left by its generation.
+pub fn schnorr_generate_key() -> (SchnorrPk, ZeroizingSchnorrSk) {
     let mut rng = OsRng;
-    let (pk, sk) = SchnorrSigScheme::keygen(&mut rng);
-    (pk.0.into_affine(), sk)
+    let (pk, mut sk) = SchnorrSigScheme::keygen(&mut rng);
+    let zeroizing_sk = Zeroizing::new(sk);
+    sk.zeroize();
+    (pk.0.into_affine(), zeroizing_sk)
 }
 
+/// Derives the Schnorr public key corresponding to `sk`.
 pub fn schnorr_get_public_key(sk: &SchnorrSk) -> SchnorrPk {
     SchnorrSigScheme::get_public_key(sk).0.into_affine()
 }
 
+/// Checks that `pk` is a valid Schnorr public key.
 pub fn schnorr_verify_public_key(pk: &SchnorrPk) -> bool {
     SchnorrSigScheme::keyverify(&FieldBasedSchnorrPk(pk.into_projective()))
 }
 
+/// Signs `msg` with the Schnorr key pair (`sk`, `pk`).
 pub fn schnorr_sign(
     msg: &FieldElement,
     sk: &SchnorrSk,
@@ -64,6 +80,7 @@ pub fn schnorr_sign(
     )
 }
 
+/// Verifies the Schnorr `signature` of `msg` against `pk`.
 pub fn schnorr_verify_signature(
     msg: &FieldElement,
     pk: &SchnorrPk,
@@ -78,8 +95,8 @@ pub fn schnorr_verify_signature(
 
 //*****************************Naive threshold sig circuit related functions************************
 
-// Computes H(H(pks), threshold): used to generate the constant value needed to be declared
-// in MC during SC creation.
+/// Computes H(H(pks), threshold): used to generate the constant value needed to be declared
+/// in MC during SC creation.
 pub fn compute_pks_threshold_hash(
     pks: &[SchnorrPk],
     threshold: u64,
@@ -99,7 +116,8 @@ pub fn compute_pks_threshold_hash(
         .finalize()
 }
 
-//Compute and return (MR(bt_list), H(sc_id, epoch_number, bt_root, end_cumulative_sc_tx_comm_tree_root, btr_fee, ft_min_amount))
+/// Computes and returns (MR(bt_list), H(sc_id, epoch_number, bt_root, end_cumulative_sc_tx_comm_tree_root, btr_fee, ft_min_amount)),
+/// i.e. the root of the backward transfers and the message the certifiers have to sign.
 pub fn compute_msg_to_sign(
     sc_id: &FieldElement,
     epoch_number: u32,
@@ -139,6 +157,9 @@ pub fn compute_msg_to_sign(
     Ok((mr_bt, msg))
 }
 
+/// Creates a naive threshold sig circuit proof for a certificate, using the proving key at
+/// `proving_key_path`. Missing signatures in `sigs` must be None.
+/// Returns the proof bytes and the number of valid signatures.
 pub fn create_naive_threshold_sig_proof(
     pks: &[SchnorrPk],
     mut sigs: Vec<Option<SchnorrSig>>,
@@ -209,8 +230,9 @@ pub fn create_naive_threshold_sig_proof(
         max_pks,
     );
 
-    let pk: ZendooProverKey = read_from_file(
+    let pk: ZendooProverKey = read_artifact_from_file(
         proving_key_path,
+        ArtifactType::ProverKey,
         Some(enforce_membership),
         Some(compressed_pk),
     )?;
@@ -238,6 +260,8 @@ pub fn create_naive_threshold_sig_proof(
     Ok((proof, valid_signatures))
 }
 
+/// Verifies a naive threshold sig circuit proof for a certificate, carrying `valid_sigs`
+/// as quality, against the vk at `vk_path`.
 pub fn verify_naive_threshold_sig_proof(
     constant: &FieldElement,
     sc_id: &FieldElement,
@@ -272,9 +296,36 @@ pub fn verify_naive_threshold_sig_proof(
         ft_min_amount,
     };
 
+    verify_certificate_proof(
+        ins,
+        &proof,
+        check_proof,
+        compressed_proof,
+        vk_path,
+        check_vk,
+        compressed_vk,
+    )
+}
+
+/// Verifies `proof` of a certificate with public inputs `ins` against the vk at `vk_path`.
+/// vk and proof may be sealed in envelopes.
+pub fn verify_certificate_proof(
+    ins: CertificateProofUserInputs,
+    proof: &[u8],
+    check_proof: bool,
+    compressed_proof: bool,
+    vk_path: &Path,
+    check_vk: bool,
+    compressed_vk: bool,
+) -> Result<bool, Error> {
+    let proof = open_artifact(ArtifactType::Proof, proof)?;
+    if proof.is_empty() {
+        Err("Empty proof")?
+    }
+
     // Check that the proving system type of the vk and proof are the same, before
     // deserializing them all
-    let vk_ps_type = read_from_file::<ProvingSystem>(vk_path, None, None)?;
+    let vk_ps_type = read_artifact_proving_system(vk_path, ArtifactType::VerifierKey)?;
 
     let proof_ps_type = deserialize_from_buffer::<ProvingSystem>(&proof[..1], None, None)?;
 
@@ -283,10 +334,15 @@ pub fn verify_naive_threshold_sig_proof(
     }
 
     // Deserialize proof and vk
-    let vk: ZendooVerifierKey = read_from_file(vk_path, Some(check_vk), Some(compressed_vk))?;
+    let vk: ZendooVerifierKey = read_artifact_from_file(
+        vk_path,
+        ArtifactType::VerifierKey,
+        Some(check_vk),
+        Some(compressed_vk),
+    )?;
 
     let proof: ZendooProof =
-        deserialize_from_buffer(proof.as_slice(), Some(check_proof), Some(compressed_proof))?;
+        deserialize_from_buffer(proof, Some(check_proof), Some(compressed_proof))?;
 
     // Verify proof
     let rng = &mut OsRng;
@@ -295,9 +351,71 @@ pub fn verify_naive_threshold_sig_proof(
     Ok(is_verified)
 }
 
+/// Computes number of constraints, proof and vk size of the naive threshold sig circuit
+/// supporting `max_pks` public keys, without performing the setup.
+pub fn get_naive_threshold_sig_circuit_size_info(
+    max_pks: usize,
+    proving_system: ProvingSystem,
+    segment_size: usize,
+    zk: bool,
+) -> Result<CircuitSizeInfo, Error> {
+    let circ = get_instance_for_setup(max_pks);
+    get_circuit_size_info(circ, proving_system, segment_size, zk)
+}
+
+/// Returns the biggest max_pks, not greater than `max_pks_bound`, for which the proof and vk
+/// of the naive threshold sig circuit don't exceed `max_proof_size` and `max_vk_size`.
+/// Returns None if not even a circuit with a single pk fits the limits.
+pub fn get_naive_threshold_sig_circuit_max_pks(
+    proving_system: ProvingSystem,
+    segment_size: usize,
+    zk: bool,
+    max_proof_size: usize,
+    max_vk_size: usize,
+    max_pks_bound: usize,
+) -> Result<Option<usize>, Error> {
+    let fits = |max_pks: usize| -> Result<bool, Error> {
+        Ok(
+            get_naive_threshold_sig_circuit_size_info(max_pks, proving_system, segment_size, zk)?
+                .fits(max_proof_size, max_vk_size),
+        )
+    };
+
+    if max_pks_bound == 0 || !fits(1)? {
+        return Ok(None);
+    }
+
+    // Proof and vk sizes are non decreasing in max_pks: find by doubling an upper bound
+    // that doesn't fit (if any), then binary search between the last two values.
+    let mut low = 1;
+    let mut high = loop {
+        let next = std::cmp::min(low * 2, max_pks_bound);
+        if next == low {
+            return Ok(Some(low));
+        }
+        if !fits(next)? {
+            break next;
+        }
+        low = next;
+    };
+
+    // Invariant: fits(low) && !fits(high)
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if fits(mid)? {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(Some(low))
+}
+
 //VRF types and functions
 
 lazy_static! {
+    /// Group hash parameters used by the VRF.
     pub static ref VRF_GH_PARAMS: BoweHopwoodPedersenParameters<G2Projective> = {
         let params = VRFParams::new();
         BoweHopwoodPedersenParameters::<G2Projective> {
@@ -306,20 +424,27 @@ lazy_static! {
     };
 }
 
-pub fn vrf_generate_key() -> (VRFPk, VRFSk) {
+/// Generates a new VRF key pair.
+/// The secret key is wiped when dropped, together with the copy left by its generation.
+pub fn vrf_generate_key() -> (VRFPk, ZeroizingVRFSk) {
     let mut rng = OsRng;
-    let (pk, sk) = VRFScheme::keygen(&mut rng);
-    (pk.0.into_affine(), sk)
+    let (pk, mut sk) = VRFScheme::keygen(&mut rng);
+    let zeroizing_sk = Zeroizing::new(sk);
+    sk.zeroize();
+    (pk.0.into_affine(), zeroizing_sk)
 }
 
+/// Derives the VRF public key corresponding to `sk`.
 pub fn vrf_get_public_key(sk: &VRFSk) -> VRFPk {
     VRFScheme::get_public_key(sk).0.into_affine()
 }
 
+/// Checks that `pk` is a valid VRF public key.
 pub fn vrf_verify_public_key(pk: &VRFPk) -> bool {
     VRFScheme::keyverify(&FieldBasedEcVrfPk(pk.into_projective()))
 }
 
+/// Computes the VRF proof for `msg` with the key pair (`sk`, `pk`), together with the VRF output.
 pub fn vrf_prove(
     msg: &FieldElement,
     sk: &VRFSk,
@@ -352,6 +477,7 @@ pub fn vrf_prove(
     Ok((proof, output))
 }
 
+/// Verifies the VRF `proof` for `msg` against `pk` and returns the VRF output.
 pub fn vrf_proof_to_hash(
     msg: &FieldElement,
     pk: &VRFPk,
@@ -365,22 +491,6 @@ pub fn vrf_proof_to_hash(
     )
 }
 
-// Test functions
-
-pub(crate) fn into_i8(v: Vec<u8>) -> Vec<i8> {
-    // first, make sure v's destructor doesn't free the data
-    // it thinks it owns when it goes out of scope
-    let mut v = std::mem::ManuallyDrop::new(v);
-
-    // then, pick apart the existing Vec
-    let p = v.as_mut_ptr();
-    let len = v.len();
-    let cap = v.capacity();
-
-    // finally, adopt the data into a new Vec
-    unsafe { Vec::from_raw_parts(p as *mut i8, len, cap) }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -390,6 +500,10 @@ mod test {
     use demo_circuit::generate_circuit_keypair;
     use rand::Rng;
 
+    fn into_i8(v: Vec<u8>) -> Vec<i8> {
+        v.into_iter().map(|b| b as i8).collect()
+    }
+
     fn create_sample_naive_threshold_sig_circuit(
         bt_num: usize,
         pk_path: &Path,
@@ -449,7 +563,7 @@ mod test {
             sks.push(keypair.1);
             println!(
                 "sk: {:?}",
-                into_i8(serialize_to_buffer(&keypair.1, None).unwrap())
+                into_i8(serialize_to_buffer(&*keypair.1, None).unwrap())
             );
         }
         println!("pks / sks finished");
@@ -526,6 +640,32 @@ mod test {
         )
         .unwrap());
 
+        //Verify again with sealed vk and proof
+        seal_artifact_file(vk_path, ArtifactType::VerifierKey).unwrap();
+        for (proof_type, expected) in
+            [(ArtifactType::Proof, true), (ArtifactType::VRFProof, false)].iter()
+        {
+            let result = verify_naive_threshold_sig_proof(
+                &constant,
+                &sc_id,
+                epoch_number,
+                &end_cumulative_sc_tx_comm_tree_root,
+                btr_fee,
+                ft_min_amount,
+                bt_list.clone(),
+                quality,
+                seal_artifact(*proof_type, &proof),
+                true,
+                true,
+                vk_path,
+                true,
+                true,
+            );
+            // A proof sealed with another type is rejected
+            assert_eq!(result.is_ok(), *expected);
+            assert!(result.unwrap_or(true));
+        }
+
         //Generate wrong public inputs by changing quality and assert proof verification doesn't pass
         assert!(!verify_naive_threshold_sig_proof(
             &constant,
@@ -593,6 +733,59 @@ mod test {
         std::fs::remove_file(proof_path_no_bwt).unwrap();
     }
 
+    #[test]
+    fn sample_calls_naive_threshold_sig_circuit_size_info() {
+        let ps_type = ProvingSystem::CoboundaryMarlin;
+        let segment_size = 1 << 14;
+        let max_pks_bound = 16;
+
+        // Size info grows with max_pks
+        let info_small =
+            get_naive_threshold_sig_circuit_size_info(2, ps_type, segment_size, false).unwrap();
+        let info =
+            get_naive_threshold_sig_circuit_size_info(8, ps_type, segment_size, false).unwrap();
+        assert!(info_small.num_constraints < info.num_constraints);
+        assert!(info_small.proof_size <= info.proof_size);
+        assert!(info.fits(info.proof_size, info.vk_size));
+        assert!(!info.fits(info.proof_size - 1, info.vk_size));
+
+        // Taking the sizes of the circuit with 8 pks as limits, the max supported pks
+        // must be at least 8, and the next one must not fit
+        let max_pks = get_naive_threshold_sig_circuit_max_pks(
+            ps_type,
+            segment_size,
+            false,
+            info.proof_size,
+            info.vk_size,
+            max_pks_bound,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(max_pks >= 8 && max_pks <= max_pks_bound);
+        if max_pks < max_pks_bound {
+            assert!(!get_naive_threshold_sig_circuit_size_info(
+                max_pks + 1,
+                ps_type,
+                segment_size,
+                false
+            )
+            .unwrap()
+            .fits(info.proof_size, info.vk_size));
+        }
+
+        // Limits too small for any circuit
+        assert!(get_naive_threshold_sig_circuit_max_pks(
+            ps_type,
+            segment_size,
+            false,
+            1,
+            1,
+            max_pks_bound
+        )
+        .unwrap()
+        .is_none());
+    }
+
     #[test]
     fn sample_calls_schnorr_sig_prove_verify() {
         let mut rng = OsRng;
@@ -614,11 +807,11 @@ mod test {
         assert_eq!(pk, pk_deserialized);
 
         //Serialize/deserialize sk
-        let sk_serialized = serialize_to_buffer(&sk, None).unwrap();
+        let sk_serialized = serialize_to_buffer(&*sk, None).unwrap();
         assert_eq!(sk_serialized.len(), SCHNORR_SK_SIZE);
         println!("sk bytes: {:?}", into_i8(sk_serialized.clone()));
         let sk_deserialized = deserialize_from_buffer(&sk_serialized, None, None).unwrap();
-        assert_eq!(sk, sk_deserialized);
+        assert_eq!(*sk, sk_deserialized);
 
         let sig = schnorr_sign(&msg, &sk, &pk).unwrap(); //Sign msg
         assert!(is_valid(&sig));
@@ -658,11 +851,11 @@ mod test {
         assert_eq!(pk, pk_deserialized);
 
         //Serialize/deserialize sk
-        let sk_serialized = serialize_to_buffer(&sk, None).unwrap();
+        let sk_serialized = serialize_to_buffer(&*sk, None).unwrap();
         assert_eq!(sk_serialized.len(), VRF_SK_SIZE);
         println!("sk bytes: {:?}", into_i8(sk_serialized.clone()));
         let sk_deserialized = deserialize_from_buffer(&sk_serialized, None, None).unwrap();
-        assert_eq!(sk, sk_deserialized);
+        assert_eq!(*sk, sk_deserialized);
 
         let (vrf_proof, vrf_out) = vrf_prove(&msg, &sk, &pk).unwrap(); //Create vrf proof for msg
         assert!(is_valid(&vrf_proof));
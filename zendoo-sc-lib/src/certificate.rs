@@ -0,0 +1,734 @@
+use crate::{cctp_calls::*, envelope::*, mc_serialization::*};
+use cctp_primitives::{
+    proving_system::{verifier::certificate::CertificateProofUserInputs, ProvingSystem},
+    utils::{data_structures::*, serialization::*},
+};
+use demo_circuit::type_mapping::*;
+
+use std::convert::TryInto;
+use std::path::Path;
+
+//*****************************Mainchain certificates with proof************************************
+//
+// Certificate of a sidechain epoch in the mainchain wire format, built from the same data the
+// certificate proof is computed on, so that what is proven and what is sent to the mainchain
+// can't differ.
+//
+// Layout of a serialized certificate (integers are little endian, vectors are prefixed by their
+// length as a Bitcoin compact size):
+//
+// | version (4) | sc id (FIELD_SIZE) | epoch number (4) | quality (8) |
+// | end cumulative sc tx commitment tree root (vector) | proof (vector) |
+// | field element custom fields (vector of vectors) | bit vector custom fields (vector) |
+// | ft min amount (8) | btr fee (8) | inputs (vector) | change outputs (vector) |
+// | backward transfers (vector) |
+//
+// where each backward transfer is a CBackwardTransferOut of the mainchain: | amount (8) |
+// pk_dest (MC_PK_SIZE) |, pk_dest being the raw public key hash of the receiver. Certificates are
+// built without inputs and change outputs, which are added by the mainchain wallet when funding
+// them. Custom fields are all field elements.
+
+/// Version of mainchain certificates (0xFFFFFFFB).
+pub const SC_CERT_VERSION: i32 = -5;
+
+/// Maximum amount of coins in the mainchain (21 million coins of 10^8 units).
+pub const MAX_MONEY: u64 = 21_000_000 * 100_000_000;
+
+pub struct Certificate {
+    sc_id: FieldElement,
+    epoch_number: u32,
+    quality: u64,
+    end_cumulative_sc_tx_commitment_tree_root: FieldElement,
+    bt_list: Vec<BackwardTransfer>,
+    custom_fields: Vec<FieldElement>,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    proof: Vec<u8>,
+}
+
+impl Certificate {
+    pub fn sc_id(&self) -> &FieldElement {
+        &self.sc_id
+    }
+
+    pub fn epoch_number(&self) -> u32 {
+        self.epoch_number
+    }
+
+    pub fn quality(&self) -> u64 {
+        self.quality
+    }
+
+    pub fn end_cumulative_sc_tx_commitment_tree_root(&self) -> &FieldElement {
+        &self.end_cumulative_sc_tx_commitment_tree_root
+    }
+
+    pub fn bt_list(&self) -> &[BackwardTransfer] {
+        self.bt_list.as_slice()
+    }
+
+    pub fn custom_fields(&self) -> &[FieldElement] {
+        self.custom_fields.as_slice()
+    }
+
+    pub fn btr_fee(&self) -> u64 {
+        self.btr_fee
+    }
+
+    pub fn ft_min_amount(&self) -> u64 {
+        self.ft_min_amount
+    }
+
+    /// The bare proof, without envelope.
+    pub fn proof(&self) -> &[u8] {
+        self.proof.as_slice()
+    }
+
+    /// Public inputs of the certificate proof, with the `constant` declared at sidechain creation.
+    pub fn proof_user_inputs<'a>(
+        &'a self,
+        constant: Option<&'a FieldElement>,
+    ) -> CertificateProofUserInputs<'a> {
+        CertificateProofUserInputs {
+            constant,
+            sc_id: &self.sc_id,
+            epoch_number: self.epoch_number,
+            quality: self.quality,
+            bt_list: if self.bt_list.is_empty() {
+                None
+            } else {
+                Some(self.bt_list.as_slice())
+            },
+            custom_fields: if self.custom_fields.is_empty() {
+                None
+            } else {
+                Some(self.custom_fields.iter().collect())
+            },
+            end_cumulative_sc_tx_commitment_tree_root: &self
+                .end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee: self.btr_fee,
+            ft_min_amount: self.ft_min_amount,
+        }
+    }
+
+    /// Check that the certificate carries the same data of the proof public inputs `ins`
+    /// (apart from the constant, which isn't part of the certificate).
+    pub fn check_proof_user_inputs(&self, ins: &CertificateProofUserInputs) -> Result<(), Error> {
+        let bt_list = ins.bt_list.unwrap_or(&[]);
+        let custom_fields = ins.custom_fields.as_deref().unwrap_or(&[]);
+
+        let mismatch = if *ins.sc_id != self.sc_id {
+            Some("sc_id")
+        } else if ins.epoch_number != self.epoch_number {
+            Some("epoch_number")
+        } else if ins.quality != self.quality {
+            Some("quality")
+        } else if bt_list.len() != self.bt_list.len()
+            || bt_list
+                .iter()
+                .zip(self.bt_list.iter())
+                .any(|(bt, cert_bt)| bt.pk_dest != cert_bt.pk_dest || bt.amount != cert_bt.amount)
+        {
+            Some("bt_list")
+        } else if custom_fields.len() != self.custom_fields.len()
+            || custom_fields
+                .iter()
+                .zip(self.custom_fields.iter())
+                .any(|(fe, cert_fe)| *fe != cert_fe)
+        {
+            Some("custom_fields")
+        } else if *ins.end_cumulative_sc_tx_commitment_tree_root
+            != self.end_cumulative_sc_tx_commitment_tree_root
+        {
+            Some("end_cumulative_sc_tx_commitment_tree_root")
+        } else if ins.btr_fee != self.btr_fee {
+            Some("btr_fee")
+        } else if ins.ft_min_amount != self.ft_min_amount {
+            Some("ft_min_amount")
+        } else {
+            None
+        };
+
+        match mismatch {
+            Some(field) => Err(format!(
+                "Certificate and proof user inputs differ in {}",
+                field
+            ))?,
+            None => Ok(()),
+        }
+    }
+
+    /// Verify the proof of the certificate against the vk at `vk_path`.
+    pub fn verify(
+        &self,
+        constant: Option<&FieldElement>,
+        check_proof: bool,
+        compressed_proof: bool,
+        vk_path: &Path,
+        check_vk: bool,
+        compressed_vk: bool,
+    ) -> Result<bool, Error> {
+        verify_certificate_proof(
+            self.proof_user_inputs(constant),
+            &self.proof,
+            check_proof,
+            compressed_proof,
+            vk_path,
+            check_vk,
+            compressed_vk,
+        )
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(
+            4 + 3 * FIELD_SIZE
+                + 4
+                + 8
+                + self.proof.len()
+                + self.custom_fields.len() * (FIELD_SIZE + 1)
+                + 16
+                + 3
+                + self.bt_list.len() * (8 + MC_PK_SIZE)
+                + 16,
+        );
+        bytes.extend_from_slice(&SC_CERT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&serialize_to_buffer(&self.sc_id, None)?);
+        bytes.extend_from_slice(&self.epoch_number.to_le_bytes());
+        bytes.extend_from_slice(&self.quality.to_le_bytes());
+        write_vector(
+            &mut bytes,
+            &serialize_to_buffer(&self.end_cumulative_sc_tx_commitment_tree_root, None)?,
+        );
+        write_vector(&mut bytes, &self.proof);
+
+        write_compact_size(&mut bytes, self.custom_fields.len());
+        for custom_field in self.custom_fields.iter() {
+            write_vector(&mut bytes, &serialize_to_buffer(custom_field, None)?);
+        }
+        // No bit vector custom fields
+        write_compact_size(&mut bytes, 0);
+
+        bytes.extend_from_slice(&self.ft_min_amount.to_le_bytes());
+        bytes.extend_from_slice(&self.btr_fee.to_le_bytes());
+
+        // No inputs and change outputs
+        write_compact_size(&mut bytes, 0);
+        write_compact_size(&mut bytes, 0);
+
+        write_compact_size(&mut bytes, self.bt_list.len());
+        for bt in self.bt_list.iter() {
+            bytes.extend_from_slice(&bt.amount.to_le_bytes());
+            bytes.extend_from_slice(&bt.pk_dest);
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = &mut bytes;
+
+        let version = i32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+        if version != SC_CERT_VERSION {
+            Err(format!(
+                "Invalid certificate: unknown version {:#x}",
+                version
+            ))?
+        }
+
+        let sc_id = read_field_element(take(bytes, FIELD_SIZE)?)?;
+        let epoch_number = read_u32(bytes)?;
+        let quality = read_u64(bytes)?;
+        let end_cumulative_sc_tx_commitment_tree_root = read_field_element(read_vector(bytes)?)?;
+        let proof = read_vector(bytes)?.to_vec();
+
+        let num_custom_fields = read_compact_size(bytes)?;
+        let custom_fields = (0..num_custom_fields)
+            .map(|_| read_field_element(read_vector(bytes)?))
+            .collect::<Result<Vec<_>, Error>>()?;
+        if read_compact_size(bytes)? != 0 {
+            Err("Invalid certificate: bit vector custom fields are not supported")?
+        }
+
+        let ft_min_amount = read_u64(bytes)?;
+        let btr_fee = read_u64(bytes)?;
+
+        // Inputs and change outputs
+        for _ in 0..2 {
+            if read_compact_size(bytes)? != 0 {
+                Err("Invalid certificate: funded certificates are not supported")?
+            }
+        }
+
+        let num_bts = read_compact_size(bytes)?;
+        let bt_list = (0..num_bts)
+            .map(|_| -> Result<BackwardTransfer, Error> {
+                let amount = read_u64(bytes)?;
+                let pk_dest = take(bytes, MC_PK_SIZE)?.try_into().unwrap();
+                Ok(BackwardTransfer { pk_dest, amount })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if !bytes.is_empty() {
+            Err(format!(
+                "Invalid certificate: {} trailing bytes",
+                bytes.len()
+            ))?
+        }
+
+        CertificateBuilder::new()
+            .sc_id(sc_id)
+            .epoch_number(epoch_number)
+            .quality(quality)
+            .end_cumulative_sc_tx_commitment_tree_root(end_cumulative_sc_tx_commitment_tree_root)
+            .bt_list(bt_list)
+            .custom_fields(custom_fields)
+            .btr_fee(btr_fee)
+            .ft_min_amount(ft_min_amount)
+            .proof(proof)
+            .build()
+    }
+}
+
+/// Builder of a `Certificate`: sc_id, epoch_number, quality, the end cumulative sc tx
+/// commitment tree root and the proof are mandatory, the other fields default to empty or zero.
+#[derive(Clone, Default)]
+pub struct CertificateBuilder {
+    sc_id: Option<FieldElement>,
+    epoch_number: Option<u32>,
+    quality: Option<u64>,
+    end_cumulative_sc_tx_commitment_tree_root: Option<FieldElement>,
+    bt_list: Vec<BackwardTransfer>,
+    custom_fields: Vec<FieldElement>,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    proof: Option<Vec<u8>>,
+}
+
+impl CertificateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder with all the data of the proof public inputs `ins`: only the proof is missing.
+    pub fn from_proof_user_inputs(ins: &CertificateProofUserInputs) -> Self {
+        Self {
+            sc_id: Some(*ins.sc_id),
+            epoch_number: Some(ins.epoch_number),
+            quality: Some(ins.quality),
+            end_cumulative_sc_tx_commitment_tree_root: Some(
+                *ins.end_cumulative_sc_tx_commitment_tree_root,
+            ),
+            bt_list: ins
+                .bt_list
+                .map(|bt_list| bt_list.to_vec())
+                .unwrap_or_default(),
+            custom_fields: ins
+                .custom_fields
+                .as_ref()
+                .map(|custom_fields| custom_fields.iter().map(|&fe| *fe).collect())
+                .unwrap_or_default(),
+            btr_fee: ins.btr_fee,
+            ft_min_amount: ins.ft_min_amount,
+            proof: None,
+        }
+    }
+
+    pub fn sc_id(mut self, sc_id: FieldElement) -> Self {
+        self.sc_id = Some(sc_id);
+        self
+    }
+
+    pub fn epoch_number(mut self, epoch_number: u32) -> Self {
+        self.epoch_number = Some(epoch_number);
+        self
+    }
+
+    pub fn quality(mut self, quality: u64) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    pub fn end_cumulative_sc_tx_commitment_tree_root(mut self, root: FieldElement) -> Self {
+        self.end_cumulative_sc_tx_commitment_tree_root = Some(root);
+        self
+    }
+
+    pub fn bt_list(mut self, bt_list: Vec<BackwardTransfer>) -> Self {
+        self.bt_list = bt_list;
+        self
+    }
+
+    pub fn add_backward_transfer(mut self, bt: BackwardTransfer) -> Self {
+        self.bt_list.push(bt);
+        self
+    }
+
+    pub fn custom_fields(mut self, custom_fields: Vec<FieldElement>) -> Self {
+        self.custom_fields = custom_fields;
+        self
+    }
+
+    pub fn btr_fee(mut self, btr_fee: u64) -> Self {
+        self.btr_fee = btr_fee;
+        self
+    }
+
+    pub fn ft_min_amount(mut self, ft_min_amount: u64) -> Self {
+        self.ft_min_amount = ft_min_amount;
+        self
+    }
+
+    /// The proof, bare or sealed in an envelope.
+    pub fn proof(mut self, proof: Vec<u8>) -> Self {
+        self.proof = Some(proof);
+        self
+    }
+
+    /// Check the fields against the mainchain limits and build the certificate.
+    pub fn build(self) -> Result<Certificate, Error> {
+        let sc_id = self.sc_id.ok_or("Missing certificate sc_id")?;
+        let epoch_number = self
+            .epoch_number
+            .ok_or("Missing certificate epoch_number")?;
+        let quality = self.quality.ok_or("Missing certificate quality")?;
+        let end_cumulative_sc_tx_commitment_tree_root = self
+            .end_cumulative_sc_tx_commitment_tree_root
+            .ok_or("Missing certificate end_cumulative_sc_tx_commitment_tree_root")?;
+        let proof = self.proof.ok_or("Missing certificate proof")?;
+
+        // The mainchain stores them as signed integers
+        if epoch_number > i32::MAX as u32 {
+            Err(format!(
+                "Invalid certificate epoch_number: {}",
+                epoch_number
+            ))?
+        }
+        if quality > i64::MAX as u64 {
+            Err(format!("Invalid certificate quality: {}", quality))?
+        }
+
+        for (name, fee) in [
+            ("btr_fee", self.btr_fee),
+            ("ft_min_amount", self.ft_min_amount),
+        ]
+        .iter()
+        {
+            if *fee > MAX_MONEY {
+                Err(format!("Invalid certificate {}: {}", name, fee))?
+            }
+        }
+
+        let mut total_amount = 0u64;
+        for bt in self.bt_list.iter() {
+            if bt.amount == 0 || bt.amount > MAX_MONEY {
+                Err(format!(
+                    "Invalid certificate backward transfer amount: {}",
+                    bt.amount
+                ))?
+            }
+            total_amount += bt.amount;
+            if total_amount > MAX_MONEY {
+                Err("Invalid certificate: total backward transfer amount out of range")?
+            }
+        }
+
+        // The mainchain expects the bare proof, starting with a defined proving system
+        let proof = open_artifact(ArtifactType::Proof, &proof)?.to_vec();
+        if proof.is_empty() {
+            Err("Empty proof")?
+        }
+        if let ProvingSystem::Undefined =
+            deserialize_from_buffer::<ProvingSystem>(&proof[..1], None, None)?
+        {
+            Err("Invalid certificate proof: undefined proving system")?
+        }
+
+        Ok(Certificate {
+            sc_id,
+            epoch_number,
+            quality,
+            end_cumulative_sc_tx_commitment_tree_root,
+            bt_list: self.bt_list,
+            custom_fields: self.custom_fields,
+            btr_fee: self.btr_fee,
+            ft_min_amount: self.ft_min_amount,
+            proof,
+        })
+    }
+}
+
+// Creates the naive threshold sig proof of a certificate and returns the certificate carrying it,
+// with the number of valid signatures as quality.
+pub fn create_naive_threshold_sig_certificate(
+    pks: &[SchnorrPk],
+    sigs: Vec<Option<SchnorrSig>>,
+    sc_id: &FieldElement,
+    epoch_number: u32,
+    end_cumulative_sc_tx_comm_tree_root: &FieldElement,
+    btr_fee: u64,
+    ft_min_amount: u64,
+    bt_list: Vec<BackwardTransfer>,
+    threshold: u64,
+    proving_key_path: &Path,
+    enforce_membership: bool,
+    zk: bool,
+    compressed_pk: bool,
+    compress_proof: bool,
+) -> Result<Certificate, Error> {
+    let (proof, quality) = create_naive_threshold_sig_proof(
+        pks,
+        sigs,
+        sc_id,
+        epoch_number,
+        end_cumulative_sc_tx_comm_tree_root,
+        btr_fee,
+        ft_min_amount,
+        bt_list.clone(),
+        threshold,
+        proving_key_path,
+        enforce_membership,
+        zk,
+        compressed_pk,
+        compress_proof,
+    )?;
+
+    CertificateBuilder::new()
+        .sc_id(*sc_id)
+        .epoch_number(epoch_number)
+        .quality(quality)
+        .end_cumulative_sc_tx_commitment_tree_root(*end_cumulative_sc_tx_comm_tree_root)
+        .bt_list(bt_list)
+        .btr_fee(btr_fee)
+        .ft_min_amount(ft_min_amount)
+        .proof(proof)
+        .build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cctp_primitives::proving_system::init_dlog_keys;
+    use demo_circuit::{generate_circuit_keypair, get_instance_for_setup};
+
+    // Not a valid proof, but enough for the checks of the builder
+    const FAKE_PROOF: [u8; 4] = [2, 1, 2, 3];
+
+    fn sample_builder() -> CertificateBuilder {
+        CertificateBuilder::new()
+            .sc_id(FieldElement::from(1u64))
+            .epoch_number(10)
+            .quality(5)
+            .end_cumulative_sc_tx_commitment_tree_root(FieldElement::from(2u64))
+            .add_backward_transfer(BackwardTransfer {
+                pk_dest: [1u8; MC_PK_SIZE],
+                amount: 100,
+            })
+            .add_backward_transfer(BackwardTransfer {
+                pk_dest: [2u8; MC_PK_SIZE],
+                amount: 200,
+            })
+            .custom_fields(vec![FieldElement::from(3u64)])
+            .btr_fee(10)
+            .ft_min_amount(20)
+            .proof(FAKE_PROOF.to_vec())
+    }
+
+    #[test]
+    fn build_and_serialize() {
+        let cert = sample_builder().build().unwrap();
+        assert_eq!(cert.quality(), 5);
+        assert_eq!(cert.bt_list().len(), 2);
+        assert_eq!(cert.proof(), &FAKE_PROOF);
+
+        // Expected serialization, built by hand following the CScCertificate serialization of
+        // the mainchain (no certificate serialized by zend is available to the tests)
+        let fe = |v: u8| {
+            let mut bytes = [0u8; FIELD_SIZE];
+            bytes[0] = v;
+            bytes
+        };
+        let expected_bytes = [
+            // version
+            &[0xfb, 0xff, 0xff, 0xff][..],
+            // sc id
+            &fe(1),
+            // epoch number
+            &[10, 0, 0, 0],
+            // quality
+            &[5, 0, 0, 0, 0, 0, 0, 0],
+            // end cumulative sc tx commitment tree root
+            &[FIELD_SIZE as u8],
+            &fe(2),
+            // proof
+            &[4],
+            &FAKE_PROOF,
+            // field element custom fields
+            &[1, FIELD_SIZE as u8],
+            &fe(3),
+            // bit vector custom fields
+            &[0],
+            // ft min amount
+            &[20, 0, 0, 0, 0, 0, 0, 0],
+            // btr fee
+            &[10, 0, 0, 0, 0, 0, 0, 0],
+            // inputs and change outputs
+            &[0, 0],
+            // backward transfers: amount and pk_dest
+            &[2],
+            &[100, 0, 0, 0, 0, 0, 0, 0],
+            &[1u8; MC_PK_SIZE],
+            &[200, 0, 0, 0, 0, 0, 0, 0],
+            &[2u8; MC_PK_SIZE],
+        ]
+        .concat();
+        let cert_bytes = cert.to_bytes().unwrap();
+        assert_eq!(cert_bytes, expected_bytes);
+
+        let deserialized = Certificate::from_bytes(&expected_bytes).unwrap();
+        assert_eq!(deserialized.to_bytes().unwrap(), expected_bytes);
+        assert!(deserialized
+            .check_proof_user_inputs(&cert.proof_user_inputs(None))
+            .is_ok());
+
+        assert!(Certificate::from_bytes(&cert_bytes[..cert_bytes.len() - 1]).is_err());
+        let mut longer_bytes = cert_bytes.clone();
+        longer_bytes.push(0);
+        assert!(Certificate::from_bytes(&longer_bytes).is_err());
+        let mut wrong_version = cert_bytes.clone();
+        wrong_version[0] = 0xfc;
+        assert!(Certificate::from_bytes(&wrong_version).is_err());
+
+        // Change outputs are not supported
+        let vout_pos = cert_bytes.len() - 1 - 2 * (8 + MC_PK_SIZE) - 1;
+        assert_eq!(cert_bytes[vout_pos], 0);
+        let mut funded = cert_bytes;
+        funded[vout_pos] = 1;
+        assert!(Certificate::from_bytes(&funded).is_err());
+
+        // The envelope of the proof is removed
+        let cert = sample_builder()
+            .proof(seal_artifact(ArtifactType::Proof, &FAKE_PROOF))
+            .build()
+            .unwrap();
+        assert_eq!(cert.proof(), &FAKE_PROOF);
+        assert!(sample_builder()
+            .proof(seal_artifact(ArtifactType::VRFProof, &FAKE_PROOF))
+            .build()
+            .is_err());
+
+        // Missing or invalid fields
+        assert!(CertificateBuilder::new()
+            .proof(FAKE_PROOF.to_vec())
+            .build()
+            .is_err());
+        assert!(sample_builder().proof(vec![]).build().is_err());
+        assert!(sample_builder().proof(vec![0, 1]).build().is_err());
+        assert!(sample_builder().quality(u64::MAX).build().is_err());
+        assert!(sample_builder().btr_fee(MAX_MONEY + 1).build().is_err());
+        assert!(sample_builder()
+            .add_backward_transfer(BackwardTransfer::default())
+            .build()
+            .is_err());
+        assert!(sample_builder()
+            .add_backward_transfer(BackwardTransfer {
+                pk_dest: [3u8; MC_PK_SIZE],
+                amount: MAX_MONEY,
+            })
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn proof_user_inputs() {
+        let cert = sample_builder().build().unwrap();
+        let constant = get_random_field_element(4);
+        let ins = cert.proof_user_inputs(Some(&constant));
+        assert!(cert.check_proof_user_inputs(&ins).is_ok());
+
+        // Same certificate from the user inputs
+        let same_cert = CertificateBuilder::from_proof_user_inputs(&ins)
+            .proof(FAKE_PROOF.to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(same_cert.to_bytes().unwrap(), cert.to_bytes().unwrap());
+
+        let other_cert = sample_builder().quality(6).build().unwrap();
+        assert!(other_cert.check_proof_user_inputs(&ins).is_err());
+        let other_cert = sample_builder().bt_list(vec![]).build().unwrap();
+        assert!(other_cert.check_proof_user_inputs(&ins).is_err());
+        let other_cert = sample_builder().custom_fields(vec![]).build().unwrap();
+        assert!(other_cert.check_proof_user_inputs(&ins).is_err());
+    }
+
+    #[test]
+    fn naive_threshold_sig_certificate() {
+        let tmp_dir = std::env::temp_dir();
+        let pk_path = tmp_dir.join("cert_sample_pk");
+        let vk_path = tmp_dir.join("cert_sample_vk");
+        let threshold = 2;
+
+        init_dlog_keys(ProvingSystem::CoboundaryMarlin, 1 << 17, 1 << 14).unwrap();
+        generate_circuit_keypair(
+            get_instance_for_setup(3),
+            ProvingSystem::CoboundaryMarlin,
+            &pk_path,
+            &vk_path,
+            7000,
+            4000,
+            false,
+            Some(true),
+            Some(true),
+        )
+        .unwrap();
+
+        let keys = (0..3).map(|_| schnorr_generate_key()).collect::<Vec<_>>();
+        let pks = keys.iter().map(|(pk, _)| *pk).collect::<Vec<_>>();
+        let sc_id = get_random_field_element(1);
+        let root = get_random_field_element(2);
+        let bt_list = vec![BackwardTransfer {
+            pk_dest: [1u8; MC_PK_SIZE],
+            amount: 100,
+        }];
+
+        let (_, msg) = compute_msg_to_sign(&sc_id, 10, &root, 30, 40, bt_list.clone()).unwrap();
+        let sigs = keys
+            .iter()
+            .map(|(pk, sk)| Some(schnorr_sign(&msg, sk, pk).unwrap()))
+            .collect::<Vec<_>>();
+
+        let cert = create_naive_threshold_sig_certificate(
+            &pks, sigs, &sc_id, 10, &root, 30, 40, bt_list, threshold, &pk_path, false, false,
+            true, true,
+        )
+        .unwrap();
+        assert_eq!(cert.quality(), 3);
+
+        let constant = compute_pks_threshold_hash(&pks, threshold).unwrap();
+        assert!(cert
+            .verify(Some(&constant), true, true, &vk_path, true, true)
+            .unwrap());
+
+        // The deserialized certificate verifies too
+        let cert = Certificate::from_bytes(&cert.to_bytes().unwrap()).unwrap();
+        assert!(cert
+            .verify(Some(&constant), true, true, &vk_path, true, true)
+            .unwrap());
+
+        // Wrong constant or quality
+        let wrong_constant = compute_pks_threshold_hash(&pks, threshold + 1).unwrap();
+        assert!(!cert
+            .verify(Some(&wrong_constant), true, true, &vk_path, true, true)
+            .unwrap());
+        let wrong_cert = CertificateBuilder::from_proof_user_inputs(&cert.proof_user_inputs(None))
+            .quality(2)
+            .proof(cert.proof().to_vec())
+            .build()
+            .unwrap();
+        assert!(!wrong_cert
+            .verify(Some(&constant), true, true, &vk_path, true, true)
+            .unwrap());
+
+        std::fs::remove_file(pk_path).unwrap();
+        std::fs::remove_file(vk_path).unwrap();
+    }
+}
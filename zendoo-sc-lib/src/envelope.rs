@@ -0,0 +1,511 @@
+use crate::framing::{checksum, CHECKSUM_SIZE, MAGIC_SIZE};
+use crate::secret::Zeroizing;
+use algebra::{serialize::*, SemanticallyValid};
+use cctp_primitives::{proving_system::ProvingSystem, utils::serialization::*};
+use demo_circuit::type_mapping::*;
+
+use std::convert::TryInto;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+//*****************************Envelope of serialized artifacts*************************************
+//
+// Optional envelope around serialized keys, signatures and proofs (in memory or in files), so that
+// artifacts of the wrong type, truncated or corrupted are detected before being deserialized.
+//
+// Layout of an envelope (integers are little endian):
+//
+// | magic (4) | artifact type (1) | version (1) | payload length (8) | checksum (8) | payload |
+//
+// The payload is the usual serialization of the artifact, and the checksum (the one of
+// framing.rs) is computed over the type, version, length and payload. When reading files, the
+// length is checked against the file size before reading the payload, and the checksum before
+// deserializing it.
+//
+// Readers are backward compatible: data that doesn't start as an envelope, i.e. with the magic, a
+// known artifact type and the current version, is a bare artifact, and is deserialized as before.
+// Data that does is an envelope, and the readers return an error if it's of another type,
+// truncated or corrupted. A bare artifact starting with these 6 bytes by chance must be sealed to
+// be read.
+
+const ENVELOPE_MAGIC: [u8; MAGIC_SIZE] = *b"ZENV";
+pub const ENVELOPE_VERSION: u8 = 1;
+
+pub const ENVELOPE_HEADER_SIZE: usize = MAGIC_SIZE + 1 + 1 + 8 + CHECKSUM_SIZE;
+
+// Size of the fields telling an envelope from a bare artifact: magic, artifact type and version
+pub const ENVELOPE_PREFIX_SIZE: usize = MAGIC_SIZE + 1 + 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArtifactType {
+    FieldElement = 1,
+    SchnorrPublicKey = 2,
+    SchnorrSecretKey = 3,
+    SchnorrSignature = 4,
+    VRFPublicKey = 5,
+    VRFSecretKey = 6,
+    VRFProof = 7,
+    ProverKey = 8,
+    VerifierKey = 9,
+    Proof = 10,
+}
+
+impl ArtifactType {
+    pub fn from_tag(tag: u8) -> Result<Self, Error> {
+        let artifact_type = match tag {
+            1 => ArtifactType::FieldElement,
+            2 => ArtifactType::SchnorrPublicKey,
+            3 => ArtifactType::SchnorrSecretKey,
+            4 => ArtifactType::SchnorrSignature,
+            5 => ArtifactType::VRFPublicKey,
+            6 => ArtifactType::VRFSecretKey,
+            7 => ArtifactType::VRFProof,
+            8 => ArtifactType::ProverKey,
+            9 => ArtifactType::VerifierKey,
+            10 => ArtifactType::Proof,
+            _ => Err(format!("Unknown artifact type: {}", tag))?,
+        };
+        Ok(artifact_type)
+    }
+
+    pub fn tag(self) -> u8 {
+        self as u8
+    }
+}
+
+// Checksum of an envelope, `header` being the fields preceding it
+fn envelope_checksum(header: &[u8], payload: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    checksum(&[
+        &header[MAGIC_SIZE..ENVELOPE_HEADER_SIZE - CHECKSUM_SIZE],
+        payload,
+    ])
+}
+
+fn envelope_header(artifact_type: ArtifactType, payload: &[u8]) -> [u8; ENVELOPE_HEADER_SIZE] {
+    let mut header = [0u8; ENVELOPE_HEADER_SIZE];
+    header[..MAGIC_SIZE].copy_from_slice(&ENVELOPE_MAGIC);
+    header[4] = artifact_type.tag();
+    header[5] = ENVELOPE_VERSION;
+    header[6..14].copy_from_slice(&(payload.len() as u64).to_le_bytes());
+    let header_checksum = envelope_checksum(&header, payload);
+    header[14..].copy_from_slice(&header_checksum);
+    header
+}
+
+// Check the fields of `header` preceding the checksum, and return the payload length
+fn check_envelope_header(header: &[u8], artifact_type: ArtifactType) -> Result<u64, Error> {
+    let found_type = ArtifactType::from_tag(header[4])?;
+    if found_type != artifact_type {
+        Err(format!(
+            "Expected {:?} envelope, found {:?}",
+            artifact_type, found_type
+        ))?
+    }
+
+    let version = header[5];
+    if version != ENVELOPE_VERSION {
+        Err(format!("Unsupported envelope version: {}", version))?
+    }
+
+    Ok(u64::from_le_bytes(header[6..14].try_into().unwrap()))
+}
+
+fn check_envelope_checksum(header: &[u8], payload: &[u8]) -> Result<(), Error> {
+    if envelope_checksum(header, payload)[..] != header[14..ENVELOPE_HEADER_SIZE] {
+        Err("Envelope checksum mismatch: data is corrupted")?
+    }
+    Ok(())
+}
+
+/// True if `bytes` starts as an envelope: the envelope magic, a known artifact type and the current
+/// version. Readers report errors on such data instead of reading it as a bare artifact.
+pub fn is_sealed_artifact(bytes: &[u8]) -> bool {
+    bytes.len() >= ENVELOPE_PREFIX_SIZE
+        && bytes.starts_with(&ENVELOPE_MAGIC)
+        && ArtifactType::from_tag(bytes[4]).is_ok()
+        && bytes[5] == ENVELOPE_VERSION
+}
+
+/// Wrap the serialized artifact `payload` into an envelope.
+pub fn seal_artifact(artifact_type: ArtifactType, payload: &[u8]) -> Vec<u8> {
+    let mut sealed = Vec::with_capacity(ENVELOPE_HEADER_SIZE + payload.len());
+    sealed.extend_from_slice(&envelope_header(artifact_type, payload));
+    sealed.extend_from_slice(payload);
+    sealed
+}
+
+/// As `seal_artifact()`, for serialized secret keys: the returned buffer is wiped when dropped.
+pub fn seal_secret_artifact(artifact_type: ArtifactType, payload: &[u8]) -> Zeroizing<Vec<u8>> {
+    // Allocated once, so that no copy of the secret is left in freed memory
+    let mut sealed = Zeroizing::new(Vec::with_capacity(ENVELOPE_HEADER_SIZE + payload.len()));
+    sealed.extend_from_slice(&envelope_header(artifact_type, payload));
+    sealed.extend_from_slice(payload);
+    sealed
+}
+
+/// Payload of `bytes` if it's sealed, checking its type, length and checksum. Bare artifacts, i.e.
+/// data not starting as an envelope (see `is_sealed_artifact()`), are returned unchanged.
+pub fn open_artifact(artifact_type: ArtifactType, bytes: &[u8]) -> Result<&[u8], Error> {
+    if !is_sealed_artifact(bytes) {
+        return Ok(bytes);
+    }
+    if bytes.len() < ENVELOPE_HEADER_SIZE {
+        Err("Envelope too short")?
+    }
+
+    let (header, payload) = bytes.split_at(ENVELOPE_HEADER_SIZE);
+    let length = check_envelope_header(header, artifact_type)?;
+    if length != payload.len() as u64 {
+        Err(format!(
+            "Invalid envelope length: expected {} payload bytes, found {}",
+            length,
+            payload.len()
+        ))?
+    }
+    check_envelope_checksum(header, payload)?;
+
+    Ok(payload)
+}
+
+// Open the file at `path` and, if sealed, check its header and size. Returns the file (positioned
+// at the beginning of the payload), its header and the payload length, or None if it is bare.
+fn open_artifact_file(
+    path: &Path,
+    artifact_type: ArtifactType,
+) -> Result<Option<(File, [u8; ENVELOPE_HEADER_SIZE], u64)>, Error> {
+    let mut file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+
+    if file_size < ENVELOPE_PREFIX_SIZE as u64 {
+        return Ok(None);
+    }
+    let mut header = [0u8; ENVELOPE_HEADER_SIZE];
+    file.read_exact(&mut header[..ENVELOPE_PREFIX_SIZE])?;
+    if !is_sealed_artifact(&header[..ENVELOPE_PREFIX_SIZE]) {
+        return Ok(None);
+    }
+
+    if file_size < ENVELOPE_HEADER_SIZE as u64 {
+        Err("Envelope too short")?
+    }
+    file.read_exact(&mut header[ENVELOPE_PREFIX_SIZE..])?;
+    let length = check_envelope_header(&header, artifact_type)?;
+    let payload_size = file_size - ENVELOPE_HEADER_SIZE as u64;
+    if length != payload_size {
+        Err(format!(
+            "Invalid envelope length: expected {} payload bytes, found {}",
+            length, payload_size
+        ))?
+    }
+
+    Ok(Some((file, header, length)))
+}
+
+// Payload of the sealed file opened by open_artifact_file(), checking its checksum
+fn read_sealed_payload(mut file: File, header: &[u8], length: u64) -> Result<Vec<u8>, Error> {
+    let mut payload = Vec::with_capacity(length.try_into()?);
+    file.read_to_end(&mut payload)?;
+    check_envelope_checksum(header, &payload)?;
+    Ok(payload)
+}
+
+/// Payload of the (sealed or bare) artifact file at `path`.
+pub fn read_artifact_file(path: &Path, artifact_type: ArtifactType) -> Result<Vec<u8>, Error> {
+    match open_artifact_file(path, artifact_type)? {
+        Some((file, header, length)) => read_sealed_payload(file, &header, length),
+        None => Ok(fs::read(path)?),
+    }
+}
+
+/// Deserialize the artifact file at `path`, sealed or bare, with the same `checked` and
+/// `compressed` semantics of `read_from_file()`.
+pub fn read_artifact_from_file<T: CanonicalDeserialize + SemanticallyValid>(
+    path: &Path,
+    artifact_type: ArtifactType,
+    checked: Option<bool>,
+    compressed: Option<bool>,
+) -> Result<T, Error> {
+    match open_artifact_file(path, artifact_type)? {
+        Some((file, header, length)) => deserialize_from_buffer(
+            &read_sealed_payload(file, &header, length)?,
+            checked,
+            compressed,
+        ),
+        // Bare files are deserialized directly from disk
+        None => read_from_file(path, checked, compressed),
+    }
+}
+
+/// Proving system of the (sealed or bare) key or proof file at `path`, reading only its header
+/// and the first byte of the payload.
+pub fn read_artifact_proving_system(
+    path: &Path,
+    artifact_type: ArtifactType,
+) -> Result<ProvingSystem, Error> {
+    match open_artifact_file(path, artifact_type)? {
+        Some((mut file, _, length)) => {
+            if length == 0 {
+                Err("Empty envelope payload")?
+            }
+            let mut ps_byte = [0u8; 1];
+            file.read_exact(&mut ps_byte)?;
+            deserialize_from_buffer(&ps_byte, None, None)
+        }
+        None => read_from_file(path, None, None),
+    }
+}
+
+/// Serialize `obj` to a sealed file at `path`.
+pub fn write_sealed_to_file<T: CanonicalSerialize>(
+    obj: &T,
+    artifact_type: ArtifactType,
+    path: &Path,
+    compressed: Option<bool>,
+) -> Result<(), Error> {
+    let payload = serialize_to_buffer(obj, compressed)?;
+    fs::write(path, seal_artifact(artifact_type, &payload))?;
+    Ok(())
+}
+
+/// Seal in place the bare artifact file at `path` (e.g. keys written by the setup). Fails if the
+/// file is already a valid envelope of `artifact_type`. The sealed file is written next to the
+/// original, with its permissions, and then renamed, so that the original is never left half
+/// written. The file may hold a secret key, so its content is wiped from memory once written.
+pub fn seal_artifact_file(path: &Path, artifact_type: ArtifactType) -> Result<(), Error> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    // Read into a buffer of the final size, so that it's never reallocated
+    let mut payload = Zeroizing::new(vec![0u8; metadata.len().try_into()?]);
+    file.read_exact(&mut payload)?;
+    if is_sealed_artifact(&payload) && open_artifact(artifact_type, &payload).is_ok() {
+        Err(format!("{} is already sealed", path.display()))?
+    }
+    let sealed = seal_secret_artifact(artifact_type, &payload);
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".sealing");
+    let tmp_path = Path::new(&tmp_path);
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // Readable only by the owner until it gets the permissions of the original
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut tmp_file = options.open(tmp_path)?;
+    // The mode above applies only to newly created files
+    tmp_file.set_permissions(metadata.permissions())?;
+    tmp_file.write_all(&sealed)?;
+    tmp_file.sync_all()?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        deserialize_secret, get_random_field_element, schnorr_generate_key, serialize_secret,
+    };
+
+    #[test]
+    fn seal_and_open() {
+        let (pk, sk) = schnorr_generate_key();
+        let pk_bytes = serialize_to_buffer(&pk, Some(true)).unwrap();
+
+        let sealed = seal_artifact(ArtifactType::SchnorrPublicKey, &pk_bytes);
+        assert!(is_sealed_artifact(&sealed));
+        assert_eq!(sealed.len(), ENVELOPE_HEADER_SIZE + pk_bytes.len());
+        assert_eq!(
+            open_artifact(ArtifactType::SchnorrPublicKey, &sealed).unwrap(),
+            pk_bytes.as_slice()
+        );
+
+        // Bare artifacts are returned unchanged
+        assert!(!is_sealed_artifact(&pk_bytes));
+        assert_eq!(
+            open_artifact(ArtifactType::SchnorrPublicKey, &pk_bytes).unwrap(),
+            pk_bytes.as_slice()
+        );
+
+        // Bare artifacts starting with the magic too, if followed by an unknown type or version
+        for &i in [4, 5].iter() {
+            let mut bare = sealed.clone();
+            bare[i] = 0xff;
+            assert!(!is_sealed_artifact(&bare));
+            assert_eq!(
+                open_artifact(ArtifactType::SchnorrPublicKey, &bare).unwrap(),
+                bare.as_slice()
+            );
+        }
+        assert!(!is_sealed_artifact(&sealed[..ENVELOPE_PREFIX_SIZE - 1]));
+
+        // Invalid envelopes are rejected
+        let mut invalid = vec![
+            // Wrong type
+            (ArtifactType::VRFPublicKey, sealed.clone()),
+            // Truncated
+            (
+                ArtifactType::SchnorrPublicKey,
+                sealed[..sealed.len() - 1].to_vec(),
+            ),
+            (
+                ArtifactType::SchnorrPublicKey,
+                sealed[..ENVELOPE_HEADER_SIZE - 1].to_vec(),
+            ),
+            (
+                ArtifactType::SchnorrPublicKey,
+                sealed[..ENVELOPE_PREFIX_SIZE].to_vec(),
+            ),
+        ];
+        // Too long
+        let mut wrong = sealed.clone();
+        wrong.push(0);
+        invalid.push((ArtifactType::SchnorrPublicKey, wrong));
+        // Corrupted
+        for &i in [6, ENVELOPE_HEADER_SIZE - 1, sealed.len() - 1].iter() {
+            let mut wrong = sealed.clone();
+            wrong[i] ^= 1;
+            invalid.push((ArtifactType::SchnorrPublicKey, wrong));
+        }
+        for (artifact_type, wrong) in invalid.iter() {
+            assert!(is_sealed_artifact(wrong));
+            assert!(open_artifact(*artifact_type, wrong).is_err());
+        }
+
+        // Secret keys
+        let sk_bytes = serialize_secret(&*sk).unwrap();
+        let sealed_sk = seal_secret_artifact(ArtifactType::SchnorrSecretKey, &sk_bytes);
+        let opened = open_artifact(ArtifactType::SchnorrSecretKey, &sealed_sk).unwrap();
+        assert_eq!(deserialize_secret::<SchnorrSk>(opened).unwrap(), sk);
+        assert!(open_artifact(ArtifactType::SchnorrPublicKey, &sealed_sk).is_err());
+
+        for tag in 1..=10 {
+            assert_eq!(ArtifactType::from_tag(tag).unwrap().tag(), tag);
+        }
+        assert!(ArtifactType::from_tag(0).is_err());
+        assert!(ArtifactType::from_tag(11).is_err());
+    }
+
+    #[test]
+    fn artifact_files() {
+        let fe = get_random_field_element(1);
+        let bare_path = Path::new("./envelope_test_bare");
+        let sealed_path = Path::new("./envelope_test_sealed");
+
+        // Bare files are still readable
+        write_to_file(&fe, bare_path, None).unwrap();
+        assert_eq!(
+            read_artifact_from_file::<FieldElement>(
+                bare_path,
+                ArtifactType::FieldElement,
+                None,
+                None
+            )
+            .unwrap(),
+            fe
+        );
+        assert_eq!(
+            read_artifact_file(bare_path, ArtifactType::FieldElement).unwrap(),
+            fs::read(bare_path).unwrap()
+        );
+
+        write_sealed_to_file(&fe, ArtifactType::FieldElement, sealed_path, None).unwrap();
+        assert_eq!(
+            read_artifact_from_file::<FieldElement>(
+                sealed_path,
+                ArtifactType::FieldElement,
+                None,
+                None
+            )
+            .unwrap(),
+            fe
+        );
+
+        // Sealing in place
+        seal_artifact_file(bare_path, ArtifactType::FieldElement).unwrap();
+        assert_eq!(fs::read(bare_path).unwrap(), fs::read(sealed_path).unwrap());
+        assert!(seal_artifact_file(bare_path, ArtifactType::FieldElement).is_err());
+
+        // Truncated, corrupted and mistyped files are rejected
+        let sealed = fs::read(sealed_path).unwrap();
+        let mut corrupted = sealed.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        let wrong_files = vec![
+            (
+                ArtifactType::FieldElement,
+                sealed[..sealed.len() - 1].to_vec(),
+            ),
+            (
+                ArtifactType::FieldElement,
+                sealed[..ENVELOPE_HEADER_SIZE - 1].to_vec(),
+            ),
+            (ArtifactType::Proof, sealed.clone()),
+        ];
+        for (artifact_type, wrong) in wrong_files.iter() {
+            fs::write(sealed_path, wrong).unwrap();
+            assert!(read_artifact_file(sealed_path, *artifact_type).is_err());
+            assert!(read_artifact_from_file::<FieldElement>(
+                sealed_path,
+                *artifact_type,
+                None,
+                None
+            )
+            .is_err());
+            assert!(read_artifact_proving_system(sealed_path, *artifact_type).is_err());
+        }
+        // The checksum is checked only when reading the whole payload
+        fs::write(sealed_path, &corrupted).unwrap();
+        assert!(read_artifact_file(sealed_path, ArtifactType::FieldElement).is_err());
+        assert!(read_artifact_from_file::<FieldElement>(
+            sealed_path,
+            ArtifactType::FieldElement,
+            None,
+            None
+        )
+        .is_err());
+
+        // Bare files starting with the magic, but not with an envelope header
+        let mut bare = ENVELOPE_MAGIC.to_vec();
+        bare.push(0);
+        bare.extend_from_slice(&serialize_to_buffer(&fe, None).unwrap());
+        fs::write(bare_path, &bare).unwrap();
+        assert_eq!(
+            read_artifact_file(bare_path, ArtifactType::FieldElement).unwrap(),
+            bare
+        );
+
+        // They can be sealed too
+        seal_artifact_file(bare_path, ArtifactType::FieldElement).unwrap();
+        assert_eq!(
+            read_artifact_file(bare_path, ArtifactType::FieldElement).unwrap(),
+            bare
+        );
+
+        fs::remove_file(bare_path).unwrap();
+        fs::remove_file(sealed_path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn seal_secret_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = Path::new("./envelope_test_secret");
+        let (_, sk) = schnorr_generate_key();
+        let sk_bytes = serialize_secret(&*sk).unwrap();
+        fs::write(path, &*sk_bytes).unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        // The sealed file keeps the permissions of the original
+        seal_artifact_file(path, ArtifactType::SchnorrSecretKey).unwrap();
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let payload = read_artifact_file(path, ArtifactType::SchnorrSecretKey).unwrap();
+        assert_eq!(deserialize_secret::<SchnorrSk>(&payload).unwrap(), sk);
+
+        fs::remove_file(path).unwrap();
+    }
+}
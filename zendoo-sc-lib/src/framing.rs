@@ -0,0 +1,112 @@
+use blake2::{Blake2b, Digest};
+use demo_circuit::type_mapping::*;
+
+use std::convert::TryInto;
+
+//*****************************Checksummed binary formats*******************************************
+//
+// Framing shared by the binary formats of this crate (tree snapshots, hash and commitment tree
+// states, persistent tree metadata and journal):
+//
+// | magic (4) | version (1) | body | checksum (8) |
+//
+// The magic tells the formats apart, and the checksum is the Blake2b hash, truncated to 8 bytes,
+// of all the preceding bytes. Envelopes of serialized artifacts use the same magic and checksum,
+// but with the checksum in their header (see envelope.rs).
+
+pub(crate) const MAGIC_SIZE: usize = 4;
+pub(crate) const CHECKSUM_SIZE: usize = 8;
+pub(crate) const FRAME_HEADER_SIZE: usize = MAGIC_SIZE + 1;
+
+// Truncated Blake2b hash of the concatenation of `parts`
+pub(crate) fn checksum(parts: &[&[u8]]) -> [u8; CHECKSUM_SIZE] {
+    let mut h = Blake2b::new();
+    for part in parts.iter() {
+        h.input(part);
+    }
+    h.result()[..CHECKSUM_SIZE].try_into().unwrap()
+}
+
+// Start a frame with room for a body of `body_size` bytes, to be appended to the returned buffer
+pub(crate) fn begin_frame(magic: &[u8; MAGIC_SIZE], version: u8, body_size: usize) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + body_size + CHECKSUM_SIZE);
+    frame.extend_from_slice(magic);
+    frame.push(version);
+    frame
+}
+
+// Complete a frame started with begin_frame() by appending its checksum
+pub(crate) fn end_frame(mut frame: Vec<u8>) -> Vec<u8> {
+    let frame_checksum = checksum(&[&frame]);
+    frame.extend_from_slice(&frame_checksum);
+    frame
+}
+
+// Check checksum, magic and version of `frame`, and return its body. `name` is the name of the
+// format, used in error messages.
+pub(crate) fn open_frame<'a>(
+    frame: &'a [u8],
+    magic: &[u8; MAGIC_SIZE],
+    version: u8,
+    name: &str,
+) -> Result<&'a [u8], Error> {
+    if frame.len() < FRAME_HEADER_SIZE + CHECKSUM_SIZE {
+        Err(format!("Invalid {}: too short", name))?
+    }
+
+    let (content, frame_checksum) = frame.split_at(frame.len() - CHECKSUM_SIZE);
+    if checksum(&[content]) != frame_checksum {
+        Err(format!(
+            "Invalid {}: checksum mismatch, data is corrupted",
+            name
+        ))?
+    }
+
+    if content[..MAGIC_SIZE] != magic[..] {
+        Err(format!("Not a {}", name))?
+    }
+
+    let found_version = content[MAGIC_SIZE];
+    if found_version != version {
+        Err(format!("Unsupported {} version: {}", name, found_version))?
+    }
+
+    Ok(&content[FRAME_HEADER_SIZE..])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        let mut frame = begin_frame(b"ZTST", 3, 5);
+        frame.extend_from_slice(b"hello");
+        let frame = end_frame(frame);
+        assert_eq!(frame.len(), FRAME_HEADER_SIZE + 5 + CHECKSUM_SIZE);
+        assert_eq!(open_frame(&frame, b"ZTST", 3, "test").unwrap(), b"hello");
+
+        // Empty body
+        let empty = end_frame(begin_frame(b"ZTST", 3, 0));
+        assert!(open_frame(&empty, b"ZTST", 3, "test").unwrap().is_empty());
+        assert!(open_frame(&empty[1..], b"ZTST", 3, "test").is_err());
+
+        // Wrong magic or version, even with a valid checksum
+        assert!(open_frame(&frame, b"ZXXX", 3, "test").is_err());
+        assert!(open_frame(&frame, b"ZTST", 4, "test").is_err());
+
+        // Corrupted, truncated, trailing data
+        for i in 0..frame.len() {
+            let mut corrupted = frame.clone();
+            corrupted[i] ^= 1;
+            assert!(open_frame(&corrupted, b"ZTST", 3, "test").is_err());
+        }
+        assert!(open_frame(&frame[..frame.len() - 1], b"ZTST", 3, "test").is_err());
+        let mut longer = frame.clone();
+        longer.push(0);
+        assert!(open_frame(&longer, b"ZTST", 3, "test").is_err());
+
+        // Checksum of parts is the one of their concatenation
+        assert_eq!(checksum(&[b"hel", b"lo"]), checksum(&[b"hello"]));
+    }
+}
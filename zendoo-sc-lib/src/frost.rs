@@ -0,0 +1,464 @@
+use algebra::{AffineCurve, Field, FromBits, ProjectiveCurve, ToBits, UniformRand};
+use cctp_primitives::utils::serialization::*;
+use demo_circuit::type_mapping::*;
+use primitives::crh::FieldBasedHash;
+use rand::rngs::OsRng;
+
+use crate::cctp_calls::{schnorr_get_public_key, schnorr_verify_signature};
+use crate::secret::{Zeroize, Zeroizing};
+use crate::secret_sharing::{lagrange_coefficient_at_zero, SecretShare, VssCommitment};
+
+use std::convert::TryInto;
+
+//*****************************FROST threshold Schnorr signatures***********************************
+//
+// Two-round FROST signing producing ordinary field based Schnorr signatures, i.e. verifiable
+// with schnorr_verify_signature() against the group public key.
+//
+// Round 1: each signer calls FrostSigner::commit() and sends the FrostSigningCommitment
+//          to the coordinator, which forwards the list of all of them to the signers;
+// Round 2: each signer calls FrostSigner::sign() on the message and the list of commitments,
+//          and sends the resulting FrostSignatureShare to the coordinator, which checks it
+//          with FrostAggregator::verify_share() and finally calls FrostAggregator::aggregate().
+
+// Domain separator for the computation of the binding factors
+const FROST_BINDING_FACTOR_TAG: u64 = 0x46524f53545f4246; // "FROST_BF"
+
+pub const FROST_SIGNING_COMMITMENT_SIZE: usize = 4 + 2 * SCHNORR_PK_SIZE;
+pub const FROST_SIGNATURE_SHARE_SIZE: usize = 4 + SCALAR_FIELD_SIZE;
+
+// Secret nonces of a signer for a single signing session. Never reuse them.
+struct FrostSigningNonces {
+    hiding: ScalarFieldElement,
+    binding: ScalarFieldElement,
+}
+
+impl Zeroize for FrostSigningNonces {
+    fn zeroize(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+// Public commitment to the nonces of the signer of index `index`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrostSigningCommitment {
+    pub index: u32,
+    pub hiding: G2,
+    pub binding: G2,
+}
+
+impl FrostSigningCommitment {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(FROST_SIGNING_COMMITMENT_SIZE);
+        bytes.extend_from_slice(&self.index.to_le_bytes());
+        bytes.extend_from_slice(&serialize_to_buffer(&self.hiding, Some(true))?);
+        bytes.extend_from_slice(&serialize_to_buffer(&self.binding, Some(true))?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != FROST_SIGNING_COMMITMENT_SIZE {
+            Err(format!(
+                "Invalid signing commitment length. Expected: {}, found: {}",
+                FROST_SIGNING_COMMITMENT_SIZE,
+                bytes.len()
+            ))?
+        }
+        let index = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        let hiding = deserialize_from_buffer(
+            &bytes[4..4 + SCHNORR_PK_SIZE],
+            Some(true),
+            Some(true),
+        )?;
+        let binding =
+            deserialize_from_buffer(&bytes[4 + SCHNORR_PK_SIZE..], Some(true), Some(true))?;
+        Ok(Self {
+            index,
+            hiding,
+            binding,
+        })
+    }
+}
+
+// Share of the signature produced by the signer of index `index`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrostSignatureShare {
+    pub index: u32,
+    pub value: ScalarFieldElement,
+}
+
+impl FrostSignatureShare {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(FROST_SIGNATURE_SHARE_SIZE);
+        bytes.extend_from_slice(&self.index.to_le_bytes());
+        bytes.extend_from_slice(&serialize_to_buffer(&self.value, None)?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != FROST_SIGNATURE_SHARE_SIZE {
+            Err(format!(
+                "Invalid signature share length. Expected: {}, found: {}",
+                FROST_SIGNATURE_SHARE_SIZE,
+                bytes.len()
+            ))?
+        }
+        let index = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        let value = deserialize_from_buffer(&bytes[4..], None, None)?;
+        Ok(Self { index, value })
+    }
+}
+
+// Convert a field element into a scalar, as done by the Schnorr signature scheme
+fn field_to_scalar(fe: &FieldElement) -> Result<ScalarFieldElement, Error> {
+    Ok(ScalarFieldElement::read_bits(fe.write_bits())?)
+}
+
+// Convert a scalar into a field element, as done by the Schnorr signature scheme
+fn scalar_to_field(scalar: &ScalarFieldElement) -> Result<FieldElement, Error> {
+    Ok(FieldElement::read_bits(scalar.write_bits())?)
+}
+
+fn check_commitments(commitments: &[FrostSigningCommitment]) -> Result<(), Error> {
+    if commitments.is_empty() {
+        Err("Empty list of signing commitments")?
+    }
+    for (i, c) in commitments.iter().enumerate() {
+        if c.index == 0 {
+            Err("Invalid signer index: 0")?
+        }
+        if commitments[..i].iter().any(|prev| prev.index == c.index) {
+            Err(format!("Duplicated signer index: {}", c.index))?
+        }
+        if c.hiding.is_zero() || c.binding.is_zero() {
+            Err(format!("Invalid signing commitment of signer {}", c.index))?
+        }
+    }
+    Ok(())
+}
+
+// Signing session data shared by signers and aggregator: binding factors,
+// group commitment R and challenge, all derived from the message and the
+// list of signing commitments.
+struct FrostSigningPackage {
+    commitments: Vec<FrostSigningCommitment>,
+    binding_factors: Vec<ScalarFieldElement>,
+    indices: Vec<u32>,
+    challenge: FieldElement,
+}
+
+impl FrostSigningPackage {
+    fn new(
+        group_pk: &SchnorrPk,
+        message: &FieldElement,
+        commitments: &[FrostSigningCommitment],
+    ) -> Result<Self, Error> {
+        check_commitments(commitments)?;
+
+        // Sort commitments by index so that every party derives the same values
+        let mut commitments = commitments.to_vec();
+        commitments.sort_by_key(|c| c.index);
+        let indices = commitments.iter().map(|c| c.index).collect::<Vec<_>>();
+
+        // Hash of the whole list of commitments
+        let commitments_hash = {
+            let mut h = FieldHash::init_constant_length(5 * commitments.len(), None);
+            for c in commitments.iter() {
+                h.update(FieldElement::from(c.index as u64));
+                h.update(c.hiding.x);
+                h.update(c.hiding.y);
+                h.update(c.binding.x);
+                h.update(c.binding.y);
+            }
+            h.finalize()?
+        };
+
+        // rho_i = H(tag, pk.x, msg, H(commitments), i)
+        let binding_factors = indices
+            .iter()
+            .map(|&index| {
+                let rho = FieldHash::init_constant_length(5, None)
+                    .update(FieldElement::from(FROST_BINDING_FACTOR_TAG))
+                    .update(group_pk.x)
+                    .update(*message)
+                    .update(commitments_hash)
+                    .update(FieldElement::from(index as u64))
+                    .finalize()?;
+
+                // Drop the most significant bit to always fit the scalar field
+                let mut rho_bits = rho.write_bits();
+                rho_bits[0] = false;
+                Ok(ScalarFieldElement::read_bits(rho_bits)?)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // R = sum_i (D_i + rho_i * E_i)
+        let mut group_commitment = G2Projective::zero();
+        for (c, rho) in commitments.iter().zip(binding_factors.iter()) {
+            group_commitment += &c.binding.mul(*rho);
+            group_commitment.add_assign_mixed(&c.hiding);
+        }
+        let group_commitment = group_commitment.into_affine();
+        if group_commitment.is_zero() {
+            Err("Group commitment is zero")?
+        }
+
+        // e = H(msg, R.x, R.y, pk.x), as in the Schnorr signature scheme
+        let challenge = FieldHash::init_constant_length(4, None)
+            .update(*message)
+            .update(group_commitment.x)
+            .update(group_commitment.y)
+            .update(group_pk.x)
+            .finalize()?;
+
+        Ok(Self {
+            commitments,
+            binding_factors,
+            indices,
+            challenge,
+        })
+    }
+
+    fn position(&self, index: u32) -> Result<usize, Error> {
+        self.indices
+            .iter()
+            .position(|&i| i == index)
+            .ok_or_else(|| format!("Signer {} is not part of the signing session", index).into())
+    }
+}
+
+// A holder of a share of the group secret key taking part to FROST signing sessions
+pub struct FrostSigner {
+    share: SecretShare,
+    group_pk: SchnorrPk,
+    nonces: Option<Zeroizing<FrostSigningNonces>>,
+}
+
+impl FrostSigner {
+    pub fn new(share: SecretShare, group_pk: SchnorrPk) -> Self {
+        Self {
+            share,
+            group_pk,
+            nonces: None,
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.share.index
+    }
+
+    // Round 1: sample fresh nonces for a new signing session and return the commitment
+    // to them. Any nonce from a previous, not completed, session is discarded.
+    pub fn commit(&mut self) -> FrostSigningCommitment {
+        let mut rng = OsRng;
+        let nonces = Zeroizing::new(FrostSigningNonces {
+            hiding: ScalarFieldElement::rand(&mut rng),
+            binding: ScalarFieldElement::rand(&mut rng),
+        });
+        let commitment = FrostSigningCommitment {
+            index: self.share.index,
+            hiding: schnorr_get_public_key(&nonces.hiding),
+            binding: schnorr_get_public_key(&nonces.binding),
+        };
+        self.nonces = Some(nonces);
+        commitment
+    }
+
+    // Round 2: sign `message` given the commitments of all the signers of the session,
+    // including the one of this signer. Nonces are consumed whatever the outcome, so a
+    // new call to commit() is needed before signing again.
+    pub fn sign(
+        &mut self,
+        message: &FieldElement,
+        commitments: &[FrostSigningCommitment],
+    ) -> Result<FrostSignatureShare, Error> {
+        let nonces = self
+            .nonces
+            .take()
+            .ok_or("No signing nonces available: commit() must be called first")?;
+
+        let package = FrostSigningPackage::new(&self.group_pk, message, commitments)?;
+        let pos = package.position(self.share.index)?;
+
+        let own_commitment = FrostSigningCommitment {
+            index: self.share.index,
+            hiding: schnorr_get_public_key(&nonces.hiding),
+            binding: schnorr_get_public_key(&nonces.binding),
+        };
+        if package.commitments[pos] != own_commitment {
+            Err("Own signing commitment doesn't match the one of the session")?
+        }
+
+        let lambda = lagrange_coefficient_at_zero(self.share.index, &package.indices)?;
+        let c = field_to_scalar(&package.challenge)?;
+
+        // z_i = d_i + rho_i * e_i + lambda_i * s_i * c
+        let value = nonces.hiding
+            + &(package.binding_factors[pos] * &nonces.binding)
+            + &(lambda * &*self.share.value * &c);
+
+        Ok(FrostSignatureShare {
+            index: self.share.index,
+            value,
+        })
+    }
+}
+
+// Coordinator of a FROST signing session: verifies the signature shares and aggregates
+// them into the final signature.
+pub struct FrostAggregator {
+    group_commitment: VssCommitment,
+    message: FieldElement,
+    package: FrostSigningPackage,
+}
+
+impl FrostAggregator {
+    // `group_commitment` is the joint VSS commitment of the key generation, from which
+    // the group public key and the public key shares of the signers are derived.
+    pub fn new(
+        group_commitment: VssCommitment,
+        message: FieldElement,
+        commitments: &[FrostSigningCommitment],
+    ) -> Result<Self, Error> {
+        if commitments.len() < group_commitment.threshold() {
+            Err(format!(
+                "Not enough signers: {}, threshold: {}",
+                commitments.len(),
+                group_commitment.threshold()
+            ))?
+        }
+        let package =
+            FrostSigningPackage::new(&group_commitment.public_key(), &message, commitments)?;
+        Ok(Self {
+            group_commitment,
+            message,
+            package,
+        })
+    }
+
+    // Check g^{z_i} == D_i + rho_i * E_i + (lambda_i * c) * Y_i, with Y_i public key share of i
+    pub fn verify_share(&self, share: &FrostSignatureShare) -> bool {
+        let check = || -> Result<bool, Error> {
+            let pos = self.package.position(share.index)?;
+            let commitment = &self.package.commitments[pos];
+            let lambda = lagrange_coefficient_at_zero(share.index, &self.package.indices)?;
+            let c = field_to_scalar(&self.package.challenge)?;
+
+            let mut expected = commitment.binding.mul(self.package.binding_factors[pos]);
+            expected.add_assign_mixed(&commitment.hiding);
+            expected += &self
+                .group_commitment
+                .public_key_share(share.index)
+                .mul(lambda * &c);
+
+            Ok(schnorr_get_public_key(&share.value) == expected.into_affine())
+        };
+        check().unwrap_or(false)
+    }
+
+    // Aggregate the shares of all the signers of the session into a Schnorr signature
+    // on the message, verifiable against the group public key.
+    pub fn aggregate(&self, shares: &[FrostSignatureShare]) -> Result<SchnorrSig, Error> {
+        let mut indices = shares.iter().map(|s| s.index).collect::<Vec<_>>();
+        indices.sort_unstable();
+        if indices != self.package.indices {
+            Err("Signature shares don't match the signers of the session")?
+        }
+
+        if let Some(share) = shares.iter().find(|s| !self.verify_share(s)) {
+            Err(format!("Invalid signature share from signer {}", share.index))?
+        }
+
+        let z = shares
+            .iter()
+            .fold(ScalarFieldElement::zero(), |acc, s| acc + &s.value);
+
+        let signature = SchnorrSig::new(self.package.challenge, scalar_to_field(&z)?);
+
+        if !schnorr_verify_signature(
+            &self.message,
+            &self.group_commitment.public_key(),
+            &signature,
+        )? {
+            Err("Aggregated signature is not valid")?
+        }
+
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cctp_calls::schnorr_generate_key;
+    use crate::secret_sharing::shamir_split;
+
+    #[test]
+    fn frost_sign_aggregate() {
+        let threshold = 3;
+        let n = 5;
+
+        let (pk, sk) = schnorr_generate_key();
+        let (shares, vss_commitment) = shamir_split(&sk, threshold, n).unwrap();
+
+        let mut signers = shares
+            .into_iter()
+            .map(|share| FrostSigner::new(share, pk))
+            .collect::<Vec<_>>();
+
+        let message = FieldElement::rand(&mut OsRng);
+
+        // Signers 1, 3 and 5 take part to the session
+        let session = [0usize, 2, 4];
+
+        // Round 1
+        let commitments = session
+            .iter()
+            .map(|&i| {
+                let c = signers[i].commit();
+                let c_bytes = c.to_bytes().unwrap();
+                assert_eq!(c_bytes.len(), FROST_SIGNING_COMMITMENT_SIZE);
+                assert_eq!(c, FrostSigningCommitment::from_bytes(&c_bytes).unwrap());
+                c
+            })
+            .collect::<Vec<_>>();
+
+        // Round 2
+        let sig_shares = session
+            .iter()
+            .map(|&i| {
+                let s = signers[i].sign(&message, &commitments).unwrap();
+                let s_bytes = s.to_bytes().unwrap();
+                assert_eq!(s_bytes.len(), FROST_SIGNATURE_SHARE_SIZE);
+                assert_eq!(s, FrostSignatureShare::from_bytes(&s_bytes).unwrap());
+                s
+            })
+            .collect::<Vec<_>>();
+
+        // Nonces can't be reused
+        assert!(signers[0].sign(&message, &commitments).is_err());
+
+        let aggregator =
+            FrostAggregator::new(vss_commitment.clone(), message, &commitments).unwrap();
+        for s in sig_shares.iter() {
+            assert!(aggregator.verify_share(s));
+        }
+
+        let signature = aggregator.aggregate(&sig_shares).unwrap();
+        assert!(schnorr_verify_signature(&message, &pk, &signature).unwrap());
+
+        // Negative cases
+        let mut wrong_share = sig_shares[1].clone();
+        wrong_share.value += &ScalarFieldElement::one();
+        assert!(!aggregator.verify_share(&wrong_share));
+        assert!(aggregator
+            .aggregate(&[sig_shares[0].clone(), wrong_share, sig_shares[2].clone()])
+            .is_err());
+        assert!(aggregator.aggregate(&sig_shares[..2]).is_err());
+        assert!(FrostAggregator::new(vss_commitment, message, &commitments[..2]).is_err());
+
+        let wrong_message = FieldElement::rand(&mut OsRng);
+        assert!(!schnorr_verify_signature(&wrong_message, &pk, &signature).unwrap());
+    }
+}
@@ -0,0 +1,301 @@
+use cctp_primitives::utils::{mht::*, serialization::*};
+use demo_circuit::type_mapping::*;
+
+use crate::framing::*;
+
+use std::convert::TryInto;
+use std::ops::Deref;
+
+//*****************************In memory optimized Merkle tree with snapshots***********************
+//
+// Wrapper of a GingerMHT keeping track of the leaves appended so far, so that the whole state
+// of the tree (including a non finalized one) can be saved to and restored from a snapshot.
+//
+// Layout of a snapshot (integers are little endian):
+//
+// | magic (4) | version (1) | height (4) | processing step (8) | finalized (1) | num leaves (8) |
+// | leaves (num leaves * FIELD_SIZE) | root (FIELD_SIZE, only if finalized) | checksum (8) |
+//
+// The internal nodes are not stored: they are recomputed when restoring the snapshot, by
+// appending again the leaves to a tree with the same parameters. For finalized trees, the root
+// obtained in this way must match the stored one. Magic, version and checksum are the framing of
+// framing.rs.
+
+const SNAPSHOT_MAGIC: [u8; 4] = *b"ZMHT";
+pub const MHT_SNAPSHOT_VERSION: u8 = 1;
+
+// The leaves of an in memory tree must fit in memory: snapshots of higher trees are rejected
+// before allocating anything
+pub const MHT_SNAPSHOT_MAX_HEIGHT: usize = 32;
+
+// Fields of the snapshot preceding the leaves, after magic and version
+const HEADER_SIZE: usize = 4 + 8 + 1 + 8;
+
+pub struct InMemoryOptimizedMerkleTree {
+    tree: GingerMHT,
+    height: usize,
+    processing_step: usize,
+    leaves: Vec<FieldElement>,
+    finalized: bool,
+}
+
+// Read-only access to the underlying tree (root, paths, ...)
+impl Deref for InMemoryOptimizedMerkleTree {
+    type Target = GingerMHT;
+
+    fn deref(&self) -> &GingerMHT {
+        &self.tree
+    }
+}
+
+impl InMemoryOptimizedMerkleTree {
+    pub fn new(height: usize, processing_step: usize) -> Result<Self, Error> {
+        Ok(Self {
+            tree: new_ginger_mht(height, processing_step)?,
+            height,
+            processing_step,
+            leaves: Vec::new(),
+            finalized: false,
+        })
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.finalized
+    }
+
+    pub fn append(&mut self, leaf: &FieldElement) -> Result<(), Error> {
+        if self.finalized {
+            Err("Unable to append a leaf to a finalized tree")?
+        }
+        append_leaf_to_ginger_mht(&mut self.tree, leaf)?;
+        self.leaves.push(*leaf);
+        Ok(())
+    }
+
+    // Return a finalized copy of this tree, leaving this one untouched
+    pub fn finalize(&self) -> Result<Self, Error> {
+        Ok(Self {
+            tree: finalize_ginger_mht(&self.tree)?,
+            height: self.height,
+            processing_step: self.processing_step,
+            leaves: self.leaves.clone(),
+            finalized: true,
+        })
+    }
+
+    pub fn finalize_in_place(&mut self) -> Result<(), Error> {
+        finalize_ginger_mht_in_place(&mut self.tree)?;
+        self.finalized = true;
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        reset_ginger_mht(&mut self.tree);
+        self.leaves.clear();
+        self.finalized = false;
+    }
+
+    pub fn serialize_snapshot(&self) -> Result<Vec<u8>, Error> {
+        let height: u32 = self.height.try_into()?;
+        let root_size = if self.finalized { FIELD_SIZE } else { 0 };
+
+        let mut snapshot = begin_frame(
+            &SNAPSHOT_MAGIC,
+            MHT_SNAPSHOT_VERSION,
+            HEADER_SIZE + (self.leaves.len() * FIELD_SIZE) + root_size,
+        );
+        snapshot.extend_from_slice(&height.to_le_bytes());
+        snapshot.extend_from_slice(&(self.processing_step as u64).to_le_bytes());
+        snapshot.push(self.finalized as u8);
+        snapshot.extend_from_slice(&(self.leaves.len() as u64).to_le_bytes());
+        for leaf in self.leaves.iter() {
+            snapshot.extend_from_slice(&serialize_to_buffer(leaf, None)?);
+        }
+        if self.finalized {
+            let root = get_ginger_mht_root(&self.tree).ok_or("Unable to get root")?;
+            snapshot.extend_from_slice(&serialize_to_buffer(&root, None)?);
+        }
+
+        Ok(end_frame(snapshot))
+    }
+
+    pub fn deserialize_snapshot(snapshot: &[u8]) -> Result<Self, Error> {
+        let content = open_frame(
+            snapshot,
+            &SNAPSHOT_MAGIC,
+            MHT_SNAPSHOT_VERSION,
+            "Merkle tree snapshot",
+        )?;
+        if content.len() < HEADER_SIZE {
+            Err("Snapshot too short")?
+        }
+
+        let height = u32::from_le_bytes(content[0..4].try_into().unwrap()) as usize;
+        let processing_step: usize =
+            u64::from_le_bytes(content[4..12].try_into().unwrap()).try_into()?;
+        let finalized = match content[12] {
+            0 => false,
+            1 => true,
+            other => Err(format!("Invalid finalized flag: {}", other))?,
+        };
+        let num_leaves = u64::from_le_bytes(content[13..HEADER_SIZE].try_into().unwrap());
+
+        if height == 0 || height > MHT_SNAPSHOT_MAX_HEIGHT {
+            Err(format!(
+                "Invalid height: {}. Must be in [1, {}]",
+                height, MHT_SNAPSHOT_MAX_HEIGHT
+            ))?
+        }
+        let max_leaves = 1u64 << height;
+        if processing_step == 0 || processing_step as u64 > max_leaves {
+            Err(format!(
+                "Invalid processing step: {}. Must be in [1, {}]",
+                processing_step, max_leaves
+            ))?
+        }
+        if num_leaves > max_leaves {
+            Err(format!(
+                "Invalid number of leaves: {}. Must be at most {}",
+                num_leaves, max_leaves
+            ))?
+        }
+
+        let root_size = if finalized { FIELD_SIZE } else { 0 };
+        let expected_len = num_leaves
+            .checked_mul(FIELD_SIZE as u64)
+            .and_then(|len| len.checked_add((HEADER_SIZE + root_size) as u64));
+        if expected_len != Some(content.len() as u64) {
+            Err("Invalid snapshot length")?
+        }
+
+        let mut tree = Self::new(height, processing_step)?;
+        let (leaves, root) = content[HEADER_SIZE..].split_at(num_leaves as usize * FIELD_SIZE);
+        for leaf_bytes in leaves.chunks(FIELD_SIZE) {
+            tree.append(&deserialize_from_buffer(leaf_bytes, None, None)?)?;
+        }
+
+        if finalized {
+            tree.finalize_in_place()?;
+            let expected_root: FieldElement = deserialize_from_buffer(root, None, None)?;
+            if get_ginger_mht_root(&tree.tree) != Some(expected_root) {
+                Err("Snapshot root mismatch")?
+            }
+        }
+
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn in_memory_mht_snapshot() {
+        let height = 6;
+        let processing_step = 8;
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let mut tree = InMemoryOptimizedMerkleTree::new(height, processing_step).unwrap();
+        let leaves = (0..21)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        for leaf in leaves.iter() {
+            tree.append(leaf).unwrap();
+        }
+
+        // Non finalized tree: the restored tree must give the same root when finalized,
+        // and must still accept new leaves
+        let snapshot = tree.serialize_snapshot().unwrap();
+        let mut restored = InMemoryOptimizedMerkleTree::deserialize_snapshot(&snapshot).unwrap();
+        assert!(!restored.is_finalized());
+        assert_eq!(restored.num_leaves(), leaves.len());
+        assert_eq!(
+            get_ginger_mht_root(&tree.finalize().unwrap()),
+            get_ginger_mht_root(&restored.finalize().unwrap())
+        );
+
+        let new_leaf = FieldElement::rand(rng);
+        tree.append(&new_leaf).unwrap();
+        restored.append(&new_leaf).unwrap();
+
+        // Finalized tree: same root and paths
+        tree.finalize_in_place().unwrap();
+        assert!(tree.append(&new_leaf).is_err());
+        let snapshot = tree.serialize_snapshot().unwrap();
+        let restored = InMemoryOptimizedMerkleTree::deserialize_snapshot(&snapshot).unwrap();
+        assert!(restored.is_finalized());
+        assert_eq!(get_ginger_mht_root(&tree), get_ginger_mht_root(&restored));
+        for i in 0..=leaves.len() as u64 {
+            assert_eq!(get_ginger_mht_path(&tree, i), get_ginger_mht_path(&restored, i));
+        }
+
+        // Corrupted snapshots
+        for i in 0..snapshot.len() {
+            let mut corrupted = snapshot.clone();
+            corrupted[i] ^= 1;
+            assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&corrupted).is_err());
+        }
+        assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&snapshot[1..]).is_err());
+        assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&[]).is_err());
+
+        // Tampered root, with checksum recomputed
+        let mut tampered = snapshot[..snapshot.len() - CHECKSUM_SIZE].to_vec();
+        let root_pos = tampered.len() - FIELD_SIZE;
+        tampered[root_pos..].copy_from_slice(
+            &serialize_to_buffer(&FieldElement::rand(rng), None).unwrap(),
+        );
+        let tampered = end_frame(tampered);
+        assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&tampered).is_err());
+
+        // Out of range height and processing step, with checksum recomputed
+        let with_header = |height: u32, processing_step: u64| {
+            let mut tampered = snapshot[..snapshot.len() - CHECKSUM_SIZE].to_vec();
+            tampered[5..9].copy_from_slice(&height.to_le_bytes());
+            tampered[9..17].copy_from_slice(&processing_step.to_le_bytes());
+            end_frame(tampered)
+        };
+        assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&with_header(
+            height as u32,
+            processing_step as u64
+        ))
+        .is_ok());
+        assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&with_header(
+            MHT_SNAPSHOT_MAX_HEIGHT as u32 + 1,
+            processing_step as u64
+        ))
+        .is_err());
+        assert!(InMemoryOptimizedMerkleTree::deserialize_snapshot(&with_header(
+            height as u32,
+            (1u64 << height) + 1
+        ))
+        .is_err());
+        assert!(
+            InMemoryOptimizedMerkleTree::deserialize_snapshot(&with_header(height as u32, 0))
+                .is_err()
+        );
+        // Too many leaves for the height
+        assert!(
+            InMemoryOptimizedMerkleTree::deserialize_snapshot(&with_header(4, processing_step as u64))
+                .is_err()
+        );
+
+        // Reset
+        let mut tree = restored;
+        tree.reset();
+        assert!(!tree.is_finalized());
+        assert_eq!(tree.num_leaves(), 0);
+        let empty = InMemoryOptimizedMerkleTree::deserialize_snapshot(
+            &tree.serialize_snapshot().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(empty.num_leaves(), 0);
+    }
+}
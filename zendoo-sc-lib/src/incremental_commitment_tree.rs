@@ -0,0 +1,1326 @@
+use algebra::Field;
+use cctp_primitives::{
+    commitment_tree::{
+        hashers::{hash_bwtr, hash_csw, hash_fwt},
+        proofs::{ScAbsenceProof, ScExistenceProof},
+        CommitmentTree, CMT_MT_HEIGHT,
+    },
+    utils::{commitment_tree::hash_vec, data_structures::*, mht::*, serialization::*},
+};
+use demo_circuit::type_mapping::*;
+
+use crate::{framing::*, sidechain_creation::SidechainCreationParams, tx_inclusion_proof::*};
+
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+//*****************************Commitment tree with state export************************************
+//
+// Wrapper of a CommitmentTree that:
+// - keeps the list of the transactions successfully added to it, so that its state can be exported
+//   and later restored (e.g. to track the commitment of the mainchain block being synced across
+//   restarts). Restoring a state means adding again the transactions, in the same order, to an
+//   empty tree;
+// - caches, for each sidechain, the roots of its sub-trees (one for each transaction type), the
+//   commitment of its creation and its commitment, together with the overall commitment. Adding
+//   a transaction invalidates only the sub-tree (or the creation) it touches, the commitment of
+//   its sidechain and the overall one. The commitment of a sidechain is then rebuilt from its
+//   cached roots, as in tx_inclusion_proof.rs, and the overall commitment from the cached
+//   commitments of the sidechains, so that only what has been touched since the last call is
+//   recomputed.
+//
+// The transactions are kept as added, so the log costs about as much memory as the
+// transactions themselves: around 150 bytes for each forward transfer, certificate leaf and
+// ceased sidechain withdrawal, plus the request data of backward transfer requests, the
+// backward transfers and custom fields of certificates and the custom data and verification
+// keys of sidechain creations, which usually dominate it. The log is freed with the tree.
+//
+// Layout of an exported state (integers are little endian):
+//
+// | magic (4) | version (1) | num transactions (4) | transactions | checksum (8) |
+//
+// Each transaction is a type byte followed by its fields; variable length fields are prefixed
+// by their length (4 bytes), optional fields by a presence flag (1 byte). Magic, version and
+// checksum are the framing of framing.rs.
+
+const STATE_MAGIC: [u8; 4] = *b"ZCMT";
+pub const COMMITMENT_TREE_STATE_VERSION: u8 = 1;
+
+enum CommitmentTreeTx {
+    ScCr {
+        sc_id: FieldElement,
+        amount: u64,
+        pub_key: [u8; FIELD_SIZE],
+        tx_hash: [u8; FIELD_SIZE],
+        out_idx: u32,
+        withdrawal_epoch_length: u32,
+        mc_btr_request_data_length: u8,
+        custom_field_elements_configs: Option<Vec<u8>>,
+        // (bit_vector_size_bits, max_compressed_byte_size)
+        custom_bitvector_elements_configs: Option<Vec<(u32, u32)>>,
+        btr_fee: u64,
+        ft_min_amount: u64,
+        custom_creation_data: Option<Vec<u8>>,
+        constant: Option<FieldElement>,
+        cert_verification_key: Vec<u8>,
+        csw_verification_key: Option<Vec<u8>>,
+    },
+    Fwt {
+        sc_id: FieldElement,
+        amount: u64,
+        pub_key: [u8; FIELD_SIZE],
+        mc_return_address: [u8; MC_PK_SIZE],
+        tx_hash: [u8; FIELD_SIZE],
+        out_idx: u32,
+    },
+    Bwtr {
+        sc_id: FieldElement,
+        sc_fee: u64,
+        sc_request_data: Vec<FieldElement>,
+        mc_destination_address: [u8; MC_PK_SIZE],
+        tx_hash: [u8; FIELD_SIZE],
+        out_idx: u32,
+    },
+    Cert {
+        sc_id: FieldElement,
+        epoch_number: u32,
+        quality: u64,
+        // (pk_dest, amount)
+        bt_list: Option<Vec<([u8; MC_PK_SIZE], u64)>>,
+        custom_fields: Option<Vec<FieldElement>>,
+        end_cumulative_sc_tx_commitment_tree_root: FieldElement,
+        btr_fee: u64,
+        ft_min_amount: u64,
+    },
+    CertLeaf {
+        sc_id: FieldElement,
+        leaf: FieldElement,
+    },
+    Csw {
+        sc_id: FieldElement,
+        amount: u64,
+        nullifier: FieldElement,
+        mc_pk_hash: [u8; MC_PK_SIZE],
+    },
+}
+
+impl CommitmentTreeTx {
+    fn sc_id(&self) -> &FieldElement {
+        match self {
+            CommitmentTreeTx::ScCr { sc_id, .. }
+            | CommitmentTreeTx::Fwt { sc_id, .. }
+            | CommitmentTreeTx::Bwtr { sc_id, .. }
+            | CommitmentTreeTx::Cert { sc_id, .. }
+            | CommitmentTreeTx::CertLeaf { sc_id, .. }
+            | CommitmentTreeTx::Csw { sc_id, .. } => sc_id,
+        }
+    }
+
+    fn tx_type(&self) -> Option<CommitmentTreeTxType> {
+        match self {
+            CommitmentTreeTx::ScCr { .. } => None,
+            CommitmentTreeTx::Fwt { .. } => Some(CommitmentTreeTxType::Fwt),
+            CommitmentTreeTx::Bwtr { .. } => Some(CommitmentTreeTxType::Bwtr),
+            CommitmentTreeTx::Cert { .. } | CommitmentTreeTx::CertLeaf { .. } => {
+                Some(CommitmentTreeTxType::Cert)
+            }
+            CommitmentTreeTx::Csw { .. } => Some(CommitmentTreeTxType::Csw),
+        }
+    }
+
+    // Leaf of the transaction in the sub-tree of its type. Not available for certificates:
+    // their leaves are read from the tree instead
+    fn leaf(&self) -> Result<FieldElement, Error> {
+        match self {
+            CommitmentTreeTx::Fwt {
+                amount,
+                pub_key,
+                mc_return_address,
+                tx_hash,
+                out_idx,
+                ..
+            } => hash_fwt(*amount, pub_key, mc_return_address, tx_hash, *out_idx),
+            CommitmentTreeTx::Bwtr {
+                sc_fee,
+                sc_request_data,
+                mc_destination_address,
+                tx_hash,
+                out_idx,
+                ..
+            } => hash_bwtr(
+                *sc_fee,
+                sc_request_data.iter().collect(),
+                mc_destination_address,
+                tx_hash,
+                *out_idx,
+            ),
+            CommitmentTreeTx::Csw {
+                amount,
+                nullifier,
+                mc_pk_hash,
+                ..
+            } => hash_csw(*amount, nullifier, mc_pk_hash),
+            _ => Err("Leaf not available for this transaction")?,
+        }
+    }
+
+    fn apply(&self, tree: &mut CommitmentTree) -> bool {
+        match self {
+            CommitmentTreeTx::ScCr {
+                sc_id,
+                amount,
+                pub_key,
+                tx_hash,
+                out_idx,
+                withdrawal_epoch_length,
+                mc_btr_request_data_length,
+                custom_field_elements_configs,
+                custom_bitvector_elements_configs,
+                btr_fee,
+                ft_min_amount,
+                custom_creation_data,
+                constant,
+                cert_verification_key,
+                csw_verification_key,
+            } => {
+                let custom_bitvector_elements_configs =
+                    custom_bitvector_elements_configs.as_ref().map(|configs| {
+                        configs
+                            .iter()
+                            .map(|&(bit_vector_size_bits, max_compressed_byte_size)| {
+                                BitVectorElementsConfig {
+                                    bit_vector_size_bits,
+                                    max_compressed_byte_size,
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    });
+                tree.add_scc(
+                    sc_id,
+                    *amount,
+                    pub_key,
+                    tx_hash,
+                    *out_idx,
+                    *withdrawal_epoch_length,
+                    *mc_btr_request_data_length,
+                    custom_field_elements_configs.as_deref(),
+                    custom_bitvector_elements_configs.as_deref(),
+                    *btr_fee,
+                    *ft_min_amount,
+                    custom_creation_data.as_deref(),
+                    constant.as_ref(),
+                    cert_verification_key.as_slice(),
+                    csw_verification_key.as_deref(),
+                )
+            }
+            CommitmentTreeTx::Fwt {
+                sc_id,
+                amount,
+                pub_key,
+                mc_return_address,
+                tx_hash,
+                out_idx,
+            } => tree.add_fwt(sc_id, *amount, pub_key, mc_return_address, tx_hash, *out_idx),
+            CommitmentTreeTx::Bwtr {
+                sc_id,
+                sc_fee,
+                sc_request_data,
+                mc_destination_address,
+                tx_hash,
+                out_idx,
+            } => tree.add_bwtr(
+                sc_id,
+                *sc_fee,
+                sc_request_data.iter().collect(),
+                mc_destination_address,
+                tx_hash,
+                *out_idx,
+            ),
+            CommitmentTreeTx::Cert {
+                sc_id,
+                epoch_number,
+                quality,
+                bt_list,
+                custom_fields,
+                end_cumulative_sc_tx_commitment_tree_root,
+                btr_fee,
+                ft_min_amount,
+            } => {
+                let bt_list = bt_list.as_ref().map(|bt_list| {
+                    bt_list
+                        .iter()
+                        .map(|&(pk_dest, amount)| BackwardTransfer { pk_dest, amount })
+                        .collect::<Vec<_>>()
+                });
+                tree.add_cert(
+                    sc_id,
+                    *epoch_number,
+                    *quality,
+                    bt_list.as_deref(),
+                    custom_fields.as_ref().map(|fields| fields.iter().collect()),
+                    end_cumulative_sc_tx_commitment_tree_root,
+                    *btr_fee,
+                    *ft_min_amount,
+                )
+            }
+            CommitmentTreeTx::CertLeaf { sc_id, leaf } => tree.add_cert_leaf(sc_id, leaf),
+            CommitmentTreeTx::Csw {
+                sc_id,
+                amount,
+                nullifier,
+                mc_pk_hash,
+            } => tree.add_csw(sc_id, *amount, nullifier, mc_pk_hash),
+        }
+    }
+
+    fn write(&self, w: &mut StateWriter) -> Result<(), Error> {
+        match self {
+            CommitmentTreeTx::ScCr {
+                sc_id,
+                amount,
+                pub_key,
+                tx_hash,
+                out_idx,
+                withdrawal_epoch_length,
+                mc_btr_request_data_length,
+                custom_field_elements_configs,
+                custom_bitvector_elements_configs,
+                btr_fee,
+                ft_min_amount,
+                custom_creation_data,
+                constant,
+                cert_verification_key,
+                csw_verification_key,
+            } => {
+                w.u8(0);
+                w.fe(sc_id)?;
+                w.u64(*amount);
+                w.bytes(pub_key);
+                w.bytes(tx_hash);
+                w.u32(*out_idx);
+                w.u32(*withdrawal_epoch_length);
+                w.u8(*mc_btr_request_data_length);
+                w.opt(custom_field_elements_configs.as_ref(), |w, c| {
+                    w.var_bytes(c);
+                    Ok(())
+                })?;
+                w.opt(custom_bitvector_elements_configs.as_ref(), |w, c| {
+                    w.u32(c.len() as u32);
+                    for (bit_vector_size_bits, max_compressed_byte_size) in c.iter() {
+                        w.u32(*bit_vector_size_bits);
+                        w.u32(*max_compressed_byte_size);
+                    }
+                    Ok(())
+                })?;
+                w.u64(*btr_fee);
+                w.u64(*ft_min_amount);
+                w.opt(custom_creation_data.as_ref(), |w, d| {
+                    w.var_bytes(d);
+                    Ok(())
+                })?;
+                w.opt(constant.as_ref(), |w, c| w.fe(c))?;
+                w.var_bytes(cert_verification_key);
+                w.opt(csw_verification_key.as_ref(), |w, k| {
+                    w.var_bytes(k);
+                    Ok(())
+                })?;
+            }
+            CommitmentTreeTx::Fwt {
+                sc_id,
+                amount,
+                pub_key,
+                mc_return_address,
+                tx_hash,
+                out_idx,
+            } => {
+                w.u8(1);
+                w.fe(sc_id)?;
+                w.u64(*amount);
+                w.bytes(pub_key);
+                w.bytes(mc_return_address);
+                w.bytes(tx_hash);
+                w.u32(*out_idx);
+            }
+            CommitmentTreeTx::Bwtr {
+                sc_id,
+                sc_fee,
+                sc_request_data,
+                mc_destination_address,
+                tx_hash,
+                out_idx,
+            } => {
+                w.u8(2);
+                w.fe(sc_id)?;
+                w.u64(*sc_fee);
+                w.fes(sc_request_data)?;
+                w.bytes(mc_destination_address);
+                w.bytes(tx_hash);
+                w.u32(*out_idx);
+            }
+            CommitmentTreeTx::Cert {
+                sc_id,
+                epoch_number,
+                quality,
+                bt_list,
+                custom_fields,
+                end_cumulative_sc_tx_commitment_tree_root,
+                btr_fee,
+                ft_min_amount,
+            } => {
+                w.u8(3);
+                w.fe(sc_id)?;
+                w.u32(*epoch_number);
+                w.u64(*quality);
+                w.opt(bt_list.as_ref(), |w, bt_list| {
+                    w.u32(bt_list.len() as u32);
+                    for (pk_dest, amount) in bt_list.iter() {
+                        w.bytes(pk_dest);
+                        w.u64(*amount);
+                    }
+                    Ok(())
+                })?;
+                w.opt(custom_fields.as_ref(), |w, fields| w.fes(fields))?;
+                w.fe(end_cumulative_sc_tx_commitment_tree_root)?;
+                w.u64(*btr_fee);
+                w.u64(*ft_min_amount);
+            }
+            CommitmentTreeTx::CertLeaf { sc_id, leaf } => {
+                w.u8(4);
+                w.fe(sc_id)?;
+                w.fe(leaf)?;
+            }
+            CommitmentTreeTx::Csw {
+                sc_id,
+                amount,
+                nullifier,
+                mc_pk_hash,
+            } => {
+                w.u8(5);
+                w.fe(sc_id)?;
+                w.u64(*amount);
+                w.fe(nullifier)?;
+                w.bytes(mc_pk_hash);
+            }
+        }
+        Ok(())
+    }
+
+    fn read(r: &mut StateReader) -> Result<Self, Error> {
+        let tx = match r.u8()? {
+            0 => CommitmentTreeTx::ScCr {
+                sc_id: r.fe()?,
+                amount: r.u64()?,
+                pub_key: r.array()?,
+                tx_hash: r.array()?,
+                out_idx: r.u32()?,
+                withdrawal_epoch_length: r.u32()?,
+                mc_btr_request_data_length: r.u8()?,
+                custom_field_elements_configs: r.opt(|r| r.var_bytes())?,
+                custom_bitvector_elements_configs: r.opt(|r| {
+                    (0..r.u32()?)
+                        .map(|_| Ok((r.u32()?, r.u32()?)))
+                        .collect::<Result<Vec<_>, Error>>()
+                })?,
+                btr_fee: r.u64()?,
+                ft_min_amount: r.u64()?,
+                custom_creation_data: r.opt(|r| r.var_bytes())?,
+                constant: r.opt(|r| r.fe())?,
+                cert_verification_key: r.var_bytes()?,
+                csw_verification_key: r.opt(|r| r.var_bytes())?,
+            },
+            1 => CommitmentTreeTx::Fwt {
+                sc_id: r.fe()?,
+                amount: r.u64()?,
+                pub_key: r.array()?,
+                mc_return_address: r.array()?,
+                tx_hash: r.array()?,
+                out_idx: r.u32()?,
+            },
+            2 => CommitmentTreeTx::Bwtr {
+                sc_id: r.fe()?,
+                sc_fee: r.u64()?,
+                sc_request_data: r.fes()?,
+                mc_destination_address: r.array()?,
+                tx_hash: r.array()?,
+                out_idx: r.u32()?,
+            },
+            3 => CommitmentTreeTx::Cert {
+                sc_id: r.fe()?,
+                epoch_number: r.u32()?,
+                quality: r.u64()?,
+                bt_list: r.opt(|r| {
+                    (0..r.u32()?)
+                        .map(|_| Ok((r.array()?, r.u64()?)))
+                        .collect::<Result<Vec<_>, Error>>()
+                })?,
+                custom_fields: r.opt(|r| r.fes())?,
+                end_cumulative_sc_tx_commitment_tree_root: r.fe()?,
+                btr_fee: r.u64()?,
+                ft_min_amount: r.u64()?,
+            },
+            4 => CommitmentTreeTx::CertLeaf {
+                sc_id: r.fe()?,
+                leaf: r.fe()?,
+            },
+            5 => CommitmentTreeTx::Csw {
+                sc_id: r.fe()?,
+                amount: r.u64()?,
+                nullifier: r.fe()?,
+                mc_pk_hash: r.array()?,
+            },
+            tx_type => Err(format!("Unknown transaction type: {}", tx_type))?,
+        };
+        Ok(tx)
+    }
+}
+
+struct StateWriter(Vec<u8>);
+
+impl StateWriter {
+    fn u8(&mut self, v: u8) {
+        self.0.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn bytes(&mut self, v: &[u8]) {
+        self.0.extend_from_slice(v);
+    }
+
+    fn var_bytes(&mut self, v: &[u8]) {
+        self.u32(v.len() as u32);
+        self.bytes(v);
+    }
+
+    fn fe(&mut self, v: &FieldElement) -> Result<(), Error> {
+        self.bytes(&serialize_to_buffer(v, None)?);
+        Ok(())
+    }
+
+    fn fes(&mut self, v: &[FieldElement]) -> Result<(), Error> {
+        self.u32(v.len() as u32);
+        v.iter().try_for_each(|fe| self.fe(fe))
+    }
+
+    fn opt<T, F: FnOnce(&mut Self, &T) -> Result<(), Error>>(
+        &mut self,
+        v: Option<&T>,
+        f: F,
+    ) -> Result<(), Error> {
+        match v {
+            Some(v) => {
+                self.u8(1);
+                f(self, v)
+            }
+            None => {
+                self.u8(0);
+                Ok(())
+            }
+        }
+    }
+}
+
+struct StateReader<'a>(&'a [u8]);
+
+impl<'a> StateReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if len > self.0.len() {
+            Err("Unexpected end of state")?
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn var_bytes(&mut self) -> Result<Vec<u8>, Error> {
+        let len = self.u32()? as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn fe(&mut self) -> Result<FieldElement, Error> {
+        deserialize_from_buffer(self.take(FIELD_SIZE)?, None, None)
+    }
+
+    fn fes(&mut self) -> Result<Vec<FieldElement>, Error> {
+        (0..self.u32()?).map(|_| self.fe()).collect()
+    }
+
+    fn opt<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<Option<T>, Error> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(f(self)?)),
+            flag => Err(format!("Invalid presence flag: {}", flag))?,
+        }
+    }
+}
+
+// Cached data of a sidechain. Each value is None if a transaction touching it has been added
+// since it was last computed.
+#[derive(Default)]
+struct ScCache {
+    // Indexed by transaction type
+    sub_tree_roots: [Option<FieldElement>; 4],
+    scc: Option<FieldElement>,
+    commitment: Option<FieldElement>,
+    ceased: bool,
+}
+
+impl ScCache {
+    fn invalidate(&mut self, tx: &CommitmentTreeTx) {
+        match tx.tx_type() {
+            Some(tx_type) => self.sub_tree_roots[tx_type as usize] = None,
+            None => self.scc = None,
+        }
+        if tx.tx_type() == Some(CommitmentTreeTxType::Csw) {
+            self.ceased = true;
+        }
+        self.commitment = None;
+    }
+
+    fn sub_tree_root(
+        &mut self,
+        tree: &mut CommitmentTree,
+        sc_id: &FieldElement,
+        tx_type: CommitmentTreeTxType,
+    ) -> Option<FieldElement> {
+        let cached = &mut self.sub_tree_roots[tx_type as usize];
+        if cached.is_none() {
+            *cached = match tx_type {
+                CommitmentTreeTxType::Fwt => tree.get_fwt_commitment(sc_id),
+                CommitmentTreeTxType::Bwtr => tree.get_bwtr_commitment(sc_id),
+                CommitmentTreeTxType::Cert => tree.get_cert_commitment(sc_id),
+                CommitmentTreeTxType::Csw => tree.get_csw_commitment(sc_id),
+            };
+        }
+        *cached
+    }
+
+    // Same inputs of the sidechain commitment listed in tx_inclusion_proof.rs
+    fn commitment(
+        &mut self,
+        tree: &mut CommitmentTree,
+        sc_id: &FieldElement,
+    ) -> Result<FieldElement, Error> {
+        if let Some(commitment) = self.commitment {
+            return Ok(commitment);
+        }
+
+        let mut inputs = if self.ceased {
+            vec![self
+                .sub_tree_root(tree, sc_id, CommitmentTreeTxType::Csw)
+                .ok_or("Missing ceased sidechain withdrawals root")?]
+        } else {
+            let fwt_root = self
+                .sub_tree_root(tree, sc_id, CommitmentTreeTxType::Fwt)
+                .ok_or("Missing forward transfers root")?;
+            let bwtr_root = self
+                .sub_tree_root(tree, sc_id, CommitmentTreeTxType::Bwtr)
+                .ok_or("Missing backward transfer requests root")?;
+            let cert_root = self
+                .sub_tree_root(tree, sc_id, CommitmentTreeTxType::Cert)
+                .ok_or("Missing certificates root")?;
+            if self.scc.is_none() {
+                self.scc = Some(tree.get_scc(sc_id).unwrap_or_else(FieldElement::zero));
+            }
+            vec![fwt_root, bwtr_root, cert_root, self.scc.unwrap()]
+        };
+        inputs.push(*sc_id);
+
+        let commitment = hash_vec(inputs)?;
+        self.commitment = Some(commitment);
+        Ok(commitment)
+    }
+}
+
+pub struct IncrementalCommitmentTree {
+    tree: CommitmentTree,
+    txs: Vec<CommitmentTreeTx>,
+    // Cached data of each sidechain in the tree, ordered by sidechain id as the leaves of the
+    // commitment tree
+    sc_caches: BTreeMap<FieldElement, ScCache>,
+    commitment: Option<FieldElement>,
+}
+
+impl Default for IncrementalCommitmentTree {
+    fn default() -> Self {
+        Self::create()
+    }
+}
+
+impl IncrementalCommitmentTree {
+    pub fn create() -> Self {
+        Self {
+            tree: CommitmentTree::create(),
+            txs: Vec::new(),
+            sc_caches: BTreeMap::new(),
+            commitment: None,
+        }
+    }
+
+    pub fn num_transactions(&self) -> usize {
+        self.txs.len()
+    }
+
+    fn add(&mut self, tx: CommitmentTreeTx) -> bool {
+        if !tx.apply(&mut self.tree) {
+            return false;
+        }
+        self.sc_caches
+            .entry(*tx.sc_id())
+            .or_default()
+            .invalidate(&tx);
+        self.commitment = None;
+        self.txs.push(tx);
+        true
+    }
+
+    pub fn add_scc(
+        &mut self,
+        sc_id: &FieldElement,
+        amount: u64,
+        pub_key: &[u8; FIELD_SIZE],
+        tx_hash: &[u8; FIELD_SIZE],
+        out_idx: u32,
+        withdrawal_epoch_length: u32,
+        mc_btr_request_data_length: u8,
+        custom_field_elements_configs: Option<&[u8]>,
+        custom_bitvector_elements_configs: Option<&[BitVectorElementsConfig]>,
+        btr_fee: u64,
+        ft_min_amount: u64,
+        custom_creation_data: Option<&[u8]>,
+        constant: Option<&FieldElement>,
+        cert_verification_key: &[u8],
+        csw_verification_key: Option<&[u8]>,
+    ) -> bool {
+        self.add(CommitmentTreeTx::ScCr {
+            sc_id: *sc_id,
+            amount,
+            pub_key: *pub_key,
+            tx_hash: *tx_hash,
+            out_idx,
+            withdrawal_epoch_length,
+            mc_btr_request_data_length,
+            custom_field_elements_configs: custom_field_elements_configs.map(|c| c.to_vec()),
+            custom_bitvector_elements_configs: custom_bitvector_elements_configs.map(|configs| {
+                configs
+                    .iter()
+                    .map(|c| (c.bit_vector_size_bits, c.max_compressed_byte_size))
+                    .collect()
+            }),
+            btr_fee,
+            ft_min_amount,
+            custom_creation_data: custom_creation_data.map(|d| d.to_vec()),
+            constant: constant.copied(),
+            cert_verification_key: cert_verification_key.to_vec(),
+            csw_verification_key: csw_verification_key.map(|k| k.to_vec()),
+        })
+    }
+
+    pub fn add_sc_creation(
+        &mut self,
+        sc_id: &FieldElement,
+        tx_hash: &[u8; FIELD_SIZE],
+        out_idx: u32,
+        params: &SidechainCreationParams,
+    ) -> bool {
+        self.add_scc(
+            sc_id,
+            params.amount,
+            &params.pub_key,
+            tx_hash,
+            out_idx,
+            params.withdrawal_epoch_length,
+            params.mc_btr_request_data_length,
+            Some(params.custom_field_elements_configs.as_slice()).filter(|c| !c.is_empty()),
+            Some(params.custom_bitvector_elements_configs.as_slice()).filter(|c| !c.is_empty()),
+            params.btr_fee,
+            params.ft_min_amount,
+            Some(params.custom_creation_data.as_slice()).filter(|d| !d.is_empty()),
+            params.constant.as_ref(),
+            params.cert_verification_key.as_slice(),
+            params.csw_verification_key.as_deref(),
+        )
+    }
+
+    pub fn add_fwt(
+        &mut self,
+        sc_id: &FieldElement,
+        amount: u64,
+        pub_key: &[u8; FIELD_SIZE],
+        mc_return_address: &[u8; MC_PK_SIZE],
+        tx_hash: &[u8; FIELD_SIZE],
+        out_idx: u32,
+    ) -> bool {
+        self.add(CommitmentTreeTx::Fwt {
+            sc_id: *sc_id,
+            amount,
+            pub_key: *pub_key,
+            mc_return_address: *mc_return_address,
+            tx_hash: *tx_hash,
+            out_idx,
+        })
+    }
+
+    pub fn add_bwtr(
+        &mut self,
+        sc_id: &FieldElement,
+        sc_fee: u64,
+        sc_request_data: Vec<&FieldElement>,
+        mc_destination_address: &[u8; MC_PK_SIZE],
+        tx_hash: &[u8; FIELD_SIZE],
+        out_idx: u32,
+    ) -> bool {
+        self.add(CommitmentTreeTx::Bwtr {
+            sc_id: *sc_id,
+            sc_fee,
+            sc_request_data: sc_request_data.into_iter().copied().collect(),
+            mc_destination_address: *mc_destination_address,
+            tx_hash: *tx_hash,
+            out_idx,
+        })
+    }
+
+    pub fn add_cert(
+        &mut self,
+        sc_id: &FieldElement,
+        epoch_number: u32,
+        quality: u64,
+        bt_list: Option<&[BackwardTransfer]>,
+        custom_fields: Option<Vec<&FieldElement>>,
+        end_cumulative_sc_tx_commitment_tree_root: &FieldElement,
+        btr_fee: u64,
+        ft_min_amount: u64,
+    ) -> bool {
+        self.add(CommitmentTreeTx::Cert {
+            sc_id: *sc_id,
+            epoch_number,
+            quality,
+            bt_list: bt_list.map(|bt_list| {
+                bt_list
+                    .iter()
+                    .map(|bt| (bt.pk_dest, bt.amount))
+                    .collect()
+            }),
+            custom_fields: custom_fields.map(|fields| fields.into_iter().copied().collect()),
+            end_cumulative_sc_tx_commitment_tree_root: *end_cumulative_sc_tx_commitment_tree_root,
+            btr_fee,
+            ft_min_amount,
+        })
+    }
+
+    pub fn add_cert_leaf(&mut self, sc_id: &FieldElement, leaf: &FieldElement) -> bool {
+        self.add(CommitmentTreeTx::CertLeaf {
+            sc_id: *sc_id,
+            leaf: *leaf,
+        })
+    }
+
+    pub fn add_csw(
+        &mut self,
+        sc_id: &FieldElement,
+        amount: u64,
+        nullifier: &FieldElement,
+        mc_pk_hash: &[u8; MC_PK_SIZE],
+    ) -> bool {
+        self.add(CommitmentTreeTx::Csw {
+            sc_id: *sc_id,
+            amount,
+            nullifier: *nullifier,
+            mc_pk_hash: *mc_pk_hash,
+        })
+    }
+
+    // Commitment of the sidechain `sc_id`, recomputed only if a transaction
+    // for it has been added since the last call. In that case, only the roots
+    // of the sub-trees touched since then are recomputed.
+    pub fn get_sc_commitment(&mut self, sc_id: &FieldElement) -> Option<FieldElement> {
+        self.sc_caches
+            .get_mut(sc_id)?
+            .commitment(&mut self.tree, sc_id)
+            .ok()
+    }
+
+    // Overall commitment, recomputed only if a transaction has been added since the last call.
+    // In that case, only the commitments of the sidechains touched since then are recomputed.
+    pub fn get_commitment(&mut self) -> Option<FieldElement> {
+        if self.commitment.is_none() {
+            self.commitment = self.compute_commitment().ok();
+        }
+        self.commitment
+    }
+
+    fn compute_commitment(&mut self) -> Result<FieldElement, Error> {
+        let mut mht = new_ginger_mht(CMT_MT_HEIGHT, 1 << CMT_MT_HEIGHT)?;
+        for (sc_id, cache) in self.sc_caches.iter_mut() {
+            let sc_commitment = cache.commitment(&mut self.tree, sc_id)?;
+            append_leaf_to_ginger_mht(&mut mht, &sc_commitment)?;
+        }
+        finalize_ginger_mht_in_place(&mut mht)?;
+        Ok(get_ginger_mht_root(&mht).ok_or("Unable to get commitment tree root")?)
+    }
+
+    // Read-only getters of the underlying tree. The transactions are added only through the
+    // functions above, so that they are recorded in the state.
+
+    pub fn get_scc(&mut self, sc_id: &FieldElement) -> Option<FieldElement> {
+        self.tree.get_scc(sc_id)
+    }
+
+    fn get_sub_tree_root(
+        &mut self,
+        tx_type: CommitmentTreeTxType,
+        sc_id: &FieldElement,
+    ) -> Option<FieldElement> {
+        self.sc_caches
+            .get_mut(sc_id)?
+            .sub_tree_root(&mut self.tree, sc_id, tx_type)
+    }
+
+    pub fn get_fwt_commitment(&mut self, sc_id: &FieldElement) -> Option<FieldElement> {
+        self.get_sub_tree_root(CommitmentTreeTxType::Fwt, sc_id)
+    }
+
+    pub fn get_bwtr_commitment(&mut self, sc_id: &FieldElement) -> Option<FieldElement> {
+        self.get_sub_tree_root(CommitmentTreeTxType::Bwtr, sc_id)
+    }
+
+    pub fn get_cert_commitment(&mut self, sc_id: &FieldElement) -> Option<FieldElement> {
+        self.get_sub_tree_root(CommitmentTreeTxType::Cert, sc_id)
+    }
+
+    pub fn get_csw_commitment(&mut self, sc_id: &FieldElement) -> Option<FieldElement> {
+        self.get_sub_tree_root(CommitmentTreeTxType::Csw, sc_id)
+    }
+
+    pub fn get_cert_leaves(&mut self, sc_id: &FieldElement) -> Option<Vec<FieldElement>> {
+        self.tree.get_cert_leaves(sc_id)
+    }
+
+    pub fn get_sc_existence_proof(&mut self, sc_id: &FieldElement) -> Option<ScExistenceProof> {
+        self.tree.get_sc_existence_proof(sc_id)
+    }
+
+    pub fn get_sc_absence_proof(&mut self, sc_id: &FieldElement) -> Option<ScAbsenceProof> {
+        self.tree.get_sc_absence_proof(sc_id)
+    }
+
+    // Inclusion proof of the `index`-th transaction of type `tx_type` of the sidechain `sc_id`.
+    // The sub-tree of the transaction is rebuilt from the transactions added so far, and its
+    // root must match the one of the underlying tree.
+    pub fn get_tx_inclusion_proof(
+        &mut self,
+        tx_type: CommitmentTreeTxType,
+        sc_id: &FieldElement,
+        index: usize,
+    ) -> Result<TxInclusionProof, Error> {
+        let leaves = match tx_type {
+            CommitmentTreeTxType::Cert => self
+                .tree
+                .get_cert_leaves(sc_id)
+                .ok_or("No certificates for the sidechain")?,
+            _ => self
+                .txs
+                .iter()
+                .filter(|tx| tx.sc_id() == sc_id && tx.tx_type() == Some(tx_type))
+                .map(|tx| tx.leaf())
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        let leaf = *leaves
+            .get(index)
+            .ok_or(format!("Transaction index out of range: {}", index))?;
+
+        let height = tx_type.sub_tree_height();
+        let mut sub_tree = new_ginger_mht(height, 1 << height)?;
+        for leaf in leaves.iter() {
+            append_leaf_to_ginger_mht(&mut sub_tree, leaf)?;
+        }
+        finalize_ginger_mht_in_place(&mut sub_tree)?;
+        let leaf_path =
+            get_ginger_mht_path(&sub_tree, index as u64).ok_or("Unable to get leaf path")?;
+
+        let fwt_root = self.get_fwt_commitment(sc_id);
+        let bwtr_root = self.get_bwtr_commitment(sc_id);
+        let cert_root = self.get_cert_commitment(sc_id);
+        let csw_root = self.get_csw_commitment(sc_id);
+        let sub_tree_root = match tx_type {
+            CommitmentTreeTxType::Fwt => fwt_root,
+            CommitmentTreeTxType::Bwtr => bwtr_root,
+            CommitmentTreeTxType::Cert => cert_root,
+            CommitmentTreeTxType::Csw => csw_root,
+        };
+        if sub_tree_root != get_ginger_mht_root(&sub_tree) {
+            Err("Sub-tree root mismatch")?
+        }
+
+        let sc_data = match tx_type {
+            CommitmentTreeTxType::Csw => vec![],
+            _ => {
+                let mut sc_data = vec![
+                    fwt_root.ok_or("Missing forward transfers root")?,
+                    bwtr_root.ok_or("Missing backward transfer requests root")?,
+                    cert_root.ok_or("Missing certificates root")?,
+                ];
+                sc_data.remove(tx_type as usize);
+                sc_data.push(self.tree.get_scc(sc_id).unwrap_or_else(FieldElement::zero));
+                sc_data
+            }
+        };
+        let sc_existence_proof = self
+            .tree
+            .get_sc_existence_proof(sc_id)
+            .ok_or("Unable to get sidechain existence proof")?;
+
+        let proof = TxInclusionProof::new(
+            tx_type,
+            *sc_id,
+            leaf,
+            leaf_path,
+            sc_data,
+            sc_existence_proof,
+        )?;
+        if Some(proof.compute_sc_commitment()?) != self.get_sc_commitment(sc_id) {
+            Err("Sidechain commitment mismatch")?
+        }
+        Ok(proof)
+    }
+
+    pub fn serialize_state(&self) -> Result<Vec<u8>, Error> {
+        let mut w = StateWriter(begin_frame(&STATE_MAGIC, COMMITMENT_TREE_STATE_VERSION, 4));
+        w.u32(self.txs.len() as u32);
+        for tx in self.txs.iter() {
+            tx.write(&mut w)?;
+        }
+
+        Ok(end_frame(w.0))
+    }
+
+    pub fn deserialize_state(state: &[u8]) -> Result<Self, Error> {
+        let mut r = StateReader(open_frame(
+            state,
+            &STATE_MAGIC,
+            COMMITMENT_TREE_STATE_VERSION,
+            "commitment tree state",
+        )?);
+
+        let mut tree = Self::create();
+        for i in 0..r.u32()? {
+            let tx = CommitmentTreeTx::read(&mut r)?;
+            if !tree.add(tx) {
+                Err(format!("Unable to add transaction {}", i))?
+            }
+        }
+        if !r.0.is_empty() {
+            Err("Unexpected trailing data in state")?
+        }
+
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    fn add_random_txs(
+        tree: &mut IncrementalCommitmentTree,
+        sc_id: &FieldElement,
+        rng: &mut XorShiftRng,
+    ) {
+        assert!(tree.add_scc(
+            sc_id,
+            rng.gen(),
+            &rng.gen(),
+            &rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            Some(&[1, 2, 3]),
+            Some(&[BitVectorElementsConfig {
+                bit_vector_size_bits: 254 * 8,
+                max_compressed_byte_size: 4000,
+            }]),
+            rng.gen(),
+            rng.gen(),
+            Some(&[7u8; 50]),
+            Some(&FieldElement::rand(rng)),
+            &[1u8; 100],
+            None,
+        ));
+        for _ in 0..5 {
+            assert!(tree.add_fwt(sc_id, rng.gen(), &rng.gen(), &rng.gen(), &rng.gen(), rng.gen()));
+        }
+        let request_data = vec![FieldElement::rand(rng), FieldElement::rand(rng)];
+        assert!(tree.add_bwtr(
+            sc_id,
+            rng.gen(),
+            request_data.iter().collect(),
+            &rng.gen(),
+            &rng.gen(),
+            rng.gen()
+        ));
+        let bt_list = vec![BackwardTransfer {
+            pk_dest: rng.gen(),
+            amount: rng.gen(),
+        }];
+        let custom_fields = vec![FieldElement::rand(rng)];
+        assert!(tree.add_cert(
+            sc_id,
+            rng.gen(),
+            rng.gen(),
+            Some(&bt_list),
+            Some(custom_fields.iter().collect()),
+            &FieldElement::rand(rng),
+            rng.gen(),
+            rng.gen()
+        ));
+        assert!(tree.add_cert_leaf(sc_id, &FieldElement::rand(rng)));
+    }
+
+    #[test]
+    fn commitment_tree_state_export() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let mut tree = IncrementalCommitmentTree::create();
+        let sc_ids = (0..3)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        for sc_id in sc_ids.iter() {
+            add_random_txs(&mut tree, sc_id, rng);
+        }
+        let ceased_sc_id = FieldElement::rand(rng);
+        assert!(tree.add_csw(
+            &ceased_sc_id,
+            rng.gen(),
+            &FieldElement::rand(rng),
+            &rng.gen()
+        ));
+
+        let commitment = tree.get_commitment().unwrap();
+        let sc_commitment = tree.get_sc_commitment(&sc_ids[0]).unwrap();
+
+        // Restored tree has the same commitments
+        let state = tree.serialize_state().unwrap();
+        let mut restored = IncrementalCommitmentTree::deserialize_state(&state).unwrap();
+        assert_eq!(restored.num_transactions(), tree.num_transactions());
+        assert_eq!(restored.get_commitment().unwrap(), commitment);
+        assert_eq!(restored.get_sc_commitment(&sc_ids[0]).unwrap(), sc_commitment);
+        for sc_id in sc_ids.iter() {
+            assert_eq!(
+                restored.get_fwt_commitment(sc_id),
+                tree.get_fwt_commitment(sc_id)
+            );
+        }
+
+        // Adding a transaction invalidates only its sub-tree and the commitments it's part of
+        assert!(tree.add_fwt(&sc_ids[1], 10, &rng.gen(), &rng.gen(), &rng.gen(), 0));
+        assert!(tree.commitment.is_none());
+        assert!(tree.sc_caches[&sc_ids[0]].commitment.is_some());
+        let touched = &tree.sc_caches[&sc_ids[1]];
+        assert!(touched.commitment.is_none());
+        assert!(touched.sub_tree_roots[CommitmentTreeTxType::Fwt as usize].is_none());
+        assert!(touched.sub_tree_roots[CommitmentTreeTxType::Bwtr as usize].is_some());
+        assert!(touched.sub_tree_roots[CommitmentTreeTxType::Cert as usize].is_some());
+        assert!(touched.scc.is_some());
+        assert_eq!(tree.get_sc_commitment(&sc_ids[0]).unwrap(), sc_commitment);
+        assert_ne!(tree.get_commitment().unwrap(), commitment);
+
+        let mut reference = CommitmentTree::create();
+        for tx in tree.txs.iter() {
+            assert!(tx.apply(&mut reference));
+        }
+        assert_eq!(tree.get_commitment(), reference.get_commitment());
+        for sc_id in sc_ids.iter().chain(std::iter::once(&ceased_sc_id)) {
+            assert_eq!(
+                tree.get_sc_commitment(sc_id),
+                reference.get_sc_commitment(sc_id)
+            );
+            assert_eq!(
+                tree.get_csw_commitment(sc_id),
+                reference.get_csw_commitment(sc_id)
+            );
+        }
+
+        // Failed additions are not recorded
+        let num_txs = tree.num_transactions();
+        assert!(!tree.add_csw(&sc_ids[0], 1, &FieldElement::rand(rng), &rng.gen()));
+        assert_eq!(tree.num_transactions(), num_txs);
+
+        // Corrupted states
+        for i in (0..state.len()).step_by(7) {
+            let mut corrupted = state.clone();
+            corrupted[i] ^= 1;
+            assert!(IncrementalCommitmentTree::deserialize_state(&corrupted).is_err());
+        }
+        assert!(IncrementalCommitmentTree::deserialize_state(&state[1..]).is_err());
+        assert!(IncrementalCommitmentTree::deserialize_state(&[]).is_err());
+
+        // Empty tree
+        let empty = IncrementalCommitmentTree::create();
+        let restored_empty =
+            IncrementalCommitmentTree::deserialize_state(&empty.serialize_state().unwrap())
+                .unwrap();
+        assert_eq!(restored_empty.num_transactions(), 0);
+        assert_eq!(
+            IncrementalCommitmentTree::create().get_commitment(),
+            CommitmentTree::create().get_commitment()
+        );
+    }
+
+    #[test]
+    fn commitment_tree_tx_inclusion_proofs() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let mut tree = IncrementalCommitmentTree::create();
+        let sc_ids = (0..2)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        for sc_id in sc_ids.iter() {
+            add_random_txs(&mut tree, sc_id, rng);
+        }
+        let ceased_sc_id = FieldElement::rand(rng);
+        for _ in 0..2 {
+            assert!(tree.add_csw(
+                &ceased_sc_id,
+                rng.gen(),
+                &FieldElement::rand(rng),
+                &rng.gen()
+            ));
+        }
+        let commitment = tree.get_commitment().unwrap();
+
+        let mut proofs = vec![];
+        for sc_id in sc_ids.iter() {
+            for (tx_type, num_txs) in [
+                (CommitmentTreeTxType::Fwt, 5),
+                (CommitmentTreeTxType::Bwtr, 1),
+                (CommitmentTreeTxType::Cert, 2),
+            ]
+            .iter()
+            {
+                for i in 0..*num_txs {
+                    proofs.push(tree.get_tx_inclusion_proof(*tx_type, sc_id, i).unwrap());
+                }
+                assert!(tree.get_tx_inclusion_proof(*tx_type, sc_id, *num_txs).is_err());
+            }
+            assert!(tree
+                .get_tx_inclusion_proof(CommitmentTreeTxType::Csw, sc_id, 0)
+                .is_err());
+        }
+        for i in 0..2 {
+            proofs.push(
+                tree.get_tx_inclusion_proof(CommitmentTreeTxType::Csw, &ceased_sc_id, i)
+                    .unwrap(),
+            );
+        }
+
+        for proof in proofs.iter() {
+            let (tx_type, sc_id, leaf) = (proof.tx_type(), proof.sc_id(), proof.leaf());
+            assert!(proof.verify(tx_type, sc_id, leaf, &commitment).unwrap());
+            assert!(!proof
+                .verify(tx_type, sc_id, leaf, &FieldElement::rand(rng))
+                .unwrap());
+
+            // Transaction other than the proven one
+            let other_tx_type = CommitmentTreeTxType::from_u8((tx_type as u8 + 1) % 4).unwrap();
+            assert!(!proof
+                .verify(other_tx_type, sc_id, leaf, &commitment)
+                .unwrap());
+            assert!(!proof
+                .verify(tx_type, &FieldElement::rand(rng), leaf, &commitment)
+                .unwrap());
+            assert!(!proof
+                .verify(tx_type, sc_id, &FieldElement::rand(rng), &commitment)
+                .unwrap());
+
+            // Serialization round trip
+            let proof_bytes = proof.to_bytes().unwrap();
+            let deserialized = TxInclusionProof::from_bytes(&proof_bytes).unwrap();
+            assert_eq!(deserialized.tx_type(), proof.tx_type());
+            assert_eq!(deserialized.sc_id(), proof.sc_id());
+            assert_eq!(deserialized.leaf(), proof.leaf());
+            assert_eq!(deserialized.leaf_index(), proof.leaf_index());
+            assert!(deserialized
+                .verify(tx_type, sc_id, leaf, &commitment)
+                .unwrap());
+            assert!(TxInclusionProof::from_bytes(&proof_bytes[1..]).is_err());
+            assert!(TxInclusionProof::from_bytes(&proof_bytes[..proof_bytes.len() - 1]).is_err());
+
+            // Tampered leaf
+            let mut tampered = proof_bytes.clone();
+            tampered[1 + FIELD_SIZE..1 + 2 * FIELD_SIZE]
+                .copy_from_slice(&serialize_to_buffer(&FieldElement::rand(rng), None).unwrap());
+            let tampered = TxInclusionProof::from_bytes(&tampered).unwrap();
+            assert!(!tampered.verify(tx_type, sc_id, leaf, &commitment).unwrap());
+            assert!(!tampered
+                .verify(tx_type, sc_id, tampered.leaf(), &commitment)
+                .unwrap());
+        }
+        assert_eq!(proofs[3].leaf_index(), 3);
+
+        // The leaves of the proofs are the ones computed on the fields of the transactions
+        let fwt_leaves = tree
+            .txs
+            .iter()
+            .filter(|tx| {
+                tx.sc_id() == &sc_ids[0] && tx.tx_type() == Some(CommitmentTreeTxType::Fwt)
+            })
+            .map(|tx| tx.leaf().unwrap())
+            .collect::<Vec<_>>();
+        for (i, leaf) in fwt_leaves.iter().enumerate() {
+            assert!(proofs[i]
+                .verify(CommitmentTreeTxType::Fwt, &sc_ids[0], leaf, &commitment)
+                .unwrap());
+        }
+
+        // Proofs are no longer valid once the tree changes
+        assert!(tree.add_fwt(&sc_ids[0], 10, &rng.gen(), &rng.gen(), &rng.gen(), 0));
+        let new_commitment = tree.get_commitment().unwrap();
+        let new_proof = tree
+            .get_tx_inclusion_proof(CommitmentTreeTxType::Fwt, &sc_ids[0], 5)
+            .unwrap();
+        assert!(!proofs[0]
+            .verify(
+                CommitmentTreeTxType::Fwt,
+                &sc_ids[0],
+                proofs[0].leaf(),
+                &new_commitment
+            )
+            .unwrap());
+        assert!(new_proof
+            .verify(
+                CommitmentTreeTxType::Fwt,
+                &sc_ids[0],
+                new_proof.leaf(),
+                &new_commitment
+            )
+            .unwrap());
+    }
+}
@@ -0,0 +1,936 @@
+use algebra::{serialize::*, SemanticallyValid};
+use cctp_primitives::{
+    commitment_tree::proofs::{ScAbsenceProof, ScCommitmentData, ScExistenceProof, ScNeighbour},
+    utils::{data_structures::BackwardTransfer, mht::get_leaf_index_from_path, serialization::*},
+};
+use demo_circuit::type_mapping::*;
+use primitives::merkle_tree::field_based_mht::FieldBasedMHTPath;
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{CommitmentTreeTxType, MerkleMultiProof, TxInclusionProof, SPARSE_MHT_MAX_HEIGHT};
+
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+//*****************************JSON representation**************************************************
+//
+// Human readable representation of the public types, for logs, REST APIs and config files.
+// Each object is a JSON object holding the name of its type ("type"), the version of the
+// representation ("version") and the named fields of the type, in camelCase:
+//
+//     {"type": "SchnorrPublicKey", "version": 1, "publicKey": "a3f1..."}
+//
+// Binary data is hex encoded with the same serialization of the JNI API (public keys and VRF
+// proofs compressed), and decoding performs the same semantic checks of binary deserialization.
+// Secret keys have no JSON representation, so that they can't end up in logs by mistake.
+//
+// The same Rust type may have more than one representation (e.g. Schnorr and VRF public keys are
+// both points of G2), so representations are implemented by the marker types below rather than
+// by the represented types:
+//
+//     let json = SchnorrPublicKeyJson::to_json(&pk)?;
+//     let pk = SchnorrPublicKeyJson::from_json(&json)?;
+//
+// Each marker can also be used as a serde `with` module for fields of serializable structs:
+//
+//     #[serde(with = "SchnorrPublicKeyJson")]
+//     signer: SchnorrPk,
+//
+// and `VecJson` does the same for vectors:
+//
+//     #[serde(with = "VecJson::<BackwardTransferJson>")]
+//     bt_list: Vec<BackwardTransfer>,
+
+/// Version of the JSON representation, bumped on incompatible changes of any type.
+pub const JSON_VERSION: u64 = 1;
+
+pub trait JsonFormat {
+    /// Represented type.
+    type Object;
+
+    /// Value of the "type" field.
+    const JSON_TYPE: &'static str;
+
+    /// Named fields of `obj`, excluding "type" and "version".
+    fn to_json_fields(obj: &Self::Object) -> Result<Map<String, JsonValue>, Error>;
+
+    /// Read the object from its named fields. Fields not belonging to the type are ignored.
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<Self::Object, Error>;
+
+    fn to_json_value(obj: &Self::Object) -> Result<JsonValue, Error> {
+        let mut json = Map::new();
+        json.insert("type".to_owned(), Self::JSON_TYPE.into());
+        json.insert("version".to_owned(), JSON_VERSION.into());
+        json.extend(Self::to_json_fields(obj)?);
+        Ok(json.into())
+    }
+
+    /// Fails if `json` is not an object of type `JSON_TYPE` and version `JSON_VERSION`.
+    fn from_json_value(json: &JsonValue) -> Result<Self::Object, Error> {
+        let fields = json
+            .as_object()
+            .ok_or_else(|| format!("{} JSON must be an object", Self::JSON_TYPE))?;
+
+        match fields.get("type").and_then(JsonValue::as_str) {
+            Some(json_type) if json_type == Self::JSON_TYPE => {}
+            Some(json_type) => Err(format!(
+                "Expected {} JSON, found {}",
+                Self::JSON_TYPE,
+                json_type
+            ))?,
+            None => Err(format!("Missing type of {} JSON", Self::JSON_TYPE))?,
+        }
+        match fields.get("version").and_then(JsonValue::as_u64) {
+            Some(JSON_VERSION) => {}
+            Some(version) => Err(format!(
+                "Unsupported version {} of {} JSON, expected {}",
+                version,
+                Self::JSON_TYPE,
+                JSON_VERSION
+            ))?,
+            None => Err(format!("Missing version of {} JSON", Self::JSON_TYPE))?,
+        }
+
+        Self::from_json_fields(fields)
+    }
+
+    fn to_json(obj: &Self::Object) -> Result<String, Error> {
+        Ok(serde_json::to_string(&Self::to_json_value(obj)?)?)
+    }
+
+    fn to_json_pretty(obj: &Self::Object) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&Self::to_json_value(obj)?)?)
+    }
+
+    fn from_json(json: &str) -> Result<Self::Object, Error> {
+        Self::from_json_value(&serde_json::from_str(json)?)
+    }
+
+    /// Serde `serialize_with` function.
+    fn serialize<S: Serializer>(obj: &Self::Object, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::to_json_value(obj)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Serde `deserialize_with` function.
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self::Object, D::Error> {
+        Self::from_json_value(&JsonValue::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+fn to_hex<T: CanonicalSerialize>(obj: &T, compressed: Option<bool>) -> Result<String, Error> {
+    Ok(hex::encode(serialize_to_buffer(obj, compressed)?))
+}
+
+fn from_hex<T: CanonicalDeserialize + SemanticallyValid>(
+    hex_str: &str,
+    compressed: Option<bool>,
+) -> Result<T, Error> {
+    deserialize_from_buffer(&hex::decode(hex_str)?, Some(true), compressed)
+}
+
+// For types without semantic checks, deserialized as in the JNI API
+fn from_hex_unchecked<T: CanonicalDeserialize>(hex_str: &str) -> Result<T, Error> {
+    let bytes = hex::decode(hex_str)?;
+    let mut reader = bytes.as_slice();
+    let obj = T::deserialize(&mut reader)?;
+    if !reader.is_empty() {
+        Err("Unexpected trailing bytes")?
+    }
+    Ok(obj)
+}
+
+fn field_element_from_hex(hex_str: &str) -> Result<FieldElement, Error> {
+    if hex_str.len() != 2 * FIELD_SIZE {
+        Err(format!(
+            "Field elements are {} bytes long, found {} hex digits",
+            FIELD_SIZE,
+            hex_str.len()
+        ))?
+    }
+    from_hex(hex_str, None)
+}
+
+fn get_field<'a>(fields: &'a Map<String, JsonValue>, name: &str) -> Result<&'a JsonValue, Error> {
+    fields
+        .get(name)
+        .ok_or_else(|| format!("Missing field {}", name).into())
+}
+
+fn get_str_field<'a>(fields: &'a Map<String, JsonValue>, name: &str) -> Result<&'a str, Error> {
+    get_field(fields, name)?
+        .as_str()
+        .ok_or_else(|| format!("Field {} must be a string", name).into())
+}
+
+fn get_u64_field(fields: &Map<String, JsonValue>, name: &str) -> Result<u64, Error> {
+    get_field(fields, name)?
+        .as_u64()
+        .ok_or_else(|| format!("Field {} must be an unsigned integer", name).into())
+}
+
+fn get_array_field<'a>(
+    fields: &'a Map<String, JsonValue>,
+    name: &str,
+) -> Result<&'a Vec<JsonValue>, Error> {
+    get_field(fields, name)?
+        .as_array()
+        .ok_or_else(|| format!("Field {} must be an array", name).into())
+}
+
+fn single_field<V: Into<JsonValue>>(name: &str, value: V) -> Map<String, JsonValue> {
+    let mut fields = Map::new();
+    fields.insert(name.to_owned(), value.into());
+    fields
+}
+
+fn field_elements_to_json(fes: &[FieldElement]) -> Result<JsonValue, Error> {
+    Ok(fes
+        .iter()
+        .map(|fe| to_hex(fe, None))
+        .collect::<Result<Vec<_>, _>>()?
+        .into())
+}
+
+fn field_elements_from_json(
+    fields: &Map<String, JsonValue>,
+    name: &str,
+) -> Result<Vec<FieldElement>, Error> {
+    get_array_field(fields, name)?
+        .iter()
+        .map(|fe| {
+            field_element_from_hex(
+                fe.as_str()
+                    .ok_or_else(|| format!("Elements of {} must be strings", name))?,
+            )
+        })
+        .collect()
+}
+
+// Nodes of a Merkle path: [{"sibling": <hex>, "isRight": <bool>}, ...]
+fn path_to_json(path: &GingerMHTPath) -> Result<JsonValue, Error> {
+    Ok(path
+        .get_raw_path()
+        .iter()
+        .map(|(sibling, is_right)| {
+            let mut node = single_field("sibling", to_hex(sibling, None)?);
+            node.insert("isRight".to_owned(), (*is_right).into());
+            Ok(node.into())
+        })
+        .collect::<Result<Vec<JsonValue>, Error>>()?
+        .into())
+}
+
+fn path_from_json(fields: &Map<String, JsonValue>, name: &str) -> Result<GingerMHTPath, Error> {
+    let path = get_array_field(fields, name)?
+        .iter()
+        .map(|node| {
+            let node = node
+                .as_object()
+                .ok_or("Merkle path nodes must be objects")?;
+            let sibling = field_element_from_hex(get_str_field(node, "sibling")?)?;
+            let is_right = get_field(node, "isRight")?
+                .as_bool()
+                .ok_or("Field isRight must be a boolean")?;
+            Ok((sibling, is_right))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(GingerMHTPath::new(path))
+}
+
+/// Serde `with` module for vectors of objects with the representation `F`, as a JSON array.
+pub struct VecJson<F>(PhantomData<F>);
+
+impl<F: JsonFormat> VecJson<F> {
+    /// Serde `serialize_with` function.
+    pub fn serialize<S: Serializer>(objs: &[F::Object], serializer: S) -> Result<S::Ok, S::Error> {
+        objs.iter()
+            .map(F::to_json_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Serde `deserialize_with` function.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<F::Object>, D::Error> {
+        Vec::<JsonValue>::deserialize(deserializer)?
+            .iter()
+            .map(F::from_json_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(D::Error::custom)
+    }
+}
+
+// Representation made of a single hex encoded field
+macro_rules! hex_json_format {
+    ($(#[$doc:meta])* $format:ident, $type:ty, $json_type:expr, $field:expr, $compressed:expr) => {
+        $(#[$doc])*
+        pub struct $format;
+
+        impl JsonFormat for $format {
+            type Object = $type;
+
+            const JSON_TYPE: &'static str = $json_type;
+
+            fn to_json_fields(obj: &$type) -> Result<Map<String, JsonValue>, Error> {
+                Ok(single_field($field, to_hex(obj, $compressed)?))
+            }
+
+            fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<$type, Error> {
+                from_hex(get_str_field(fields, $field)?, $compressed)
+            }
+        }
+    };
+}
+
+/// `{"type": "FieldElement", "version": 1, "value": <hex>}`
+pub struct FieldElementJson;
+
+impl JsonFormat for FieldElementJson {
+    type Object = FieldElement;
+
+    const JSON_TYPE: &'static str = "FieldElement";
+
+    fn to_json_fields(obj: &FieldElement) -> Result<Map<String, JsonValue>, Error> {
+        Ok(single_field("value", to_hex(obj, None)?))
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<FieldElement, Error> {
+        field_element_from_hex(get_str_field(fields, "value")?)
+    }
+}
+
+hex_json_format!(
+    /// `{"type": "SchnorrPublicKey", "version": 1, "publicKey": <compressed hex>}`
+    SchnorrPublicKeyJson,
+    SchnorrPk,
+    "SchnorrPublicKey",
+    "publicKey",
+    Some(true)
+);
+
+hex_json_format!(
+    /// `{"type": "SchnorrSignature", "version": 1, "signature": <hex>}`
+    SchnorrSignatureJson,
+    SchnorrSig,
+    "SchnorrSignature",
+    "signature",
+    None
+);
+
+hex_json_format!(
+    /// `{"type": "VRFPublicKey", "version": 1, "publicKey": <compressed hex>}`
+    VRFPublicKeyJson,
+    VRFPk,
+    "VRFPublicKey",
+    "publicKey",
+    Some(true)
+);
+
+hex_json_format!(
+    /// `{"type": "VRFProof", "version": 1, "proof": <compressed hex>}`
+    VRFProofJson,
+    VRFProof,
+    "VRFProof",
+    "proof",
+    Some(true)
+);
+
+/// `{"type": "MerklePath", "version": 1, "path": [{"sibling": <hex>, "isRight": <bool>}, ...]}`,
+/// from the leaf up to the root. `isRight` tells if the node on the path (not its sibling) is a
+/// right child.
+pub struct MerklePathJson;
+
+impl JsonFormat for MerklePathJson {
+    type Object = GingerMHTPath;
+
+    const JSON_TYPE: &'static str = "MerklePath";
+
+    fn to_json_fields(obj: &GingerMHTPath) -> Result<Map<String, JsonValue>, Error> {
+        Ok(single_field("path", path_to_json(obj)?))
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<GingerMHTPath, Error> {
+        path_from_json(fields, "path")
+    }
+}
+
+/// `{"type": "SparseMerkleProof", "version": 1, "position": <leaf position>, "path": [...]}`,
+/// membership or non membership proof of a sparse Merkle tree, with the path as in
+/// `MerklePathJson`. The position is determined by the path, and must be consistent with it.
+pub struct SparseMerkleProofJson;
+
+impl JsonFormat for SparseMerkleProofJson {
+    type Object = GingerMHTPath;
+
+    const JSON_TYPE: &'static str = "SparseMerkleProof";
+
+    fn to_json_fields(obj: &GingerMHTPath) -> Result<Map<String, JsonValue>, Error> {
+        let mut fields = single_field("position", get_leaf_index_from_path(obj));
+        fields.insert("path".to_owned(), path_to_json(obj)?);
+        Ok(fields)
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<GingerMHTPath, Error> {
+        let position = get_u64_field(fields, "position")?;
+        let path = path_from_json(fields, "path")?;
+        if path.get_length() > SPARSE_MHT_MAX_HEIGHT as usize {
+            Err(format!("Path too long: {}", path.get_length()))?
+        }
+        if position != get_leaf_index_from_path(&path) {
+            Err(format!(
+                "Position {} doesn't match the path, leading to {}",
+                position,
+                get_leaf_index_from_path(&path)
+            ))?
+        }
+        Ok(path)
+    }
+}
+
+/// `{"type": "MerkleMultiProof", "version": 1, "height": <height>, "leafIndices": [<index>, ...],
+/// "nodes": [<hex>, ...]}`, with the nodes in the order of `MerkleMultiProof`.
+pub struct MerkleMultiProofJson;
+
+impl JsonFormat for MerkleMultiProofJson {
+    type Object = MerkleMultiProof;
+
+    const JSON_TYPE: &'static str = "MerkleMultiProof";
+
+    fn to_json_fields(obj: &MerkleMultiProof) -> Result<Map<String, JsonValue>, Error> {
+        let mut fields = single_field("height", obj.height());
+        fields.insert("leafIndices".to_owned(), obj.leaf_indices().into());
+        fields.insert("nodes".to_owned(), field_elements_to_json(obj.nodes())?);
+        Ok(fields)
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<MerkleMultiProof, Error> {
+        let height: u8 = get_u64_field(fields, "height")?
+            .try_into()
+            .map_err(|_| "Field height out of range")?;
+        let leaf_indices = get_array_field(fields, "leafIndices")?
+            .iter()
+            .map(|index| {
+                index
+                    .as_u64()
+                    .ok_or_else(|| "Leaf indices must be unsigned integers".into())
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let nodes = field_elements_from_json(fields, "nodes")?;
+        MerkleMultiProof::from_parts(height, leaf_indices, nodes)
+    }
+}
+
+/// `{"type": "ScExistenceProof", "version": 1, "path": [...]}`, with the path of the sidechain
+/// commitment in the commitment tree as in `MerklePathJson`.
+pub struct ScExistenceProofJson;
+
+impl JsonFormat for ScExistenceProofJson {
+    type Object = ScExistenceProof;
+
+    const JSON_TYPE: &'static str = "ScExistenceProof";
+
+    fn to_json_fields(obj: &ScExistenceProof) -> Result<Map<String, JsonValue>, Error> {
+        Ok(single_field("path", path_to_json(&obj.mpath)?))
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<ScExistenceProof, Error> {
+        Ok(ScExistenceProof::create(path_from_json(fields, "path")?))
+    }
+}
+
+fn sc_neighbour_to_json(neighbour: &Option<ScNeighbour>) -> Result<JsonValue, Error> {
+    Ok(match neighbour {
+        None => JsonValue::Null,
+        Some(neighbour) => {
+            let mut fields = single_field("scId", to_hex(&neighbour.id, None)?);
+            fields.insert("path".to_owned(), path_to_json(&neighbour.mpath)?);
+            fields.insert(
+                "scData".to_owned(),
+                to_hex(&neighbour.sc_data, None)?.into(),
+            );
+            fields.into()
+        }
+    })
+}
+
+fn sc_neighbour_from_json(
+    fields: &Map<String, JsonValue>,
+    name: &str,
+) -> Result<Option<ScNeighbour>, Error> {
+    match get_field(fields, name)? {
+        JsonValue::Null => Ok(None),
+        JsonValue::Object(neighbour) => Ok(Some(ScNeighbour::create(
+            field_element_from_hex(get_str_field(neighbour, "scId")?)?,
+            path_from_json(neighbour, "path")?,
+            from_hex_unchecked::<ScCommitmentData>(get_str_field(neighbour, "scData")?)?,
+        ))),
+        _ => Err(format!("Field {} must be an object or null", name))?,
+    }
+}
+
+/// `{"type": "ScAbsenceProof", "version": 1, "left": <neighbour>, "right": <neighbour>}`, with
+/// the sidechains next to the absent one in the commitment tree, or null if there are none:
+/// `{"scId": <hex>, "path": [...], "scData": <hex>}`, where `scData` is the data the commitment
+/// of the sidechain is computed from.
+pub struct ScAbsenceProofJson;
+
+impl JsonFormat for ScAbsenceProofJson {
+    type Object = ScAbsenceProof;
+
+    const JSON_TYPE: &'static str = "ScAbsenceProof";
+
+    fn to_json_fields(obj: &ScAbsenceProof) -> Result<Map<String, JsonValue>, Error> {
+        let mut fields = single_field("left", sc_neighbour_to_json(&obj.left)?);
+        fields.insert("right".to_owned(), sc_neighbour_to_json(&obj.right)?);
+        Ok(fields)
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<ScAbsenceProof, Error> {
+        Ok(ScAbsenceProof::create(
+            sc_neighbour_from_json(fields, "left")?,
+            sc_neighbour_from_json(fields, "right")?,
+        ))
+    }
+}
+
+/// `{"type": "TxInclusionProof", "version": 1, "txType": <0 to 3>, "scId": <hex>, "leaf": <hex>,
+/// "leafPath": [...], "scData": [<hex>, ...], "scExistenceProof": <ScExistenceProof JSON>}`,
+/// with the fields of `TxInclusionProof`.
+pub struct TxInclusionProofJson;
+
+impl JsonFormat for TxInclusionProofJson {
+    type Object = TxInclusionProof;
+
+    const JSON_TYPE: &'static str = "TxInclusionProof";
+
+    fn to_json_fields(obj: &TxInclusionProof) -> Result<Map<String, JsonValue>, Error> {
+        let mut fields = single_field("txType", obj.tx_type() as u8);
+        fields.insert("scId".to_owned(), to_hex(obj.sc_id(), None)?.into());
+        fields.insert("leaf".to_owned(), to_hex(obj.leaf(), None)?.into());
+        fields.insert("leafPath".to_owned(), path_to_json(obj.leaf_path())?);
+        fields.insert("scData".to_owned(), field_elements_to_json(obj.sc_data())?);
+        fields.insert(
+            "scExistenceProof".to_owned(),
+            ScExistenceProofJson::to_json_value(obj.sc_existence_proof())?,
+        );
+        Ok(fields)
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<TxInclusionProof, Error> {
+        let tx_type: u8 = get_u64_field(fields, "txType")?
+            .try_into()
+            .map_err(|_| "Field txType out of range")?;
+        TxInclusionProof::new(
+            CommitmentTreeTxType::from_u8(tx_type)?,
+            field_element_from_hex(get_str_field(fields, "scId")?)?,
+            field_element_from_hex(get_str_field(fields, "leaf")?)?,
+            path_from_json(fields, "leafPath")?,
+            field_elements_from_json(fields, "scData")?,
+            ScExistenceProofJson::from_json_value(get_field(fields, "scExistenceProof")?)?,
+        )
+    }
+}
+
+/// `{"type": "BackwardTransfer", "version": 1, "pkDest": <hex>, "amount": <amount>}`, where
+/// `pkDest` is the hash of the receiver public key on the mainchain.
+pub struct BackwardTransferJson;
+
+impl JsonFormat for BackwardTransferJson {
+    type Object = BackwardTransfer;
+
+    const JSON_TYPE: &'static str = "BackwardTransfer";
+
+    fn to_json_fields(obj: &BackwardTransfer) -> Result<Map<String, JsonValue>, Error> {
+        let mut fields = single_field("pkDest", hex::encode(obj.pk_dest));
+        fields.insert("amount".to_owned(), obj.amount.into());
+        Ok(fields)
+    }
+
+    fn from_json_fields(fields: &Map<String, JsonValue>) -> Result<BackwardTransfer, Error> {
+        let pk_dest = hex::decode(get_str_field(fields, "pkDest")?)?;
+        let pk_dest: [u8; MC_PK_SIZE] = pk_dest.as_slice().try_into().map_err(|_| {
+            format!(
+                "Field pkDest must be {} bytes long, found {}",
+                MC_PK_SIZE,
+                pk_dest.len()
+            )
+        })?;
+        Ok(BackwardTransfer {
+            pk_dest,
+            amount: get_u64_field(fields, "amount")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        get_random_field_element, schnorr_generate_key, schnorr_get_public_key, schnorr_sign,
+        verify_sparse_mht_membership_proof, vrf_generate_key, vrf_get_public_key,
+        vrf_proof_to_hash, vrf_prove, InMemoryOptimizedMerkleTree, IncrementalCommitmentTree,
+        SparseMerkleTree,
+    };
+    use cctp_primitives::{
+        commitment_tree::CommitmentTree,
+        utils::{data_structures::*, mht::*},
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn field_element_json() {
+        let fe = get_random_field_element(1);
+        let json = FieldElementJson::to_json(&fe).unwrap();
+        let value: JsonValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["type"], "FieldElement");
+        assert_eq!(value["version"], JSON_VERSION);
+        assert_eq!(value["value"], to_hex(&fe, None).unwrap());
+        assert_eq!(FieldElementJson::from_json(&json).unwrap(), fe);
+
+        // Pretty printing and unknown fields don't matter
+        let mut value = FieldElementJson::to_json_value(&fe).unwrap();
+        value["comment"] = "sidechain id".into();
+        assert_eq!(FieldElementJson::from_json_value(&value).unwrap(), fe);
+        let pretty = FieldElementJson::to_json_pretty(&fe).unwrap();
+        assert_eq!(FieldElementJson::from_json(&pretty).unwrap(), fe);
+
+        // Wrong length, not hex, not a string
+        let hex_fe = to_hex(&fe, None).unwrap();
+        for wrong_value in [
+            JsonValue::from(&hex_fe[2..]),
+            JsonValue::from(hex_fe.replace(&hex_fe[..2], "zz")),
+            JsonValue::from(1),
+        ]
+        .iter()
+        {
+            let mut value = FieldElementJson::to_json_value(&fe).unwrap();
+            value["value"] = wrong_value.clone();
+            assert!(FieldElementJson::from_json_value(&value).is_err());
+        }
+
+        // Not in the field
+        let mut value = FieldElementJson::to_json_value(&fe).unwrap();
+        value["value"] = "ff".repeat(FIELD_SIZE).into();
+        assert!(FieldElementJson::from_json_value(&value).is_err());
+
+        assert!(FieldElementJson::from_json("not json").is_err());
+        assert!(FieldElementJson::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn type_and_version() {
+        let (pk, _) = schnorr_generate_key();
+        let mut value = SchnorrPublicKeyJson::to_json_value(&pk).unwrap();
+        assert_eq!(value["type"], "SchnorrPublicKey");
+        assert_eq!(SchnorrPublicKeyJson::from_json_value(&value).unwrap(), pk);
+
+        // Same Rust type, different representation
+        assert!(VRFPublicKeyJson::from_json_value(&value).is_err());
+
+        value["version"] = (JSON_VERSION + 1).into();
+        assert!(SchnorrPublicKeyJson::from_json_value(&value).is_err());
+        value["version"] = "1".into();
+        assert!(SchnorrPublicKeyJson::from_json_value(&value).is_err());
+        value.as_object_mut().unwrap().remove("version");
+        assert!(SchnorrPublicKeyJson::from_json_value(&value).is_err());
+
+        let mut value = SchnorrPublicKeyJson::to_json_value(&pk).unwrap();
+        value.as_object_mut().unwrap().remove("type");
+        assert!(SchnorrPublicKeyJson::from_json_value(&value).is_err());
+    }
+
+    #[test]
+    fn schnorr_json() {
+        let (pk, sk) = schnorr_generate_key();
+        let msg = get_random_field_element(1);
+        let sig = schnorr_sign(&msg, &sk, &pk).unwrap();
+
+        let pk_json = SchnorrPublicKeyJson::to_json(&pk).unwrap();
+        let value: JsonValue = serde_json::from_str(&pk_json).unwrap();
+        assert_eq!(value["publicKey"], to_hex(&pk, Some(true)).unwrap());
+        assert_eq!(SchnorrPublicKeyJson::from_json(&pk_json).unwrap(), pk);
+        assert_eq!(
+            SchnorrPublicKeyJson::from_json(&pk_json).unwrap(),
+            schnorr_get_public_key(&sk)
+        );
+
+        let sig_json = SchnorrSignatureJson::to_json(&sig).unwrap();
+        assert_eq!(SchnorrSignatureJson::from_json(&sig_json).unwrap(), sig);
+
+        // Invalid public key
+        let mut value = SchnorrPublicKeyJson::to_json_value(&pk).unwrap();
+        value["publicKey"] = "ff".repeat(SCHNORR_PK_SIZE).into();
+        assert!(SchnorrPublicKeyJson::from_json_value(&value).is_err());
+    }
+
+    #[test]
+    fn vrf_json() {
+        let (pk, sk) = vrf_generate_key();
+        let msg = get_random_field_element(1);
+        let (proof, output) = vrf_prove(&msg, &sk, &pk).unwrap();
+
+        let pk_json = VRFPublicKeyJson::to_json(&pk).unwrap();
+        let proof_json = VRFProofJson::to_json(&proof).unwrap();
+        let pk = VRFPublicKeyJson::from_json(&pk_json).unwrap();
+        let proof = VRFProofJson::from_json(&proof_json).unwrap();
+        assert_eq!(pk, vrf_get_public_key(&sk));
+        assert_eq!(vrf_proof_to_hash(&msg, &pk, &proof).unwrap(), output);
+
+        // A VRF proof is not a Schnorr signature
+        assert!(SchnorrSignatureJson::from_json(&proof_json).is_err());
+    }
+
+    #[test]
+    fn merkle_path_json() {
+        let height = 4;
+        let leaves = (0..5).map(get_random_field_element).collect::<Vec<_>>();
+
+        let mut tree = InMemoryOptimizedMerkleTree::new(height, 1 << height).unwrap();
+        leaves.iter().for_each(|leaf| tree.append(leaf).unwrap());
+        tree.finalize_in_place().unwrap();
+        let root = get_ginger_mht_root(&tree).unwrap();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = get_ginger_mht_path(&tree, i as u64).unwrap();
+            let value = MerklePathJson::to_json_value(&path).unwrap();
+            let nodes = value["path"].as_array().unwrap();
+            assert_eq!(nodes.len(), height);
+            assert_eq!(nodes[0]["isRight"], i % 2 == 1);
+
+            let deserialized = MerklePathJson::from_json(&value.to_string()).unwrap();
+            assert_eq!(
+                serialize_to_buffer(&deserialized, None).unwrap(),
+                serialize_to_buffer(&path, None).unwrap()
+            );
+            assert!(verify_ginger_merkle_path(&deserialized, height, leaf, &root).unwrap());
+        }
+
+        let path = get_ginger_mht_path(&tree, 0).unwrap();
+        let mut value = MerklePathJson::to_json_value(&path).unwrap();
+        value["path"][0]["isRight"] = "false".into();
+        assert!(MerklePathJson::from_json_value(&value).is_err());
+        value["path"] = JsonValue::Null;
+        assert!(MerklePathJson::from_json_value(&value).is_err());
+    }
+
+    #[test]
+    fn commitment_tree_proofs_json() {
+        let sc_id = get_random_field_element(1);
+        let mut tree = CommitmentTree::create();
+        assert!(tree.add_fwt(
+            &sc_id,
+            10,
+            &[1u8; FIELD_SIZE],
+            &[2u8; MC_PK_SIZE],
+            &[3u8; FIELD_SIZE],
+            0
+        ));
+
+        let existence_proof = tree.get_sc_existence_proof(&sc_id).unwrap();
+        let json = ScExistenceProofJson::to_json(&existence_proof).unwrap();
+        assert_eq!(
+            serialize_to_buffer(&ScExistenceProofJson::from_json(&json).unwrap(), None).unwrap(),
+            serialize_to_buffer(&existence_proof, None).unwrap()
+        );
+        assert!(ScAbsenceProofJson::from_json(&json).is_err());
+
+        let absence_proof = tree
+            .get_sc_absence_proof(&get_random_field_element(2))
+            .unwrap();
+        let json = ScAbsenceProofJson::to_json(&absence_proof).unwrap();
+        assert_eq!(
+            serialize_to_buffer(&ScAbsenceProofJson::from_json(&json).unwrap(), None).unwrap(),
+            serialize_to_buffer(&absence_proof, None).unwrap()
+        );
+
+        // Named fields
+        let value = ScExistenceProofJson::to_json_value(&existence_proof).unwrap();
+        assert_eq!(
+            value["path"].as_array().unwrap().len(),
+            existence_proof.mpath.get_length()
+        );
+        let value = ScAbsenceProofJson::to_json_value(&absence_proof).unwrap();
+        let neighbour = if value["left"].is_null() {
+            &value["right"]
+        } else {
+            &value["left"]
+        };
+        assert_eq!(neighbour["scId"], to_hex(&sc_id, None).unwrap());
+
+        let mut value = ScAbsenceProofJson::to_json_value(&absence_proof).unwrap();
+        value["left"] = 1.into();
+        assert!(ScAbsenceProofJson::from_json_value(&value).is_err());
+    }
+
+    #[test]
+    fn tx_inclusion_proof_json() {
+        let sc_id = get_random_field_element(1);
+        let mut tree = IncrementalCommitmentTree::create();
+        for i in 0..3 {
+            assert!(tree.add_fwt(
+                &sc_id,
+                10 + i,
+                &[1u8; FIELD_SIZE],
+                &[2u8; MC_PK_SIZE],
+                &[3u8; FIELD_SIZE],
+                i as u32
+            ));
+        }
+        let commitment = tree.get_commitment().unwrap();
+
+        let proof = tree
+            .get_tx_inclusion_proof(CommitmentTreeTxType::Fwt, &sc_id, 2)
+            .unwrap();
+        let value = TxInclusionProofJson::to_json_value(&proof).unwrap();
+        assert_eq!(value["txType"], CommitmentTreeTxType::Fwt as u8);
+        assert_eq!(value["scId"], to_hex(&sc_id, None).unwrap());
+        assert_eq!(value["scData"].as_array().unwrap().len(), 3);
+        assert_eq!(value["scExistenceProof"]["type"], "ScExistenceProof");
+
+        let deserialized = TxInclusionProofJson::from_json(&value.to_string()).unwrap();
+        assert_eq!(deserialized.to_bytes().unwrap(), proof.to_bytes().unwrap());
+        assert_eq!(deserialized.leaf_index(), 2);
+        assert!(deserialized
+            .verify(CommitmentTreeTxType::Fwt, &sc_id, proof.leaf(), &commitment)
+            .unwrap());
+
+        // Unknown transaction type, path of the wrong height
+        let mut wrong_value = value.clone();
+        wrong_value["txType"] = 4.into();
+        assert!(TxInclusionProofJson::from_json_value(&wrong_value).is_err());
+        let mut wrong_value = value;
+        wrong_value["leafPath"].as_array_mut().unwrap().pop();
+        assert!(TxInclusionProofJson::from_json_value(&wrong_value).is_err());
+    }
+
+    #[test]
+    fn merkle_multiproof_json() {
+        let height = 5;
+        let mut tree = new_ginger_mht(height, 1 << height).unwrap();
+        let leaves = (0..20).map(get_random_field_element).collect::<Vec<_>>();
+        for leaf in leaves.iter() {
+            append_leaf_to_ginger_mht(&mut tree, leaf).unwrap();
+        }
+        finalize_ginger_mht_in_place(&mut tree).unwrap();
+        let root = get_ginger_mht_root(&tree).unwrap();
+
+        let proof = MerkleMultiProof::new(&tree, &[7, 2, 3, 19]).unwrap();
+        let value = MerkleMultiProofJson::to_json_value(&proof).unwrap();
+        assert_eq!(value["height"], height as u64);
+        assert_eq!(value["leafIndices"], serde_json::json!([2, 3, 7, 19]));
+        assert_eq!(value["nodes"].as_array().unwrap().len(), proof.num_nodes());
+
+        let deserialized = MerkleMultiProofJson::from_json(&value.to_string()).unwrap();
+        assert_eq!(deserialized, proof);
+        let proven_leaves = [leaves[2], leaves[3], leaves[7], leaves[19]];
+        assert!(deserialized.verify(&proven_leaves, &root).unwrap());
+
+        // Missing node, unsorted indices
+        let mut wrong_value = value.clone();
+        wrong_value["nodes"].as_array_mut().unwrap().pop();
+        assert!(MerkleMultiProofJson::from_json_value(&wrong_value).is_err());
+        let mut wrong_value = value;
+        wrong_value["leafIndices"] = serde_json::json!([3, 2, 7, 19]);
+        assert!(MerkleMultiProofJson::from_json_value(&wrong_value).is_err());
+    }
+
+    #[test]
+    fn sparse_merkle_proof_json() {
+        let height = 6;
+        let mut smt = SparseMerkleTree::new(height).unwrap();
+        let leaf = get_random_field_element(1);
+        smt.insert(37, &leaf).unwrap();
+        let root = smt.root().unwrap();
+
+        let proof = smt.get_membership_proof(37).unwrap();
+        let value = SparseMerkleProofJson::to_json_value(&proof).unwrap();
+        assert_eq!(value["position"], 37);
+        assert_eq!(value["path"].as_array().unwrap().len(), height as usize);
+
+        let deserialized = SparseMerkleProofJson::from_json(&value.to_string()).unwrap();
+        assert_eq!(deserialized, proof);
+        assert!(
+            verify_sparse_mht_membership_proof(&deserialized, height, 37, &leaf, &root).unwrap()
+        );
+
+        // Not a plain Merkle path, position inconsistent with the path
+        assert!(MerklePathJson::from_json_value(&value).is_err());
+        let mut wrong_value = value;
+        wrong_value["position"] = 36.into();
+        assert!(SparseMerkleProofJson::from_json_value(&wrong_value).is_err());
+    }
+
+    #[test]
+    fn backward_transfer_json() {
+        let bt = BackwardTransfer {
+            pk_dest: [7u8; MC_PK_SIZE],
+            amount: 100,
+        };
+        let value = BackwardTransferJson::to_json_value(&bt).unwrap();
+        assert_eq!(value["pkDest"], "07".repeat(MC_PK_SIZE));
+        assert_eq!(value["amount"], 100);
+        let deserialized = BackwardTransferJson::from_json(&value.to_string()).unwrap();
+        assert_eq!(deserialized.pk_dest, bt.pk_dest);
+        assert_eq!(deserialized.amount, bt.amount);
+
+        let mut wrong_value = value.clone();
+        wrong_value["pkDest"] = "07".repeat(MC_PK_SIZE + 1).into();
+        assert!(BackwardTransferJson::from_json_value(&wrong_value).is_err());
+        let mut wrong_value = value;
+        wrong_value["amount"] = (-1).into();
+        assert!(BackwardTransferJson::from_json_value(&wrong_value).is_err());
+    }
+
+    #[test]
+    fn serde_with() {
+        #[derive(Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Registration {
+            #[serde(with = "SchnorrPublicKeyJson")]
+            signing_key: SchnorrPk,
+            #[serde(with = "VRFPublicKeyJson")]
+            vrf_key: VRFPk,
+            epoch: u32,
+            #[serde(with = "VecJson::<BackwardTransferJson>")]
+            bt_list: Vec<BackwardTransfer>,
+        }
+
+        let (signing_key, _) = schnorr_generate_key();
+        let (vrf_key, _) = vrf_generate_key();
+        let json = serde_json::to_string(&Registration {
+            signing_key,
+            vrf_key,
+            epoch: 7,
+            bt_list: vec![BackwardTransfer {
+                pk_dest: [1u8; MC_PK_SIZE],
+                amount: 5,
+            }],
+        })
+        .unwrap();
+
+        let value: JsonValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["signingKey"]["type"], "SchnorrPublicKey");
+        assert_eq!(value["vrfKey"]["type"], "VRFPublicKey");
+        assert_eq!(value["btList"][0]["type"], "BackwardTransfer");
+
+        let registration: Registration = serde_json::from_str(&json).unwrap();
+        assert_eq!(registration.signing_key, signing_key);
+        assert_eq!(registration.vrf_key, vrf_key);
+        assert_eq!(registration.epoch, 7);
+        assert_eq!(registration.bt_list.len(), 1);
+        assert_eq!(registration.bt_list[0].amount, 5);
+
+        // Keys swapped
+        let swapped = json
+            .replace("\"SchnorrPublicKey\"", "\"tmp\"")
+            .replace("\"VRFPublicKey\"", "\"SchnorrPublicKey\"")
+            .replace("\"tmp\"", "\"VRFPublicKey\"");
+        assert!(serde_json::from_str::<Registration>(&swapped).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+use algebra::{serialize::*, SemanticallyValid};
+use blake2::{Blake2b, Digest};
+use demo_circuit::type_mapping::*;
+use rand::{rngs::OsRng, RngCore};
+
+use crate::secret::{
+    ct_eq, deserialize_secret, serialize_secret, Zeroize, Zeroizing, ZeroizingSchnorrSk,
+    ZeroizingVRFSk,
+};
+
+use std::convert::TryInto;
+
+//*****************************Password-based secret key keystore***********************************
+//
+// Layout of an encrypted keystore (integers are little endian):
+//
+// | magic (4) | version (1) | key type (1) | kdf iterations (4) | salt (16) | nonce (16) |
+// | ciphertext (SCALAR_FIELD_SIZE) | tag (32) | checksum (4) |
+//
+// - The key derived from the passphrase is an iterated, salted Blake2b hash, from which
+//   independent encryption and MAC keys are obtained;
+// - Encryption is a XOR with a Blake2b keystream (Blake2b used as a PRF on key, nonce and counter);
+// - The tag is a Blake2b MAC on the whole header and the ciphertext (encrypt-then-MAC);
+// - The checksum is a truncated Blake2b hash of all the preceding bytes: it allows to tell apart
+//   a corrupted keystore from a wrong passphrase.
+
+const KEYSTORE_MAGIC: [u8; 4] = *b"ZKSK";
+pub const KEYSTORE_VERSION: u8 = 1;
+
+pub const KEYSTORE_KDF_ITERATIONS: u32 = 1 << 16;
+// Upper bound to the iterations accepted when decrypting, to avoid DoS by crafted keystores
+const KEYSTORE_MAX_KDF_ITERATIONS: u32 = 1 << 24;
+
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 16;
+const TAG_SIZE: usize = 32;
+const CHECKSUM_SIZE: usize = 4;
+const HEADER_SIZE: usize = 4 + 1 + 1 + 4 + SALT_SIZE + NONCE_SIZE;
+
+pub const KEYSTORE_SIZE: usize = HEADER_SIZE + SCALAR_FIELD_SIZE + TAG_SIZE + CHECKSUM_SIZE;
+
+// Domain separators
+const KDF_TAG: &[u8] = b"zendoo_keystore_kdf";
+const ENC_KEY_TAG: &[u8] = b"zendoo_keystore_enc";
+const MAC_KEY_TAG: &[u8] = b"zendoo_keystore_mac";
+const CHECKSUM_TAG: &[u8] = b"zendoo_keystore_checksum";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeystoreKeyType {
+    Schnorr = 1,
+    Vrf = 2,
+}
+
+impl KeystoreKeyType {
+    fn from_u8(key_type: u8) -> Result<Self, Error> {
+        match key_type {
+            1 => Ok(KeystoreKeyType::Schnorr),
+            2 => Ok(KeystoreKeyType::Vrf),
+            _ => Err(format!("Unknown keystore key type: {}", key_type))?,
+        }
+    }
+}
+
+fn blake2b(chunks: &[&[u8]]) -> Zeroizing<Vec<u8>> {
+    let mut h = Blake2b::new();
+    for chunk in chunks {
+        h.input(chunk);
+    }
+    Zeroizing::new(h.result().to_vec())
+}
+
+// Return (encryption key, MAC key) derived from `password` and `salt`
+fn derive_keys(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+) -> (Zeroizing<Vec<u8>>, Zeroizing<Vec<u8>>) {
+    let mut key = blake2b(&[KDF_TAG, salt, password]);
+    for i in 1..iterations {
+        key = blake2b(&[KDF_TAG, &i.to_le_bytes(), &key, password]);
+    }
+    (
+        blake2b(&[ENC_KEY_TAG, &key]),
+        blake2b(&[MAC_KEY_TAG, &key]),
+    )
+}
+
+// XOR `data` in place with the keystream given by `enc_key` and `nonce`
+fn apply_keystream(enc_key: &[u8], nonce: &[u8], data: &mut [u8]) {
+    for (counter, block) in data.chunks_mut(64).enumerate() {
+        let keystream = blake2b(&[enc_key, nonce, &(counter as u64).to_le_bytes()]);
+        block
+            .iter_mut()
+            .zip(keystream.iter())
+            .for_each(|(b, k)| *b ^= k);
+    }
+}
+
+fn compute_tag(mac_key: &[u8], header_and_ciphertext: &[u8]) -> Vec<u8> {
+    blake2b(&[mac_key, header_and_ciphertext])[..TAG_SIZE].to_vec()
+}
+
+fn compute_checksum(bytes: &[u8]) -> Vec<u8> {
+    blake2b(&[CHECKSUM_TAG, bytes])[..CHECKSUM_SIZE].to_vec()
+}
+
+fn encrypt_secret_key<T: CanonicalSerialize>(
+    sk: &T,
+    key_type: KeystoreKeyType,
+    password: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let mut rng = OsRng;
+    let mut salt = [0u8; SALT_SIZE];
+    let mut nonce = [0u8; NONCE_SIZE];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let mut keystore = Vec::with_capacity(KEYSTORE_SIZE);
+    keystore.extend_from_slice(&KEYSTORE_MAGIC);
+    keystore.push(KEYSTORE_VERSION);
+    keystore.push(key_type as u8);
+    keystore.extend_from_slice(&KEYSTORE_KDF_ITERATIONS.to_le_bytes());
+    keystore.extend_from_slice(&salt);
+    keystore.extend_from_slice(&nonce);
+
+    let (enc_key, mac_key) = derive_keys(password, &salt, KEYSTORE_KDF_ITERATIONS);
+
+    let mut ciphertext = serialize_secret(sk)?;
+    if ciphertext.len() != SCALAR_FIELD_SIZE {
+        Err("Unexpected secret key length")?
+    }
+    apply_keystream(&enc_key, &nonce, &mut ciphertext);
+    keystore.extend_from_slice(&ciphertext);
+
+    let tag = compute_tag(&mac_key, &keystore);
+    keystore.extend_from_slice(&tag);
+
+    let checksum = compute_checksum(&keystore);
+    keystore.extend_from_slice(&checksum);
+
+    Ok(keystore)
+}
+
+fn decrypt_secret_key<T: CanonicalDeserialize + SemanticallyValid + Zeroize>(
+    keystore: &[u8],
+    key_type: KeystoreKeyType,
+    password: &[u8],
+) -> Result<Zeroizing<T>, Error> {
+    if keystore.len() != KEYSTORE_SIZE {
+        Err(format!(
+            "Invalid keystore length. Expected: {}, found: {}",
+            KEYSTORE_SIZE,
+            keystore.len()
+        ))?
+    }
+
+    let (content, checksum) = keystore.split_at(KEYSTORE_SIZE - CHECKSUM_SIZE);
+    if compute_checksum(content) != checksum {
+        Err("Keystore checksum mismatch: data is corrupted")?
+    }
+
+    if content[..4] != KEYSTORE_MAGIC {
+        Err("Not a keystore")?
+    }
+
+    let version = content[4];
+    if version != KEYSTORE_VERSION {
+        Err(format!("Unsupported keystore version: {}", version))?
+    }
+
+    let found_key_type = KeystoreKeyType::from_u8(content[5])?;
+    if found_key_type != key_type {
+        Err(format!(
+            "Unexpected keystore key type. Expected: {:?}, found: {:?}",
+            key_type, found_key_type
+        ))?
+    }
+
+    let iterations = u32::from_le_bytes(content[6..10].try_into().unwrap());
+    if iterations == 0 || iterations > KEYSTORE_MAX_KDF_ITERATIONS {
+        Err(format!("Invalid keystore KDF iterations: {}", iterations))?
+    }
+
+    let salt = &content[10..10 + SALT_SIZE];
+    let nonce = &content[10 + SALT_SIZE..HEADER_SIZE];
+    let (header_and_ciphertext, tag) = content.split_at(HEADER_SIZE + SCALAR_FIELD_SIZE);
+
+    let (enc_key, mac_key) = derive_keys(password, salt, iterations);
+    if !ct_eq(&compute_tag(&mac_key, header_and_ciphertext), tag) {
+        Err("Wrong passphrase")?
+    }
+
+    let mut sk_bytes = Zeroizing::new(header_and_ciphertext[HEADER_SIZE..].to_vec());
+    apply_keystream(&enc_key, nonce, &mut sk_bytes);
+
+    deserialize_secret(&sk_bytes)
+}
+
+pub fn schnorr_encrypt_secret_key(sk: &SchnorrSk, password: &[u8]) -> Result<Vec<u8>, Error> {
+    encrypt_secret_key(sk, KeystoreKeyType::Schnorr, password)
+}
+
+pub fn schnorr_decrypt_secret_key(
+    keystore: &[u8],
+    password: &[u8],
+) -> Result<ZeroizingSchnorrSk, Error> {
+    decrypt_secret_key(keystore, KeystoreKeyType::Schnorr, password)
+}
+
+pub fn vrf_encrypt_secret_key(sk: &VRFSk, password: &[u8]) -> Result<Vec<u8>, Error> {
+    encrypt_secret_key(sk, KeystoreKeyType::Vrf, password)
+}
+
+pub fn vrf_decrypt_secret_key(
+    keystore: &[u8],
+    password: &[u8],
+) -> Result<ZeroizingVRFSk, Error> {
+    decrypt_secret_key(keystore, KeystoreKeyType::Vrf, password)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cctp_calls::{schnorr_generate_key, vrf_generate_key};
+
+    #[test]
+    fn keystore_encrypt_decrypt() {
+        let password = b"correct horse battery staple";
+
+        let (_, schnorr_sk) = schnorr_generate_key();
+        let keystore = schnorr_encrypt_secret_key(&schnorr_sk, password).unwrap();
+        assert_eq!(keystore.len(), KEYSTORE_SIZE);
+        assert_eq!(
+            schnorr_decrypt_secret_key(&keystore, password).unwrap(),
+            schnorr_sk
+        );
+
+        // Encryption is randomized
+        assert_ne!(
+            keystore,
+            schnorr_encrypt_secret_key(&schnorr_sk, password).unwrap()
+        );
+
+        let (_, vrf_sk) = vrf_generate_key();
+        let vrf_keystore = vrf_encrypt_secret_key(&vrf_sk, password).unwrap();
+        assert_eq!(vrf_decrypt_secret_key(&vrf_keystore, password).unwrap(), vrf_sk);
+
+        // Wrong passphrase
+        assert!(schnorr_decrypt_secret_key(&keystore, b"wrong password").is_err());
+        assert!(schnorr_decrypt_secret_key(&keystore, b"").is_err());
+
+        // Wrong key type
+        assert!(vrf_decrypt_secret_key(&keystore, password).is_err());
+        assert!(schnorr_decrypt_secret_key(&vrf_keystore, password).is_err());
+
+        // Corrupted keystore
+        for i in 0..keystore.len() {
+            let mut corrupted = keystore.clone();
+            corrupted[i] ^= 1;
+            assert!(schnorr_decrypt_secret_key(&corrupted, password).is_err());
+        }
+
+        // Tampered keystore, with checksum recomputed
+        let mut tampered = keystore.clone();
+        tampered[HEADER_SIZE] ^= 1;
+        let checksum = compute_checksum(&tampered[..KEYSTORE_SIZE - CHECKSUM_SIZE]);
+        tampered[KEYSTORE_SIZE - CHECKSUM_SIZE..].copy_from_slice(&checksum);
+        assert!(schnorr_decrypt_secret_key(&tampered, password).is_err());
+
+        // Wrong length
+        assert!(schnorr_decrypt_secret_key(&keystore[1..], password).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Rust API of the Zendoo sidechain crypto library.
+//!
+//! Schnorr and VRF keys, naive threshold signature proofs, Merkle trees, the sidechain
+//! commitment tree and the Poseidon based primitives, with the field, curve and proving
+//! system types of `demo_circuit::type_mapping` and `cctp_primitives`.
+//! The JNI library (`api` crate) and its C API are thin wrappers over this crate.
+#![allow(
+    clippy::upper_case_acronyms,
+    clippy::too_many_arguments,
+    clippy::type_complexity,
+    clippy::try_err,
+    clippy::map_collect_result_unit,
+    clippy::suspicious_op_assign_impl,
+    clippy::suspicious_arithmetic_impl,
+    clippy::assertions_on_constants
+)]
+
+/// Schnorr and VRF keys, naive threshold signature setup, proving and verification.
+pub mod cctp_calls;
+pub use self::cctp_calls::*;
+
+/// Shamir secret sharing and Feldman VSS of Schnorr secret keys.
+pub mod secret_sharing;
+pub use self::secret_sharing::*;
+
+/// FROST threshold Schnorr signatures.
+pub mod frost;
+pub use self::frost::*;
+
+/// Passphrase encrypted keystore for Schnorr and VRF secret keys.
+pub mod keystore;
+pub use self::keystore::*;
+
+/// Wiping of secret data on drop and constant time comparison.
+pub mod secret;
+pub use self::secret::*;
+
+/// In memory Merkle tree supporting state serialization.
+pub mod in_memory_mht;
+pub use self::in_memory_mht::*;
+
+/// Batched Merkle paths for multiple leaves of the same tree.
+pub mod mht_multiproof;
+pub use self::mht_multiproof::*;
+
+/// Append-only Merkle tree whose nodes are stored on disk.
+pub mod persistent_mht;
+pub use self::persistent_mht::*;
+
+/// Sparse Merkle tree with membership and non-membership proofs.
+pub mod sparse_mht;
+pub use self::sparse_mht::*;
+
+/// Commitment tree with state export and incremental root updates.
+pub mod incremental_commitment_tree;
+pub use self::incremental_commitment_tree::*;
+
+/// Inclusion proofs of single transactions in the commitment tree.
+pub mod tx_inclusion_proof;
+pub use self::tx_inclusion_proof::*;
+
+/// Poseidon hash of byte strings.
+pub mod poseidon_bytes;
+pub use self::poseidon_bytes::*;
+
+/// Poseidon hash with cloneable and serializable state.
+pub mod resumable_poseidon;
+pub use self::resumable_poseidon::*;
+
+/// Poseidon based commitment, PRF and KDF.
+pub mod poseidon_primitives;
+pub use self::poseidon_primitives::*;
+
+mod mc_serialization;
+
+mod mht_hash;
+
+mod framing;
+
+/// Mainchain certificates carrying the proof of their data.
+pub mod certificate;
+pub use self::certificate::*;
+
+/// Mainchain sidechain creation parameters with their validation.
+pub mod sidechain_creation;
+pub use self::sidechain_creation::*;
+
+/// Versioned JSON representation of the public types.
+pub mod json;
+pub use self::json::*;
+
+/// Envelope with type, version and checksum of serialized artifacts.
+pub mod envelope;
+pub use self::envelope::*;
@@ -0,0 +1,117 @@
+use cctp_primitives::utils::serialization::*;
+use demo_circuit::type_mapping::*;
+
+use std::convert::TryInto;
+
+//*****************************Mainchain serialization**********************************************
+//
+// Helpers for the serialization of mainchain objects (certificates, sidechain creation outputs):
+// integers are little endian, and vectors are prefixed by their length as a Bitcoin compact size.
+
+// Max length of a vector in the mainchain serialization
+const MAX_VECTOR_SIZE: u64 = 0x0200_0000;
+
+pub(crate) fn write_compact_size(bytes: &mut Vec<u8>, size: usize) {
+    let size = size as u64;
+    if size < 0xfd {
+        bytes.push(size as u8);
+    } else if size <= 0xffff {
+        bytes.push(0xfd);
+        bytes.extend_from_slice(&(size as u16).to_le_bytes());
+    } else if size <= 0xffff_ffff {
+        bytes.push(0xfe);
+        bytes.extend_from_slice(&(size as u32).to_le_bytes());
+    } else {
+        bytes.push(0xff);
+        bytes.extend_from_slice(&size.to_le_bytes());
+    }
+}
+
+pub(crate) fn write_vector(bytes: &mut Vec<u8>, data: &[u8]) {
+    write_compact_size(bytes, data.len());
+    bytes.extend_from_slice(data);
+}
+
+pub(crate) fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if len > bytes.len() {
+        Err("Unexpected end of data")?
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+pub(crate) fn read_u32(bytes: &mut &[u8]) -> Result<u32, Error> {
+    Ok(u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap()))
+}
+
+pub(crate) fn read_u64(bytes: &mut &[u8]) -> Result<u64, Error> {
+    Ok(u64::from_le_bytes(take(bytes, 8)?.try_into().unwrap()))
+}
+
+// Compact sizes must use the shortest encoding, as in the mainchain
+pub(crate) fn read_compact_size(bytes: &mut &[u8]) -> Result<usize, Error> {
+    let (size, min_size) = match take(bytes, 1)?[0] {
+        0xfd => (
+            u16::from_le_bytes(take(bytes, 2)?.try_into().unwrap()) as u64,
+            0xfd,
+        ),
+        0xfe => (read_u32(bytes)? as u64, 0x10000),
+        0xff => (read_u64(bytes)?, 0x1_0000_0000),
+        size => (size as u64, 0),
+    };
+    if size < min_size {
+        Err("Non-canonical compact size")?
+    }
+    if size > MAX_VECTOR_SIZE {
+        Err(format!("Vector too long: {}", size))?
+    }
+    Ok(size as usize)
+}
+
+pub(crate) fn read_vector<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+    let len = read_compact_size(bytes)?;
+    take(bytes, len)
+}
+
+pub(crate) fn read_field_element(bytes: &[u8]) -> Result<FieldElement, Error> {
+    if bytes.len() != FIELD_SIZE {
+        Err(format!(
+            "Field elements are {} bytes long, found {}",
+            FIELD_SIZE,
+            bytes.len()
+        ))?
+    }
+    deserialize_from_buffer(bytes, None, None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compact_size() {
+        for size in [
+            0usize,
+            0xfc,
+            0xfd,
+            0xffff,
+            0x10000,
+            MAX_VECTOR_SIZE as usize,
+        ]
+        .iter()
+        {
+            let mut bytes = vec![];
+            write_compact_size(&mut bytes, *size);
+            let mut slice = bytes.as_slice();
+            assert_eq!(read_compact_size(&mut slice).unwrap(), *size);
+            assert!(slice.is_empty());
+        }
+
+        // Non-canonical, too long or truncated
+        assert!(read_compact_size(&mut &[0xfd, 0xfc, 0x00][..]).is_err());
+        assert!(read_compact_size(&mut &[0xfe, 0x00, 0x00, 0x00, 0x04][..]).is_err());
+        assert!(read_compact_size(&mut &[0xfd, 0xfd][..]).is_err());
+        assert!(read_vector(&mut &[0x02, 0x00][..]).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use demo_circuit::type_mapping::*;
+use primitives::crh::FieldBasedHash;
+
+//*****************************Poseidon Merkle tree nodes*******************************************
+//
+// Node hash of the Poseidon Merkle trees of this crate (persistent and sparse trees, multi-proofs,
+// commitment tree paths). It's the one of the GingerMHT of cctp, so that roots and paths are the
+// same of the ones of an InMemoryOptimizedMerkleTree with the same leaves.
+
+// Parent of the nodes `left` and `right`
+pub(crate) fn hash_children(
+    left: &FieldElement,
+    right: &FieldElement,
+) -> Result<FieldElement, Error> {
+    FieldHash::init_constant_length(2, None)
+        .update(*left)
+        .update(*right)
+        .finalize()
+}
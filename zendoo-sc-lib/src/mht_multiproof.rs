@@ -0,0 +1,337 @@
+use cctp_primitives::utils::{mht::*, serialization::*};
+use demo_circuit::type_mapping::*;
+use primitives::merkle_tree::field_based_mht::FieldBasedMHTPath;
+
+use crate::mht_hash::hash_children;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::TryInto;
+
+//*****************************Poseidon Merkle multi-proofs*****************************************
+//
+// Proof of membership of several leaves in the same Poseidon Merkle tree. Instead of one path
+// per leaf, the proof holds only the nodes that can't be computed from the leaves themselves or
+// from other nodes of the proof: siblings shared among paths are stored once, and siblings that
+// are themselves on a path are not stored at all.
+//
+// The nodes are ordered level by level, starting from the leaves, and by increasing index inside
+// each level: this is the same order in which they are consumed when recomputing the root.
+//
+// Layout of a serialized multi-proof (integers are little endian):
+//
+// | height (1) | num leaves (4) | leaf indices (num leaves * 8) | nodes (num nodes * FIELD_SIZE) |
+//
+// The number of nodes is not stored, as it's determined by the height and the leaf indices.
+
+pub const MHT_MULTIPROOF_MAX_HEIGHT: u8 = 63;
+
+// Call `f(level, index)` for each node needed by a multi-proof for the leaves at `leaf_indices`
+// (sorted and without duplicates), in the order in which they are stored in the proof.
+fn for_each_proof_node<F: FnMut(u8, u64) -> Result<(), Error>>(
+    height: u8,
+    leaf_indices: &[u64],
+    mut f: F,
+) -> Result<(), Error> {
+    let mut current = leaf_indices.iter().copied().collect::<BTreeSet<u64>>();
+    for level in 0..height {
+        let mut next = BTreeSet::new();
+        for &index in current.iter() {
+            if !current.contains(&(index ^ 1)) {
+                f(level, index ^ 1)?;
+            }
+            next.insert(index >> 1);
+        }
+        current = next;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleMultiProof {
+    height: u8,
+    leaf_indices: Vec<u64>,
+    nodes: Vec<FieldElement>,
+}
+
+impl MerkleMultiProof {
+    fn check_leaf_indices(height: u8, leaf_indices: &[u64]) -> Result<(), Error> {
+        if height > MHT_MULTIPROOF_MAX_HEIGHT {
+            Err(format!("Invalid height: {}", height))?
+        }
+        if leaf_indices.is_empty() {
+            Err("No leaf indices")?
+        }
+        if leaf_indices.windows(2).any(|w| w[0] >= w[1]) {
+            Err("Leaf indices must be sorted and without duplicates")?
+        }
+        if leaf_indices[leaf_indices.len() - 1] >= 1u64 << height {
+            Err("Leaf index out of range")?
+        }
+        Ok(())
+    }
+
+    fn count_nodes(height: u8, leaf_indices: &[u64]) -> Result<usize, Error> {
+        let mut num_nodes = 0;
+        for_each_proof_node(height, leaf_indices, |_, _| {
+            num_nodes += 1;
+            Ok(())
+        })?;
+        Ok(num_nodes)
+    }
+
+    // Build the multi-proof for the leaves at `leaf_indices` of the finalized tree `tree`.
+    // Indices can be passed in any order and with duplicates.
+    pub fn new(tree: &GingerMHT, leaf_indices: &[u64]) -> Result<Self, Error> {
+        let mut leaf_indices = leaf_indices.to_vec();
+        leaf_indices.sort_unstable();
+        leaf_indices.dedup();
+
+        // Collect the siblings of all the paths
+        let mut siblings = HashMap::new();
+        let mut height = 0;
+        for &leaf_index in leaf_indices.iter() {
+            let path = get_ginger_mht_path(tree, leaf_index)
+                .ok_or(format!("Unable to get path for leaf {}", leaf_index))?;
+            height = path.get_length().try_into()?;
+            for (level, (sibling, _)) in path.get_raw_path().iter().enumerate() {
+                siblings.insert((level as u8, (leaf_index >> level) ^ 1), *sibling);
+            }
+        }
+        Self::check_leaf_indices(height, &leaf_indices)?;
+
+        let mut nodes = Vec::new();
+        for_each_proof_node(height, &leaf_indices, |level, index| {
+            nodes.push(*siblings.get(&(level, index)).ok_or("Missing node")?);
+            Ok(())
+        })?;
+
+        Ok(Self {
+            height,
+            leaf_indices,
+            nodes,
+        })
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    // Indices of the leaves proven, in increasing order
+    pub fn leaf_indices(&self) -> &[u64] {
+        &self.leaf_indices
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // Nodes of the proof, in the order in which they are consumed when computing the root
+    pub fn nodes(&self) -> &[FieldElement] {
+        &self.nodes
+    }
+
+    // Build the multi-proof from its parts, checking that `nodes` are as many as needed
+    // by a proof for the leaves at `leaf_indices`
+    pub fn from_parts(
+        height: u8,
+        leaf_indices: Vec<u64>,
+        nodes: Vec<FieldElement>,
+    ) -> Result<Self, Error> {
+        Self::check_leaf_indices(height, &leaf_indices)?;
+        let num_nodes = Self::count_nodes(height, &leaf_indices)?;
+        if nodes.len() != num_nodes {
+            Err(format!(
+                "Invalid number of nodes. Expected: {}, found: {}",
+                num_nodes,
+                nodes.len()
+            ))?
+        }
+        Ok(Self {
+            height,
+            leaf_indices,
+            nodes,
+        })
+    }
+
+    // Compute the root of the tree given the leaves at `leaf_indices()`, in the same order
+    pub fn compute_root(&self, leaves: &[FieldElement]) -> Result<FieldElement, Error> {
+        if leaves.len() != self.leaf_indices.len() {
+            Err(format!(
+                "Invalid number of leaves. Expected: {}, found: {}",
+                self.leaf_indices.len(),
+                leaves.len()
+            ))?
+        }
+
+        let mut current = self
+            .leaf_indices
+            .iter()
+            .copied()
+            .zip(leaves.iter().copied())
+            .collect::<BTreeMap<u64, FieldElement>>();
+        let mut nodes = self.nodes.iter();
+        let mut next_node = || nodes.next().copied().ok_or("Not enough nodes in the proof");
+
+        for _ in 0..self.height {
+            let mut next = BTreeMap::new();
+            for (&index, node) in current.iter() {
+                let (left, right) = if index & 1 == 0 {
+                    let right = match current.get(&(index + 1)) {
+                        Some(right) => *right,
+                        None => next_node()?,
+                    };
+                    (*node, right)
+                } else if current.contains_key(&(index - 1)) {
+                    // Already processed together with its left sibling
+                    continue;
+                } else {
+                    (next_node()?, *node)
+                };
+                next.insert(index >> 1, hash_children(&left, &right)?);
+            }
+            current = next;
+        }
+
+        if next_node().is_ok() {
+            Err("Too many nodes in the proof")?
+        }
+
+        Ok(current[&0])
+    }
+
+    // Verify that `leaves` are at `leaf_indices()` in the tree with root `root`
+    pub fn verify(&self, leaves: &[FieldElement], root: &FieldElement) -> Result<bool, Error> {
+        Ok(&self.compute_root(leaves)? == root)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(
+            1 + 4 + (self.leaf_indices.len() * 8) + (self.nodes.len() * FIELD_SIZE),
+        );
+        bytes.push(self.height);
+        bytes.extend_from_slice(&(self.leaf_indices.len() as u32).to_le_bytes());
+        for leaf_index in self.leaf_indices.iter() {
+            bytes.extend_from_slice(&leaf_index.to_le_bytes());
+        }
+        for node in self.nodes.iter() {
+            bytes.extend_from_slice(&serialize_to_buffer(node, None)?);
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 5 {
+            Err("Invalid multi-proof: missing header")?
+        }
+        let height = bytes[0];
+        let num_leaves = u32::from_le_bytes(bytes[1..5].try_into().unwrap()) as usize;
+
+        let nodes_start = num_leaves
+            .checked_mul(8)
+            .and_then(|len| len.checked_add(5))
+            .filter(|&len| len <= bytes.len())
+            .ok_or("Invalid multi-proof: truncated leaf indices")?;
+        let leaf_indices = bytes[5..nodes_start]
+            .chunks(8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        Self::check_leaf_indices(height, &leaf_indices)?;
+
+        let num_nodes = Self::count_nodes(height, &leaf_indices)?;
+        if bytes.len() != nodes_start + num_nodes * FIELD_SIZE {
+            Err(format!(
+                "Invalid multi-proof length. Expected: {}, found: {}",
+                nodes_start + num_nodes * FIELD_SIZE,
+                bytes.len()
+            ))?
+        }
+
+        let nodes = bytes[nodes_start..]
+            .chunks(FIELD_SIZE)
+            .map(|b| deserialize_from_buffer(b, None, None))
+            .collect::<Result<Vec<FieldElement>, _>>()?;
+
+        Ok(Self {
+            height,
+            leaf_indices,
+            nodes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{Field, UniformRand};
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn merkle_multiproof() {
+        let height = 8;
+        let num_leaves = 200;
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let leaves = (0..num_leaves)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        let mut tree = new_ginger_mht(height, 1 << height).unwrap();
+        for leaf in leaves.iter() {
+            append_leaf_to_ginger_mht(&mut tree, leaf).unwrap();
+        }
+        finalize_ginger_mht_in_place(&mut tree).unwrap();
+        let root = get_ginger_mht_root(&tree).unwrap();
+
+        for num_proven in [1, 2, 7, 32, num_leaves].iter() {
+            let mut indices = (0..*num_proven)
+                .map(|_| rng.gen_range(0..1u64 << height))
+                .collect::<Vec<_>>();
+            if *num_proven == num_leaves {
+                indices = (0..num_leaves as u64).rev().collect();
+            }
+
+            let proof = MerkleMultiProof::new(&tree, &indices).unwrap();
+            assert_eq!(proof.height() as usize, height);
+            assert!(proof.leaf_indices().windows(2).all(|w| w[0] < w[1]));
+            assert!(proof.num_nodes() <= proof.leaf_indices().len() * height);
+
+            // Leaves beyond the appended ones are empty
+            let proven_leaves = proof
+                .leaf_indices()
+                .iter()
+                .map(|&i| {
+                    leaves
+                        .get(i as usize)
+                        .copied()
+                        .unwrap_or(FieldElement::zero())
+                })
+                .collect::<Vec<_>>();
+            assert!(proof.verify(&proven_leaves, &root).unwrap());
+
+            // Serialization round trip
+            let proof_bytes = proof.to_bytes().unwrap();
+            let deserialized_proof = MerkleMultiProof::from_bytes(&proof_bytes).unwrap();
+            assert_eq!(proof, deserialized_proof);
+            assert!(MerkleMultiProof::from_bytes(&proof_bytes[1..]).is_err());
+            assert!(MerkleMultiProof::from_bytes(&proof_bytes[..proof_bytes.len() - 1]).is_err());
+
+            // Wrong leaves
+            let mut wrong_leaves = proven_leaves.clone();
+            wrong_leaves[0] = FieldElement::rand(rng);
+            assert!(!proof.verify(&wrong_leaves, &root).unwrap());
+            assert!(proof.verify(&proven_leaves[1..], &root).is_err());
+        }
+
+        // A multi-proof of all the leaves has no nodes
+        let all = (0..1u64 << height).collect::<Vec<_>>();
+        assert_eq!(MerkleMultiProof::new(&tree, &all).unwrap().num_nodes(), 0);
+
+        // Adjacent leaves share all the path but the first level
+        let proof = MerkleMultiProof::new(&tree, &[4, 5]).unwrap();
+        assert_eq!(proof.num_nodes(), height - 1);
+
+        // Negative cases
+        assert!(MerkleMultiProof::new(&tree, &[]).is_err());
+        assert!(MerkleMultiProof::new(&tree, &[1 << height]).is_err());
+    }
+}
@@ -0,0 +1,654 @@
+use algebra::Field;
+use cctp_primitives::utils::serialization::*;
+use demo_circuit::type_mapping::*;
+
+use crate::{framing::*, mht_hash::hash_children};
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+//*****************************Persistent Poseidon Merkle tree**************************************
+//
+// Append-only (with in place updates of existing leaves) binary Poseidon Merkle tree whose nodes
+// are stored on disk, in the directory passed at creation time:
+//
+// - `nodes_<level>`: one file for each level (0 for leaves), holding the FIELD_SIZE nodes of that
+//   level ordered by index. Leaves are appended from the left, so only the nodes of non empty
+//   subtrees are stored and the files are dense: their total size is about 2 * num_leaves nodes,
+//   whatever the height of the tree;
+// - `meta`: magic, version, height, number of leaves and root of the last commit, plus a checksum.
+//   It's always replaced atomically (write to a temporary file and rename);
+// - `journal`: present only during a commit. Holds all the nodes being written, so that an
+//   interrupted commit can be completed when reopening the tree.
+//
+// Both `meta` and `journal` use the checksummed framing of framing.rs.
+//
+// Changes are kept in memory until commit() is called, and can be discarded with rollback().
+// Roots and paths are the same as the ones of an InMemoryOptimizedMerkleTree with the same
+// height and leaves.
+
+const META_MAGIC: [u8; 4] = *b"ZPMT";
+const JOURNAL_MAGIC: [u8; 4] = *b"ZPMJ";
+pub const PERSISTENT_MHT_VERSION: u8 = 1;
+
+// 2^40 leaves are far more than any UTXO set we need to handle
+pub const PERSISTENT_MHT_MAX_HEIGHT: u8 = 40;
+
+const META_SIZE: usize = FRAME_HEADER_SIZE + 1 + 8 + FIELD_SIZE + CHECKSUM_SIZE;
+// level + index + node
+const JOURNAL_ENTRY_SIZE: usize = 1 + 8 + FIELD_SIZE;
+
+const NODES_FILE_PREFIX: &str = "nodes_";
+const META_FILE: &str = "meta";
+const META_TMP_FILE: &str = "meta.tmp";
+const JOURNAL_FILE: &str = "journal";
+
+// Failure while replaying a journal
+#[derive(Debug)]
+enum JournalError {
+    // The journal is malformed, i.e. it has not been completely written: the nodes files
+    // have not been touched yet and it can be safely discarded
+    Invalid(String),
+    // The journal is valid but the nodes couldn't be written: it must be kept to retry
+    Io(io::Error),
+}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::Invalid(msg) => write!(f, "{}", msg),
+            JournalError::Io(e) => write!(f, "Unable to apply journal: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+impl From<io::Error> for JournalError {
+    fn from(e: io::Error) -> Self {
+        JournalError::Io(e)
+    }
+}
+
+// Make the creation/renaming/removal of files in `dir` durable
+fn sync_dir(dir: &Path) -> Result<(), Error> {
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+pub struct PersistentMerkleTree {
+    dir: PathBuf,
+    height: u8,
+    // nulls[i] is the root of an empty subtree of height i
+    nulls: Vec<FieldElement>,
+    // nodes_files[level] holds the nodes at `level`
+    nodes_files: RefCell<Vec<File>>,
+    committed_num_leaves: u64,
+    num_leaves: u64,
+    // Nodes, indexed by (level, index), modified since last commit
+    dirty: HashMap<(u8, u64), FieldElement>,
+}
+
+impl PersistentMerkleTree {
+    // Open the tree stored in `dir`, or create a new empty one if `dir` doesn't contain any.
+    // If a previous commit was interrupted, it is completed.
+    pub fn open<P: AsRef<Path>>(dir: P, height: u8) -> Result<Self, Error> {
+        if height == 0 || height > PERSISTENT_MHT_MAX_HEIGHT {
+            Err(format!(
+                "Invalid height: {}. Must be in [1, {}]",
+                height, PERSISTENT_MHT_MAX_HEIGHT
+            ))?
+        }
+
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut nodes_files = Vec::with_capacity(height as usize + 1);
+        for level in 0..=height {
+            nodes_files.push(
+                OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .open(dir.join(format!("{}{}", NODES_FILE_PREFIX, level)))?,
+            );
+        }
+
+        let mut nulls = Vec::with_capacity(height as usize + 1);
+        nulls.push(FieldElement::zero());
+        for i in 0..height as usize {
+            nulls.push(hash_children(&nulls[i], &nulls[i])?);
+        }
+
+        let mut tree = Self {
+            dir,
+            height,
+            nulls,
+            nodes_files: RefCell::new(nodes_files),
+            committed_num_leaves: 0,
+            num_leaves: 0,
+            dirty: HashMap::new(),
+        };
+
+        tree.recover_journal()?;
+
+        if tree.dir.join(META_FILE).exists() {
+            let (meta_height, num_leaves, root) = tree.read_meta()?;
+            if meta_height != height {
+                Err(format!(
+                    "Height mismatch. Requested: {}, stored: {}",
+                    height, meta_height
+                ))?
+            }
+            tree.committed_num_leaves = num_leaves;
+            tree.num_leaves = num_leaves;
+            if tree.root()? != root {
+                Err("Stored root doesn't match the nodes: data is corrupted")?
+            }
+        } else {
+            tree.write_meta()?;
+        }
+
+        Ok(tree)
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    pub fn num_leaves(&self) -> u64 {
+        self.num_leaves
+    }
+
+    pub fn has_uncommitted_changes(&self) -> bool {
+        !self.dirty.is_empty() || self.num_leaves != self.committed_num_leaves
+    }
+
+    fn max_leaves(&self) -> u64 {
+        1u64 << self.height
+    }
+
+    fn read_node(&self, level: u8, index: u64) -> Result<FieldElement, Error> {
+        let mut bytes = [0u8; FIELD_SIZE];
+        let mut files = self.nodes_files.borrow_mut();
+        let file = &mut files[level as usize];
+        file.seek(SeekFrom::Start(index * FIELD_SIZE as u64))?;
+        file.read_exact(&mut bytes)?;
+        deserialize_from_buffer(&bytes, None, None).map_err(|e| {
+            format!("Unable to read node {} at level {}: {}", index, level, e).into()
+        })
+    }
+
+    fn node(&self, level: u8, index: u64) -> Result<FieldElement, Error> {
+        // Subtrees not containing any leaf are empty
+        if index << level >= self.num_leaves {
+            return Ok(self.nulls[level as usize]);
+        }
+        match self.dirty.get(&(level, index)) {
+            Some(node) => Ok(*node),
+            None => self.read_node(level, index),
+        }
+    }
+
+    // Set the leaf at `index` and recompute the nodes on its path to the root
+    fn set_leaf(&mut self, index: u64, leaf: FieldElement) -> Result<(), Error> {
+        let mut node = leaf;
+        let mut index = index;
+        self.dirty.insert((0, index), node);
+        for level in 0..self.height {
+            let sibling = self.node(level, index ^ 1)?;
+            node = if index & 1 == 0 {
+                hash_children(&node, &sibling)?
+            } else {
+                hash_children(&sibling, &node)?
+            };
+            index >>= 1;
+            self.dirty.insert((level + 1, index), node);
+        }
+        Ok(())
+    }
+
+    // Append `leaf` to the tree, returning its index
+    pub fn append(&mut self, leaf: &FieldElement) -> Result<u64, Error> {
+        if self.num_leaves == self.max_leaves() {
+            Err("Tree is full")?
+        }
+        let index = self.num_leaves;
+        self.num_leaves += 1;
+        if let Err(e) = self.set_leaf(index, *leaf) {
+            self.num_leaves -= 1;
+            Err(e)?
+        }
+        Ok(index)
+    }
+
+    // Replace the leaf at `index`, that must have been already appended
+    pub fn update(&mut self, index: u64, leaf: &FieldElement) -> Result<(), Error> {
+        if index >= self.num_leaves {
+            Err(format!(
+                "Invalid leaf index: {}. Number of leaves: {}",
+                index, self.num_leaves
+            ))?
+        }
+        self.set_leaf(index, *leaf)
+    }
+
+    pub fn get_leaf(&self, index: u64) -> Result<FieldElement, Error> {
+        if index >= self.num_leaves {
+            Err(format!(
+                "Invalid leaf index: {}. Number of leaves: {}",
+                index, self.num_leaves
+            ))?
+        }
+        self.node(0, index)
+    }
+
+    // Root of the tree, including uncommitted changes
+    pub fn root(&self) -> Result<FieldElement, Error> {
+        self.node(self.height, 0)
+    }
+
+    // Path from the leaf at `index` (possibly empty) to the root
+    pub fn get_merkle_path(&self, index: u64) -> Result<GingerMHTPath, Error> {
+        if index >= self.max_leaves() {
+            Err(format!("Invalid leaf index: {}", index))?
+        }
+        let mut path = Vec::with_capacity(self.height as usize);
+        let mut index = index;
+        for level in 0..self.height {
+            path.push((self.node(level, index ^ 1)?, index & 1 == 1));
+            index >>= 1;
+        }
+        Ok(GingerMHTPath::new(path))
+    }
+
+    // Discard all the changes since last commit
+    pub fn rollback(&mut self) {
+        self.dirty.clear();
+        self.num_leaves = self.committed_num_leaves;
+    }
+
+    // Durably store all the changes since last commit
+    pub fn commit(&mut self) -> Result<(), Error> {
+        if !self.has_uncommitted_changes() {
+            return Ok(());
+        }
+
+        // 1. Write the journal
+        let journal = self.journal()?;
+        let journal_path = self.dir.join(JOURNAL_FILE);
+        {
+            let mut journal_file = File::create(&journal_path)?;
+            journal_file.write_all(&journal)?;
+            journal_file.sync_all()?;
+        }
+        sync_dir(&self.dir)?;
+
+        // 2. Apply it
+        self.apply_journal(&journal)?;
+
+        // 3. Write metadata and remove the journal
+        self.committed_num_leaves = self.num_leaves;
+        self.write_meta()?;
+        fs::remove_file(&journal_path)?;
+        sync_dir(&self.dir)?;
+
+        self.dirty.clear();
+        Ok(())
+    }
+
+    // Serialize the journal of the changes since last commit
+    fn journal(&self) -> Result<Vec<u8>, Error> {
+        let mut nodes = self.dirty.iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|(position, _)| **position);
+
+        let mut journal = begin_frame(
+            &JOURNAL_MAGIC,
+            PERSISTENT_MHT_VERSION,
+            16 + nodes.len() * JOURNAL_ENTRY_SIZE,
+        );
+        journal.extend_from_slice(&self.num_leaves.to_le_bytes());
+        journal.extend_from_slice(&(nodes.len() as u64).to_le_bytes());
+        for ((level, index), node) in nodes {
+            journal.push(*level);
+            journal.extend_from_slice(&index.to_le_bytes());
+            journal.extend_from_slice(&serialize_to_buffer(node, None)?);
+        }
+        Ok(end_frame(journal))
+    }
+
+    // Parse `journal` and write its nodes to the nodes files, returning the number of leaves
+    fn apply_journal(&self, journal: &[u8]) -> Result<u64, JournalError> {
+        let invalid = |msg: &str| JournalError::Invalid(format!("Invalid journal: {}", msg));
+
+        let content = open_frame(journal, &JOURNAL_MAGIC, PERSISTENT_MHT_VERSION, "journal")
+            .map_err(|e| JournalError::Invalid(e.to_string()))?;
+        if content.len() < 16 {
+            return Err(invalid("too short"));
+        }
+
+        let num_leaves = u64::from_le_bytes(content[0..8].try_into().unwrap());
+        let num_nodes = u64::from_le_bytes(content[8..16].try_into().unwrap());
+        let entries = &content[16..];
+        if num_leaves > self.max_leaves()
+            || entries.len() as u64 != num_nodes.saturating_mul(JOURNAL_ENTRY_SIZE as u64)
+        {
+            return Err(invalid("inconsistent length"));
+        }
+        if entries
+            .chunks(JOURNAL_ENTRY_SIZE)
+            .any(|entry| entry[0] > self.height)
+        {
+            return Err(invalid("level out of range"));
+        }
+
+        let mut files = self.nodes_files.borrow_mut();
+        let mut touched = HashSet::new();
+        for entry in entries.chunks(JOURNAL_ENTRY_SIZE) {
+            let level = entry[0] as usize;
+            let index = u64::from_le_bytes(entry[1..9].try_into().unwrap());
+            let file = &mut files[level];
+            file.seek(SeekFrom::Start(index * FIELD_SIZE as u64))?;
+            file.write_all(&entry[9..])?;
+            touched.insert(level);
+        }
+        for level in touched {
+            files[level].sync_all()?;
+        }
+
+        Ok(num_leaves)
+    }
+
+    // Complete or discard a commit interrupted by a crash
+    fn recover_journal(&mut self) -> Result<(), Error> {
+        let journal_path = self.dir.join(JOURNAL_FILE);
+        if !journal_path.exists() {
+            return Ok(());
+        }
+
+        let journal = fs::read(&journal_path)?;
+
+        match self.apply_journal(&journal) {
+            Ok(num_leaves) => {
+                self.committed_num_leaves = num_leaves;
+                self.num_leaves = num_leaves;
+                self.write_meta()?;
+            }
+            // A journal not completely written means that the nodes files have not been
+            // touched: the last commit is still the valid one.
+            Err(JournalError::Invalid(_)) => {}
+            // Keep the journal, so that the commit can be completed by a later attempt
+            Err(e @ JournalError::Io(_)) => Err(e)?,
+        }
+
+        fs::remove_file(&journal_path)?;
+        sync_dir(&self.dir)?;
+        Ok(())
+    }
+
+    fn write_meta(&self) -> Result<(), Error> {
+        let mut meta = begin_frame(
+            &META_MAGIC,
+            PERSISTENT_MHT_VERSION,
+            META_SIZE - FRAME_HEADER_SIZE - CHECKSUM_SIZE,
+        );
+        meta.push(self.height);
+        meta.extend_from_slice(&self.committed_num_leaves.to_le_bytes());
+        meta.extend_from_slice(&serialize_to_buffer(&self.root()?, None)?);
+        let meta = end_frame(meta);
+
+        let tmp_path = self.dir.join(META_TMP_FILE);
+        {
+            let mut tmp_file = File::create(&tmp_path)?;
+            tmp_file.write_all(&meta)?;
+            tmp_file.sync_all()?;
+        }
+        fs::rename(&tmp_path, self.dir.join(META_FILE))?;
+        sync_dir(&self.dir)
+    }
+
+    // Return (height, number of leaves, root) stored in the metadata file
+    fn read_meta(&self) -> Result<(u8, u64, FieldElement), Error> {
+        let meta = fs::read(self.dir.join(META_FILE))?;
+        if meta.len() != META_SIZE {
+            Err("Invalid metadata length")?
+        }
+        let content = open_frame(
+            &meta,
+            &META_MAGIC,
+            PERSISTENT_MHT_VERSION,
+            "persistent Merkle tree metadata",
+        )?;
+        let height = content[0];
+        let num_leaves = u64::from_le_bytes(content[1..9].try_into().unwrap());
+        if num_leaves > 1u64 << height {
+            Err("Invalid number of leaves")?
+        }
+        let root = deserialize_from_buffer(&content[9..], None, None)?;
+        Ok((height, num_leaves, root))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use cctp_primitives::utils::mht::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "persistent_mht_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn persistent_mht_matches_in_memory_mht() {
+        let height = 6;
+        let num_leaves = 45;
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let dir = temp_dir("compat");
+
+        let mut tree = PersistentMerkleTree::open(&dir, height).unwrap();
+        let mut mht = new_ginger_mht(height as usize, 1 << height).unwrap();
+
+        // Empty trees have the same root
+        let empty_mht = finalize_ginger_mht(&mht).unwrap();
+        assert_eq!(tree.root().unwrap(), get_ginger_mht_root(&empty_mht).unwrap());
+
+        let leaves = (0..num_leaves)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(tree.append(leaf).unwrap(), i as u64);
+            append_leaf_to_ginger_mht(&mut mht, leaf).unwrap();
+        }
+        finalize_ginger_mht_in_place(&mut mht).unwrap();
+
+        let root = tree.root().unwrap();
+        assert_eq!(root, get_ginger_mht_root(&mht).unwrap());
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = tree.get_merkle_path(i as u64).unwrap();
+            assert_eq!(path, get_ginger_mht_path(&mht, i as u64).unwrap());
+            assert!(verify_ginger_merkle_path(&path, height as usize, leaf, &root).unwrap());
+        }
+
+        // Paths to empty leaves
+        for i in num_leaves..1u64 << height {
+            let path = tree.get_merkle_path(i).unwrap();
+            assert!(
+                verify_ginger_merkle_path(&path, height as usize, &FieldElement::zero(), &root)
+                    .unwrap()
+            );
+        }
+        assert!(tree.get_merkle_path(1u64 << height).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn persistent_mht_commit_rollback_reopen() {
+        let height = 5;
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let dir = temp_dir("persistence");
+
+        let leaves = (0..1u64 << height)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+
+        let committed_root = {
+            let mut tree = PersistentMerkleTree::open(&dir, height).unwrap();
+            for leaf in leaves[..20].iter() {
+                tree.append(leaf).unwrap();
+            }
+            tree.update(3, &leaves[30]).unwrap();
+            assert!(tree.has_uncommitted_changes());
+            tree.commit().unwrap();
+            assert!(!tree.has_uncommitted_changes());
+            let committed_root = tree.root().unwrap();
+
+            // Uncommitted changes are lost
+            tree.append(&leaves[20]).unwrap();
+            tree.update(0, &leaves[31]).unwrap();
+            assert_ne!(tree.root().unwrap(), committed_root);
+            committed_root
+        };
+
+        let mut tree = PersistentMerkleTree::open(&dir, height).unwrap();
+        assert_eq!(tree.num_leaves(), 20);
+        assert_eq!(tree.root().unwrap(), committed_root);
+        assert_eq!(tree.get_leaf(3).unwrap(), leaves[30]);
+        assert_eq!(tree.get_leaf(4).unwrap(), leaves[4]);
+
+        // Rollback
+        tree.append(&leaves[20]).unwrap();
+        tree.rollback();
+        assert_eq!(tree.num_leaves(), 20);
+        assert_eq!(tree.root().unwrap(), committed_root);
+
+        // Fill the tree
+        for leaf in leaves[20..].iter() {
+            tree.append(leaf).unwrap();
+        }
+        assert!(tree.append(&leaves[0]).is_err());
+        tree.commit().unwrap();
+        let full_root = tree.root().unwrap();
+        drop(tree);
+
+        // Interrupted commit: complete journal is replayed, incomplete one discarded
+        {
+            let mut tree = PersistentMerkleTree::open(&dir, height).unwrap();
+            assert_eq!(tree.root().unwrap(), full_root);
+            tree.update(7, &leaves[0]).unwrap();
+            let updated_root = tree.root().unwrap();
+
+            let journal = tree.journal().unwrap();
+
+            // Entry with a level out of range, but valid checksum
+            let mut bad_journal = journal[..journal.len() - CHECKSUM_SIZE].to_vec();
+            bad_journal[FRAME_HEADER_SIZE + 16] = height + 1;
+            let bad_journal = end_frame(bad_journal);
+            assert!(matches!(
+                tree.apply_journal(&bad_journal),
+                Err(JournalError::Invalid(_))
+            ));
+
+            fs::write(dir.join(JOURNAL_FILE), &journal[..journal.len() - 1]).unwrap();
+            drop(tree);
+            let tree = PersistentMerkleTree::open(&dir, height).unwrap();
+            assert_eq!(tree.root().unwrap(), full_root);
+            drop(tree);
+
+            fs::write(dir.join(JOURNAL_FILE), &journal).unwrap();
+            let tree = PersistentMerkleTree::open(&dir, height).unwrap();
+            assert_eq!(tree.root().unwrap(), updated_root);
+            assert_eq!(tree.get_leaf(7).unwrap(), leaves[0]);
+            assert!(!dir.join(JOURNAL_FILE).exists());
+        }
+
+        // Negative cases
+        assert!(PersistentMerkleTree::open(&dir, height + 1).is_err());
+        assert!(PersistentMerkleTree::open(temp_dir("invalid"), 0).is_err());
+        assert!(
+            PersistentMerkleTree::open(temp_dir("invalid"), PERSISTENT_MHT_MAX_HEIGHT + 1).is_err()
+        );
+
+        let mut meta = fs::read(dir.join(META_FILE)).unwrap();
+        meta[10] ^= 1;
+        fs::write(dir.join(META_FILE), &meta).unwrap();
+        assert!(PersistentMerkleTree::open(&dir, height).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+        let _ = fs::remove_dir_all(temp_dir("invalid"));
+    }
+
+    #[test]
+    fn persistent_mht_max_height() {
+        let height = PERSISTENT_MHT_MAX_HEIGHT;
+        let num_leaves = 5;
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let dir = temp_dir("max_height");
+
+        let leaves = (0..num_leaves)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+
+        let root = {
+            let mut tree = PersistentMerkleTree::open(&dir, height).unwrap();
+            for leaf in leaves.iter() {
+                tree.append(leaf).unwrap();
+            }
+            tree.commit().unwrap();
+            tree.root().unwrap()
+        };
+
+        // Only the nodes of non empty subtrees are stored
+        let stored_size = (0..=height)
+            .map(|level| {
+                fs::metadata(dir.join(format!("{}{}", NODES_FILE_PREFIX, level)))
+                    .unwrap()
+                    .len()
+            })
+            .sum::<u64>();
+        assert!(stored_size <= (2 * num_leaves as u64 + height as u64) * FIELD_SIZE as u64);
+
+        let mut tree = PersistentMerkleTree::open(&dir, height).unwrap();
+        assert_eq!(tree.num_leaves(), num_leaves as u64);
+        assert_eq!(tree.root().unwrap(), root);
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = tree.get_merkle_path(i as u64).unwrap();
+            assert!(verify_ginger_merkle_path(&path, height as usize, leaf, &root).unwrap());
+        }
+        for &i in [num_leaves as u64, (1u64 << height) - 1].iter() {
+            let path = tree.get_merkle_path(i).unwrap();
+            assert!(
+                verify_ginger_merkle_path(&path, height as usize, &FieldElement::zero(), &root)
+                    .unwrap()
+            );
+        }
+        assert!(tree.get_merkle_path(1u64 << height).is_err());
+
+        tree.update(num_leaves as u64 - 1, &leaves[0]).unwrap();
+        tree.commit().unwrap();
+        let updated_root = tree.root().unwrap();
+        assert_ne!(updated_root, root);
+        drop(tree);
+        let tree = PersistentMerkleTree::open(&dir, height).unwrap();
+        assert_eq!(tree.root().unwrap(), updated_root);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
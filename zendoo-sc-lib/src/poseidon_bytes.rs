@@ -0,0 +1,89 @@
+use cctp_primitives::utils::{commitment_tree::ByteAccumulator, poseidon_hash::*};
+use demo_circuit::type_mapping::*;
+
+//*****************************Poseidon hash over bytes*********************************************
+//
+// Byte strings are packed into field elements with the same ByteAccumulator used when computing
+// the message to sign for certificates. Before packing, each byte string is prefixed by its length
+// (8 bytes, little endian): the zero padding added by the accumulator to the last field element
+// would otherwise make, for instance, [0x01] and [0x01, 0x00] indistinguishable, and the byte
+// strings absorbed by consecutive updates could be split differently with the same result.
+//
+// The one-shot hash absorbs a domain separation tag (encoded in the same way) before the data,
+// so that hashes computed for different purposes never collide even on the same data.
+
+// Field elements encoding `bytes`, length prefixed
+pub fn bytes_to_field_elements(bytes: &[u8]) -> Result<Vec<FieldElement>, Error> {
+    ByteAccumulator::init()
+        .update(bytes.len() as u64)?
+        .update(bytes)?
+        .get_field_elements()
+}
+
+// Absorb `bytes` into `h`. Note that more than one field element is absorbed: when `h` is a
+// constant length instance, its input size must take into account
+// `bytes_to_field_elements(bytes).len()`.
+pub fn update_poseidon_hash_with_bytes(h: &mut FieldHash, bytes: &[u8]) -> Result<(), Error> {
+    for fe in bytes_to_field_elements(bytes)?.iter() {
+        update_poseidon_hash(h, fe);
+    }
+    Ok(())
+}
+
+// Poseidon hash of `data` under the domain separation tag `domain`
+pub fn poseidon_hash_bytes(domain: &[u8], data: &[u8]) -> Result<FieldElement, Error> {
+    let mut h = get_poseidon_hash_variable_length(false, None);
+    update_poseidon_hash_with_bytes(&mut h, domain)?;
+    update_poseidon_hash_with_bytes(&mut h, data)?;
+    finalize_poseidon_hash(&h)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn poseidon_hash_bytes_encoding() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let data = (0..200).map(|_| rng.gen()).collect::<Vec<u8>>();
+
+        // Deterministic, and equal to absorbing the domain and the data one after the other
+        let digest = poseidon_hash_bytes(b"domain", &data).unwrap();
+        assert_eq!(digest, poseidon_hash_bytes(b"domain", &data).unwrap());
+        let mut h = get_poseidon_hash_variable_length(false, None);
+        update_poseidon_hash_with_bytes(&mut h, b"domain").unwrap();
+        update_poseidon_hash_with_bytes(&mut h, &data).unwrap();
+        assert_eq!(digest, finalize_poseidon_hash(&h).unwrap());
+
+        // Domain separation
+        assert_ne!(digest, poseidon_hash_bytes(b"other domain", &data).unwrap());
+        assert_ne!(digest, poseidon_hash_bytes(b"", &data).unwrap());
+
+        // Trailing zeros are not ignored
+        let mut padded = data.clone();
+        padded.push(0);
+        assert_ne!(digest, poseidon_hash_bytes(b"domain", &padded).unwrap());
+        assert_ne!(
+            poseidon_hash_bytes(b"", &[]).unwrap(),
+            poseidon_hash_bytes(b"", &[0]).unwrap()
+        );
+
+        // The same bytes split differently among updates give different digests
+        let split_digest = |at: usize| {
+            let mut h = get_poseidon_hash_variable_length(false, None);
+            update_poseidon_hash_with_bytes(&mut h, &data[..at]).unwrap();
+            update_poseidon_hash_with_bytes(&mut h, &data[at..]).unwrap();
+            finalize_poseidon_hash(&h).unwrap()
+        };
+        assert_ne!(split_digest(10), split_digest(11));
+
+        // Constant length instances absorb the number of field elements of the encoding
+        let num_fes = bytes_to_field_elements(&data).unwrap().len();
+        assert!(num_fes > 1);
+        let mut h = get_poseidon_hash_constant_length(num_fes, None);
+        update_poseidon_hash_with_bytes(&mut h, &data).unwrap();
+        assert!(finalize_poseidon_hash(&h).is_ok());
+    }
+}
@@ -0,0 +1,110 @@
+use demo_circuit::{
+    poseidon_domain_hash, type_mapping::*, POSEIDON_COMMITMENT_DOMAIN, POSEIDON_KDF_DOMAIN,
+    POSEIDON_PRF_DOMAIN,
+};
+
+//*****************************Poseidon commitment, PRF and KDF*************************************
+//
+// Each primitive is a constant length Poseidon hash personalized with its own domain tag:
+//
+//     commit(message, randomness) = H_COMMITMENT_DOMAIN(randomness, message[0], ...)
+//     prf(key, input)             = H_PRF_DOMAIN(key, input)
+//     kdf(key, domain, index)     = H_KDF_DOMAIN(key, domain, index)
+//
+// The same computations are enforced in circuit by the gadgets of demo_circuit::poseidon_primitives,
+// so that e.g. note commitments and nullifiers computed natively can be checked inside a proof.
+// The commitment is hiding as long as the randomness is a uniformly random field element and it is
+// never reused.
+
+fn hash_with_domain(domain: u64, inputs: &[&FieldElement]) -> Result<FieldElement, Error> {
+    let inputs = inputs.iter().map(|input| **input).collect::<Vec<_>>();
+    poseidon_domain_hash(domain, &inputs)
+}
+
+// Commitment to `message` with randomness `randomness`
+pub fn poseidon_commit(
+    message: &[FieldElement],
+    randomness: &FieldElement,
+) -> Result<FieldElement, Error> {
+    let mut inputs = Vec::with_capacity(message.len() + 1);
+    inputs.push(randomness);
+    inputs.extend(message.iter());
+    hash_with_domain(POSEIDON_COMMITMENT_DOMAIN, &inputs)
+}
+
+// Check that (`message`, `randomness`) is an opening of `commitment`
+pub fn verify_poseidon_commitment(
+    commitment: &FieldElement,
+    message: &[FieldElement],
+    randomness: &FieldElement,
+) -> Result<bool, Error> {
+    Ok(poseidon_commit(message, randomness)? == *commitment)
+}
+
+// PRF of `input` keyed by `key`
+pub fn poseidon_prf(key: &FieldElement, input: &FieldElement) -> Result<FieldElement, Error> {
+    hash_with_domain(POSEIDON_PRF_DOMAIN, &[key, input])
+}
+
+// Key of index `index` in the domain `domain` derived from the master key `key`
+pub fn poseidon_kdf(
+    key: &FieldElement,
+    domain: &FieldElement,
+    index: u64,
+) -> Result<FieldElement, Error> {
+    hash_with_domain(
+        POSEIDON_KDF_DOMAIN,
+        &[key, domain, &FieldElement::from(index)],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn poseidon_primitives() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let message = (0..3)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        let randomness = FieldElement::rand(rng);
+        let key = FieldElement::rand(rng);
+        let domain = FieldElement::rand(rng);
+
+        // Commitment
+        let commitment = poseidon_commit(&message, &randomness).unwrap();
+        assert!(verify_poseidon_commitment(&commitment, &message, &randomness).unwrap());
+        assert!(
+            !verify_poseidon_commitment(&commitment, &message, &FieldElement::rand(rng)).unwrap()
+        );
+        assert!(!verify_poseidon_commitment(&commitment, &message[..2], &randomness).unwrap());
+        let mut other_message = message.clone();
+        other_message[1] = FieldElement::rand(rng);
+        assert!(!verify_poseidon_commitment(&commitment, &other_message, &randomness).unwrap());
+
+        // PRF
+        let prf = poseidon_prf(&key, &message[0]).unwrap();
+        assert_eq!(prf, poseidon_prf(&key, &message[0]).unwrap());
+        assert_ne!(prf, poseidon_prf(&domain, &message[0]).unwrap());
+        assert_ne!(prf, poseidon_prf(&key, &message[1]).unwrap());
+
+        // KDF
+        let derived_key = poseidon_kdf(&key, &domain, 0).unwrap();
+        assert_ne!(derived_key, poseidon_kdf(&key, &domain, 1).unwrap());
+        assert_ne!(derived_key, poseidon_kdf(&key, &message[0], 0).unwrap());
+
+        // Domain separation: same inputs, different primitives
+        assert_ne!(
+            poseidon_commit(&[message[0]], &key).unwrap(),
+            poseidon_prf(&key, &message[0]).unwrap()
+        );
+        assert_ne!(
+            poseidon_kdf(&key, &domain, 0).unwrap(),
+            poseidon_commit(&[domain, FieldElement::from(0u64)], &key).unwrap()
+        );
+    }
+}
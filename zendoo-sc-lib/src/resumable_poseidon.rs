@@ -0,0 +1,346 @@
+use cctp_primitives::utils::{poseidon_hash::*, serialization::*};
+use demo_circuit::type_mapping::*;
+
+use crate::framing::*;
+use crate::poseidon_bytes::bytes_to_field_elements;
+
+use std::convert::TryInto;
+
+//*****************************Resumable Poseidon hash**********************************************
+//
+// Wrapper of a FieldHash keeping track of how it was instantiated and, for resumable instances
+// only, of the field elements absorbed so far, so that it can be cloned and its state saved and
+// later restored (e.g. to checkpoint a hash computed over all the transactions of an epoch across
+// restarts). The internal state of the sponge is not exposed, so restoring a state means
+// absorbing again the same inputs into a new instance with the same parameters: the memory used
+// by a resumable instance and the size of its saved state grow linearly with the number of inputs.
+// Instances that don't need to be cloned or saved should not be created as resumable.
+//
+// Layout of a saved state (integers are little endian):
+//
+// | magic (4) | version (1) | constant length (1) | input size or mod rate (8) |
+// | num personalization (4) | personalization (num personalization * FIELD_SIZE) |
+// | num inputs (8) | inputs (num inputs * FIELD_SIZE) | checksum (8) |
+//
+// For constant length instances the fourth field is the input size; for variable length ones
+// it's 1 if the input is required to be a multiple of the rate, 0 otherwise. Magic, version and
+// checksum are the framing of framing.rs.
+
+const STATE_MAGIC: [u8; 4] = *b"ZPHS";
+pub const POSEIDON_STATE_VERSION: u8 = 1;
+
+fn personalization_opt(personalization: &[FieldElement]) -> Option<Vec<&FieldElement>> {
+    if personalization.is_empty() {
+        None
+    } else {
+        Some(personalization.iter().collect())
+    }
+}
+
+// New FieldHash with the given parameters
+fn instantiate(
+    input_size: Option<usize>,
+    mod_rate: bool,
+    personalization: &[FieldElement],
+) -> FieldHash {
+    let personalization = personalization_opt(personalization);
+    match input_size {
+        Some(input_size) => get_poseidon_hash_constant_length(input_size, personalization),
+        None => get_poseidon_hash_variable_length(mod_rate, personalization),
+    }
+}
+
+pub struct ResumablePoseidonHash {
+    h: FieldHash,
+    // Some(input size) for constant length instances
+    input_size: Option<usize>,
+    mod_rate: bool,
+    personalization: Vec<FieldElement>,
+    num_inputs: usize,
+    // Inputs absorbed so far, kept only by resumable instances
+    inputs: Option<Vec<FieldElement>>,
+}
+
+impl ResumablePoseidonHash {
+    fn new(
+        input_size: Option<usize>,
+        mod_rate: bool,
+        personalization: Option<Vec<&FieldElement>>,
+        resumable: bool,
+    ) -> Self {
+        let personalization: Vec<FieldElement> = personalization
+            .map(|p| p.into_iter().copied().collect())
+            .unwrap_or_default();
+        Self {
+            h: instantiate(input_size, mod_rate, &personalization),
+            input_size,
+            mod_rate,
+            personalization,
+            num_inputs: 0,
+            inputs: if resumable { Some(Vec::new()) } else { None },
+        }
+    }
+
+    pub fn constant_length(
+        input_size: usize,
+        personalization: Option<Vec<&FieldElement>>,
+    ) -> Self {
+        Self::new(Some(input_size), false, personalization, false)
+    }
+
+    pub fn variable_length(mod_rate: bool, personalization: Option<Vec<&FieldElement>>) -> Self {
+        Self::new(None, mod_rate, personalization, false)
+    }
+
+    // Same as constant_length(), but the instance can be cloned and its state saved
+    pub fn resumable_constant_length(
+        input_size: usize,
+        personalization: Option<Vec<&FieldElement>>,
+    ) -> Self {
+        Self::new(Some(input_size), false, personalization, true)
+    }
+
+    // Same as variable_length(), but the instance can be cloned and its state saved
+    pub fn resumable_variable_length(
+        mod_rate: bool,
+        personalization: Option<Vec<&FieldElement>>,
+    ) -> Self {
+        Self::new(None, mod_rate, personalization, true)
+    }
+
+    pub fn is_resumable(&self) -> bool {
+        self.inputs.is_some()
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn tracked_inputs(&self) -> Result<&[FieldElement], Error> {
+        Ok(self
+            .inputs
+            .as_deref()
+            .ok_or("Poseidon hash instance is not resumable")?)
+    }
+
+    pub fn update(&mut self, input: &FieldElement) {
+        update_poseidon_hash(&mut self.h, input);
+        self.num_inputs += 1;
+        if let Some(inputs) = self.inputs.as_mut() {
+            inputs.push(*input);
+        }
+    }
+
+    pub fn update_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for fe in bytes_to_field_elements(bytes)?.iter() {
+            self.update(fe);
+        }
+        Ok(())
+    }
+
+    pub fn finalize(&self) -> Result<FieldElement, Error> {
+        finalize_poseidon_hash(&self.h)
+    }
+
+    pub fn reset(&mut self, personalization: Option<Vec<&FieldElement>>) {
+        reset_poseidon_hash(&mut self.h, personalization.clone());
+        self.personalization = personalization
+            .map(|p| p.into_iter().copied().collect())
+            .unwrap_or_default();
+        self.num_inputs = 0;
+        if let Some(inputs) = self.inputs.as_mut() {
+            inputs.clear();
+        }
+    }
+
+    // Independent copy of a resumable instance, in the same state
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        let inputs = self.tracked_inputs()?;
+        let mut h = instantiate(self.input_size, self.mod_rate, &self.personalization);
+        for input in inputs.iter() {
+            update_poseidon_hash(&mut h, input);
+        }
+        Ok(Self {
+            h,
+            input_size: self.input_size,
+            mod_rate: self.mod_rate,
+            personalization: self.personalization.clone(),
+            num_inputs: self.num_inputs,
+            inputs: Some(inputs.to_vec()),
+        })
+    }
+
+    // Save the state of a resumable instance. The restored instance is resumable too.
+    pub fn serialize_state(&self) -> Result<Vec<u8>, Error> {
+        let inputs = self.tracked_inputs()?;
+        let mut state = begin_frame(
+            &STATE_MAGIC,
+            POSEIDON_STATE_VERSION,
+            1 + 8 + 4 + (self.personalization.len() * FIELD_SIZE) + 8 + (inputs.len() * FIELD_SIZE),
+        );
+        match self.input_size {
+            Some(input_size) => {
+                state.push(1);
+                state.extend_from_slice(&(input_size as u64).to_le_bytes());
+            }
+            None => {
+                state.push(0);
+                state.extend_from_slice(&(self.mod_rate as u64).to_le_bytes());
+            }
+        }
+        state.extend_from_slice(&(self.personalization.len() as u32).to_le_bytes());
+        for fe in self.personalization.iter() {
+            state.extend_from_slice(&serialize_to_buffer(fe, None)?);
+        }
+        state.extend_from_slice(&(inputs.len() as u64).to_le_bytes());
+        for fe in inputs.iter() {
+            state.extend_from_slice(&serialize_to_buffer(fe, None)?);
+        }
+
+        Ok(end_frame(state))
+    }
+
+    pub fn deserialize_state(state: &[u8]) -> Result<Self, Error> {
+        const HEADER_SIZE: usize = 1 + 8 + 4;
+
+        let content = open_frame(
+            state,
+            &STATE_MAGIC,
+            POSEIDON_STATE_VERSION,
+            "Poseidon hash state",
+        )?;
+        if content.len() < HEADER_SIZE + 8 {
+            Err("State too short")?
+        }
+
+        let param = u64::from_le_bytes(content[1..9].try_into().unwrap());
+        let (input_size, mod_rate) = match content[0] {
+            1 => (Some(param.try_into()?), false),
+            0 => match param {
+                0 => (None, false),
+                1 => (None, true),
+                other => Err(format!("Invalid mod rate flag: {}", other))?,
+            },
+            other => Err(format!("Invalid constant length flag: {}", other))?,
+        };
+
+        let num_personalization =
+            u32::from_le_bytes(content[9..HEADER_SIZE].try_into().unwrap()) as usize;
+        let inputs_start = HEADER_SIZE + num_personalization * FIELD_SIZE + 8;
+        if content.len() < inputs_start {
+            Err("Invalid state length")?
+        }
+        let personalization = content[HEADER_SIZE..inputs_start - 8]
+            .chunks(FIELD_SIZE)
+            .map(|b| deserialize_from_buffer(b, None, None))
+            .collect::<Result<Vec<FieldElement>, _>>()?;
+
+        let num_inputs =
+            u64::from_le_bytes(content[inputs_start - 8..inputs_start].try_into().unwrap());
+        let expected_len = num_inputs
+            .checked_mul(FIELD_SIZE as u64)
+            .and_then(|len| len.checked_add(inputs_start as u64));
+        if expected_len != Some(content.len() as u64) {
+            Err("Invalid state length")?
+        }
+
+        let mut h = Self::new(
+            input_size,
+            mod_rate,
+            personalization_opt(&personalization),
+            true,
+        );
+        for input_bytes in content[inputs_start..].chunks(FIELD_SIZE) {
+            h.update(&deserialize_from_buffer(input_bytes, None, None)?);
+        }
+
+        Ok(h)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn resumable_poseidon_hash() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let inputs = (0..10)
+            .map(|_| FieldElement::rand(rng))
+            .collect::<Vec<_>>();
+        let personalization = vec![FieldElement::rand(rng)];
+
+        let instances = vec![
+            ResumablePoseidonHash::resumable_variable_length(false, None),
+            ResumablePoseidonHash::resumable_variable_length(
+                true,
+                Some(personalization.iter().collect()),
+            ),
+            ResumablePoseidonHash::resumable_constant_length(inputs.len(), None),
+        ];
+
+        for mut h in instances.into_iter() {
+            let mut expected = instantiate(h.input_size, h.mod_rate, &h.personalization);
+            for input in inputs[..5].iter() {
+                h.update(input);
+                update_poseidon_hash(&mut expected, input);
+            }
+
+            // Checkpoint in the middle of the hash, then resume both instances
+            let state = h.serialize_state().unwrap();
+            let mut restored = ResumablePoseidonHash::deserialize_state(&state).unwrap();
+            let mut cloned = h.try_clone().unwrap();
+            assert_eq!(restored.num_inputs(), 5);
+            for input in inputs[5..].iter() {
+                h.update(input);
+                restored.update(input);
+                cloned.update(input);
+                update_poseidon_hash(&mut expected, input);
+            }
+            let digest = h.finalize().unwrap();
+            assert_eq!(digest, finalize_poseidon_hash(&expected).unwrap());
+            assert_eq!(digest, restored.finalize().unwrap());
+            assert_eq!(digest, cloned.finalize().unwrap());
+
+            // Clones are independent
+            cloned.update(&inputs[0]);
+            assert_eq!(digest, h.finalize().unwrap());
+
+            // Corrupted states
+            for i in 0..state.len() {
+                let mut corrupted = state.clone();
+                corrupted[i] ^= 1;
+                assert!(ResumablePoseidonHash::deserialize_state(&corrupted).is_err());
+            }
+            assert!(ResumablePoseidonHash::deserialize_state(&state[1..]).is_err());
+
+            // Reset
+            h.reset(None);
+            assert_eq!(h.num_inputs(), 0);
+            let restored =
+                ResumablePoseidonHash::deserialize_state(&h.serialize_state().unwrap()).unwrap();
+            assert_eq!(restored.num_inputs(), 0);
+            assert!(restored.is_resumable());
+        }
+
+        // Instances not created as resumable don't keep their inputs, so they can't be
+        // cloned or saved
+        let mut h = ResumablePoseidonHash::constant_length(inputs.len(), None);
+        let mut expected = instantiate(Some(inputs.len()), false, &[]);
+        for input in inputs.iter() {
+            h.update(input);
+            update_poseidon_hash(&mut expected, input);
+        }
+        assert!(!h.is_resumable());
+        assert_eq!(h.num_inputs(), inputs.len());
+        assert_eq!(
+            h.finalize().unwrap(),
+            finalize_poseidon_hash(&expected).unwrap()
+        );
+        assert!(h.try_clone().is_err());
+        assert!(h.serialize_state().is_err());
+    }
+}
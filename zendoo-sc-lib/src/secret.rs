@@ -0,0 +1,171 @@
+use algebra::{serialize::*, Field, SemanticallyValid};
+use cctp_primitives::utils::serialization::*;
+use demo_circuit::type_mapping::*;
+
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{compiler_fence, Ordering};
+
+//*****************************Zeroization of secret data*******************************************
+
+// Types whose memory can be securely wiped. Writes are volatile, so that they are not
+// optimized away even if the value is never read again.
+pub trait Zeroize {
+    fn zeroize(&mut self);
+}
+
+impl Zeroize for u8 {
+    fn zeroize(&mut self) {
+        unsafe { std::ptr::write_volatile(self, 0) };
+    }
+}
+
+impl<T: Zeroize> Zeroize for [T] {
+    fn zeroize(&mut self) {
+        for x in self.iter_mut() {
+            x.zeroize();
+        }
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
+// Only the initialized part of the buffer is wiped: to avoid leaving copies around,
+// secret data must never be written to a Vec that may reallocate.
+impl<T: Zeroize> Zeroize for Vec<T> {
+    fn zeroize(&mut self) {
+        self.as_mut_slice().zeroize();
+    }
+}
+
+impl Zeroize for ScalarFieldElement {
+    fn zeroize(&mut self) {
+        unsafe { std::ptr::write_volatile(self, ScalarFieldElement::zero()) };
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
+// Wrapper wiping the inner value when dropped
+pub struct Zeroizing<T: Zeroize + ?Sized>(T);
+
+impl<T: Zeroize> Zeroizing<T> {
+    pub fn new(value: T) -> Self {
+        Zeroizing(value)
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Zeroizing<T> {
+    fn clone(&self) -> Self {
+        Zeroizing(self.0.clone())
+    }
+}
+
+impl<T: Zeroize> Deref for Zeroizing<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> DerefMut for Zeroizing<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize + ?Sized> Drop for Zeroizing<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+// Never print secret data
+impl<T: Zeroize> std::fmt::Debug for Zeroizing<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Zeroizing(<redacted>)")
+    }
+}
+
+impl PartialEq for Zeroizing<ScalarFieldElement> {
+    fn eq(&self, other: &Self) -> bool {
+        match (serialize_secret(&self.0), serialize_secret(&other.0)) {
+            (Ok(a), Ok(b)) => ct_eq(&a, &b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Zeroizing<ScalarFieldElement> {}
+
+pub type ZeroizingSchnorrSk = Zeroizing<SchnorrSk>;
+pub type ZeroizingVRFSk = Zeroizing<VRFSk>;
+
+// Equality check whose running time doesn't depend on the content of `a` and `b`
+// (but only on their lengths, that are not considered secret).
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    unsafe { std::ptr::read_volatile(&diff) == 0 }
+}
+
+// Serialize `secret` into a buffer that is wiped when dropped. The buffer is allocated
+// upfront with the exact size, so that no copies are left behind by reallocations.
+pub fn serialize_secret<T: CanonicalSerialize>(secret: &T) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let mut buffer = Zeroizing::new(Vec::with_capacity(secret.serialized_size()));
+    CanonicalSerialize::serialize(secret, &mut *buffer)?;
+    Ok(buffer)
+}
+
+// Deserialize a secret from `bytes`. The caller is responsible for wiping `bytes`.
+pub fn deserialize_secret<T: CanonicalDeserialize + SemanticallyValid + Zeroize>(
+    bytes: &[u8],
+) -> Result<Zeroizing<T>, Error> {
+    Ok(Zeroizing::new(deserialize_from_buffer(
+        bytes,
+        Some(true),
+        None,
+    )?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cctp_calls::schnorr_generate_key;
+
+    #[test]
+    fn zeroizing_secret_key() {
+        let (_, mut zsk) = schnorr_generate_key();
+        let sk = *zsk;
+        assert_eq!(zsk, zsk.clone());
+        assert_eq!(format!("{:?}", zsk), "Zeroizing(<redacted>)");
+
+        // Serialization round trip
+        let sk_bytes = serialize_secret(&*zsk).unwrap();
+        assert_eq!(sk_bytes.len(), SCHNORR_SK_SIZE);
+        assert_eq!(sk_bytes.as_slice(), serialize_to_buffer(&sk, None).unwrap().as_slice());
+        let deserialized_zsk = deserialize_secret::<SchnorrSk>(&sk_bytes).unwrap();
+        assert_eq!(deserialized_zsk, zsk);
+
+        let (_, other_zsk) = schnorr_generate_key();
+        assert_ne!(zsk, other_zsk);
+
+        zsk.zeroize();
+        assert!(zsk.is_zero());
+
+        let mut buffer = vec![0xffu8; 64];
+        buffer.zeroize();
+        assert!(buffer.iter().all(|&b| b == 0));
+
+        let mut scalars = vec![ScalarFieldElement::one(); 3];
+        scalars.zeroize();
+        assert!(scalars.iter().all(|s| s.is_zero()));
+
+        assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+    }
+}
@@ -0,0 +1,577 @@
+use algebra::{serialize::*, AffineCurve, Field, FromBits, ProjectiveCurve, ToBits, UniformRand};
+use cctp_primitives::utils::serialization::*;
+use demo_circuit::type_mapping::*;
+use primitives::crh::FieldBasedHash;
+use rand::rngs::OsRng;
+
+use crate::{cctp_calls::schnorr_get_public_key, secret::*};
+
+use std::{borrow::Borrow, convert::TryInto};
+
+//*****************************Shamir secret sharing and Feldman VSS*********************************
+
+// A share of a secret key: the evaluation at `index` of the sharing polynomial.
+// Index 0 is reserved to the secret itself, so valid shares have index >= 1.
+// The value is wiped when the share is dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecretShare {
+    pub index: u32,
+    pub value: Zeroizing<ScalarFieldElement>,
+}
+
+pub const SECRET_SHARE_SIZE: usize = 4 + SCALAR_FIELD_SIZE;
+
+impl SecretShare {
+    // The buffer holding the serialized share is wiped when dropped
+    pub fn to_bytes(&self) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(SECRET_SHARE_SIZE));
+        bytes.extend_from_slice(&self.index.to_le_bytes());
+        CanonicalSerialize::serialize(&*self.value, &mut *bytes)?;
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != SECRET_SHARE_SIZE {
+            Err(format!(
+                "Invalid secret share length. Expected: {}, found: {}",
+                SECRET_SHARE_SIZE,
+                bytes.len()
+            ))?
+        }
+        let index = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        if index == 0 {
+            Err("Invalid secret share index: 0")?
+        }
+        let value = deserialize_secret(&bytes[4..])?;
+        Ok(Self { index, value })
+    }
+}
+
+// Feldman commitment to a sharing polynomial f(x) = a_0 + a_1*x + ... + a_{t-1}*x^{t-1}:
+// the list of g^{a_j}, with g the generator used for Schnorr public keys.
+// The first element is therefore the public key corresponding to the shared secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VssCommitment(Vec<G2>);
+
+impl VssCommitment {
+    // Commitment made of the given g^{a_j}, of which there must be at least one
+    pub fn new(coefficients: Vec<G2>) -> Result<Self, Error> {
+        if coefficients.is_empty() {
+            Err("Invalid VSS commitment: no coefficients")?
+        }
+        Ok(Self(coefficients))
+    }
+
+    pub fn coefficients(&self) -> &[G2] {
+        &self.0
+    }
+
+    // Number of shares needed to reconstruct the secret
+    pub fn threshold(&self) -> usize {
+        self.0.len()
+    }
+
+    // Public key corresponding to the shared secret, i.e. g^{f(0)}
+    pub fn public_key(&self) -> SchnorrPk {
+        self.0[0]
+    }
+
+    // Public key corresponding to the share of index `index`, i.e. g^{f(index)}
+    pub fn public_key_share(&self, index: u32) -> SchnorrPk {
+        let x = ScalarFieldElement::from(index as u64);
+
+        // Horner's rule in the exponent
+        let mut acc = G2Projective::zero();
+        for c in self.0.iter().rev() {
+            acc = acc.into_affine().mul(x);
+            acc.add_assign_mixed(c);
+        }
+        acc.into_affine()
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(4 + self.0.len() * SCHNORR_PK_SIZE);
+        bytes.extend_from_slice(&(self.0.len() as u32).to_le_bytes());
+        for c in self.0.iter() {
+            bytes.extend_from_slice(&serialize_to_buffer(c, Some(true))?);
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 4 {
+            Err("Invalid VSS commitment: missing length")?
+        }
+        let len = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        if len == 0 || bytes.len() != 4 + len * SCHNORR_PK_SIZE {
+            Err(format!(
+                "Invalid VSS commitment length. Expected: {}, found: {}",
+                4 + len * SCHNORR_PK_SIZE,
+                bytes.len()
+            ))?
+        }
+        let commitments = bytes[4..]
+            .chunks(SCHNORR_PK_SIZE)
+            .map(|c| deserialize_from_buffer(c, Some(true), Some(true)))
+            .collect::<Result<Vec<G2>, _>>()?;
+        Self::new(commitments)
+    }
+}
+
+// Domain separator for the challenges of the DKG proofs of knowledge
+const DKG_POK_TAG: u64 = 0x444b475f504f4b; // "DKG_POK"
+
+pub const DKG_PROOF_OF_KNOWLEDGE_SIZE: usize = SCHNORR_PK_SIZE + SCALAR_FIELD_SIZE;
+
+// Schnorr proof of knowledge of the secret a_0 dealt by a DKG participant, binding it to the
+// participant's index and to a `context` identifying the DKG session. Verifying it before
+// combining the commitments prevents rogue-key attacks, in which a participant chooses its
+// commitment as a function of the others' ones to control the joint public key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DkgProofOfKnowledge {
+    pub commitment: G2,
+    pub response: ScalarFieldElement,
+}
+
+impl DkgProofOfKnowledge {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(DKG_PROOF_OF_KNOWLEDGE_SIZE);
+        bytes.extend_from_slice(&serialize_to_buffer(&self.commitment, Some(true))?);
+        bytes.extend_from_slice(&serialize_to_buffer(&self.response, None)?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != DKG_PROOF_OF_KNOWLEDGE_SIZE {
+            Err(format!(
+                "Invalid proof of knowledge length. Expected: {}, found: {}",
+                DKG_PROOF_OF_KNOWLEDGE_SIZE,
+                bytes.len()
+            ))?
+        }
+        let commitment =
+            deserialize_from_buffer(&bytes[..SCHNORR_PK_SIZE], Some(true), Some(true))?;
+        let response = deserialize_from_buffer(&bytes[SCHNORR_PK_SIZE..], None, None)?;
+        Ok(Self {
+            commitment,
+            response,
+        })
+    }
+}
+
+// c = H(tag, index, context, g^{a_0}, R), with the most significant bit dropped to always
+// fit the scalar field
+fn dkg_pok_challenge(
+    index: u32,
+    context: &FieldElement,
+    public_key: &G2,
+    commitment: &G2,
+) -> Result<ScalarFieldElement, Error> {
+    let c = FieldHash::init_constant_length(7, None)
+        .update(FieldElement::from(DKG_POK_TAG))
+        .update(FieldElement::from(index as u64))
+        .update(*context)
+        .update(public_key.x)
+        .update(public_key.y)
+        .update(commitment.x)
+        .update(commitment.y)
+        .finalize()?;
+    let mut c_bits = c.write_bits();
+    c_bits[0] = false;
+    Ok(ScalarFieldElement::read_bits(c_bits)?)
+}
+
+// Verify the proof of knowledge of the dealer of index `index`, in the DKG session
+// identified by `context`, of the secret committed in `commitment`,
+// i.e. that g^{response} == R + c * g^{a_0}
+pub fn verify_dkg_proof_of_knowledge(
+    index: u32,
+    context: &FieldElement,
+    commitment: &VssCommitment,
+    proof: &DkgProofOfKnowledge,
+) -> bool {
+    let public_key = commitment.public_key();
+    if index == 0 || public_key.is_zero() || proof.commitment.is_zero() {
+        return false;
+    }
+    match dkg_pok_challenge(index, context, &public_key, &proof.commitment) {
+        Ok(c) => {
+            let mut expected = public_key.mul(c);
+            expected.add_assign_mixed(&proof.commitment);
+            schnorr_get_public_key(&proof.response) == expected.into_affine()
+        }
+        Err(_) => false,
+    }
+}
+
+// Random polynomial of degree `threshold - 1` whose constant term is `secret`.
+// The coefficients are wiped when dropped.
+fn sample_polynomial(
+    secret: &ScalarFieldElement,
+    threshold: usize,
+) -> Zeroizing<Vec<ScalarFieldElement>> {
+    let mut rng = OsRng;
+    let mut coeffs = Zeroizing::new(Vec::with_capacity(threshold));
+    coeffs.push(*secret);
+    for _ in 1..threshold {
+        coeffs.push(ScalarFieldElement::rand(&mut rng));
+    }
+    coeffs
+}
+
+fn evaluate_polynomial(coeffs: &[ScalarFieldElement], index: u32) -> ScalarFieldElement {
+    let x = ScalarFieldElement::from(index as u64);
+    coeffs
+        .iter()
+        .rev()
+        .fold(ScalarFieldElement::zero(), |acc, c| acc * &x + c)
+}
+
+fn commit_to_polynomial(coeffs: &[ScalarFieldElement]) -> VssCommitment {
+    VssCommitment(coeffs.iter().map(schnorr_get_public_key).collect())
+}
+
+fn check_sharing_params(threshold: usize, num_shares: usize) -> Result<(), Error> {
+    if threshold == 0 || threshold > num_shares || num_shares > u32::MAX as usize {
+        Err(format!(
+            "Invalid sharing parameters: threshold {}, number of shares {}",
+            threshold, num_shares
+        ))?
+    }
+    Ok(())
+}
+
+// Lagrange coefficient, evaluated at 0, of the share of index `index` among the
+// shares of indices `indices`.
+pub fn lagrange_coefficient_at_zero(
+    index: u32,
+    indices: &[u32],
+) -> Result<ScalarFieldElement, Error> {
+    let x_i = ScalarFieldElement::from(index as u64);
+    let mut num = ScalarFieldElement::one();
+    let mut den = ScalarFieldElement::one();
+    for &j in indices.iter().filter(|&&j| j != index) {
+        let x_j = ScalarFieldElement::from(j as u64);
+        num *= &x_j;
+        den *= &(x_j - &x_i);
+    }
+    let den_inv = den
+        .inverse()
+        .ok_or("Unable to compute Lagrange coefficient: duplicated indices")?;
+    Ok(num * &den_inv)
+}
+
+fn check_indices(indices: &[u32]) -> Result<(), Error> {
+    for (i, &index) in indices.iter().enumerate() {
+        if index == 0 {
+            Err("Invalid share index: 0")?
+        }
+        if indices[..i].contains(&index) {
+            Err(format!("Duplicated share index: {}", index))?
+        }
+    }
+    Ok(())
+}
+
+// Split `secret` into `num_shares` shares, any `threshold` of which are enough to
+// reconstruct it. Returns the shares, of indices 1..=num_shares, together with the
+// Feldman commitment allowing each holder to verify its own share.
+pub fn shamir_split(
+    secret: &ScalarFieldElement,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(Vec<SecretShare>, VssCommitment), Error> {
+    check_sharing_params(threshold, num_shares)?;
+
+    let coeffs = sample_polynomial(secret, threshold);
+    let shares = (1..=num_shares as u32)
+        .map(|index| SecretShare {
+            index,
+            value: Zeroizing::new(evaluate_polynomial(&coeffs, index)),
+        })
+        .collect();
+
+    Ok((shares, commit_to_polynomial(&coeffs)))
+}
+
+// Reconstruct the secret from `shares`. Passing less shares than the threshold
+// used when splitting will silently result in a wrong secret: use the commitment,
+// if available, to check the result.
+pub fn shamir_reconstruct<S: Borrow<SecretShare>>(
+    shares: &[S],
+) -> Result<Zeroizing<ScalarFieldElement>, Error> {
+    if shares.is_empty() {
+        Err("No shares to reconstruct the secret from")?
+    }
+    let indices = shares.iter().map(|s| s.borrow().index).collect::<Vec<_>>();
+    check_indices(&indices)?;
+
+    let mut secret = Zeroizing::new(ScalarFieldElement::zero());
+    for share in shares.iter().map(Borrow::borrow) {
+        *secret += &(lagrange_coefficient_at_zero(share.index, &indices)? * &*share.value);
+    }
+    Ok(secret)
+}
+
+// Verify that `share` is consistent with the polynomial committed in `commitment`,
+// i.e. that g^{share} == prod_j C_j^{index^j}
+pub fn verify_secret_share(share: &SecretShare, commitment: &VssCommitment) -> bool {
+    share.index != 0
+        && schnorr_get_public_key(&share.value) == commitment.public_key_share(share.index)
+}
+
+//*****************************Distributed key generation*******************************************
+
+// A participant to a Pedersen DKG, in which each of the `num_participants` parties acts as
+// the dealer of a Feldman VSS of a random secret. The joint secret key, never known to
+// anyone, is the sum of all the dealt secrets; any `threshold` participants can use their
+// shares of it (e.g. to sign with FROST).
+//
+// Protocol:
+// 1) Each participant creates a DkgParticipant and broadcasts its commitment(), together
+//    with its proof_of_knowledge() for the `context` identifying the DKG session;
+// 2) Each participant checks the proofs of all the others with
+//    verify_dkg_proof_of_knowledge(), aborting if any is invalid, and privately sends
+//    share_for(j) to participant j;
+// 3) Each participant checks the received shares against the dealers' commitments
+//    with verify_secret_share() and, if all are valid, computes its share of the
+//    joint secret with combine_dkg_shares() and the joint commitment (holding the
+//    joint public key) with combine_vss_commitments().
+pub struct DkgParticipant {
+    index: u32,
+    num_participants: usize,
+    coeffs: Zeroizing<Vec<ScalarFieldElement>>,
+    commitment: VssCommitment,
+}
+
+impl DkgParticipant {
+    pub fn new(index: u32, threshold: usize, num_participants: usize) -> Result<Self, Error> {
+        check_sharing_params(threshold, num_participants)?;
+        if index == 0 || index as usize > num_participants {
+            Err(format!("Invalid participant index: {}", index))?
+        }
+
+        let secret = Zeroizing::new(ScalarFieldElement::rand(&mut OsRng));
+        let coeffs = sample_polynomial(&secret, threshold);
+        let commitment = commit_to_polynomial(&coeffs);
+
+        Ok(Self {
+            index,
+            num_participants,
+            coeffs,
+            commitment,
+        })
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn commitment(&self) -> &VssCommitment {
+        &self.commitment
+    }
+
+    // Proof of knowledge of the secret dealt by this participant, for the DKG session
+    // identified by `context`
+    pub fn proof_of_knowledge(&self, context: &FieldElement) -> Result<DkgProofOfKnowledge, Error> {
+        let k = Zeroizing::new(ScalarFieldElement::rand(&mut OsRng));
+        let commitment = schnorr_get_public_key(&k);
+        let c = dkg_pok_challenge(
+            self.index,
+            context,
+            &self.commitment.public_key(),
+            &commitment,
+        )?;
+        Ok(DkgProofOfKnowledge {
+            commitment,
+            response: *k + &(self.coeffs[0] * &c),
+        })
+    }
+
+    // Share of this participant's secret to be privately sent to participant `index`
+    pub fn share_for(&self, index: u32) -> Result<SecretShare, Error> {
+        if index == 0 || index as usize > self.num_participants {
+            Err(format!("Invalid participant index: {}", index))?
+        }
+        Ok(SecretShare {
+            index,
+            value: Zeroizing::new(evaluate_polynomial(&self.coeffs, index)),
+        })
+    }
+}
+
+// Combine the shares received by a participant from all the dealers (including
+// the one dealt by itself) into its share of the joint secret key.
+pub fn combine_dkg_shares<S: Borrow<SecretShare>>(shares: &[S]) -> Result<SecretShare, Error> {
+    let index = match shares.first() {
+        Some(share) => share.borrow().index,
+        None => Err("No shares to combine")?,
+    };
+    if shares.iter().any(|s| s.borrow().index != index) {
+        Err("Shares to combine must all have the same index")?
+    }
+    let mut value = Zeroizing::new(ScalarFieldElement::zero());
+    for share in shares.iter().map(Borrow::borrow) {
+        *value += &*share.value;
+    }
+    Ok(SecretShare { index, value })
+}
+
+// Combine the commitments of all the dealers into the commitment to the joint
+// sharing polynomial. Its public_key() is the joint public key.
+pub fn combine_vss_commitments(commitments: &[VssCommitment]) -> Result<VssCommitment, Error> {
+    let threshold = match commitments.first() {
+        Some(c) => c.threshold(),
+        None => Err("No commitments to combine")?,
+    };
+    if commitments.iter().any(|c| c.threshold() != threshold) {
+        Err("Commitments to combine must all have the same threshold")?
+    }
+    let combined = (0..threshold)
+        .map(|j| {
+            commitments
+                .iter()
+                .fold(G2Projective::zero(), |mut acc, c| {
+                    acc.add_assign_mixed(&c.0[j]);
+                    acc
+                })
+                .into_affine()
+        })
+        .collect();
+    Ok(VssCommitment(combined))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shamir_split_reconstruct() {
+        let (pk, sk) = crate::cctp_calls::schnorr_generate_key();
+        let (shares, commitment) = shamir_split(&sk, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(commitment.threshold(), 3);
+        assert_eq!(commitment.public_key(), pk);
+
+        for share in shares.iter() {
+            assert!(verify_secret_share(share, &commitment));
+
+            // Serialization/deserialization
+            let share_bytes = share.to_bytes().unwrap();
+            assert_eq!(share_bytes.len(), SECRET_SHARE_SIZE);
+            assert_eq!(share, &SecretShare::from_bytes(&share_bytes).unwrap());
+        }
+
+        let commitment_bytes = commitment.to_bytes().unwrap();
+        assert_eq!(commitment, VssCommitment::from_bytes(&commitment_bytes).unwrap());
+        assert!(VssCommitment::new(vec![]).is_err());
+        assert!(VssCommitment::from_bytes(&0u32.to_le_bytes()).is_err());
+
+        // Any 3 shares reconstruct the secret
+        assert_eq!(shamir_reconstruct(&shares[..3]).unwrap(), sk);
+        assert_eq!(shamir_reconstruct(&shares[2..]).unwrap(), sk);
+        assert_eq!(
+            shamir_reconstruct(&[shares[4].clone(), shares[0].clone(), shares[2].clone()])
+                .unwrap(),
+            sk
+        );
+
+        // Less shares don't
+        assert_ne!(shamir_reconstruct(&shares[..2]).unwrap(), sk);
+
+        // Negative cases
+        let mut wrong_share = shares[0].clone();
+        *wrong_share.value += &ScalarFieldElement::one();
+        assert!(!verify_secret_share(&wrong_share, &commitment));
+        assert!(shamir_reconstruct(&[shares[0].clone(), shares[0].clone()]).is_err());
+        assert!(shamir_split(&sk, 0, 5).is_err());
+        assert!(shamir_split(&sk, 6, 5).is_err());
+    }
+
+    #[test]
+    fn dkg() {
+        let threshold = 3;
+        let n = 5;
+
+        let participants = (1..=n as u32)
+            .map(|i| DkgParticipant::new(i, threshold, n).unwrap())
+            .collect::<Vec<_>>();
+
+        // Each participant proves the knowledge of its secret
+        let context = FieldElement::rand(&mut OsRng);
+        for p in participants.iter() {
+            let proof = p.proof_of_knowledge(&context).unwrap();
+            let proof_bytes = proof.to_bytes().unwrap();
+            assert_eq!(proof_bytes.len(), DKG_PROOF_OF_KNOWLEDGE_SIZE);
+            assert_eq!(
+                proof,
+                DkgProofOfKnowledge::from_bytes(&proof_bytes).unwrap()
+            );
+            assert!(verify_dkg_proof_of_knowledge(
+                p.index(),
+                &context,
+                p.commitment(),
+                &proof
+            ));
+
+            // Proofs are bound to the participant, the session and the commitment
+            assert!(!verify_dkg_proof_of_knowledge(
+                p.index() % n as u32 + 1,
+                &context,
+                p.commitment(),
+                &proof
+            ));
+            assert!(!verify_dkg_proof_of_knowledge(
+                p.index(),
+                &(context + &FieldElement::one()),
+                p.commitment(),
+                &proof
+            ));
+        }
+
+        // A rogue key, chosen to cancel the others' ones, can't be proven
+        let rogue_commitment = {
+            let mut rogue_key = G2Projective::zero();
+            for p in participants[1..].iter() {
+                rogue_key -= &p.commitment().public_key().into_projective();
+            }
+            VssCommitment::new(vec![rogue_key.into_affine(); threshold]).unwrap()
+        };
+        let forged_proof = participants[0].proof_of_knowledge(&context).unwrap();
+        assert!(!verify_dkg_proof_of_knowledge(
+            1,
+            &context,
+            &rogue_commitment,
+            &forged_proof
+        ));
+
+        let commitments = participants
+            .iter()
+            .map(|p| p.commitment().clone())
+            .collect::<Vec<_>>();
+        let joint_commitment = combine_vss_commitments(&commitments).unwrap();
+
+        // Each participant verifies and combines the shares it received
+        let joint_shares = (1..=n as u32)
+            .map(|j| {
+                let received = participants
+                    .iter()
+                    .map(|p| {
+                        let share = p.share_for(j).unwrap();
+                        assert!(verify_secret_share(&share, p.commitment()));
+                        share
+                    })
+                    .collect::<Vec<_>>();
+                combine_dkg_shares(&received).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        for share in joint_shares.iter() {
+            assert!(verify_secret_share(share, &joint_commitment));
+        }
+
+        // Any threshold shares give the secret key of the joint public key
+        let sk = shamir_reconstruct(&joint_shares[1..4]).unwrap();
+        assert_eq!(schnorr_get_public_key(&sk), joint_commitment.public_key());
+    }
+}
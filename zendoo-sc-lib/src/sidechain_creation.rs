@@ -0,0 +1,509 @@
+use crate::{certificate::MAX_MONEY, envelope::*, mc_serialization::*};
+use algebra::{FpParameters, PrimeField};
+use cctp_primitives::{
+    proving_system::{check_proof_vk_size, ProvingSystem, ZendooVerifierKey},
+    utils::{compute_sc_id, data_structures::*, serialization::*},
+};
+use demo_circuit::type_mapping::*;
+
+use std::convert::TryInto;
+
+//*****************************Mainchain sidechain creation outputs*********************************
+//
+// Parameters of a sidechain declared in the sidechain creation output of a mainchain transaction,
+// with the checks the mainchain performs on them, so that they can be validated before the
+// transaction is broadcast.
+//
+// Layout of a serialized sidechain creation output (integers are little endian, vectors are
+// prefixed by their length as a Bitcoin compact size, optional values by a 1 byte flag):
+//
+// | withdrawal epoch length (4) | amount (8) | pub key (FIELD_SIZE) | custom creation data (vector) |
+// | constant (optional vector) | cert vk (vector) | csw vk (optional vector) |
+// | field element configs (vector of n bits (1)) |
+// | bit vector configs (vector of bit vector size bits (4) | max compressed byte size (4)) |
+// | ft min amount (8) | btr fee (8) | mc btr request data length (1) |
+
+/// Bounds of the withdrawal epoch length (the minimum is the regtest one, other networks may
+/// require longer epochs).
+pub const MIN_WITHDRAWAL_EPOCH_LENGTH: u32 = 2;
+pub const MAX_WITHDRAWAL_EPOCH_LENGTH: u32 = 4032;
+
+/// Max length of the custom creation data.
+pub const MAX_SC_CUSTOM_DATA_LEN: usize = 1024;
+
+/// Max number of field elements of the data of mainchain backward transfer requests.
+pub const MAX_SC_MBTR_DATA_LEN: u8 = 4;
+
+/// Max size of the bit vector of a bit vector certificate field.
+pub const MAX_BIT_VECTOR_SIZE_BITS: u32 = 1_000_192;
+
+/// Limits on the verification keys of sidechains, and on the proofs they verify, enforced
+/// by the mainchain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerificationKeyLimits {
+    pub supported_segment_size: usize,
+    pub zk: bool,
+    pub max_proof_size: usize,
+    pub max_vk_size: usize,
+}
+
+pub struct SidechainCreationParams {
+    pub withdrawal_epoch_length: u32,
+    pub amount: u64,
+    pub pub_key: [u8; FIELD_SIZE],
+    pub custom_creation_data: Vec<u8>,
+    pub constant: Option<FieldElement>,
+    pub cert_verification_key: Vec<u8>,
+    pub csw_verification_key: Option<Vec<u8>>,
+    pub custom_field_elements_configs: Vec<u8>,
+    pub custom_bitvector_elements_configs: Vec<BitVectorElementsConfig>,
+    pub ft_min_amount: u64,
+    pub btr_fee: u64,
+    pub mc_btr_request_data_length: u8,
+}
+
+// Check that `vk` is a valid verification key, with proofs and vk within `limits`
+fn check_verification_key(
+    vk: &[u8],
+    name: &str,
+    limits: &VerificationKeyLimits,
+) -> Result<(), Error> {
+    let vk: ZendooVerifierKey = deserialize_from_buffer(
+        open_artifact(ArtifactType::VerifierKey, vk)?,
+        Some(true),
+        Some(true),
+    )
+    .map_err(|e| format!("Invalid {}: {}", name, e))?;
+
+    let ps_type = vk.get_proving_system_type();
+    if let ProvingSystem::Undefined = ps_type {
+        Err(format!("Invalid {}: undefined proving system", name))?
+    }
+
+    let index_info = match vk {
+        ZendooVerifierKey::CoboundaryMarlin(cob_marlin_vk) => cob_marlin_vk.index_info,
+        ZendooVerifierKey::Darlin(darlin_vk) => darlin_vk.index_info,
+    };
+
+    if !check_proof_vk_size(
+        limits.supported_segment_size,
+        index_info,
+        limits.zk,
+        ps_type,
+        limits.max_proof_size,
+        limits.max_vk_size,
+    ) {
+        Err(format!(
+            "The proof or the {} exceed the max sizes ({} and {} bytes)",
+            name, limits.max_proof_size, limits.max_vk_size
+        ))?
+    }
+    Ok(())
+}
+
+impl SidechainCreationParams {
+    /// Id of the sidechain created by output `out_idx` of the transaction `tx_hash`.
+    pub fn sc_id(tx_hash: &[u8; 32], out_idx: u32) -> Result<FieldElement, Error> {
+        compute_sc_id(tx_hash, out_idx)
+    }
+
+    /// Check the parameters as the mainchain does: `cert_circuit_has_constant` tells if the
+    /// certificate circuit has the constant among its public inputs, and `vk_limits` are the
+    /// limits on the verification keys.
+    pub fn validate(
+        &self,
+        cert_circuit_has_constant: bool,
+        vk_limits: &VerificationKeyLimits,
+    ) -> Result<(), Error> {
+        if self.withdrawal_epoch_length < MIN_WITHDRAWAL_EPOCH_LENGTH
+            || self.withdrawal_epoch_length > MAX_WITHDRAWAL_EPOCH_LENGTH
+        {
+            Err(format!(
+                "Withdrawal epoch length must be between {} and {}, found {}",
+                MIN_WITHDRAWAL_EPOCH_LENGTH,
+                MAX_WITHDRAWAL_EPOCH_LENGTH,
+                self.withdrawal_epoch_length
+            ))?
+        }
+
+        for (name, amount) in [
+            ("amount", self.amount),
+            ("ft_min_amount", self.ft_min_amount),
+            ("btr_fee", self.btr_fee),
+        ]
+        .iter()
+        {
+            if *amount > MAX_MONEY {
+                Err(format!("Invalid {}: {}", name, amount))?
+            }
+        }
+
+        if self.custom_creation_data.len() > MAX_SC_CUSTOM_DATA_LEN {
+            Err(format!(
+                "Custom creation data must be at most {} bytes long, found {}",
+                MAX_SC_CUSTOM_DATA_LEN,
+                self.custom_creation_data.len()
+            ))?
+        }
+
+        if self.mc_btr_request_data_length > MAX_SC_MBTR_DATA_LEN {
+            Err(format!(
+                "Mainchain backward transfer request data length must be at most {}, found {}",
+                MAX_SC_MBTR_DATA_LEN, self.mc_btr_request_data_length
+            ))?
+        }
+
+        // Custom field elements must fit in a field element
+        let field_capacity = <<FieldElement as PrimeField>::Params as FpParameters>::CAPACITY;
+        for &n_bits in self.custom_field_elements_configs.iter() {
+            if n_bits == 0 || n_bits as u32 > field_capacity {
+                Err(format!(
+                    "Custom field element bits must be between 1 and {}, found {}",
+                    field_capacity, n_bits
+                ))?
+            }
+        }
+
+        // Bit vectors are made of whole bytes, split in the leaves of their Merkle tree
+        for config in self.custom_bitvector_elements_configs.iter() {
+            let size = config.bit_vector_size_bits;
+            if size == 0
+                || size > MAX_BIT_VECTOR_SIZE_BITS
+                || size % 8 != 0
+                || size % field_capacity != 0
+            {
+                Err(format!(
+                    "Bit vector size must be a positive multiple of 8 and {}, at most {}, found {}",
+                    field_capacity, MAX_BIT_VECTOR_SIZE_BITS, size
+                ))?
+            }
+            if config.max_compressed_byte_size == 0 {
+                Err("Bit vector max compressed byte size must be positive")?
+            }
+        }
+
+        if self.constant.is_some() != cert_circuit_has_constant {
+            Err(if cert_circuit_has_constant {
+                "The certificate circuit requires a constant"
+            } else {
+                "The certificate circuit doesn't use a constant"
+            })?
+        }
+
+        check_verification_key(
+            &self.cert_verification_key,
+            "cert verification key",
+            vk_limits,
+        )?;
+        if let Some(csw_verification_key) = self.csw_verification_key.as_ref() {
+            check_verification_key(csw_verification_key, "csw verification key", vk_limits)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(
+            4 + 8
+                + FIELD_SIZE
+                + self.custom_creation_data.len()
+                + FIELD_SIZE
+                + self.cert_verification_key.len()
+                + self.csw_verification_key.as_ref().map_or(0, |vk| vk.len())
+                + self.custom_field_elements_configs.len()
+                + self.custom_bitvector_elements_configs.len() * 8
+                + 32,
+        );
+        bytes.extend_from_slice(&self.withdrawal_epoch_length.to_le_bytes());
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.pub_key);
+        write_vector(&mut bytes, &self.custom_creation_data);
+
+        match self.constant.as_ref() {
+            Some(constant) => {
+                bytes.push(1);
+                write_vector(&mut bytes, &serialize_to_buffer(constant, None)?);
+            }
+            None => bytes.push(0),
+        }
+
+        write_vector(&mut bytes, &self.cert_verification_key);
+        match self.csw_verification_key.as_ref() {
+            Some(csw_verification_key) => {
+                bytes.push(1);
+                write_vector(&mut bytes, csw_verification_key);
+            }
+            None => bytes.push(0),
+        }
+
+        write_vector(&mut bytes, &self.custom_field_elements_configs);
+        write_compact_size(&mut bytes, self.custom_bitvector_elements_configs.len());
+        for config in self.custom_bitvector_elements_configs.iter() {
+            bytes.extend_from_slice(&config.bit_vector_size_bits.to_le_bytes());
+            bytes.extend_from_slice(&config.max_compressed_byte_size.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&self.ft_min_amount.to_le_bytes());
+        bytes.extend_from_slice(&self.btr_fee.to_le_bytes());
+        bytes.push(self.mc_btr_request_data_length);
+
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = &mut bytes;
+
+        let read_flag = |bytes: &mut &[u8]| -> Result<bool, Error> {
+            match take(bytes, 1)?[0] {
+                0 => Ok(false),
+                1 => Ok(true),
+                flag => Err(format!("Invalid optional flag: {}", flag).into()),
+            }
+        };
+
+        let withdrawal_epoch_length = read_u32(bytes)?;
+        let amount = read_u64(bytes)?;
+        let pub_key = take(bytes, FIELD_SIZE)?.try_into().unwrap();
+        let custom_creation_data = read_vector(bytes)?.to_vec();
+        let constant = if read_flag(bytes)? {
+            Some(read_field_element(read_vector(bytes)?)?)
+        } else {
+            None
+        };
+        let cert_verification_key = read_vector(bytes)?.to_vec();
+        let csw_verification_key = if read_flag(bytes)? {
+            Some(read_vector(bytes)?.to_vec())
+        } else {
+            None
+        };
+
+        let custom_field_elements_configs = read_vector(bytes)?.to_vec();
+        let num_bitvector_configs = read_compact_size(bytes)?;
+        let custom_bitvector_elements_configs = (0..num_bitvector_configs)
+            .map(|_| -> Result<BitVectorElementsConfig, Error> {
+                Ok(BitVectorElementsConfig {
+                    bit_vector_size_bits: read_u32(bytes)?,
+                    max_compressed_byte_size: read_u32(bytes)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let ft_min_amount = read_u64(bytes)?;
+        let btr_fee = read_u64(bytes)?;
+        let mc_btr_request_data_length = take(bytes, 1)?[0];
+
+        if !bytes.is_empty() {
+            Err(format!(
+                "Invalid sidechain creation output: {} trailing bytes",
+                bytes.len()
+            ))?
+        }
+
+        Ok(Self {
+            withdrawal_epoch_length,
+            amount,
+            pub_key,
+            custom_creation_data,
+            constant,
+            cert_verification_key,
+            csw_verification_key,
+            custom_field_elements_configs,
+            custom_bitvector_elements_configs,
+            ft_min_amount,
+            btr_fee,
+            mc_btr_request_data_length,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{get_random_field_element, IncrementalCommitmentTree};
+    use cctp_primitives::proving_system::init_dlog_keys;
+    use demo_circuit::{generate_circuit_keypair, get_instance_for_setup};
+
+    const VK_LIMITS: VerificationKeyLimits = VerificationKeyLimits {
+        supported_segment_size: 1 << 14,
+        zk: false,
+        max_proof_size: 7000,
+        max_vk_size: 4000,
+    };
+
+    fn sample_params(cert_verification_key: Vec<u8>) -> SidechainCreationParams {
+        SidechainCreationParams {
+            withdrawal_epoch_length: 10,
+            amount: 1000,
+            pub_key: [1u8; FIELD_SIZE],
+            custom_creation_data: vec![1, 2, 3],
+            constant: Some(get_random_field_element(1)),
+            csw_verification_key: None,
+            cert_verification_key,
+            custom_field_elements_configs: vec![8, 254],
+            custom_bitvector_elements_configs: vec![BitVectorElementsConfig {
+                bit_vector_size_bits: 254 * 8,
+                max_compressed_byte_size: 300,
+            }],
+            ft_min_amount: 20,
+            btr_fee: 10,
+            mc_btr_request_data_length: 1,
+        }
+    }
+
+    #[test]
+    fn serialization() {
+        let params = sample_params(vec![2, 5, 6, 7]);
+        let params_bytes = params.to_bytes().unwrap();
+        let deserialized = SidechainCreationParams::from_bytes(&params_bytes).unwrap();
+        assert_eq!(deserialized.to_bytes().unwrap(), params_bytes);
+        assert_eq!(deserialized.constant, params.constant);
+        assert_eq!(
+            deserialized.cert_verification_key,
+            params.cert_verification_key
+        );
+
+        let params = SidechainCreationParams {
+            constant: None,
+            csw_verification_key: Some(vec![2, 8]),
+            ..sample_params(vec![2, 5, 6, 7])
+        };
+        let params_bytes = params.to_bytes().unwrap();
+        let deserialized = SidechainCreationParams::from_bytes(&params_bytes).unwrap();
+        assert_eq!(deserialized.to_bytes().unwrap(), params_bytes);
+        assert!(deserialized.constant.is_none());
+        assert_eq!(deserialized.csw_verification_key, Some(vec![2, 8]));
+
+        assert!(
+            SidechainCreationParams::from_bytes(&params_bytes[..params_bytes.len() - 1]).is_err()
+        );
+        let mut longer_bytes = params_bytes;
+        longer_bytes.push(0);
+        assert!(SidechainCreationParams::from_bytes(&longer_bytes).is_err());
+    }
+
+    #[test]
+    fn commitment_tree() {
+        let params = sample_params(vec![2, 5, 6, 7]);
+        let tx_hash = [3u8; FIELD_SIZE];
+        let sc_id = SidechainCreationParams::sc_id(&tx_hash, 1).unwrap();
+
+        let mut tree = IncrementalCommitmentTree::create();
+        assert!(tree.add_sc_creation(&sc_id, &tx_hash, 1, &params));
+
+        // Same commitment of the sidechain creation added field by field
+        let mut reference = IncrementalCommitmentTree::create();
+        assert!(reference.add_scc(
+            &sc_id,
+            params.amount,
+            &params.pub_key,
+            &tx_hash,
+            1,
+            params.withdrawal_epoch_length,
+            params.mc_btr_request_data_length,
+            Some(params.custom_field_elements_configs.as_slice()),
+            Some(params.custom_bitvector_elements_configs.as_slice()),
+            params.btr_fee,
+            params.ft_min_amount,
+            Some(params.custom_creation_data.as_slice()),
+            params.constant.as_ref(),
+            &params.cert_verification_key,
+            None,
+        ));
+        assert_eq!(tree.get_commitment(), reference.get_commitment());
+        assert_ne!(SidechainCreationParams::sc_id(&tx_hash, 2).unwrap(), sc_id);
+    }
+
+    #[test]
+    fn validation() {
+        let vk_path = std::env::temp_dir().join("sc_creation_sample_vk");
+        let pk_path = std::env::temp_dir().join("sc_creation_sample_pk");
+
+        init_dlog_keys(ProvingSystem::CoboundaryMarlin, 1 << 17, 1 << 14).unwrap();
+        generate_circuit_keypair(
+            get_instance_for_setup(3),
+            ProvingSystem::CoboundaryMarlin,
+            &pk_path,
+            &vk_path,
+            VK_LIMITS.max_proof_size,
+            VK_LIMITS.max_vk_size,
+            VK_LIMITS.zk,
+            Some(true),
+            Some(true),
+        )
+        .unwrap();
+        let vk = std::fs::read(&vk_path).unwrap();
+
+        let params = sample_params(vk.clone());
+        assert!(params.validate(true, &VK_LIMITS).is_ok());
+
+        // Sealed vks are accepted too
+        let sealed_params = SidechainCreationParams {
+            csw_verification_key: Some(seal_artifact(ArtifactType::VerifierKey, &vk)),
+            ..sample_params(vk.clone())
+        };
+        assert!(sealed_params.validate(true, &VK_LIMITS).is_ok());
+
+        // Constant not matching the circuit
+        assert!(params.validate(false, &VK_LIMITS).is_err());
+
+        // Vk exceeding the limits, or invalid
+        let small_limits = VerificationKeyLimits {
+            max_vk_size: 1,
+            ..VK_LIMITS
+        };
+        assert!(params.validate(true, &small_limits).is_err());
+        assert!(sample_params(vk[1..].to_vec())
+            .validate(true, &VK_LIMITS)
+            .is_err());
+        let invalid_csw_vk = SidechainCreationParams {
+            csw_verification_key: Some(vec![2, 5, 6, 7]),
+            ..sample_params(vk.clone())
+        };
+        assert!(invalid_csw_vk.validate(true, &VK_LIMITS).is_err());
+
+        // Parameters out of bounds
+        let invalid = vec![
+            SidechainCreationParams {
+                withdrawal_epoch_length: MIN_WITHDRAWAL_EPOCH_LENGTH - 1,
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                withdrawal_epoch_length: MAX_WITHDRAWAL_EPOCH_LENGTH + 1,
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                amount: MAX_MONEY + 1,
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                custom_creation_data: vec![0; MAX_SC_CUSTOM_DATA_LEN + 1],
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                mc_btr_request_data_length: MAX_SC_MBTR_DATA_LEN + 1,
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                custom_field_elements_configs: vec![0],
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                custom_field_elements_configs: vec![255],
+                ..sample_params(vk.clone())
+            },
+            SidechainCreationParams {
+                custom_bitvector_elements_configs: vec![BitVectorElementsConfig {
+                    bit_vector_size_bits: 100,
+                    max_compressed_byte_size: 200,
+                }],
+                ..sample_params(vk.clone())
+            },
+        ];
+        for params in invalid.iter() {
+            assert!(params.validate(true, &VK_LIMITS).is_err());
+        }
+
+        std::fs::remove_file(pk_path).unwrap();
+        std::fs::remove_file(vk_path).unwrap();
+    }
+}
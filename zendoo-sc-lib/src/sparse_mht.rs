@@ -0,0 +1,417 @@
+use algebra::{Field, ToBits};
+use cctp_primitives::utils::mht::*;
+use demo_circuit::type_mapping::*;
+use primitives::merkle_tree::field_based_mht::FieldBasedMHTPath;
+
+use crate::mht_hash::hash_children;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+//*****************************Sparse Poseidon Merkle tree******************************************
+//
+// Poseidon Merkle tree of fixed height in which leaves can be inserted, updated and removed at
+// any position. Empty positions hold the zero leaf, so that roots and paths are the same of an
+// InMemoryOptimizedMerkleTree of the same height having the same leaves at the same positions,
+// and proofs are ordinary Merkle paths:
+// - a membership proof is the path of a non zero leaf at the given position;
+// - a non-membership proof is the path of the zero leaf at the given position.
+// For this reason the zero leaf can't be inserted.
+//
+// Changes are applied lazily: all the nodes affected by the changes since the last root
+// computation are recomputed in a single pass, level by level, whenever the root or a proof
+// is requested.
+//
+// Leaves don't commit to anything but their value, so a position must identify a single key:
+// keys (e.g. of nullifier or account sets) are mapped to positions only if they are smaller than
+// 2^height, and rejected otherwise, so that distinct keys never share the same position.
+
+// Positions are u64
+pub const SPARSE_MHT_MAX_HEIGHT: u8 = 63;
+
+// Position of the leaf corresponding to `key` in a sparse tree of height `height`, i.e. `key`
+// itself. Fails if `key` is not smaller than 2^height, instead of truncating it.
+pub fn sparse_mht_position_from_key(key: &FieldElement, height: u8) -> Result<u64, Error> {
+    if height > SPARSE_MHT_MAX_HEIGHT {
+        Err(format!("Invalid height: {}", height))?
+    }
+
+    // Bits are big endian
+    let bits = key.write_bits();
+    let (high_bits, low_bits) = bits.split_at(bits.len() - height as usize);
+    if high_bits.iter().any(|&bit| bit) {
+        Err(format!(
+            "Key out of range for a sparse tree of height {}",
+            height
+        ))?
+    }
+    Ok(low_bits
+        .iter()
+        .fold(0u64, |pos, &bit| (pos << 1) | (bit as u64)))
+}
+
+pub struct SparseMerkleTree {
+    height: u8,
+    // nulls[i] is the root of an empty subtree of height i
+    nulls: Vec<FieldElement>,
+    // Non empty nodes, by level (0 for leaves) and index
+    nodes: HashMap<(u8, u64), FieldElement>,
+    // Leaves changed since last root computation: None means removed
+    pending: BTreeMap<u64, Option<FieldElement>>,
+    num_leaves: u64,
+}
+
+impl SparseMerkleTree {
+    pub fn new(height: u8) -> Result<Self, Error> {
+        if height == 0 || height > SPARSE_MHT_MAX_HEIGHT {
+            Err(format!(
+                "Invalid height: {}. Must be in [1, {}]",
+                height, SPARSE_MHT_MAX_HEIGHT
+            ))?
+        }
+
+        let mut nulls = Vec::with_capacity(height as usize + 1);
+        nulls.push(FieldElement::zero());
+        for i in 0..height as usize {
+            nulls.push(hash_children(&nulls[i], &nulls[i])?);
+        }
+
+        Ok(Self {
+            height,
+            nulls,
+            nodes: HashMap::new(),
+            pending: BTreeMap::new(),
+            num_leaves: 0,
+        })
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    pub fn num_leaves(&self) -> u64 {
+        self.num_leaves
+    }
+
+    fn check_position(&self, position: u64) -> Result<(), Error> {
+        if position >= 1u64 << self.height {
+            Err(format!(
+                "Invalid position: {}. Tree height: {}",
+                position, self.height
+            ))?
+        }
+        Ok(())
+    }
+
+    pub fn get_leaf(&self, position: u64) -> Option<FieldElement> {
+        match self.pending.get(&position) {
+            Some(leaf) => *leaf,
+            None => self.nodes.get(&(0, position)).copied(),
+        }
+    }
+
+    pub fn contains(&self, position: u64) -> bool {
+        self.get_leaf(position).is_some()
+    }
+
+    // Insert `leaf` at the empty `position`
+    pub fn insert(&mut self, position: u64, leaf: &FieldElement) -> Result<(), Error> {
+        self.check_position(position)?;
+        if leaf.is_zero() {
+            Err("Zero leaf is reserved to empty positions")?
+        }
+        if self.contains(position) {
+            Err(format!("Position {} is not empty", position))?
+        }
+        self.pending.insert(position, Some(*leaf));
+        self.num_leaves += 1;
+        Ok(())
+    }
+
+    // Replace the leaf at the non empty `position` with `leaf`
+    pub fn update(&mut self, position: u64, leaf: &FieldElement) -> Result<(), Error> {
+        self.check_position(position)?;
+        if leaf.is_zero() {
+            Err("Zero leaf is reserved to empty positions")?
+        }
+        if !self.contains(position) {
+            Err(format!("Position {} is empty", position))?
+        }
+        self.pending.insert(position, Some(*leaf));
+        Ok(())
+    }
+
+    // Remove the leaf at the non empty `position`
+    pub fn remove(&mut self, position: u64) -> Result<(), Error> {
+        self.check_position(position)?;
+        if !self.contains(position) {
+            Err(format!("Position {} is empty", position))?
+        }
+        self.pending.insert(position, None);
+        self.num_leaves -= 1;
+        Ok(())
+    }
+
+    fn node(&self, level: u8, index: u64) -> FieldElement {
+        self.nodes
+            .get(&(level, index))
+            .copied()
+            .unwrap_or(self.nulls[level as usize])
+    }
+
+    // Recompute all the nodes affected by the pending changes
+    fn process_pending(&mut self) -> Result<(), Error> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut touched = BTreeSet::new();
+        for (position, leaf) in std::mem::take(&mut self.pending) {
+            match leaf {
+                Some(leaf) => self.nodes.insert((0, position), leaf),
+                None => self.nodes.remove(&(0, position)),
+            };
+            touched.insert(position >> 1);
+        }
+
+        for level in 1..=self.height {
+            let mut next_touched = BTreeSet::new();
+            for index in touched {
+                let left_empty = !self.nodes.contains_key(&(level - 1, 2 * index));
+                let right_empty = !self.nodes.contains_key(&(level - 1, 2 * index + 1));
+                if left_empty && right_empty {
+                    self.nodes.remove(&(level, index));
+                } else {
+                    let node = hash_children(
+                        &self.node(level - 1, 2 * index),
+                        &self.node(level - 1, 2 * index + 1),
+                    )?;
+                    self.nodes.insert((level, index), node);
+                }
+                next_touched.insert(index >> 1);
+            }
+            touched = next_touched;
+        }
+
+        Ok(())
+    }
+
+    pub fn root(&mut self) -> Result<FieldElement, Error> {
+        self.process_pending()?;
+        Ok(self.node(self.height, 0))
+    }
+
+    fn get_path(&mut self, position: u64) -> Result<GingerMHTPath, Error> {
+        self.process_pending()?;
+        let mut path = Vec::with_capacity(self.height as usize);
+        let mut index = position;
+        for level in 0..self.height {
+            path.push((self.node(level, index ^ 1), index & 1 == 1));
+            index >>= 1;
+        }
+        Ok(GingerMHTPath::new(path))
+    }
+
+    // Proof that the non empty `position` holds its current leaf
+    pub fn get_membership_proof(&mut self, position: u64) -> Result<GingerMHTPath, Error> {
+        self.check_position(position)?;
+        if !self.contains(position) {
+            Err(format!("Position {} is empty", position))?
+        }
+        self.get_path(position)
+    }
+
+    // Proof that `position` is empty
+    pub fn get_non_membership_proof(&mut self, position: u64) -> Result<GingerMHTPath, Error> {
+        self.check_position(position)?;
+        if self.contains(position) {
+            Err(format!("Position {} is not empty", position))?
+        }
+        self.get_path(position)
+    }
+}
+
+// Verify that `leaf` is at `position` in the sparse tree of height `height` and root `root`
+pub fn verify_sparse_mht_membership_proof(
+    proof: &GingerMHTPath,
+    height: u8,
+    position: u64,
+    leaf: &FieldElement,
+    root: &FieldElement,
+) -> Result<bool, Error> {
+    // Check the length before computing the position from the path
+    Ok(height <= SPARSE_MHT_MAX_HEIGHT
+        && proof.get_length() == height as usize
+        && !leaf.is_zero()
+        && get_leaf_index_from_path(proof) == position
+        && verify_ginger_merkle_path(proof, height as usize, leaf, root)?)
+}
+
+// Verify that `position` is empty in the sparse tree of height `height` and root `root`
+pub fn verify_sparse_mht_non_membership_proof(
+    proof: &GingerMHTPath,
+    height: u8,
+    position: u64,
+    root: &FieldElement,
+) -> Result<bool, Error> {
+    // Check the length before computing the position from the path
+    Ok(height <= SPARSE_MHT_MAX_HEIGHT
+        && proof.get_length() == height as usize
+        && get_leaf_index_from_path(proof) == position
+        && verify_ginger_merkle_path(proof, height as usize, &FieldElement::zero(), root)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::UniformRand;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn sparse_mht_matches_in_memory_mht() {
+        let height = 7;
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let mut smt = SparseMerkleTree::new(height).unwrap();
+        let mut leaves = vec![FieldElement::zero(); 1 << height];
+
+        // Random insertions, updates and removals
+        for _ in 0..300 {
+            let position = rng.gen_range(0..1u64 << height);
+            let leaf = FieldElement::rand(rng);
+            match (smt.contains(position), rng.gen_bool(0.3)) {
+                (false, _) => {
+                    smt.insert(position, &leaf).unwrap();
+                    leaves[position as usize] = leaf;
+                }
+                (true, true) => {
+                    smt.remove(position).unwrap();
+                    leaves[position as usize] = FieldElement::zero();
+                }
+                (true, false) => {
+                    smt.update(position, &leaf).unwrap();
+                    leaves[position as usize] = leaf;
+                }
+            }
+
+            // Recompute the root from time to time, to test batching
+            if rng.gen_bool(0.1) {
+                smt.root().unwrap();
+            }
+        }
+
+        let mut mht = new_ginger_mht(height as usize, 1 << height).unwrap();
+        for leaf in leaves.iter() {
+            append_leaf_to_ginger_mht(&mut mht, leaf).unwrap();
+        }
+        finalize_ginger_mht_in_place(&mut mht).unwrap();
+
+        let root = smt.root().unwrap();
+        assert_eq!(root, get_ginger_mht_root(&mht).unwrap());
+        assert_eq!(
+            smt.num_leaves(),
+            leaves.iter().filter(|leaf| !leaf.is_zero()).count() as u64
+        );
+
+        for (position, leaf) in leaves.iter().enumerate() {
+            let position = position as u64;
+            if leaf.is_zero() {
+                assert!(smt.get_membership_proof(position).is_err());
+                let proof = smt.get_non_membership_proof(position).unwrap();
+                assert!(verify_sparse_mht_non_membership_proof(&proof, height, position, &root)
+                    .unwrap());
+                assert!(
+                    !verify_sparse_mht_non_membership_proof(&proof, height, position ^ 1, &root)
+                        .unwrap()
+                );
+            } else {
+                assert!(smt.get_non_membership_proof(position).is_err());
+                let proof = smt.get_membership_proof(position).unwrap();
+                assert_eq!(proof, get_ginger_mht_path(&mht, position).unwrap());
+                assert!(
+                    verify_sparse_mht_membership_proof(&proof, height, position, leaf, &root)
+                        .unwrap()
+                );
+                assert!(!verify_sparse_mht_non_membership_proof(&proof, height, position, &root)
+                    .unwrap());
+                let wrong_leaf = FieldElement::rand(rng);
+                assert!(!verify_sparse_mht_membership_proof(
+                    &proof,
+                    height,
+                    position,
+                    &wrong_leaf,
+                    &root
+                )
+                .unwrap());
+            }
+        }
+
+        // Removing all the leaves gives back the empty tree
+        for position in 0..1u64 << height {
+            if smt.contains(position) {
+                smt.remove(position).unwrap();
+            }
+        }
+        assert_eq!(smt.num_leaves(), 0);
+        assert_eq!(
+            smt.root().unwrap(),
+            SparseMerkleTree::new(height).unwrap().root().unwrap()
+        );
+        assert!(smt.nodes.is_empty());
+    }
+
+    #[test]
+    fn sparse_mht_negative_cases() {
+        let height = 4;
+        let mut smt = SparseMerkleTree::new(height).unwrap();
+        let leaf = FieldElement::one();
+
+        assert!(SparseMerkleTree::new(0).is_err());
+        assert!(SparseMerkleTree::new(SPARSE_MHT_MAX_HEIGHT + 1).is_err());
+
+        assert!(smt.insert(1 << height, &leaf).is_err());
+        assert!(smt.insert(0, &FieldElement::zero()).is_err());
+        assert!(smt.update(0, &leaf).is_err());
+        assert!(smt.remove(0).is_err());
+
+        smt.insert(0, &leaf).unwrap();
+        assert!(smt.insert(0, &leaf).is_err());
+        assert!(smt.update(0, &FieldElement::zero()).is_err());
+
+        // Keys are positions, and are rejected instead of truncated if out of range
+        let key = FieldElement::from(0x1234u64);
+        assert!(sparse_mht_position_from_key(&key, height).is_err());
+        assert!(sparse_mht_position_from_key(&key, 12).is_err());
+        assert_eq!(sparse_mht_position_from_key(&key, 13).unwrap(), 0x1234);
+        assert_eq!(sparse_mht_position_from_key(&key, 16).unwrap(), 0x1234);
+        assert_eq!(
+            sparse_mht_position_from_key(&FieldElement::zero(), 1).unwrap(),
+            0
+        );
+        assert_eq!(
+            sparse_mht_position_from_key(
+                &FieldElement::from((1u64 << SPARSE_MHT_MAX_HEIGHT) - 1),
+                SPARSE_MHT_MAX_HEIGHT
+            )
+            .unwrap(),
+            (1u64 << SPARSE_MHT_MAX_HEIGHT) - 1
+        );
+        assert!(
+            sparse_mht_position_from_key(&-FieldElement::one(), SPARSE_MHT_MAX_HEIGHT).is_err()
+        );
+        assert!(sparse_mht_position_from_key(&key, SPARSE_MHT_MAX_HEIGHT + 1).is_err());
+
+        // Paths of the wrong length, longer than the positions, are rejected
+        let root = smt.root().unwrap();
+        let proof = smt.get_membership_proof(0).unwrap();
+        let mut raw_path = proof.get_raw_path().to_vec();
+        raw_path.extend(vec![(FieldElement::zero(), true); 64]);
+        let long_proof = GingerMHTPath::new(raw_path);
+        assert!(!verify_sparse_mht_membership_proof(&long_proof, height, 0, &leaf, &root).unwrap());
+        assert!(!verify_sparse_mht_non_membership_proof(&long_proof, height, 0, &root).unwrap());
+        let short_proof = GingerMHTPath::new(proof.get_raw_path()[1..].to_vec());
+        assert!(
+            !verify_sparse_mht_membership_proof(&short_proof, height, 0, &leaf, &root).unwrap()
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use cctp_primitives::{
+    commitment_tree::{
+        proofs::ScExistenceProof,
+        sidechain_tree_alive::{BWTR_MT_HEIGHT, CERT_MT_HEIGHT, FWT_MT_HEIGHT},
+        sidechain_tree_ceased::CSW_MT_HEIGHT,
+        CommitmentTree,
+    },
+    utils::{commitment_tree::hash_vec, mht::*, serialization::*},
+};
+use demo_circuit::type_mapping::*;
+use primitives::merkle_tree::field_based_mht::FieldBasedMHTPath;
+
+use crate::{mc_serialization::take, mht_hash::hash_children};
+
+use std::convert::TryInto;
+
+//*****************************Commitment tree transaction inclusion proofs*************************
+//
+// Proof that a single transaction (forward transfer, backward transfer request, certificate or
+// ceased sidechain withdrawal) is included in the commitment tree. It's made of three parts:
+// - the Merkle path of the transaction leaf in the sub-tree of its type of the sidechain;
+// - the other data the commitment of the sidechain is computed from:
+//     alive sidechains:  H(fwt root, bwtr root, cert root, scc, sc id)
+//     ceased sidechains: H(csw root, sc id)
+//   i.e. the roots of the other sub-trees and the sidechain creation commitment for alive
+//   sidechains, nothing for ceased ones;
+// - the existence proof of the sidechain commitment in the commitment tree.
+//
+// Layout of a serialized proof (integers are little endian):
+//
+// | tx type (1) | sc id (FIELD_SIZE) | leaf (FIELD_SIZE) | leaf path length (4) | leaf path |
+// | num sc data (1) | sc data (num sc data * FIELD_SIZE) | existence proof length (4) |
+// | existence proof |
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitmentTreeTxType {
+    Fwt = 0,
+    Bwtr = 1,
+    Cert = 2,
+    Csw = 3,
+}
+
+impl CommitmentTreeTxType {
+    pub fn from_u8(tx_type: u8) -> Result<Self, Error> {
+        match tx_type {
+            0 => Ok(CommitmentTreeTxType::Fwt),
+            1 => Ok(CommitmentTreeTxType::Bwtr),
+            2 => Ok(CommitmentTreeTxType::Cert),
+            3 => Ok(CommitmentTreeTxType::Csw),
+            _ => Err(format!("Unknown transaction type: {}", tx_type))?,
+        }
+    }
+
+    // Height of the sub-tree holding the transactions of this type
+    pub fn sub_tree_height(&self) -> usize {
+        match self {
+            CommitmentTreeTxType::Fwt => FWT_MT_HEIGHT,
+            CommitmentTreeTxType::Bwtr => BWTR_MT_HEIGHT,
+            CommitmentTreeTxType::Cert => CERT_MT_HEIGHT,
+            CommitmentTreeTxType::Csw => CSW_MT_HEIGHT,
+        }
+    }
+
+    // Number of the inputs of the sidechain commitment other than the sub-tree root and the
+    // sidechain id
+    fn num_sc_data(&self) -> usize {
+        match self {
+            CommitmentTreeTxType::Csw => 0,
+            _ => 3,
+        }
+    }
+}
+
+pub struct TxInclusionProof {
+    tx_type: CommitmentTreeTxType,
+    sc_id: FieldElement,
+    leaf: FieldElement,
+    leaf_path: GingerMHTPath,
+    sc_data: Vec<FieldElement>,
+    sc_existence_proof: ScExistenceProof,
+}
+
+impl TxInclusionProof {
+    pub(crate) fn new(
+        tx_type: CommitmentTreeTxType,
+        sc_id: FieldElement,
+        leaf: FieldElement,
+        leaf_path: GingerMHTPath,
+        sc_data: Vec<FieldElement>,
+        sc_existence_proof: ScExistenceProof,
+    ) -> Result<Self, Error> {
+        if sc_data.len() != tx_type.num_sc_data() {
+            Err(format!(
+                "Invalid number of sidechain data. Expected: {}, found: {}",
+                tx_type.num_sc_data(),
+                sc_data.len()
+            ))?
+        }
+        if leaf_path.get_length() != tx_type.sub_tree_height() {
+            Err(format!(
+                "Invalid leaf path length. Expected: {}, found: {}",
+                tx_type.sub_tree_height(),
+                leaf_path.get_length()
+            ))?
+        }
+        Ok(Self {
+            tx_type,
+            sc_id,
+            leaf,
+            leaf_path,
+            sc_data,
+            sc_existence_proof,
+        })
+    }
+
+    pub fn tx_type(&self) -> CommitmentTreeTxType {
+        self.tx_type
+    }
+
+    pub fn sc_id(&self) -> &FieldElement {
+        &self.sc_id
+    }
+
+    pub fn leaf(&self) -> &FieldElement {
+        &self.leaf
+    }
+
+    pub fn leaf_path(&self) -> &GingerMHTPath {
+        &self.leaf_path
+    }
+
+    // Inputs of the sidechain commitment other than the sub-tree root and the sidechain id
+    pub fn sc_data(&self) -> &[FieldElement] {
+        &self.sc_data
+    }
+
+    pub fn sc_existence_proof(&self) -> &ScExistenceProof {
+        &self.sc_existence_proof
+    }
+
+    // Position of the transaction in the sub-tree of its type
+    pub fn leaf_index(&self) -> u64 {
+        get_leaf_index_from_path(&self.leaf_path)
+    }
+
+    // Root of the sub-tree of the transaction type
+    pub fn compute_sub_tree_root(&self) -> Result<FieldElement, Error> {
+        let mut node = self.leaf;
+        for (sibling, is_right) in self.leaf_path.get_raw_path().iter() {
+            let (left, right) = if *is_right {
+                (*sibling, node)
+            } else {
+                (node, *sibling)
+            };
+            node = hash_children(&left, &right)?;
+        }
+        Ok(node)
+    }
+
+    // Commitment of the sidechain the transaction belongs to
+    pub fn compute_sc_commitment(&self) -> Result<FieldElement, Error> {
+        let sub_tree_root = self.compute_sub_tree_root()?;
+        let mut inputs = self.sc_data.clone();
+        match self.tx_type {
+            CommitmentTreeTxType::Csw => inputs.insert(0, sub_tree_root),
+            tx_type => inputs.insert(tx_type as usize, sub_tree_root),
+        }
+        inputs.push(self.sc_id);
+        hash_vec(inputs)
+    }
+
+    // Verify that the transaction of type `tx_type` of the sidechain `sc_id`, with leaf `leaf` in
+    // the sub-tree of its type, is included in the commitment tree with commitment `commitment`.
+    // The leaf of a transaction is the one computed by hash_fwt, hash_bwtr or hash_csw on its
+    // fields, or the certificate leaf.
+    pub fn verify(
+        &self,
+        tx_type: CommitmentTreeTxType,
+        sc_id: &FieldElement,
+        leaf: &FieldElement,
+        commitment: &FieldElement,
+    ) -> Result<bool, Error> {
+        if self.tx_type != tx_type || &self.sc_id != sc_id || &self.leaf != leaf {
+            return Ok(false);
+        }
+        let sc_commitment = self.compute_sc_commitment()?;
+        Ok(CommitmentTree::verify_sc_commitment(
+            &sc_commitment,
+            &self.sc_existence_proof,
+            commitment,
+        ))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let leaf_path = serialize_to_buffer(&self.leaf_path, None)?;
+        let sc_existence_proof = serialize_to_buffer(&self.sc_existence_proof, None)?;
+
+        let mut bytes = Vec::new();
+        bytes.push(self.tx_type as u8);
+        bytes.extend_from_slice(&serialize_to_buffer(&self.sc_id, None)?);
+        bytes.extend_from_slice(&serialize_to_buffer(&self.leaf, None)?);
+        bytes.extend_from_slice(&(leaf_path.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&leaf_path);
+        bytes.push(self.sc_data.len() as u8);
+        for data in self.sc_data.iter() {
+            bytes.extend_from_slice(&serialize_to_buffer(data, None)?);
+        }
+        bytes.extend_from_slice(&(sc_existence_proof.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&sc_existence_proof);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut rest = bytes;
+        let rest = &mut rest;
+
+        let tx_type = CommitmentTreeTxType::from_u8(take(rest, 1)?[0])?;
+        let sc_id = deserialize_from_buffer(take(rest, FIELD_SIZE)?, None, None)?;
+        let leaf = deserialize_from_buffer(take(rest, FIELD_SIZE)?, None, None)?;
+        let leaf_path_len = u32::from_le_bytes(take(rest, 4)?.try_into().unwrap()) as usize;
+        let leaf_path = deserialize_from_buffer(take(rest, leaf_path_len)?, None, None)?;
+        let num_sc_data = take(rest, 1)?[0] as usize;
+        let sc_data = (0..num_sc_data)
+            .map(|_| deserialize_from_buffer(take(rest, FIELD_SIZE)?, None, None))
+            .collect::<Result<Vec<FieldElement>, _>>()?;
+        let sc_existence_proof_len =
+            u32::from_le_bytes(take(rest, 4)?.try_into().unwrap()) as usize;
+        let sc_existence_proof =
+            deserialize_from_buffer(take(rest, sc_existence_proof_len)?, None, None)?;
+        if !rest.is_empty() {
+            Err("Invalid inclusion proof: unexpected trailing data")?
+        }
+
+        Self::new(tx_type, sc_id, leaf, leaf_path, sc_data, sc_existence_proof)
+    }
+}